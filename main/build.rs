@@ -3,6 +3,12 @@ use rustc_version::{version_meta, Channel};
 fn main() {
     println!("cargo:rerun-if-changed=build.rs");
 
+    // The helpers generated by the `fuzzing` feature are only present with `--cfg fuzzing`.
+    println!("cargo:rustc-check-cfg=cfg(fuzzing)");
+
+    // This is set below when compiling with the nightly compiler.
+    println!("cargo:rustc-check-cfg=cfg(nightly)");
+
     match version_meta() {
         Ok(version) if version.channel == Channel::Nightly => {
             println!("cargo:rustc-cfg=nightly");
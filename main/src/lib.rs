@@ -127,6 +127,12 @@
 //! - Since pre works by adding an additional argument to a function, it changes the function
 //!   signature. That won't make a difference in many cases, but if you use function pointers or
 //!   pass a function as an argument, it will have a different type from what it appears to be.
+//! - Operators such as `a + b` or `a[i]` cannot be used with an `assure` attribute, because there
+//!   is no call that the precondition handling could be added to. Preconditions also cannot be
+//!   added to the methods of operator traits, because changing the signature breaks the trait
+//!   implementation. Instead, put the preconditions on an inherent method and call it
+//!   explicitly, such as `a.add(b)`. Because of the first limitation above, this only works on
+//!   nightly.
 //! - Because attribute macros are not supported for expressions and statements on the current
 //!   stable compiler, functions that contain an `assure` attribute must have at least one `pre`
 //!   attribute, though it could be empty: [`#[pre]`](attr.pre.html#checking-functionality).
@@ -171,6 +177,48 @@
 //!       # fn main() {}
 //!       ```
 //!
+//! # Finding the `pre` crate
+//!
+//! The generated code refers to items of this crate, so pre needs to know the name under which
+//! the crate using it depends on `pre`. The name is determined in this order:
+//!
+//! 1. If the `PRE_CRATE_NAME` environment variable is set while compiling a crate, its value is
+//!    used. It must be an identifier, such as `my_pre` for a dependency declared as
+//!    `my-pre = { package = "pre", ... }`.
+//! 2. Otherwise the dependencies in the `Cargo.toml` of the crate are searched for `pre`, which
+//!    also finds a renamed dependency.
+//! 3. Otherwise, if the crate being compiled is `pre` itself, `pre` is used.
+//!
+//! The environment variable is meant for build systems other than Cargo, such as Bazel or Buck,
+//! where the `Cargo.toml` of a crate cannot be found. Like `PRE_MIN_REASON_LENGTH`, it applies to
+//! every crate compiled while it is set, so it should only be set for the crates that depend on
+//! `pre` under that name. The compilation is not known to depend on the variable, so a crate that
+//! was compiled before the variable was changed keeps the old name until it is rebuilt from
+//! scratch.
+//!
+//! # Features that affect the whole build
+//!
+//! Cargo unifies features, so a feature of `pre` that is enabled by one crate is enabled for all
+//! crates in the same build. Most features only add something to the annotated items, but the
+//! following ones change how every crate using `pre` is compiled. If a library enables one of them,
+//! other crates in the build may stop compiling or lose checks, so they should only be enabled by
+//! the final binary or for a whole workspace:
+//!
+//! - `assoc-const` checks the preconditions with associated constants instead of an additional
+//!   parameter, so most calls without `assure` attributes are no longer rejected in any crate.
+//! - `case-insensitive-custom` compares all custom preconditions ignoring their case, so a crate
+//!   that relies on it fails to compile in a build without it.
+//! - `both` adds `debug_assert`s to the functions of every crate, which can fail for callers that
+//!   never expected their arguments to be checked at runtime.
+//! - `strip` removes all markers and checks, so no preconditions are checked in any crate and
+//!   manually [passed markers](attr.pre.html#passing-the-markers-by-hand) no longer compile.
+//! - `strict-spans` rejects the stable compiler for every crate using `pre`, not only for the
+//!   crate that enabled it.
+//! - `runtime-checks` turns boolean preconditions that refer to values other than parameters into
+//!   errors, so crates that compile without it can fail to compile with it.
+//! - `marker-first` moves the marker to the front of every function and call, so markers that are
+//!   passed by hand need to be moved as well.
+//!
 //! # Understanding the error messages
 //!
 //! pre tries to be as helpful as possible in the error messages it gives. Unfortunately in some
@@ -210,17 +258,16 @@
 //!
 //! **nightly compiler error**
 //! ```text
-//! error[E0308]: mismatched types
+//! error[E0277]: the assured preconditions don't match the preconditions of the called function
 //!   --> src/main.rs:8:5
 //!    |
 //! 8  | /     #[assure(
 //! 9  | |         x > 41.0,
 //! 10 | |         reason = "42.0 > 41.0"
 //! 11 | |     )]
-//!    | |______^ expected `"x > 41.9"`, found `"x > 41.0"`
+//!    | |______^ the assured preconditions are `(pre::BooleanCondition<"x > 41.0">,)`
 //!    |
-//!    = note: expected struct `std::marker::PhantomData<(pre::BooleanCondition<"x > 41.9">,)>`
-//!               found struct `std::marker::PhantomData<(pre::BooleanCondition<"x > 41.0">,)>`
+//!    = note: the called function has the preconditions `(pre::BooleanCondition<"x > 41.9">,)`
 //! ```
 //!
 //! **stable compiler error**
@@ -248,17 +295,16 @@
 //!
 //! **nightly compiler error**
 //! ```text
-//! error[E0308]: mismatched types
+//! error[E0277]: the assured preconditions don't match the preconditions of the called function
 //!   --> src/main.rs:9:5
 //!    |
 //! 9  | /     #[assure(
 //! 10 | |         x > 41.9,
 //! 11 | |         reason = "42.0 > 41.9"
 //! 12 | |     )]
-//!    | |______^ expected a tuple with 2 elements, found one with 1 element
+//!    | |______^ the assured preconditions are `(pre::BooleanCondition<"x > 41.9">,)`
 //!    |
-//!    = note: expected struct `std::marker::PhantomData<(pre::BooleanCondition<"x < 42.1">, pre::BooleanCondition<"x > 41.9">)>`
-//!               found struct `std::marker::PhantomData<(pre::BooleanCondition<"x > 41.9">,)>`
+//!    = note: the called function has the preconditions `(pre::BooleanCondition<"x < 42.1">, pre::BooleanCondition<"x > 41.9">)`
 //! ```
 //!
 //! **stable compiler error**
@@ -476,9 +522,27 @@
 //!
 //! Because the warnings only work on the nightly compiler, **usage of `"TODO"` as a reason is
 //! discouraged when using the stable compiler**.
+//!
+//! If a placeholder reason is intentional for a while, the warning can be suppressed for a single
+//! call site by adding the `allow_placeholder` flag after the reason, such as
+//! `#[assure(x > 0, reason = "TODO", allow_placeholder)]`. This should only be used temporarily,
+//! for example while a larger change is in progress, because it removes the reminder.
+//!
+//! Reasons that are too short to explain anything, like `"ok"` or `"safe"`, are treated the same
+//! way. By default a reason with fewer than 5 characters causes a warning. A different minimum
+//! length can be set with the `PRE_MIN_REASON_LENGTH` environment variable while compiling a
+//! crate, and `PRE_MIN_REASON_LENGTH=0` disables the warning. Cargo does not notice when the
+//! variable changes, so crates that were already compiled need to be rebuilt from scratch, for
+//! example after `cargo clean -p <crate>`, to use a new value.
+//!
+//! A reason that only restates the precondition, like `"p is a valid pointer"` for
+//! `valid_ptr(p, r)`, also causes a warning, because it doesn't explain why the precondition
+//! holds. Reasons are compared word by word, ignoring case, punctuation and filler words such as
+//! "is" or "must", and only a reason that mentions nothing beyond the precondition is reported.
 
 #![allow(clippy::needless_doctest_main)]
 #![cfg_attr(nightly, feature(const_generics))]
+#![cfg_attr(nightly, feature(rustc_attrs))]
 #![cfg_attr(nightly, allow(incomplete_features))]
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -528,6 +592,11 @@
 ///    #[pre("describe your precondition here")]
 ///    fn foo() {}
 ///    ```
+///
+///    By default custom preconditions are case-sensitive. If the `case-insensitive-custom`
+///    feature is enabled, they are converted to lower case before they are compared, so
+///    `"Frees Memory"` and `"frees memory"` are considered to be the same precondition.
+///    A warning is emitted for every custom precondition that is changed by this conversion.
 /// 2. Valid pointer preconditions:
 ///
 ///    This precondition requires that a raw pointer is
@@ -537,7 +606,8 @@
 ///
 ///    - `<ptr_name>`: The identifier of the pointer argument that must be valid.
 ///    - `<access_modes>`: One of `r`, `w` or `r+w`. This specifies whether the pointer is valid
-///    for reads (`r`) or writes (`w`) or both (`r+w`).
+///      for reads (`r`) or writes (`w`) or both (`r+w`). If it is left out together with the
+///      preceding comma, as in `#[pre(valid_ptr(<ptr_name>))]`, `r+w` is used.
 ///
 ///    ### Example
 ///
@@ -548,6 +618,29 @@
 ///    fn foo(ptr_name: *mut i32) {}
 ///    ```
 ///
+///    The access modes are part of the precondition, so assuring `valid_ptr(src, r)` does not
+///    satisfy `valid_ptr(src, w)` or `valid_ptr(src, r+w)`.
+///
+///    Optionally the type of the pointer can be specified as well:
+///    `#[pre(valid_ptr(<ptr_name>: <ptr_type>, <access_modes>))]`. The type must match the type of
+///    the parameter and then also needs to be specified when assuring the precondition. This way
+///    a change of the pointer type is noticed at every call site. Types that depend on generic
+///    parameters are only checked against the parameter and are not part of the precondition, so
+///    they are not specified when assuring it.
+///
+///    ```rust
+///    # use pre::pre;
+///    #
+///    #[pre(valid_ptr(ptr_name: *const u8, r))]
+///    fn foo(ptr_name: *const u8) {}
+///
+///    #[pre]
+///    fn main() {
+///        #[assure(valid_ptr(ptr_name: *const u8, r), reason = "it is created from a reference")]
+///        foo(&42);
+///    }
+///    ```
+///
 ///    This precondition **does not** guarantee:
 ///
 ///    - A valid alignment of the pointer.
@@ -576,275 +669,2065 @@
 ///    fn foo(a: i32, b: i32) {}
 ///    ```
 ///
-/// # General syntax
+///    The expression can also be written as `#[pre(condition = <expr>)]`, which makes it stand
+///    out from the other kinds of preconditions. It is the same precondition as `#[pre(<expr>)]`,
+///    so either form can be used to assure it. `#[pre(condition(<expr>))]` is not special, it is
+///    a call of a function named `condition`.
+/// 4. Documented preconditions:
 ///
-/// There are three uses of the `pre` attribute:
+///    This precondition refers to an item whose documentation describes the condition. It works
+///    like a custom precondition, but the generated documentation contains a link to the item.
 ///
-/// 1. Specify one or multiple preconditions (for the exact syntax of the preconditions, see
-///    ["Precondition syntax"](#precondition-syntax)):
+///    The syntax is `#[pre(see = "<path>")]`.
 ///
-///    ```rust,ignore
-///    #[pre(<first precondition>)]
-///    #[pre(<second precondition>)]
-///    #[pre(<third precondition>)]
+///    - `<path>`: The path of the documented item, as used in an intra-doc link.
+///
+///    ### Example
+///
+///    ```rust
+///    # use pre::pre;
+///    #
+///    /// The rules for calling `foo`.
+///    pub struct FooRules;
+///
+///    #[pre(see = "FooRules")]
 ///    fn foo() {}
 ///    ```
-/// 2. Enable handling of [`assure`](attr.assure.html) and [`forward`](attr.forward.html)
-///    attributes for the annotated item (see ["Checking functionality"](#checking-functionality)):
+///
+///    The precondition is identified by the path, so it needs to be assured as
+///    `#[assure(see = "<path>", reason = "...")]` with the same path. It is distinct from a custom
+///    precondition with the same text.
+/// 5. Range preconditions:
+///
+///    This precondition requires that a value lies in a range. Like for boolean preconditions, a
+///    `debug_assert` statement is added to the function, which respects the inclusivity of the
+///    range: `0..10` checks `0 <= x && x < 10`, while `0..=10` checks `0 <= x && x <= 10`.
+///
+///    The syntax is `#[pre(kind = in_range(<name>, <range>))]`.
+///
+///    - `<name>`: The identifier of the parameter that must lie in the range.
+///    - `<range>`: A range expression, such as `a..b`, `a..=b`, `a..`, `..b` or `..=b`. If both
+///      bounds are integer literals, ranges that are reversed or empty are rejected.
+///
+///    ### Example
 ///
 ///    ```rust
 ///    # use pre::pre;
 ///    #
-///    #[pre]
-///    fn main() {
-///        // `assure` and `forward` attributes here are properly handled.
-///    }
+///    #[pre(kind = in_range(index, 0..16))]
+///    fn foo(index: usize) {}
 ///    ```
+/// 6. User-defined preconditions:
 ///
-///    This works with any of the forms of the `pre` attribute, but in case no other functionality
-///    is needed, a bare `#[pre]` can be used.
-/// 3. Disable generating documentation for the preconditions (see ["Documentation on items with
-///    preconditions"](#documentation-on-items-with-preconditions)):
+///    This precondition is of a kind that is defined with the
+///    [`define_precondition_kind!`](macro.define_precondition_kind.html) macro. It applies the
+///    requirements described in the documentation of the kind to the given parameters.
+///
+///    The syntax is `#[pre(kind = <path>(<arguments>))]`.
+///
+///    - `<path>`: The path of the kind, such as `non_zero` or `crate::kinds::non_zero`.
+///    - `<arguments>`: A comma-separated list of the identifiers of the parameters the kind
+///      applies to. The number of arguments must match the definition of the kind.
+///
+///    ### Example
 ///
 ///    ```rust
-///    # use pre::pre;
+///    # use pre::{define_precondition_kind, pre};
 ///    #
-///    #[pre(no_doc)]
-///    #[pre("some precondition")]
-///    fn foo() {} // foo will not have any documentation generated by pre.
+///    define_precondition_kind! {
+///        /// The value is not zero.
+///        non_zero(value)
+///    }
+///
+///    #[pre(kind = non_zero(divisor))]
+///    fn foo(divisor: u32) {}
 ///    ```
-/// 4. Disable debug assertions for boolean preconditions.
+///
+///    The precondition is identified by the path as it is written and the arguments, so it needs
+///    to be assured with the same path, such as
+///    `#[assure(kind = non_zero(divisor), reason = "...")]`.
+///
+///    The built-in kinds other than `valid_ptr`, such as `in_range` above and `sorted` below, are
+///    written the same way, for example `kind = sorted(v)`. This keeps `sorted(v)` a boolean
+///    precondition that calls a function named `sorted`. To use a user-defined kind with the name
+///    of a built-in kind, write its path with more than one segment, such as
+///    `kind = self::sorted(v)`.
+/// 7. Relation preconditions:
+///
+///    This precondition requires that two values are related by a comparison. Like for boolean
+///    preconditions, a `debug_assert` statement comparing the values is added to the function.
+///
+///    The syntax is `#[pre(kind = relation("<operator>", <left>, <right>))]`.
+///
+///    - `<operator>`: One of `"<"`, `"<="`, `">"`, `">="`, `"=="` or `"!="`.
+///    - `<left>`: The identifier of the parameter on the left side of the comparison.
+///    - `<right>`: The identifier of the parameter on the right side of the comparison.
+///
+///    ### Example
+///
 ///    ```rust
 ///    # use pre::pre;
 ///    #
-///    #[pre(no_debug_assert)]
-///    #[pre(old_val < new_val)]
-///    fn foo() {} // foo will not have any `debug_assert`s generated by pre.
+///    #[pre(kind = relation("<=", start, end))]
+///    fn foo(start: usize, end: usize) {}
 ///    ```
 ///
-/// # Checking functionality
-///
-/// The `pre` attribute can also be used to enable the functionalities of the
-/// [`assure`](attr.assure.html) and [`forward`](attr.forward.html) attributes for the item it is
-/// attached to. In this case it can be left empty: `#[pre]`. Any other form of a `pre` attribute
-/// being present on an item renders the empty one obsolete though, as any of its forms enables
-/// this functionality.
+///    Unlike the equivalent boolean precondition `start <= end`, the generated documentation
+///    describes the relation in words.
+/// 8. Lock preconditions:
 ///
-/// Doing this is currently necessary, because the current (1.44.1) stable rust compiler does not
-/// support attribute macros being applied to statements or expressions directly.
+///    This precondition requires that the lock protecting a value is held while the function is
+///    called. It documents locking contracts, such as the order in which locks must be acquired,
+///    that cannot be expressed in the type system. The precondition is never checked at runtime.
 ///
-/// # Documentation on items with preconditions
+///    The syntax is `#[pre(kind = locked(<ident>))]`.
 ///
-/// Items annotated with one or more preconditions have information about their preconditions
-/// and how to call them with the preconditions appended at the end of their documentation.
+///    - `<ident>`: The identifier of the parameter that is protected by the lock, such as the
+///      lock itself or a pointer to the data it protects.
 ///
-/// If you wish not to add such documentation to a particular item, you can add `#[pre(no_doc)]` to
-/// the attributes of the item, to prevent its generation.
-pub use pre_proc_macro::pre;
-
-/// Assure that a precondition holds.
+///    ### Example
 ///
-/// This is the counterpart of the [`pre` attribute](attr.pre.html).
+///    ```rust
+///    # use pre::pre;
+///    #
+///    #[pre(kind = locked(counter))]
+///    unsafe fn increment_unlocked(counter: *mut u32) {
+///        *counter += 1;
+///    }
+///    ```
 ///
-/// Currently this attribute does not work by itself.
-/// It needs to be used inside of a context that is annotated by a `pre` attribute.
+///    A user-defined kind named `locked` is a different precondition, which is written with a
+///    longer path, such as `kind = self::locked(counter)`.
+/// 9. Provenance preconditions:
 ///
-/// # Basic usage example
+///    This precondition requires that two pointers are derived from the same allocation, as it
+///    is required for example by `offset_from`. It only documents the contract and is never
+///    checked at runtime.
 ///
-/// Suppose you want to call the function `use_foo` from the [example in the `pre` attribute
-/// documentation](attr.pre.html).
+///    The syntax is `#[pre(kind = same_provenance(<first ident>, <second ident>))]`.
 ///
-/// First you need to find out what its preconditions are. You can do that by looking at its
-/// documentation. For this example we can also look at the source code for the function and see
-/// the precondition there.
+///    - `<first ident>` and `<second ident>`: The identifiers of the two pointers.
 ///
-/// It has one precondition:
+///    The order of the pointers does not matter, so `kind = same_provenance(start, end)` can be
+///    assured as `kind = same_provenance(end, start)`. Both are shown with the names in sorted
+///    order.
 ///
-/// - is only called after `init_foo` was called
+///    ### Example
 ///
-/// Armed with that knowledge, we can write the code to call the function.
+///    ```rust
+///    # use pre::pre;
+///    #
+///    #[pre(kind = same_provenance(start, end))]
+///    unsafe fn distance(start: *const u8, end: *const u8) -> isize {
+///        end.offset_from(start)
+///    }
 ///
-/// ```rust
-/// use pre::pre;
-/// #
-/// # fn init_foo() {}
-/// # #[pre("is only called after `init_foo` was called")]
-/// # fn use_foo() {}
+///    #[pre]
+///    fn main() {
+///        let array = [0u8; 4];
+///        let start = array.as_ptr();
+///        let end = unsafe { start.add(3) };
 ///
-/// #[pre] // This is required, so `assure` works
-/// fn main() {
-///     // This call allows us to safely assure the precondition later.
-///     init_foo(/* ... */);
+///        #[assure(kind = same_provenance(end, start), reason = "both point into `array`")]
+///        let distance = unsafe { distance(start, end) };
+///        assert_eq!(distance, 3);
+///    }
+///    ```
+/// 10. Non-null pointer preconditions:
 ///
-///     #[assure(
-///         "is only called after `init_foo` was called",
-///         reason = "we just called `init_foo`"
-///     )]
-///     use_foo(/* ... */);
-/// }
-/// ```
+///     This precondition requires that a raw pointer is not null. Unlike `valid_ptr`, it says
+///     nothing about whether the pointer can be read from or written to, which is often all that
+///     an FFI function requires.
 ///
-/// # Terminology
+///     The syntax is `#[pre(kind = non_null(<ptr_name>))]`.
 ///
-/// The term `assure` was chosen, because it most accurately describes the function of the
-/// attribute.
+///     - `<ptr_name>`: The identifier of the pointer argument that must not be null.
 ///
-/// There are no guarantees that a precondition holds, other than the fact that the programmer
-/// promises that it does. The burden is still on the programmer in this case. pre only makes
-/// sure that the programmer cannot forget that a precondition exists and that no precondition is
-/// changed without the programmer noticing.
+///     By default a `debug_assert` statement checking the pointer is added to the function,
+///     which can be disabled by a `#[pre(no_debug_assert)]` attribute.
 ///
-/// # Syntax
+///     ### Example
 ///
-/// The basic syntax of the `assure` attribute is:
+///     ```rust
+///     # use pre::pre;
+///     #
+///     #[pre(kind = non_null(name))]
+///     fn name_len(name: *const u8) -> usize {
+///         0
+///     }
 ///
-/// ```rust,ignore
-/// #[assure(
-///     <first precondition>,
-///     reason = "<the reason why the first precondition can be assured>"
-/// )]
-/// #[assure(
-///     <second precondition>,
-///     reason = "<the reason why the second precondition can be assured>"
-/// )]
-/// #[assure(
-///     <third precondition>,
-///     reason = "<the reason why the third precondition can be assured>"
-/// )]
-/// foo();
-/// ```
+///     #[pre]
+///     fn main() {
+///         #[assure(kind = non_null(name), reason = "the pointer is created from a reference")]
+///         name_len(&42);
+///     }
+///     ```
 ///
-/// To learn more about the precondition syntax and the possible types of preconditions, you should
-/// look at the [documentation of the `pre` attribute](attr.pre.html#precondition-syntax).
-pub use pre_proc_macro::assure;
-
-/// Forward the call to a different function that has the preconditions for the original function.
+///     `kind = non_null(ptr)` and `valid_ptr(ptr, r)` are different preconditions, so assuring one
+///     of them does not satisfy the other.
+/// 11. Alignment preconditions:
 ///
-/// Currently this attribute does not work by itself.
-/// It needs to be used inside of a context that is annotated by a `pre` attribute.
+///     This precondition requires that a raw pointer is properly aligned for the type it points
+///     to. Alignment is a separate requirement from validity, so it can be stated independently
+///     of `valid_ptr`.
 ///
-/// The purpose of this attribute is to apply the preconditions defined on items in a
-/// [`extern_crate` module](attr.extern_crate.html) to the use of the original item.
+///     The syntax is `#[pre(kind = aligned(<ptr_name>))]`.
 ///
-/// It is useful if you want to check preconditions for an existing file without making
-/// modifications to the file besides adding attributes.
+///     - `<ptr_name>`: The identifier of the pointer argument that must be aligned.
 ///
-/// For most use cases, it is better to just use the item inside of the `extern_crate` module
-/// directly. The only case where this attribute is currently absolutely needed, is if you want to
-/// check preconditions for functions and methods defined inside of an `impl` block in an
-/// `extern_crate` attribute.
+///     Alignment is not checked at runtime.
 ///
-/// # Examples
+///     ### Example
 ///
-/// Basic usage:
+///     ```rust
+///     # use pre::pre;
+///     #
+///     #[pre(valid_ptr(ptr, r))]
+///     #[pre(kind = aligned(ptr))]
+///     unsafe fn read(ptr: *const u32) -> u32 {
+///         *ptr
+///     }
 ///
-/// ```rust
-/// use pre::pre;
+///     #[pre]
+///     fn main() {
+///         let value = 42;
 ///
-/// #[pre::extern_crate(std)]
-/// mod new_std {
-///     mod ptr {
-///         #[pre(valid_ptr(dst, w))]
-///         unsafe fn write_unaligned<T>(dst: *mut T, src: T);
+///         #[assure(valid_ptr(ptr, r), reason = "the pointer is created from a reference")]
+///         #[assure(kind = aligned(ptr), reason = "references are always aligned")]
+///         let result = unsafe { read(&value) };
+///         assert_eq!(result, 42);
 ///     }
-/// }
+///     ```
 ///
-/// #[pre]
-/// fn main() {
-///     // Replacing this with `new_std::ptr::write_unaligned` would make the first `forward`
-///     // attribute below unnecessary.
-///     use std::ptr::write_unaligned;
+///     `kind = aligned(ptr)` is a different precondition than `valid_ptr(ptr, r)` and
+///     `kind = non_null(ptr)`, even though they refer to the same pointer.
+/// 12. Initialization preconditions:
 ///
-///     let mut x = 0;
+///     This precondition requires that a raw pointer points to initialized memory. A pointer can
+///     be valid and aligned, but still point to uninitialized memory, such as the memory of a
+///     `MaybeUninit`. Reading from it is only allowed if the memory is initialized.
 ///
-///     // No preconditions are checked here.
-///     unsafe { write_unaligned(&mut x, 1) };
-/// #   assert_eq!(x, 1);
+///     The syntax is `#[pre(kind = init(<ptr_name>))]`, `initialized` is accepted instead of `init`
+///     as well.
 ///
-///     // Here the actual function being called is `new_std::ptr::write_unaligned`, which checks
-///     // the preconditions and is otherwise functionally equivalent to
-///     // `std::ptr::write_unaligned`.
-///     #[forward(new_std::ptr)]
-///     #[assure(
-///         valid_ptr(dst, w),
-///         reason = "`dst` is created from a reference"
-///     )]
-///     unsafe { write_unaligned(&mut x, 2) };
-/// #   assert_eq!(x, 2);
+///     - `<ptr_name>`: The identifier of the pointer argument that must point to initialized
+///       memory.
 ///
-///     // Here the `std` segment of the path is replaced with `new_std`, so again
-///     // `new_std::ptr::write_unaligned` is called.
-///     // The same effect could be achieved without a `forward` attribute, by replacing the `std`
-///     // in the path of the call with `new_std`.
-///     #[forward(std -> new_std)]
-///     #[assure(
-///         valid_ptr(dst, w),
-///         reason = "`dst` is created from a reference"
-///     )]
-///     unsafe { std::ptr::write_unaligned(&mut x, 3) };
-/// #   assert_eq!(x, 3);
-/// }
-/// ```
+///     Initialization is not checked at runtime.
 ///
-/// For functions and methods inside of `impl` blocks, using the `forward` attribute with the `impl`
-/// keyword inside is necessary to check the preconditions:
+///     ### Example
 ///
-/// ```rust
-/// use pre::pre;
+///     ```rust
+///     # use pre::pre;
+///     # use std::mem::MaybeUninit;
+///     #
+///     #[pre(valid_ptr(ptr, w))]
+///     #[pre(kind = aligned(ptr))]
+///     unsafe fn write(ptr: *mut u32, value: u32) {
+///         ptr.write(value);
+///     }
 ///
-/// #[pre::extern_crate(std)]
-/// mod new_std {
-///     mod ptr {
-///         impl<T> NonNull<T> {
-///             #[pre(!ptr.is_null())]
-///             const unsafe fn new_unchecked(ptr: *mut T) -> NonNull<T>;
-///         }
+///     #[pre(valid_ptr(ptr, r))]
+///     #[pre(kind = aligned(ptr))]
+///     #[pre(kind = init(ptr))]
+///     unsafe fn read(ptr: *const u32) -> u32 {
+///         *ptr
 ///     }
-/// }
 ///
-/// #[pre]
-/// fn main() {
-///     let mut val = 0;
+///     #[pre]
+///     fn main() {
+///         let mut slot = MaybeUninit::<u32>::uninit();
 ///
-///     // Even though this uses the `NonNull` type through the `extern_crate` module, this will
-///     // unfortunately not check the preconditions.
-///     let non_null = unsafe { new_std::ptr::NonNull::new_unchecked(&mut val) };
+///         #[assure(valid_ptr(ptr, w), reason = "the pointer is created from a `MaybeUninit`")]
+///         #[assure(kind = aligned(ptr), reason = "`MaybeUninit` has the alignment of `u32`")]
+///         unsafe { write(slot.as_mut_ptr(), 42) };
 ///
-///     // This call actually checks the preconditions. Note the `impl` keyword before the path
-///     // below. This is required, so that the preconditions can be properly checked for a
-///     // function in an `impl` block inside of a `extern_crate` module.
-///     #[forward(impl new_std::ptr::NonNull)]
-///     #[assure(
-///         !ptr.is_null(),
-///         reason = "a reference is never null"
-///     )]
-///     let non_null = unsafe { new_std::ptr::NonNull::new_unchecked(&mut val) };
+///         #[assure(valid_ptr(ptr, r), reason = "the pointer is created from a `MaybeUninit`")]
+///         #[assure(kind = aligned(ptr), reason = "`MaybeUninit` has the alignment of `u32`")]
+///         #[assure(kind = init(ptr), reason = "the value was written above")]
+///         let result = unsafe { read(slot.as_ptr()) };
+///         assert_eq!(result, 42);
+///     }
+///     ```
 ///
-///     // The same thing also works when using the `NonNull` through the `std::ptr` path.
-///     #[forward(impl new_std::ptr::NonNull)]
-///     #[assure(
-///         !ptr.is_null(),
-///         reason = "a reference is never null"
-///     )]
-///     let non_null = unsafe { std::ptr::NonNull::new_unchecked(&mut val) };
-/// }
-/// ```
+///     `kind = init(ptr)` is a different precondition than `valid_ptr(ptr, r)` and
+///     `kind = aligned(ptr)`, so a function that may be called with uninitialized memory can simply
+///     leave it out.
+/// 13. Non-overlapping preconditions:
 ///
-/// # Syntax
+///     This precondition requires that the memory regions behind two pointers do not overlap, as
+///     it is required for example by `copy_nonoverlapping`. Both regions consist of `count`
+///     elements of the type the pointers point to, so both pointers must point to the same type.
 ///
-/// This attribute has three different forms:
+///     The syntax is `#[pre(kind = non_overlapping(<first ptr>, <second ptr>, <count>))]`.
 ///
-/// - [Direct call](#direct-call)
-/// - [Path replacement](#path-replacement)
-/// - [Impl call](#impl-call)
+///     - `<first ptr>` and `<second ptr>`: The identifiers of the two pointers.
+///     - `<count>`: The identifier of the number of elements in each region.
 ///
-/// ## Direct call
+///     The order of the pointers does not matter, so `kind = non_overlapping(dst, src, count)` can
+///     be assured as `kind = non_overlapping(src, dst, count)`. Both are shown with the names of
+///     the pointers in sorted order. The precondition is checked at runtime using `debug_assert`,
+///     just like boolean preconditions.
 ///
-/// `#[forward(<path>)]`
+///     ### Example
 ///
-/// ### How it works
+///     ```rust
+///     # use pre::pre;
+///     #
+///     #[pre(valid_ptr(src, r))]
+///     #[pre(valid_ptr(dst, w))]
+///     #[pre(kind = non_overlapping(dst, src, count))]
+///     unsafe fn copy_nonoverlapping(src: *const u8, dst: *mut u8, count: usize) {
+///         std::ptr::copy_nonoverlapping(src, dst, count)
+///     }
 ///
-/// `<path>` is prepended to the path of the annotated call.
+///     #[pre]
+///     fn main() {
+///         let src = [1, 2, 3, 4];
+///         let mut dst = [0; 4];
+///
+///         #[assure(valid_ptr(src, r), reason = "`src` points to an array")]
+///         #[assure(valid_ptr(dst, w), reason = "`dst` points to an array")]
+///         #[assure(kind = non_overlapping(src, dst, count), reason = "the arrays are different")]
+///         unsafe {
+///             copy_nonoverlapping(src.as_ptr(), dst.as_mut_ptr(), 4)
+///         };
+///         assert_eq!(dst, [1, 2, 3, 4]);
+///     }
+///     ```
+/// 14. Length preconditions:
+///
+///     This precondition requires that a value, such as a slice or a buffer behind a pointer, has
+///     a given length. `len` requires exactly that length, while `min_len` only requires the
+///     length to be at least that long.
+///
+///     The syntax is `#[pre(kind = len(<ident>, <length>))]` or
+///     `#[pre(kind = min_len(<ident>, <length>))]`.
+///
+///     - `<ident>`: The identifier of the value.
+///     - `<length>`: The required length, either an integer literal or the identifier of another
+///       parameter.
+///
+///     `kind = len(buf, 16)` and `kind = min_len(buf, 16)` are different preconditions, so one
+///     cannot be assured in place of the other. The length is only documented and not checked at
+///     runtime.
+///
+///     ### Example
+///
+///     ```rust
+///     # use pre::pre;
+///     #
+///     #[pre(valid_ptr(buf, r))]
+///     #[pre(kind = min_len(buf, count))]
+///     unsafe fn sum(buf: *const u8, count: usize) -> u32 {
+///         (0..count).map(|i| *buf.add(i) as u32).sum()
+///     }
+///
+///     #[pre(kind = len(key, 16))]
+///     fn checksum(key: &[u8]) -> u8 {
+///         key.iter().fold(0, |sum, byte| sum ^ byte)
+///     }
+///
+///     #[pre]
+///     fn main() {
+///         let buf = [1, 2, 3, 4];
+///
+///         #[assure(valid_ptr(buf, r), reason = "the pointer is created from an array")]
+///         #[assure(kind = min_len(buf, count), reason = "the array has four elements")]
+///         let result = unsafe { sum(buf.as_ptr(), 3) };
+///         assert_eq!(result, 6);
+///
+///         #[assure(kind = len(key, 16), reason = "the array has 16 elements")]
+///         let result = checksum(&[0; 16]);
+///         assert_eq!(result, 0);
+///     }
+///     ```
+/// 15. Nonzero preconditions:
+///
+///     This precondition requires that an integer, such as a divisor, is not zero.
+///
+///     The syntax is `#[pre(kind = nonzero(<ident>))]`.
+///
+///     - `<ident>`: The identifier of the integer parameter that must not be zero.
+///
+///     `kind = nonzero(x)` and `x != 0` are different preconditions, so one cannot be assured in
+///     place of the other. With the `runtime-checks` feature enabled, a `debug_assert` statement
+///     checking the value is added to the function, which can be disabled by a
+///     `#[pre(no_debug_assert)]` attribute.
+///
+///     ### Example
+///
+///     ```rust
+///     # use pre::pre;
+///     #
+///     #[pre(kind = nonzero(divisor))]
+///     fn divide(dividend: u32, divisor: u32) -> u32 {
+///         dividend / divisor
+///     }
+///
+///     #[pre]
+///     fn main() {
+///         #[assure(kind = nonzero(divisor), reason = "`4` is not zero")]
+///         let result = divide(12, 4);
+///         assert_eq!(result, 3);
+///     }
+///     ```
+/// 16. Sorted preconditions:
+///
+///     This precondition requires that a slice is sorted, such as the input of a binary search.
+///
+///     The syntax is `#[pre(kind = sorted(<ident>))]` or
+///     `#[pre(kind = sorted(<ident>, by = "<order>"))]`.
+///
+///     - `<ident>`: The identifier of the slice parameter that must be sorted.
+///     - `<order>`: An optional description of the order, such as the key function to sort by.
+///       Without it, the slice must be sorted in ascending order.
+///
+///     Two `sorted` preconditions with different orders are different preconditions, so a slice
+///     sorted by one key cannot be passed where it must be sorted by another one. A `sorted`
+///     precondition is also different from a custom precondition with the same meaning, such as
+///     `"`v` is sorted"`. To migrate from such a custom precondition, change it in the
+///     declaration of a function and in the `assure` attributes of its calls at the same time.
+///     Functions can be migrated one at a time, because both kinds can be used side by side.
+///
+///     ### Example
+///
+///     ```rust
+///     # use pre::pre;
+///     #
+///     #[pre(kind = sorted(haystack, by = "the `id` field"))]
+///     fn contains_id(haystack: &[(u32, &str)], id: u32) -> bool {
+///         haystack.binary_search_by_key(&id, |&(id, _)| id).is_ok()
+///     }
+///
+///     #[pre]
+///     fn main() {
+///         let users = [(1, "alice"), (4, "bob"), (7, "carol")];
+///
+///         #[assure(
+///             kind = sorted(haystack, by = "the `id` field"),
+///             reason = "the ids are written in ascending order"
+///         )]
+///         let found = contains_id(&users, 4);
+///         assert!(found);
+///     }
+///     ```
+/// 17. Alignment preconditions for a type:
+///
+///     This precondition requires that a raw pointer is properly aligned for a specific type,
+///     which means that its address is a multiple of `align_of::<T>()`. This is useful, when the
+///     pointer is cast to a pointer to a different type before it is used, such as a `*const u8`
+///     that is read as a `u64`.
+///
+///     The syntax is `#[pre(kind = proper_align(<ptr_name>, <type>))]`.
+///
+///     - `<ptr_name>`: The identifier of the pointer argument that must be aligned.
+///     - `<type>`: The type whose alignment the pointer must have.
+///
+///     Two `proper_align` preconditions with different types are different preconditions, so
+///     assuring the alignment for `u32` does not satisfy a function that requires the alignment
+///     for `u64`. This is also true for types that happen to have the same alignment. A
+///     `proper_align` precondition is also different from an `aligned` precondition for the same
+///     pointer. Alignment is not checked at runtime.
+///
+///     ### Example
+///
+///     ```rust
+///     # use pre::pre;
+///     #
+///     #[pre(valid_ptr(ptr, r))]
+///     #[pre(kind = proper_align(ptr, u64))]
+///     unsafe fn read_u64(ptr: *const u8) -> u64 {
+///         *(ptr as *const u64)
+///     }
+///
+///     #[pre]
+///     fn main() {
+///         let value: u64 = 42;
+///         let ptr = &value as *const u64 as *const u8;
+///
+///         #[assure(valid_ptr(ptr, r), reason = "the pointer is created from a reference")]
+///         #[assure(
+///             kind = proper_align(ptr, u64),
+///             reason = "the pointer is created from a reference to a `u64`"
+///         )]
+///         let result = unsafe { read_u64(ptr) };
+///         assert_eq!(result, 42);
+///     }
+///     ```
+/// 18. Readable and writable length preconditions:
+///
+///     These preconditions state how many elements behind a raw pointer can be read or written.
+///     This is more precise than `valid_ptr`, which does not say how many elements are valid.
+///
+///     The syntax is `#[pre(kind = readable_len(<ptr_name>, <length>))]` or
+///     `#[pre(kind = writable_len(<ptr_name>, <length>))]`.
+///
+///     - `<ptr_name>`: The identifier of the pointer argument.
+///     - `<length>`: The number of elements that can be accessed. This is either an integer
+///       literal or the identifier of another parameter.
+///
+///     `kind = readable_len(p, n)` and `kind = writable_len(p, n)` are different preconditions, so
+///     assuring that elements can be read does not satisfy a function that writes to them. A
+///     function that does both needs both preconditions. With the `runtime-checks` feature
+///     enabled, a `debug_assert` statement checks that the pointer is not null, unless the length
+///     is zero.
+///
+///     ### Example
+///
+///     ```rust
+///     # use pre::pre;
+///     #
+///     #[pre(kind = readable_len(src, count))]
+///     #[pre(kind = writable_len(dst, count))]
+///     #[pre(kind = non_overlapping(src, dst, count))]
+///     unsafe fn copy(src: *const u8, dst: *mut u8, count: usize) {
+///         std::ptr::copy_nonoverlapping(src, dst, count);
+///     }
+///
+///     #[pre]
+///     fn main() {
+///         let src = [1, 2, 3];
+///         let mut dst = [0; 3];
+///
+///         #[assure(kind = readable_len(src, count), reason = "`src` has 3 elements")]
+///         #[assure(kind = writable_len(dst, count), reason = "`dst` has 3 elements")]
+///         #[assure(
+///             kind = non_overlapping(src, dst, count),
+///             reason = "`src` and `dst` are different arrays"
+///         )]
+///         unsafe {
+///             copy(src.as_ptr(), dst.as_mut_ptr(), 3)
+///         };
+///         assert_eq!(dst, [1, 2, 3]);
+///     }
+///     ```
+///
+/// 19. Exclusive access preconditions:
+///
+///     This precondition states that a raw pointer is the only way to access the memory behind it
+///     while the function runs, like a `&mut` reference. No other pointer or reference may be
+///     used to access that memory in the meantime.
+///
+///     The syntax is `#[pre(kind = exclusive(<ptr_name>))]`. `no_alias` is accepted as another
+///     spelling: `#[pre(kind = no_alias(<ptr_name>))]` is the same precondition.
+///
+///     - `<ptr_name>`: The identifier of the pointer argument that must not be aliased.
+///
+///     `kind = exclusive(ptr)` is a different precondition than `valid_ptr(ptr, w)` and
+///     `kind = init(ptr)`, so a pointer that is valid for writes is not assumed to be unaliased.
+///     Exclusivity must be assured with its own reason. It is not checked at runtime.
+///
+///     ### Example
+///
+///     ```rust
+///     # use pre::pre;
+///     #
+///     #[pre(valid_ptr(ptr, r+w))]
+///     #[pre(kind = init(ptr))]
+///     #[pre(kind = exclusive(ptr))]
+///     unsafe fn increment(ptr: *mut u32) {
+///         *ptr += 1;
+///     }
+///
+///     #[pre]
+///     fn main() {
+///         let mut value = 41;
+///
+///         #[assure(valid_ptr(ptr, r+w), reason = "the pointer is created from a reference")]
+///         #[assure(kind = init(ptr), reason = "`value` is initialized")]
+///         #[assure(kind = exclusive(ptr), reason = "`value` is not borrowed during the call")]
+///         unsafe { increment(&mut value) };
+///         assert_eq!(value, 42);
+///     }
+///     ```
+///
+/// 20. UTF-8 preconditions:
+///
+///     This precondition requires that bytes are valid UTF-8, such as the bytes passed to
+///     [`str::from_utf8_unchecked`](https://doc.rust-lang.org/core/str/fn.from_utf8_unchecked.html).
+///
+///     The syntax is `#[pre(kind = utf8(<ident>))]`.
+///
+///     - `<ident>`: The identifier of the parameter containing the bytes.
+///
+///     `kind = utf8(bytes)` is independent of the other preconditions, so a pointer can be required
+///     to be both valid and to point to valid UTF-8 by combining `kind = utf8(ptr)` with
+///     `valid_ptr(ptr, r)`. With the `runtime-checks` feature enabled, a `debug_assert` statement
+///     checking byte slices and vectors with `core::str::from_utf8` is added to the function, which
+///     can be disabled by a `#[pre(no_debug_assert)]` attribute. Pointers are not checked, because
+///     the length of the memory behind them is not known.
+///
+///     ### Example
+///
+///     ```rust
+///     # use pre::pre;
+///     #
+///     #[pre(kind = utf8(bytes))]
+///     unsafe fn to_str(bytes: &[u8]) -> &str {
+///         core::str::from_utf8_unchecked(bytes)
+///     }
+///
+///     #[pre]
+///     fn main() {
+///         #[assure(kind = utf8(bytes), reason = "the bytes are taken from a string literal")]
+///         let text = unsafe { to_str("pre".as_bytes()) };
+///         assert_eq!(text, "pre");
+///     }
+///     ```
+///
+/// 21. Thread affinity preconditions:
+///
+///     This precondition requires that a function is called on a specific thread, such as the
+///     main thread of a UI framework.
+///
+///     The syntax is `#[pre(kind = on_thread("<name>"))]`.
+///
+///     - `<name>`: The name of the thread, as returned by
+///       [`Thread::name`](https://doc.rust-lang.org/std/thread/struct.Thread.html#method.name).
+///
+///     With the `runtime-checks` and `std` features enabled, a `debug_assert` statement comparing
+///     the name of the current thread to `<name>` is added to the function, which can be disabled
+///     by a `#[pre(no_debug_assert)]` attribute. Without the `std` feature, the thread is not
+///     checked.
+///
+///     ### Example
+///
+///     ```rust
+///     # use pre::pre;
+///     #
+///     #[pre(kind = on_thread("main"))]
+///     fn redraw() {}
+///
+///     #[pre]
+///     fn main() {
+///         #[assure(kind = on_thread("main"), reason = "this is called directly from `main`")]
+///         redraw();
+///     }
+///     ```
+///
+/// # General syntax
+///
+/// There are ten uses of the `pre` attribute:
+///
+/// 1. Specify one or multiple preconditions (for the exact syntax of the preconditions, see
+///    ["Precondition syntax"](#precondition-syntax)):
+///
+///    ```rust,ignore
+///    #[pre(<first precondition>)]
+///    #[pre(<second precondition>)]
+///    #[pre(<third precondition>)]
+///    fn foo() {}
+///    ```
+/// 2. Enable handling of [`assure`](attr.assure.html) and [`forward`](attr.forward.html)
+///    attributes for the annotated item (see ["Checking functionality"](#checking-functionality)):
+///
+///    ```rust
+///    # use pre::pre;
+///    #
+///    #[pre]
+///    fn main() {
+///        // `assure` and `forward` attributes here are properly handled.
+///    }
+///    ```
+///
+///    This works with any of the forms of the `pre` attribute, but in case no other functionality
+///    is needed, a bare `#[pre]` can be used.
+/// 3. Disable generating documentation for the preconditions (see ["Documentation on items with
+///    preconditions"](#documentation-on-items-with-preconditions)):
+///
+///    ```rust
+///    # use pre::pre;
+///    #
+///    #[pre(no_doc)]
+///    #[pre("some precondition")]
+///    fn foo() {} // foo will not have any documentation generated by pre.
+///    ```
+/// 4. Disable debug assertions for boolean preconditions.
+///    ```rust
+///    # use pre::pre;
+///    #
+///    #[pre(no_debug_assert)]
+///    #[pre(old_val < new_val)]
+///    fn foo() {} // foo will not have any `debug_assert`s generated by pre.
+///    ```
+/// 5. Report the location of the caller in the message of failing debug assertions. This also adds
+///    a `#[track_caller]` attribute to the function.
+///    ```rust
+///    # use pre::pre;
+///    #
+///    #[pre(track_caller)]
+///    #[pre(x > 17)]
+///    fn foo(x: i32) {} // panics with "precondition `x > 17` violated at <caller location>".
+///    ```
+/// 6. Require the reasons of all `assure` attributes in the item to match a pattern (see
+///    ["Requiring reasons to match a pattern"](#requiring-reasons-to-match-a-pattern)):
+///
+///    ```rust,ignore
+///    #[pre(reason_pattern = "JIRA-\\d+")]
+///    fn main() {}
+///    ```
+/// 7. Declare that two preconditions of the functions in a module cannot both hold (see
+///    ["Mutually exclusive preconditions"](#mutually-exclusive-preconditions)):
+///
+///    ```rust,ignore
+///    #[pre(exclusive(<first precondition>, <second precondition>))]
+///    mod foo {}
+///    ```
+/// 8. Require the custom preconditions in the item to come from an approved vocabulary (see
+///    ["Restricting custom preconditions to a vocabulary"](#restricting-custom-preconditions-to-a-vocabulary)):
+///
+///    ```rust,ignore
+///    #[pre(vocabulary = "<path to the vocabulary>")]
+///    mod foo {}
+///    ```
+/// 9. Give the marker parameter that is added to the function a name (see ["Naming the marker
+///    parameter"](#naming-the-marker-parameter)):
+///
+///    ```rust,ignore
+///    #[pre(marker = "__pre")]
+///    #[pre(x > 0)]
+///    fn foo(x: i32) {}
+///    ```
+/// 10. Declare the preconditions of an alias defined with
+///     [`precondition_alias!`](macro.precondition_alias.html) for an identifier:
+///
+///     ```rust,ignore
+///     #[pre(alias <name of the alias> for <identifier>)]
+///     fn foo() {}
+///     ```
+///
+/// # Checking functionality
+///
+/// The `pre` attribute can also be used to enable the functionalities of the
+/// [`assure`](attr.assure.html) and [`forward`](attr.forward.html) attributes for the item it is
+/// attached to. In this case it can be left empty: `#[pre]`. Any other form of a `pre` attribute
+/// being present on an item renders the empty one obsolete though, as any of its forms enables
+/// this functionality.
+///
+/// Doing this is currently necessary, because the current (1.44.1) stable rust compiler does not
+/// support attribute macros being applied to statements or expressions directly.
+///
+/// If a `pre` attribute is applied to a whole module, the preconditions of the functions defined
+/// directly in that module are known. For calls to such a function that have at least one `assure`
+/// attribute, every precondition that is declared, but not assured, is reported as a separate
+/// error. This only applies to calls by the name of the function, optionally preceded by `self::`.
+/// Calls without any `assure` attribute are reported by the compiler instead, because they lack
+/// the argument that `pre` adds to the function (see ["Checking
+/// functionality"](#checking-functionality)), and calls of functions in other modules are only
+/// checked that way.
+///
+/// The `pre` attributes of methods in `impl` blocks inside of the annotated item are handled the
+/// same way as those of functions, so they don't need to be imported separately. Methods are only
+/// supported on the nightly compiler though.
+///
+/// # Preconditions on `impl` blocks
+///
+/// A precondition can also be attached to a whole `impl` block. It then applies to every method in
+/// that block, exactly as if it was written on each of them:
+///
+/// ```rust,ignore
+/// # use pre::pre;
+/// struct Buffer;
+///
+/// #[pre("the buffer is initialized")]
+/// impl Buffer {
+///     // Requires "the buffer is initialized".
+///     fn read(&self) {}
+///
+///     // Requires "the buffer is initialized" and `len > 0`.
+///     #[pre(len > 0)]
+///     fn truncate(&mut self, len: usize) {}
+/// }
+/// ```
+///
+/// The preconditions of the `impl` block are added after the ones of the method. A method may
+/// declare a precondition of its `impl` block again, which does not change anything.
+/// Preconditions cannot be added to `default` methods this way.
+///
+/// # Preconditions on function pointer types
+///
+/// A type alias of a function pointer type can have preconditions as well. They document the
+/// contract that callers of a function stored in such a value must uphold:
+///
+/// ```rust
+/// # use pre::pre;
+/// #[pre(valid_ptr(arg0, r))]
+/// #[pre(kind = readable_len(arg0, len))]
+/// type Callback = unsafe fn(*const u8, len: usize) -> u8;
+/// ```
+///
+/// The parameters of a function pointer type usually have no names, so they are referred to by
+/// their position: `arg0` is the first parameter, `arg1` the second and so on. A parameter with a
+/// name in the type, such as `len` above, can also be referred to by that name. Like for functions,
+/// the pointers and lengths of the preconditions must refer to parameters.
+///
+/// The preconditions only appear in the documentation of the type alias. Calls through a function
+/// pointer are not checked, because the function that is called is not known.
+///
+/// # Documentation on items with preconditions
+///
+/// Items annotated with one or more preconditions have information about their preconditions
+/// and how to call them with the preconditions appended at the end of their documentation.
+///
+/// If you wish not to add such documentation to a particular item, you can add `#[pre(no_doc)]` to
+/// the attributes of the item, to prevent its generation.
+///
+/// The documentation is generated by the `doc` feature, which is enabled by default. To not
+/// generate it for any item, disable the default features of `pre` and enable only `std` and `core`
+/// again. The documentation is appended after the existing documentation of the item, so that the
+/// first line of the existing documentation remains its summary.
+///
+/// # Preconditions on `const fn`s
+///
+/// A `const fn` with preconditions stays a `const fn`, so it can still be called in a `const`
+/// initializer. The `assure` attributes for such a call are placed the same way as elsewhere:
+///
+/// ```rust
+/// # use pre::pre;
+/// #
+/// #[pre(x > 0)]
+/// const fn double(x: i32) -> i32 {
+///     x * 2
+/// }
+///
+/// #[pre]
+/// const VALUE: i32 = {
+///     #[assure(x > 0, reason = "21 > 0")]
+///     double(21)
+/// };
+/// #
+/// # fn main() {
+/// #     assert_eq!(VALUE, 42);
+/// # }
+/// ```
+///
+/// The `debug_assert`s checking the preconditions are also evaluated at compile time, so a
+/// precondition that doesn't hold in a `const` initializer is reported as an error in debug
+/// builds. Their messages only contain the precondition, because messages are not formatted
+/// during constant evaluation. The addresses of pointers are not known at compile time, so
+/// `non_overlapping` preconditions are not checked in a `const fn`.
+///
+/// # Requiring reasons to match a pattern
+///
+/// Some projects require every justification to refer to a ticket or an issue. This can be enforced
+/// by adding `#[pre(reason_pattern = "<pattern>")]` to an item. The reasons of all `assure`
+/// attributes inside of that item then must contain a match of the pattern, otherwise an error is
+/// emitted. By default no pattern is required.
+///
+/// The pattern supports a small subset of the regular expression syntax: literal characters,
+/// escaped special characters (e.g. `\.`), `.`, `\d`, `\w`, `\s`, character classes like
+/// `[A-Z0-9]` or `[^ ]`, the quantifiers `*`, `+` and `?` as well as `^` and `$` to anchor the
+/// pattern at the start or end of the reason.
+///
+/// ```rust
+/// # use pre::pre;
+/// #
+/// #[pre("some precondition")]
+/// fn foo() {}
+///
+/// #[pre(reason_pattern = "JIRA-\\d+")]
+/// fn main() {
+///     #[assure("some precondition", reason = "checked in JIRA-1234")]
+///     foo();
+/// }
+/// ```
+///
+/// If the pattern is specified on a function, it applies to that function. If it is specified on
+/// any other item, such as a module, it applies to all functions in that item. An inner pattern
+/// overrides an outer one.
+///
+/// # Mutually exclusive preconditions
+///
+/// Some preconditions contradict each other, such as a buffer being empty and being full. Adding
+/// `#[pre(exclusive(<first precondition>, <second precondition>))]` to a module declares that the
+/// two preconditions cannot both hold. Every call in the module that assures both preconditions is
+/// then reported as an error, because at least one of the reasons must be wrong.
+///
+/// ```rust,compile_fail
+/// # use pre::pre;
+/// #
+/// #[pre(exclusive("the buffer is empty", "the buffer is full"))]
+/// mod buffer {
+///     #[pre("the buffer is empty")]
+///     fn fill() {}
+///
+///     #[pre("the buffer is full")]
+///     fn drain() {}
+///
+///     #[pre("the buffer is empty")]
+///     #[pre("the buffer is full")]
+///     fn reset() {}
+///
+///     fn cycle() {
+///         #[assure("the buffer is empty", reason = "the buffer is created empty")]
+///         fill();
+///
+///         #[assure("the buffer is empty", reason = "`drain` was not called yet")]
+///         #[assure("the buffer is full", reason = "`fill` fills the buffer")]
+///         reset(); // error: the preconditions are mutually exclusive
+///     }
+/// }
+/// ```
+///
+/// Both preconditions must be declared by functions in the module or its nested modules. The
+/// declaration applies to all calls in the module, including calls using a
+/// [`forward`](attr.forward.html) attribute, but not to calls in
+/// [`pre_call!`](macro.pre_call.html) or [`pre_for_each!`](macro.pre_for_each.html). It cannot be
+/// added to a function.
+///
+/// # Restricting custom preconditions to a vocabulary
+///
+/// To keep the wording of contracts consistent, the custom preconditions of an item can be
+/// restricted to a list of approved phrases with `#[pre(vocabulary = "<path>")]`. The path refers
+/// to a text file with one phrase per line and is relative to the file containing the attribute,
+/// like for [`include_str!`](https://doc.rust-lang.org/std/macro.include_str.html). Empty lines and
+/// lines starting with `#` are ignored.
+///
+/// ```text
+/// # vocabulary.txt
+/// the buffer is initialized
+/// the pointer is aligned
+/// ```
+///
+/// Every custom precondition declared by a function in the item must then be one of the phrases.
+/// Otherwise an error is emitted, which suggests the most similar approved phrase.
+///
+/// ```rust,ignore
+/// #[pre(vocabulary = "vocabulary.txt")]
+/// mod buffer {
+///     #[pre("the buffer is initialized")]
+///     fn read() {}
+///
+///     #[pre("the buffer is initialised")] // error: suggests "the buffer is initialized"
+///     fn write() {}
+/// }
+/// ```
+///
+/// The vocabulary applies in the same way as a
+/// [`reason_pattern`](#requiring-reasons-to-match-a-pattern). To use a vocabulary for all items of a
+/// crate, set the `PRE_VOCABULARY` environment variable to the path of the file relative to the
+/// directory containing the `Cargo.toml` of the crate while compiling it. A vocabulary specified in
+/// an attribute takes precedence. With the `case-insensitive-custom` feature, the phrases are
+/// compared ignoring their case. Other kinds of preconditions are not affected. The items are
+/// compiled again when a vocabulary file or the `PRE_VOCABULARY` environment variable changes.
+///
+/// # Keeping the function signature with the `assoc-const` feature
+///
+/// By default the preconditions are checked by adding an additional parameter to the function.
+/// If the `assoc-const` feature is enabled, the function signature is left unchanged instead. The
+/// preconditions are stored in an associated constant of a generated struct with the same name as
+/// the function and compared to the `assure`d preconditions during constant evaluation at the call
+/// site.
+///
+/// This comes with some tradeoffs:
+///
+/// - Function pointers and functions passed as arguments keep their original type.
+/// - **Most calls without any `assure` attribute compile without an error.** The only rejected
+///   unassured calls are those inside of a module with a `pre` attribute that call a function of
+///   that module by its name or by a relative path that stays inside of it, such as `self::foo()`,
+///   `inner::foo()` or `super::foo()`. Method calls, calls through `use` declarations or `crate::`
+///   paths, calls of functions in other crates or in [`extern_crate`](attr.extern_crate.html)
+///   modules and all calls outside of such a module are not checked.
+/// - Mismatching preconditions only produce an error stating that the preconditions don't match,
+///   without naming the precondition that differs.
+/// - Preconditions on methods and `assure` attributes on method calls are not supported, the same
+///   way as on the stable compiler.
+/// - A compiler that supports panics in constant evaluation (Rust 1.57 or newer) is required.
+///
+/// The feature applies to the whole build, so all crates using pre use the same representation.
+///
+/// # Additional runtime checks with the `both` feature
+///
+/// Boolean preconditions are always checked both at compile time and with a `debug_assert` at
+/// runtime. Other preconditions are normally only checked at compile time. If the `both` feature
+/// is enabled, the function still gets the additional parameter used for the compile-time checks,
+/// but `pre` also adds `debug_assert`s for preconditions that can be partially checked at runtime:
+///
+/// - `valid_ptr(p, r)` checks that `p` is not null. The pointer must be a raw pointer parameter
+///   for this check to compile.
+///
+/// The checks are inserted at the start of the function body and don't interact with the
+/// additional parameter, so calls are written the same way as without the feature. They can be
+/// disabled for a single function with `#[pre(no_debug_assert)]`.
+///
+/// Enabling the feature has a compile-time cost: every checked precondition adds a statement to
+/// the body of the function that the compiler needs to check, even in release builds where the
+/// `debug_assert`s are removed afterwards.
+///
+/// # Restricting boolean preconditions to parameters with the `runtime-checks` feature
+///
+/// The `debug_assert` for a boolean precondition is inserted at the start of the function body,
+/// so it can only see the parameters of the function and the items in scope. If the `runtime-checks`
+/// feature is enabled, an error is emitted for every value in a boolean precondition that is not a
+/// parameter of the function. This makes sure that every boolean precondition describes the
+/// arguments of a call and that its `debug_assert` checks exactly what the callers assure:
+///
+/// ```rust,ignore
+/// #[pre(x < limit)] // error: `limit` is not a parameter of this function
+/// fn foo(x: i32) {}
+/// ```
+///
+/// Names starting with an uppercase letter are assumed to be constants and are allowed, as are
+/// the names of called functions and the parameters of closures in the expression. The check is
+/// skipped for expressions that contain macros or bind names in other ways, such as `let`,
+/// `if let`, `match`, `for` or closure parameters with patterns like `|(a, b)|`, because the names
+/// used there are not tracked. Functions with a `#[pre(no_debug_assert)]` attribute are not
+/// checked, because no `debug_assert` is generated for them.
+///
+/// The feature also adds a `debug_assert` for every
+/// [`nonzero`](#precondition-syntax) precondition, which checks that the value is not `0`.
+///
+/// # Removing all checks with the `strip` feature
+///
+/// If the `strip` feature is enabled, all `pre`, [`assure`](attr.assure.html),
+/// [`forward`](attr.forward.html) and [`forbid_pre`](attr.forbid_pre.html) attributes are removed
+/// without any further effects. Function
+/// signatures are left unchanged, no documentation or `debug_assert`s are generated and
+/// [`pre_call!`](macro.pre_call.html) expands to the call it contains. Modules annotated with
+/// [`extern_crate`](attr.extern_crate.html) only re-export the items of the original crate.
+///
+/// This is useful to generate a copy of code using pre that is free of any markers, for example to
+/// vendor it into a project that does not use pre. The preconditions are not checked at all in
+/// this mode, so it should not be used during development.
+///
+/// # Recording precondition coverage with the `coverage` feature
+///
+/// If the `coverage` feature is enabled, every module annotated with `pre` records how many call
+/// sites in it assure each precondition of the functions defined directly in the module. The counts
+/// are written to `pre-coverage.txt` in the `OUT_DIR` of the crate being compiled, so the crate
+/// needs a build script for the report to be created. All modules of a crate share one report,
+/// which is locked while it is updated. If the report cannot be written, a warning is emitted and
+/// the compilation continues. A lock left over from an aborted compilation is not removed
+/// automatically, so `pre-coverage.lock` needs to be deleted by hand in that case.
+///
+/// The report contains one line per declared precondition with the following tab-separated fields:
+///
+/// 1. the full path of the module, such as `my_crate::parser::inner`
+/// 2. the name of the function
+/// 3. the precondition as it is written in the `pre` attribute, such as `x > 0`
+/// 4. the number of call sites in the module that assure the precondition
+///
+/// The path of the module is derived from the location of the file containing the annotated item,
+/// so it is wrong for files included with `#[path]` attributes or `include!`. The lines are sorted
+/// and all lines of a module are replaced when it is compiled again. Calls using
+/// [`forward`](attr.forward.html) or [`pre_call!`](macro.pre_call.html) are not counted.
+///
+/// # Listing the preconditions with the `verbose` feature
+///
+/// If the `verbose` feature is enabled, a warning listing all preconditions of every function with
+/// a `pre` attribute is emitted while compiling. The preconditions are listed in the order used
+/// for the markers, so this can be used to confirm that a complex set of preconditions was parsed
+/// as intended, for example while writing a function or debugging unexpected errors at call
+/// sites.
+///
+/// Like the warning for `"TODO"` reasons, this only works on the nightly compiler. On the stable
+/// compiler, the feature has no effect.
+///
+/// # Requiring the nightly compiler with the `strict-spans` feature
+///
+/// Many errors point to a range of code, such as a whole `assure` attribute. Spans can only be
+/// joined into such a range on the nightly compiler, so on the stable compiler these errors only
+/// point to the first token of the range. If the `strict-spans` feature is enabled, every macro of
+/// pre emits an error when the stable compiler is used. This can be used to ensure that a crate is
+/// always built with the full diagnostics of the nightly compiler. On the nightly compiler, the
+/// feature has no effect.
+///
+/// # Annotations for verification tools with the `tool-annotations` feature
+///
+/// If the `tool-annotations` feature is enabled, every function with preconditions gets a hidden
+/// constant at the start of its body, which external static analyzers can look for:
+///
+/// ```rust,ignore
+/// #[doc(hidden)]
+/// #[allow(dead_code)]
+/// const __PRE_TOOL_ANNOTATION: (&str, &[&str]) = ("pre-tool-annotation/1", &["x > 0"]);
+/// ```
+///
+/// The first element names the version of the format, which changes whenever the format does.
+/// The second element lists the preconditions as they are written in the `pre` attributes, in a
+/// fixed order. The constant is local to the function body and never used, so it does not affect
+/// the compiled code.
+///
+/// # Reasons as `SAFETY` comments with the `safety-comments` feature
+///
+/// Tools that read the expanded code, such as the output of `cargo expand`, don't see the `assure`
+/// attributes anymore. If the `safety-comments` feature is enabled, every call with `assure`
+/// attributes is wrapped in a block that keeps the reasons next to the call. Comments don't
+/// survive macro expansion, so they are written as `doc` attributes of an unused constant:
+///
+/// ```rust,ignore
+/// ({
+///     #[doc = "SAFETY: x > 0: `1` is positive"]
+///     #[doc = "SAFETY: \"`x` is small\": `1` is small"]
+///     const _: () = ();
+///
+///     foo(1, /* the marker */)
+/// })
+/// ```
+///
+/// There is one line for every assured precondition, in the order of the `assure` attributes. It
+/// consists of `SAFETY: `, the precondition as it is written in a `pre` attribute, `: ` and the
+/// reason with its [placeholders](attr.assure.html#referring-to-the-precondition-in-the-reason)
+/// replaced. The block doesn't change what the call does.
+///
+/// # Migrating `# Safety` sections with the `suggest-preconditions` feature
+///
+/// Code written without `pre` often describes the preconditions of a function only in its
+/// documentation. If the `suggest-preconditions` feature is enabled, a warning is emitted for
+/// every function in an item with a `pre` attribute whose `# Safety` section lists preconditions
+/// that are not declared. The warning suggests the `pre` attributes that would declare them.
+///
+/// Only the list items in the `# Safety` section are considered, up to the next heading. An item
+/// that starts with a precondition in backticks, optionally followed by a colon and an
+/// explanation, is suggested as that precondition. Every other item is suggested as a custom
+/// precondition with the text of the item:
+///
+/// ```rust,ignore
+/// #[pre]
+/// mod legacy {
+///     /// # Safety
+///     ///
+///     /// - `valid_ptr(ptr, r)`: the pointer must be valid for reads
+///     /// - `ptr` must point to an initialized value
+///     pub unsafe fn read(ptr: *const u8) -> u8 {
+///         *ptr
+///     }
+/// }
+/// ```
+///
+/// results in a warning suggesting `#[pre(valid_ptr(ptr, r))]` and
+/// `#[pre("`ptr` must point to an initialized value")]` for `read`. Once an attribute is added, the
+/// precondition is no longer suggested. Nothing else about the generated code changes.
+///
+/// Like the warnings of the `verbose` feature, this only works on the nightly compiler. On the
+/// stable compiler, the feature has no effect.
+///
+/// # Showing the differences of the preconditions with the `diff-errors` feature
+///
+/// If a function defined in a module with a `pre` attribute is called with the wrong preconditions
+/// assured, the errors of the compiler refer to the generated markers, which are hard to read. If
+/// the `diff-errors` feature is enabled, such a call additionally gets a single error that lists
+/// all declared and assured preconditions as a unified diff, in the same order as in the markers:
+///
+/// ```text
+/// error: the assured preconditions don't match the preconditions of `foo`
+///
+///          = note: the differences are:
+///        --- declared
+///        +++ assured
+///        + x < 100
+///        - x > 17
+///          "is a prime number"
+/// ```
+///
+/// Preconditions marked with `-` are declared, but not assured, while preconditions marked with
+/// `+` are assured, but not declared. This replaces the separate errors for every precondition
+/// that is not assured. Functions that are defined elsewhere are not known to the `pre` attribute,
+/// so calls to them are not affected.
+///
+/// # Calling functions from fuzz harnesses with the `fuzzing` feature
+///
+/// Fuzz harnesses call functions with arbitrary inputs, so there is no reason to give for why the
+/// preconditions hold. If the `fuzzing` feature is enabled, every function with preconditions gets
+/// a hidden helper with the same signature and the name prefixed with `fuzz_`, which calls the
+/// function with all of its preconditions assured:
+///
+/// ```rust,ignore
+/// #[pre(!data.is_empty())]
+/// fn parse(data: &[u8]) -> u8 {
+///     data[0]
+/// }
+///
+/// fuzz_target!(|data: &[u8]| {
+///     if !data.is_empty() {
+///         fuzz_parse(data); // called without any `assure` attributes
+///     }
+/// });
+/// ```
+///
+/// **The helpers bypass the contract checking.** A harness using them must rule out the inputs
+/// that violate the preconditions itself, because they are never reported. Boolean preconditions
+/// are still checked by `debug_assert`s, which the fuzzer will report as crashes.
+///
+/// To prevent the helpers from being used in regular builds, they only exist when compiling with
+/// `--cfg fuzzing`, which `cargo fuzz` sets automatically. Crates using the feature should declare
+/// the configuration as expected, for example with `println!("cargo:rustc-check-cfg=cfg(fuzzing)")`
+/// in their build script. Methods taking `self` don't get a helper.
+///
+/// # Enumerating contracts at runtime with the `registry` feature
+///
+/// If the `registry` feature is enabled, every function with preconditions registers its
+/// [`Contract`](struct.Contract.html) in the global slice [`CONTRACTS`](static.CONTRACTS.html).
+/// This allows tools to list all contracts in a binary at runtime:
+///
+/// ```rust,ignore
+/// for contract in pre::CONTRACTS {
+///     println!("{}::{}: {:?}", contract.module_path, contract.function, contract.preconditions);
+/// }
+/// ```
+///
+/// The registration uses the [`linkme`](https://docs.rs/linkme) crate, which lets the linker
+/// collect the contracts into a single slice. No code runs at startup and enumerating the
+/// contracts only reads the slice. The runtime cost is the size of the binary: every registered
+/// function adds a static of three references and an address and the text of its module path, its
+/// name and its preconditions, which are kept even if the function itself is never used. It should therefore
+/// only be enabled in builds that need the registry.
+///
+/// Functions are registered with their name only, so methods of different types with the same
+/// name can only be told apart by their module. Functions declared in an
+/// [`extern_crate`](attr.extern_crate.html) module are not registered.
+///
+/// The contract of a function can also be looked up by its address with
+/// [`preconditions_of`](fn.preconditions_of.html), for example for a function that is only known as
+/// a function pointer. This only works for functions that are not generic, are not in an `impl`
+/// block, don't take `self` and don't mention `Self` in their signature.
+///
+/// # Passing the marker first with the `marker-first` feature
+///
+/// By default the marker for the preconditions is added as the last parameter of a function and
+/// as the last argument of every call. If the `marker-first` feature is enabled, it is added as
+/// the first one instead, directly after `self` for methods. This can be useful for code that
+/// forwards a variable number of trailing arguments, for example in macros.
+///
+/// The position of the marker must be the same for the definition of a function and all of its
+/// calls, otherwise they don't compile. Because of this, the feature applies to the whole build:
+/// since cargo unifies features, all crates using `pre` in one build see the same setting, which
+/// includes calls to functions from other crates and from the standard library wrappers. Markers
+/// that are [passed by hand](#passing-the-markers-by-hand) must be moved accordingly.
+///
+/// # Testing the enforcement with the `compile-fail-fixtures` feature
+///
+/// A function with preconditions is only useful if calls of it that don't assure the
+/// preconditions are rejected. If the `compile-fail-fixtures` feature is enabled, a
+/// [`trybuild`](https://docs.rs/trybuild) fixture is generated for every public function with
+/// preconditions inside of a module with a `pre` attribute. The fixture calls the function without
+/// any `assure` attributes, so it must fail to compile:
+///
+/// ```rust,ignore
+/// // This fixture was generated by `pre` for `my_crate::math::divide`.
+/// // It calls the function without assuring its preconditions, so it must fail to compile:
+/// // - divisor != 0
+///
+/// #[allow(unreachable_code)]
+/// fn main() {
+///     my_crate::math::divide(::core::todo!(), ::core::todo!());
+/// }
+/// ```
+///
+/// The fixtures are written to the directory in the `PRE_FIXTURES_DIR` environment variable,
+/// relative to the manifest of the crate, or to `pre-fixtures` in its `OUT_DIR` if the variable
+/// is not set. Each fixture is named after the path of the function relative to the annotated
+/// module, such as `math__divide.rs`. A typical workflow looks like this:
+///
+/// 1. Build the crate once with `PRE_FIXTURES_DIR=tests/enforced` and the feature enabled.
+/// 2. Add a test running the fixtures:
+///
+///    ```rust,ignore
+///    #[test]
+///    fn preconditions_are_enforced() {
+///        trybuild::TestCases::new().compile_fail("tests/enforced/*.rs");
+///    }
+///    ```
+/// 3. Run the test once with `TRYBUILD=overwrite` to record the errors, check that they complain
+///    about the missing `assure` attributes and commit the fixtures together with the errors.
+///
+/// Running the build again updates the fixtures of changed functions, but fixtures of removed
+/// functions are not deleted. Only the crates that are compiled again write their fixtures, and
+/// Cargo does not recompile a crate when only `PRE_FIXTURES_DIR` changes, so run
+/// `cargo clean -p <crate>` first to write all fixtures to a new directory.
+///
+/// The path of the function consists of the crate name followed by the modules from the annotated
+/// item to the function, so it is only correct for modules annotated directly at the crate root.
+/// The modules on that path must be public. Generic functions, functions in `impl` blocks,
+/// functions mentioning `Self` and functions that are not declared with `pub` don't get a fixture.
+/// With the `assoc-const` feature, calls without any `assure` attributes are not checked, so no
+/// fixtures are generated.
+///
+/// # Gating the contracts behind a feature with `PRE_CONTRACTS_FEATURE`
+///
+/// If the `PRE_CONTRACTS_FEATURE` environment variable is set to the name of a feature while
+/// compiling a crate, everything that `pre` generates to check the preconditions is put behind
+/// `#[cfg(feature = "<name>")]` or `#[cfg_attr(feature = "<name>", ...)]`. This includes the
+/// markers of definitions and calls, the documentation of the preconditions and the runtime checks
+/// of the `both` feature. Without the feature, functions with preconditions have their original
+/// signature and calls of them compile the same as calls of any other function. This makes it
+/// possible to let users of a crate decide whether they want the contracts.
+///
+/// The variable is usually set for the whole build, for example in the `[env]` section of a
+/// `.cargo/config.toml`:
+///
+/// ```toml
+/// [env]
+/// PRE_CONTRACTS_FEATURE = "contracts"
+/// ```
+///
+/// The feature is evaluated separately by every crate, so this requires some coordination:
+///
+/// - Every crate using `pre` that is compiled with the variable should declare the feature, even
+///   if it does not define any functions with preconditions. Otherwise the gated code is never
+///   compiled and `unexpected_cfgs` warns about the unknown feature.
+/// - A crate calling functions with preconditions of another crate must enable the feature of
+///   that crate together with its own, like `contracts = ["upstream/contracts"]`. Otherwise the
+///   calls pass markers the functions don't have, or the other way around.
+/// - The `pre` crate cannot declare the feature, so the functions in [`core`](core/index.html) and
+///   [`std`](std/index.html) are never gated. Calls of them can only be `assure`d while the
+///   feature is enabled.
+///
+/// Cargo does not know that the generated code depends on the variable, so changing it requires
+/// a clean build of the affected crates. What the `coverage`, `registry` and `tool-annotations`
+/// features generate is not gated.
+///
+/// # Passing the markers by hand
+///
+/// Macro-generated or generic code may need to pass the marker of a call by hand instead of using
+/// an [`assure`](attr.assure.html) attribute. The marker is the last argument of the call, or the
+/// first one with the [`marker-first`](#passing-the-marker-first-with-the-marker-first-feature)
+/// feature. No reasons are checked, so `assure` is preferable whenever possible.
+///
+/// On the nightly compiler, the marker is a `::core::marker::PhantomData` of a tuple with one type
+/// per precondition, such as [`ValidPtrCondition`](struct.ValidPtrCondition.html). The types are
+/// sorted in the order of the table below. Preconditions in the same row are sorted by their text
+/// as written in a `pre` attribute, such as `valid_ptr(p: * const u8, r+w)`, compared byte by byte.
+/// A `sorted` precondition without an order is represented with `""` as its order.
+///
+/// | Precondition                      | Nightly type                                |
+/// |-----------------------------------|---------------------------------------------|
+/// | `valid_ptr(p: *const u8, r+w)`    | `ValidPtrCondition<"p: * const u8", "r+w">` |
+/// | `x > 0`                           | `BooleanCondition<"x > 0">`                 |
+/// | `"custom"`                        | `CustomCondition<"custom">`                 |
+/// | `see = "path"`                    | `SeeCondition<"path">`                      |
+/// | `kind = in_range(x, 0..10)`       | `InRangeCondition<"x", "0 .. 10">`          |
+/// | `kind = path::to::kind(a, b)`     | `KindCondition<"path::to::kind", "a, b">`   |
+/// | `kind = relation("<=", a, b)`     | `RelationCondition<"<=", "a", "b">`         |
+/// | `kind = locked(m)`                | `LockedCondition<"m">`                      |
+/// | `kind = same_provenance(q, p)`    | `SameProvenanceCondition<"p", "q">`         |
+/// | `kind = non_null(p)`              | `NonNullCondition<"p">`                     |
+/// | `kind = aligned(p)`               | `AlignedCondition<"p">`                     |
+/// | `kind = init(p)`                  | `InitializedCondition<"p">`                 |
+/// | `kind = non_overlapping(q, p, n)` | `NonOverlappingCondition<"p", "q", "n">`    |
+/// | `kind = len(b, 16)`               | `LenCondition<"b", "16">`                   |
+/// | `kind = min_len(b, n)`            | `MinLenCondition<"b", "n">`                 |
+/// | `kind = nonzero(x)`               | `NonZeroCondition<"x">`                     |
+/// | `kind = sorted(v, by = "key")`    | `SortedCondition<"v", "key">`               |
+/// | `kind = proper_align(p, u64)`     | `AlignForTypeConditionHolds<"p", "u64">`    |
+/// | `kind = readable_len(p, n)`       | `ReadableLenCondition<"p", "n">`            |
+/// | `kind = writable_len(p, n)`       | `WritableLenCondition<"p", "n">`            |
+/// | `kind = exclusive(p)`             | `ExclusiveConditionHolds<"p">`              |
+/// | `kind = utf8(b)`                  | `Utf8ConditionHolds<"b">`                   |
+/// | `kind = on_thread("main")`        | `OnThreadConditionHolds<"main">`            |
+///
+/// On the stable compiler, the marker is a struct with the same name as the function and a field of
+/// type `()` per precondition, so the order doesn't matter there. The name of the field is `_`, the
+/// keyword of the precondition and its arguments, separated by `_`. Boolean and custom
+/// preconditions use `boolean` and `custom` as their keyword and user-defined kinds use `kind`.
+/// `valid_ptr` writes the access as `r`, `w` or `rw`, followed by the type, and the arguments of
+/// user-defined kinds are separated by `,`. In the name, `_` is written as `__` and every other
+/// character that is not an ASCII letter or digit as `_` followed by its code point in hexadecimal,
+/// so `x > 0` is passed as `foo { _boolean_x_20_3e_200: () }`.
+///
+/// In both representations, expressions, types and ranges are written with a single space between
+/// their tokens, integer literals in decimal without a suffix and the pointers of `same_provenance`
+/// and `non_overlapping` in sorted order.
+///
+/// ```rust,ignore
+/// #[pre(valid_ptr(p, r))]
+/// #[pre(x > 0)]
+/// unsafe fn foo(p: *const u8, x: usize) {}
+///
+/// unsafe {
+///     foo(
+///         ptr,
+///         1,
+///         PhantomData::<(ValidPtrCondition<"p", "r">, BooleanCondition<"x > 0">)>,
+///     )
+/// };
+/// ```
+///
+/// This representation is part of the public API and only changes with a new major version, except
+/// that new kinds of preconditions may be added. Their types are sorted after the existing ones.
+///
+/// # Naming the marker parameter
+///
+/// The marker parameter is bound to the wildcard pattern `_` by default. This never clashes with
+/// the parameters of the function, not even with other parameters that are bound to `_`. Some code
+/// needs all parameters to have a name though, such as macros that generate the body of the
+/// function from the names of its parameters. For such functions, the marker parameter can be
+/// named with a `marker` attribute:
+///
+/// ```rust
+/// # use pre::pre;
+/// #
+/// #[pre(marker = "__pre")]
+/// #[pre(x > 0)]
+/// fn foo(x: i32, _: i32) -> i32 {
+///     x
+/// }
+/// #
+/// # #[pre]
+/// # fn main() {
+/// #     #[assure(x > 0, reason = "1 > 0")]
+/// #     foo(1, 2);
+/// # }
+/// ```
+///
+/// The name must be a valid identifier that is not already used by a parameter of the function.
+/// It only affects the definition of the function: the marker is still passed at the same
+/// position for every call. With the `assoc-const` feature, there is no marker parameter, so the
+/// name is ignored. The marker cannot be passed as a generic parameter with a default instead,
+/// because defaults for the generic parameters of functions are not allowed by the compiler.
+pub use pre_proc_macro::pre;
+
+/// Assure that a precondition holds.
+///
+/// This is the counterpart of the [`pre` attribute](attr.pre.html).
+///
+/// Currently this attribute does not work by itself.
+/// It needs to be used inside of a context that is annotated by a `pre` attribute.
+///
+/// # Basic usage example
+///
+/// Suppose you want to call the function `use_foo` from the [example in the `pre` attribute
+/// documentation](attr.pre.html).
+///
+/// First you need to find out what its preconditions are. You can do that by looking at its
+/// documentation. For this example we can also look at the source code for the function and see
+/// the precondition there.
+///
+/// It has one precondition:
+///
+/// - is only called after `init_foo` was called
+///
+/// Armed with that knowledge, we can write the code to call the function.
+///
+/// ```rust
+/// use pre::pre;
+/// #
+/// # fn init_foo() {}
+/// # #[pre("is only called after `init_foo` was called")]
+/// # fn use_foo() {}
+///
+/// #[pre] // This is required, so `assure` works
+/// fn main() {
+///     // This call allows us to safely assure the precondition later.
+///     init_foo(/* ... */);
+///
+///     #[assure(
+///         "is only called after `init_foo` was called",
+///         reason = "we just called `init_foo`"
+///     )]
+///     use_foo(/* ... */);
+/// }
+/// ```
+///
+/// # Terminology
+///
+/// The term `assure` was chosen, because it most accurately describes the function of the
+/// attribute.
+///
+/// There are no guarantees that a precondition holds, other than the fact that the programmer
+/// promises that it does. The burden is still on the programmer in this case. pre only makes
+/// sure that the programmer cannot forget that a precondition exists and that no precondition is
+/// changed without the programmer noticing.
+///
+/// # Syntax
+///
+/// The basic syntax of the `assure` attribute is:
+///
+/// ```rust,ignore
+/// #[assure(
+///     <first precondition>,
+///     reason = "<the reason why the first precondition can be assured>"
+/// )]
+/// #[assure(
+///     <second precondition>,
+///     reason = "<the reason why the second precondition can be assured>"
+/// )]
+/// #[assure(
+///     <third precondition>,
+///     reason = "<the reason why the third precondition can be assured>"
+/// )]
+/// foo();
+/// ```
+///
+/// Every precondition is assured by its own attribute and can be assured only once for each call.
+///
+/// To learn more about the precondition syntax and the possible types of preconditions, you should
+/// look at the [documentation of the `pre` attribute](attr.pre.html#precondition-syntax).
+///
+/// A placeholder reason, such as `"TODO"`, causes a warning. It can be suppressed for one attribute
+/// by adding `allow_placeholder` after the reason:
+/// `#[assure(<precondition>, reason = "TODO", allow_placeholder)]`. This is meant to be temporary
+/// and causes a warning itself, if the reason is not a placeholder. Reasons with fewer than 5
+/// characters cause a warning as well, unless [a different minimum
+/// length](index.html#todo-as-a-reason) is configured, and so do reasons that only restate the
+/// precondition.
+///
+/// # Evidence
+///
+/// For preconditions that can be checked at runtime and refer to a single value, the value that
+/// the precondition was verified for can be passed as evidence:
+/// `#[assure(<precondition>, reason = "...", evidence = <expr>)]`. Then a `debug_assert` checks the
+/// precondition for the evidence before the call, with the evidence in place of the value. This
+/// is supported for boolean preconditions using a single value, `in_range` preconditions and, with
+/// the `both` feature, `valid_ptr` preconditions.
+///
+/// This is useful when the value passed to the function is computed from something that was
+/// checked earlier, because a wrong reason is then noticed at the call site instead of inside the
+/// function. The evidence is evaluated before the arguments and is usually a copy of the
+/// argument or of the value it is derived from. If the check fails, the panic message contains the
+/// reason.
+///
+/// ```rust
+/// # use pre::pre;
+/// #
+/// #[pre(x > 0)]
+/// fn foo(x: i32) {}
+///
+/// #[pre]
+/// fn main() {
+///     let len = 3;
+///
+///     #[assure(x > 0, reason = "`len` is positive", evidence = len)]
+///     foo(len * 2);
+/// }
+/// ```
+///
+/// # Referring to the precondition in the reason
+///
+/// A reason may contain the placeholder `{cond}`, which is replaced by the precondition as it is
+/// written in the attribute. This avoids repeating the precondition, for example in
+/// `reason = "{cond} holds, because the length was checked above"`. Literal braces can be written
+/// as `{{` and `}}`, any other text in braces is kept as it is.
+///
+/// The reason is used with the placeholder replaced wherever its text matters: it is matched
+/// against a [`reason_pattern`](attr.pre.html#requiring-reasons-to-match-a-pattern) and it is
+/// part of the message when an [evidence](#evidence) check fails.
+///
+/// # Placement
+///
+/// The attribute applies to the call in the expression or statement it is attached to. If that
+/// expression contains other expressions, the call is searched in them, as long as it is obvious
+/// which call is meant. For `if let` and `while let` expressions, the attribute should be placed on
+/// the whole expression and applies to the call in the scrutinee:
+///
+/// ```rust
+/// # use pre::pre;
+/// #
+/// # #[pre("returns a value")]
+/// # fn foo() -> Option<i32> { Some(42) }
+/// #
+/// #[pre]
+/// fn main() {
+///     #[assure("returns a value", reason = "`foo` always returns a value")]
+///     if let Some(x) = foo() {
+///         // ...
+///     }
+/// }
+/// ```
+///
+/// For `while let` expressions, the call is evaluated in every iteration, so the precondition must
+/// hold every time.
+///
+/// An attribute on a `let` statement applies to the call in its initializer, whatever the pattern
+/// on the left is. This includes destructuring patterns, such as in
+/// `#[assure(...)] let (a, b) = foo();` or `#[assure(...)] let Point { x, y } = foo();`. If the
+/// initializer is a tuple, the attribute applies to the call in it, as long as only one of its
+/// elements contains a call, such as in `#[assure(...)] let ((a, b), c) = (foo(), 0);`.
+///
+/// An attribute on a `let ... else` statement applies to the call in its initializer, such as in
+/// `#[assure(...)] let Some(x) = foo() else { return; };`. Calls inside of the `else` block can
+/// carry their own attributes.
+///
+/// A call in the condition of a plain `if` can carry the attribute directly:
+/// `if #[assure(...)] foo(x) { ... }`. This also works if the call is only part of the condition,
+/// such as in `if y > 0 && #[assure(...)] foo(x) { ... }`. An attribute on the whole `if`
+/// expression applies to the call in the condition, like for `if let`.
+///
+/// The arguments of macros are usually opaque to pre, so calls inside of them are not found. The
+/// exception are the assertion macros of the standard library (`assert!`, `assert_eq!`,
+/// `assert_ne!` and their `debug_` variants), whose arguments are expressions. A call in such an
+/// argument can carry the attribute directly: `assert!(#[assure(...)] foo(x))`. An attribute on
+/// the macro invocation itself is not supported.
+///
+/// With the `macro-arguments` feature, the arguments of all other macros, such as `matches!` or
+/// `format!`, are searched as well, as far as they are expressions separated by commas:
+/// `matches!(#[assure(...)] foo(x), Some(_))`. This is a best-effort approach, because only the
+/// macro knows what its arguments mean:
+///
+/// - Arguments are only searched up to the first one that is not an expression followed by a
+///   comma or the end of the arguments. In `matches!(foo(x), Some(y) if y > 0)`, the pattern with
+///   its guard is not searched, but `foo(x)` is.
+/// - Macros with other separators, such as `vec![foo(x); 4]`, are not searched at all. An
+///   `assure` attribute in them is left in place, which causes an error about an unknown
+///   attribute.
+/// - Tokens that look like an expression are searched, even if the macro uses them differently.
+///
+/// A macro invocation is left exactly as it was written, unless an attribute in it was applied.
+///
+/// Calls followed by the `?` operator are handled the same way, the attribute applies to the call
+/// before the `?`: `#[assure(...)] foo()?`. In a chain such as `foo()?.bar(x)`, the attribute
+/// applies to the last call, so it is `bar` that must have the preconditions. This also holds if
+/// the chain ends with another `?`, as in `foo()?.bar(x)?`.
+///
+/// For a call that is the value of a `break`, the canonical placement is directly on the call:
+/// `break #[assure(...)] foo()`. This also works for labeled `break`s, such as
+/// `break 'outer #[assure(...)] foo()`. An attribute on the whole `break` expression applies to
+/// the call as well.
+///
+/// If only a call nested in another call has preconditions, such as `inner` in
+/// `outer(inner(x))`, the attribute can be placed directly on the nested call:
+/// `outer(#[assure(...)] inner(x))`. Nested calls are handled before the calls containing them,
+/// so both calls may have their own attributes. This works for every argument of a function or
+/// method call, such as `register(#[assure(...)] foo(x), #[assure(...)] bar(y))`. An attribute on
+/// the whole expression applies to `outer` instead.
+///
+/// This is the canonical form for wrapping a call in a smart pointer, such as
+/// `Box::new(#[assure(...)] foo(x))`. The constructors `Box::new`, `Rc::new` and `Arc::new` never
+/// have preconditions, so an attribute on the whole `Box::new(foo(x))` expression applies to the
+/// inner call `foo(x)` instead.
+///
+/// The same applies to the enum variants `Ok`, `Err` and `Some`. When returning a wrapped call,
+/// such as `return Ok(foo(x))`, the attribute is placed on the inner call:
+/// `return Ok(#[assure(...)] foo(x))`. An attribute on the whole `return` expression applies to
+/// `foo(x)` as well.
+pub use pre_proc_macro::assure;
+
+/// Require every call of a function with preconditions in an item to be assured.
+///
+/// Calls without an [`assure`](attr.assure.html) attribute usually fail to compile, because the
+/// marker for the preconditions is missing. The error is not always obvious however, and the
+/// marker could also be constructed by hand. With a `forbid_pre` attribute, every call in the item
+/// that is not assured is reported with an error that names the function. This can be used as a
+/// hard gate for modules where every call of a function with preconditions must be reviewed.
+///
+/// The attribute can be placed on a module, a function or an `impl` block inside of a module with a
+/// [`pre` attribute](attr.pre.html). On a module, it also applies to all nested modules. Like the
+/// `assure` attribute, it is handled by the `pre` attribute and does not need to be imported.
+///
+/// ```rust,compile_fail
+/// # use pre::pre;
+/// #
+/// #[pre]
+/// mod buffer {
+///     #[pre("the buffer is empty")]
+///     fn fill() {}
+///
+///     #[forbid_pre]
+///     fn refill() {
+///         fill(); // error: the call of `fill` is not assured
+///     }
+/// }
+/// ```
+///
+/// # Recognized calls
+///
+/// The preconditions of the functions are taken from their `pre` attributes, so only calls of
+/// functions that are defined in the module with the `pre` attribute are recognized. The paths of
+/// the calls are resolved relative to the surrounding module, so calls such as `fill()`,
+/// `self::fill()`, `inner::fill()` and `super::fill()` are recognized, as long as the path stays
+/// inside of the module with the `pre` attribute. Other functions are never reported, even if they
+/// happen to take a `PhantomData` argument that looks like a marker.
+///
+/// This means that some unassured calls are not found:
+///
+/// - method calls, such as `buffer.fill()`, including the methods in the module
+/// - calls of functions that are imported with `use` or called by a `crate::` path
+/// - calls of functions declared in [`extern_crate`](attr.extern_crate.html) modules
+///
+/// Without the `assoc-const` feature, the compiler still rejects these calls, if they are not
+/// assured. A call is assured, if it has an `assure` or a
+/// [`forward`](attr.forward.html) attribute. Calls in [`pre_call!`](macro.pre_call.html) and
+/// [`pre_for_each!`](macro.pre_for_each.html) are assured as well. Like `assure` attributes, calls
+/// in the arguments of other macros than the assertion macros of the standard library are not
+/// searched, unless the `macro-arguments` feature is enabled.
+pub use pre_proc_macro::forbid_pre;
+
+/// Assure that preconditions hold for a call in expression position.
+///
+/// This macro is an alternative to the [`assure` attribute](attr.assure.html). It can be used
+/// where attributes are not available, for example in a function that is not annotated with a
+/// [`pre` attribute](attr.pre.html) or in expressions where stable Rust does not accept attributes.
+///
+/// # Syntax
+///
+/// The syntax is the same as in the `assure` attribute, except that all preconditions are
+/// specified in a single invocation, followed by the call expression:
+///
+/// ```rust,ignore
+/// pre_call!(
+///     <first precondition>,
+///     reason = "<the reason why the first precondition can be assured>",
+///     <second precondition>,
+///     reason = "<the reason why the second precondition can be assured>",
+///     <call expression>
+/// )
+/// ```
+///
+/// This is equivalent to
+///
+/// ```rust,ignore
+/// #[assure(
+///     <first precondition>,
+///     reason = "<the reason why the first precondition can be assured>"
+/// )]
+/// #[assure(
+///     <second precondition>,
+///     reason = "<the reason why the second precondition can be assured>"
+/// )]
+/// <call expression>
+/// ```
+///
+/// The call expression is handled the same way as the expression an `assure` attribute is
+/// attached to. Using a [`forward` attribute](attr.forward.html) is not supported in this macro and
+/// a [`reason_pattern`](attr.pre.html#requiring-reasons-to-match-a-pattern) of a surrounding item
+/// does not apply to it.
+///
+/// # Example
+///
+/// ```rust
+/// use pre::{pre, pre_call};
+///
+/// #[pre(x > 17)]
+/// #[pre("is a good value")]
+/// fn foo(x: i32) -> i32 {
+///     x
+/// }
+///
+/// fn main() {
+///     let value = 2 * pre_call!(
+///         x > 17,
+///         reason = "42 > 17",
+///         "is a good value",
+///         reason = "42 is always a good value",
+///         foo(42)
+///     );
+/// #   assert_eq!(value, 84);
+/// }
+/// ```
+pub use pre_proc_macro::pre_call;
+
+/// Assure that preconditions hold for a call for every item of an iterator.
+///
+/// This macro is a shorthand for a `for` loop that contains a single
+/// [`pre_call!`](macro.pre_call.html). It is useful when the same preconditions hold for a call
+/// that is made for every item of a collection, for example because all items were validated
+/// when the collection was created.
+///
+/// # Syntax
+///
+/// ```rust,ignore
+/// pre_for_each!(
+///     <first precondition>,
+///     reason = "<the reason why the first precondition holds for every item>",
+///     <second precondition>,
+///     reason = "<the reason why the second precondition holds for every item>",
+///     <iterator expression> => |<item pattern>| <call expression>
+/// )
+/// ```
+///
+/// This is equivalent to
+///
+/// ```rust,ignore
+/// for <item pattern> in <iterator expression> {
+///     pre_call!(
+///         <first precondition>,
+///         reason = "<the reason why the first precondition holds for every item>",
+///         <second precondition>,
+///         reason = "<the reason why the second precondition holds for every item>",
+///         <call expression>
+///     );
+/// }
+/// ```
+///
+/// The assurances are the same as in `pre_call!`. In particular, an
+/// [`evidence`](attr.assure.html#evidence) value may refer to the item and is then checked in
+/// every iteration before the call is made. This way the reason can be verified at runtime for
+/// every item in debug builds.
+///
+/// # Example
+///
+/// ```rust
+/// use pre::{pre, pre_for_each};
+///
+/// #[pre(x > 0.0)]
+/// fn reciprocal(x: f64) -> f64 {
+///     1.0 / x
+/// }
+///
+/// fn main() {
+///     let values = [1.0, 2.0, 4.0];
+///     let mut sum = 0.0;
+///
+///     pre_for_each!(
+///         x > 0.0,
+///         reason = "all values are positive",
+///         evidence = *value,
+///         values.iter() => |value| sum += reciprocal(*value)
+///     );
+/// #   assert_eq!(sum, 1.75);
+/// }
+/// ```
+///
+/// # Limitations
+///
+/// - The closure is not a real closure, it only specifies the pattern for the items and the call
+///   expression. It must have exactly one parameter without a type annotation and it cannot be
+///   `async`, `static` or `move`. `return`, `break` and `continue` in the call expression refer
+///   to the surrounding function and the generated loop respectively.
+/// - The call expression is handled the same way as in `pre_call!`, so it must contain a single
+///   unambiguous call that the preconditions apply to.
+/// - The value of the call expression is discarded.
+/// - The preconditions are only checked at runtime if an `evidence` value is given. Without it,
+///   the reasons must hold for every item without any further checks.
+pub use pre_proc_macro::pre_for_each;
+
+/// List the preconditions of a function at compile time.
+///
+/// `declared_preconditions!(<path to the function>)` expands to a `&'static [&'static str]` containing
+/// the preconditions of the function. Each precondition is written as it would be in a `pre`
+/// attribute, the same way as in the [markers](attr.pre.html#passing-the-markers-by-hand), such as
+/// `x > 0` or `"the buffer is initialized"` (including the quotes). The preconditions are listed in
+/// the order of the markers, so it doesn't matter in which order the attributes are written.
+///
+/// This can be used to write a test that fails when the contract of a function changes:
+///
+/// ```rust
+/// use pre::{declared_preconditions, pre};
+///
+/// mod buffer {
+///     # use pre::pre;
+///     #[pre(valid_ptr(ptr, r))]
+///     #[pre(len > 0)]
+///     pub unsafe fn first(ptr: *const u8, len: usize) -> u8 {
+///         *ptr
+///     }
+/// }
+///
+/// fn main() {
+///     assert_eq!(
+///         declared_preconditions!(buffer::first),
+///         ["valid_ptr(ptr, r)", "len > 0"]
+///     );
+/// }
+/// ```
+///
+/// The path is resolved like the path to the function itself, so it can refer to a function in
+/// another module or crate, as long as the function is visible from the macro call. Methods in an
+/// `impl` block are named with the type, such as `declared_preconditions!(Buffer::first)`.
+/// Generic arguments of the function are ignored.
+///
+/// # Limitations
+///
+/// - Only functions declared with a `pre` attribute that have at least one precondition can be
+///   listed. Functions in an [`extern_crate`](attr.extern_crate.html) module cannot be listed.
+///   For these, the error is about a missing item named `__pre_preconditions_of_<name>`.
+/// - The list is generated next to the function, so the crate declaring the function must be
+///   compiled with a version of `pre` that includes this macro.
+/// - If the `strip` feature is enabled, the preconditions are not known and an error is emitted.
+pub use pre_proc_macro::declared_preconditions;
+
+/// List the preconditions of a function at compile time.
+///
+/// This is the same macro as [`declared_preconditions`](macro.declared_preconditions.html), under
+/// the name it was first requested with. Macros and functions are in different namespaces, so it
+/// can be imported together with the [`preconditions_of`](fn.preconditions_of.html) function of
+/// the `registry` feature.
+///
+/// ```rust
+/// use pre::{pre, preconditions_of};
+///
+/// #[pre(x > 0)]
+/// fn double(x: i32) -> i32 {
+///     x * 2
+/// }
+///
+/// assert_eq!(preconditions_of!(double), ["x > 0"]);
+/// ```
+pub use pre_proc_macro::preconditions_of;
+
+/// Give a name to a group of preconditions that many functions share.
+///
+/// `precondition_alias! { <name> = <first precondition>, <second precondition>; }` defines an
+/// alias for the preconditions, which all refer to the same identifier. A function in the same
+/// module declares them with `#[pre(alias <name> for <identifier>)]`, which replaces the
+/// identifier in all of the preconditions:
+///
+/// ```rust
+/// use pre::pre;
+///
+/// #[pre]
+/// mod bytes {
+///     pre::precondition_alias! {
+///         Readable = valid_ptr(p, r), kind = aligned(p), kind = init(p);
+///     }
+///
+///     // The same as `valid_ptr(src, r)`, `kind = aligned(src)` and `kind = init(src)`.
+///     #[pre(alias Readable for src)]
+///     pub unsafe fn read(src: *const u8) -> u8 {
+///         *src
+///     }
+///
+///     pub fn answer() -> u8 {
+///         let value = 42;
+///
+///         #[assure(valid_ptr(src, r), reason = "`src` points to a local variable")]
+///         #[assure(kind = aligned(src), reason = "`u8` has an alignment of 1")]
+///         #[assure(kind = init(src), reason = "`value` is initialized")]
+///         unsafe {
+///             read(&value)
+///         }
+///     }
+/// }
+///
+/// fn main() {
+///     assert_eq!(bytes::answer(), 42);
+/// }
+/// ```
+///
+/// The identifier that is replaced is the one that all of the preconditions refer to. If there is
+/// more than one such identifier, or the alias contains preconditions that refer to no identifier,
+/// such as boolean expressions, it is given explicitly after the name, such as
+/// `Bounded(n) = kind = nonzero(n), n < 64;`. One macro can define multiple aliases, each ending
+/// in a semicolon.
+///
+/// # Scope
+///
+/// The aliases are found by the `pre` attribute of the module containing the macro, so the macro
+/// must be used in a module with a `pre` attribute. An alias can be used by all functions, `impl`
+/// blocks and type aliases of function pointers in the module and its nested modules. Using an
+/// alias that is not defined there is an error.
+pub use pre_proc_macro::precondition_alias;
+
+/// Forward the call to a different function that has the preconditions for the original function.
+///
+/// Currently this attribute does not work by itself.
+/// It needs to be used inside of a context that is annotated by a `pre` attribute.
+///
+/// The purpose of this attribute is to apply the preconditions defined on items in a
+/// [`extern_crate` module](attr.extern_crate.html) to the use of the original item.
+///
+/// It is useful if you want to check preconditions for an existing file without making
+/// modifications to the file besides adding attributes.
+///
+/// For most use cases, it is better to just use the item inside of the `extern_crate` module
+/// directly. For functions and methods defined inside of an `impl` block in an `extern_crate`
+/// module, this means calling the
+/// [generated wrapper function](attr.extern_crate.html#calling-functions-in-impl-blocks-directly)
+/// instead of the method. This attribute is then only needed to keep the original method call
+/// syntax.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use pre::pre;
+///
+/// #[pre::extern_crate(std)]
+/// mod new_std {
+///     mod ptr {
+///         #[pre(valid_ptr(dst, w))]
+///         unsafe fn write_unaligned<T>(dst: *mut T, src: T);
+///     }
+/// }
+///
+/// #[pre]
+/// fn main() {
+///     // Replacing this with `new_std::ptr::write_unaligned` would make the first `forward`
+///     // attribute below unnecessary.
+///     use std::ptr::write_unaligned;
+///
+///     let mut x = 0;
+///
+///     // No preconditions are checked here.
+///     unsafe { write_unaligned(&mut x, 1) };
+/// #   assert_eq!(x, 1);
+///
+///     // Here the actual function being called is `new_std::ptr::write_unaligned`, which checks
+///     // the preconditions and is otherwise functionally equivalent to
+///     // `std::ptr::write_unaligned`.
+///     #[forward(new_std::ptr)]
+///     #[assure(
+///         valid_ptr(dst, w),
+///         reason = "`dst` is created from a reference"
+///     )]
+///     unsafe { write_unaligned(&mut x, 2) };
+/// #   assert_eq!(x, 2);
+///
+///     // Here the `std` segment of the path is replaced with `new_std`, so again
+///     // `new_std::ptr::write_unaligned` is called.
+///     // The same effect could be achieved without a `forward` attribute, by replacing the `std`
+///     // in the path of the call with `new_std`.
+///     #[forward(std -> new_std)]
+///     #[assure(
+///         valid_ptr(dst, w),
+///         reason = "`dst` is created from a reference"
+///     )]
+///     unsafe { std::ptr::write_unaligned(&mut x, 3) };
+/// #   assert_eq!(x, 3);
+/// }
+/// ```
+///
+/// For functions and methods inside of `impl` blocks, using the `forward` attribute with the `impl`
+/// keyword inside is necessary to check the preconditions:
+///
+/// ```rust
+/// use pre::pre;
+///
+/// #[pre::extern_crate(std)]
+/// mod new_std {
+///     mod ptr {
+///         impl<T> NonNull<T> {
+///             #[pre(!ptr.is_null())]
+///             const unsafe fn new_unchecked(ptr: *mut T) -> NonNull<T>;
+///         }
+///     }
+/// }
+///
+/// #[pre]
+/// fn main() {
+///     let mut val = 0;
+///
+///     // Even though this uses the `NonNull` type through the `extern_crate` module, this will
+///     // unfortunately not check the preconditions.
+///     let non_null = unsafe { new_std::ptr::NonNull::new_unchecked(&mut val) };
+///
+///     // This call actually checks the preconditions. Note the `impl` keyword before the path
+///     // below. This is required, so that the preconditions can be properly checked for a
+///     // function in an `impl` block inside of a `extern_crate` module.
+///     #[forward(impl new_std::ptr::NonNull)]
+///     #[assure(
+///         !ptr.is_null(),
+///         reason = "a reference is never null"
+///     )]
+///     let non_null = unsafe { new_std::ptr::NonNull::new_unchecked(&mut val) };
+///
+///     // The same thing also works when using the `NonNull` through the `std::ptr` path.
+///     #[forward(impl new_std::ptr::NonNull)]
+///     #[assure(
+///         !ptr.is_null(),
+///         reason = "a reference is never null"
+///     )]
+///     let non_null = unsafe { std::ptr::NonNull::new_unchecked(&mut val) };
+/// }
+/// ```
+///
+/// # Syntax
+///
+/// This attribute has three different forms:
+///
+/// - [Direct call](#direct-call)
+/// - [Path replacement](#path-replacement)
+/// - [Impl call](#impl-call)
+///
+/// ## Direct call
+///
+/// `#[forward(<path>)]`
+///
+/// ### How it works
+///
+/// `<path>` is prepended to the path of the annotated call.
 ///
 /// ### Example
 ///
@@ -880,6 +2763,11 @@ pub use pre_proc_macro::assure;
 /// def::ghi::jkl();
 /// ```
 ///
+/// Only the written path of the call is looked at, imports are not resolved. A function imported
+/// with `use abc::ghi::jkl as _` is not in scope by its name, so it is called by its path, which
+/// can be replaced as usual. A function called through a renaming import, such as
+/// `use abc::ghi::jkl as mno`, is replaced by its new name: `#[forward(mno -> def::ghi::jkl)]`.
+///
 /// ## Impl call
 ///
 /// `#[forward(impl <path>)]`
@@ -920,6 +2808,31 @@ pub use pre_proc_macro::assure;
 ///
 /// The exact inner workings of this are different to make it work in more contexts, but this is a
 /// good mental model to think about it.
+///
+/// Because the preconditions are checked on a call to a function, this also works on the stable
+/// compiler, where `assure` attributes on method calls are not supported otherwise. A method taking
+/// `self` must be declared inside of an `impl` block in the `extern_crate` module for this.
+///
+/// # Routing preconditions to different locations
+///
+/// Usually a call can only have one `forward` attribute. If the preconditions of a call are
+/// declared in different places, for example for a function that is re-exported from multiple
+/// modules with different signatures, each form can be followed by `for (<precondition>, ...)` to
+/// check only the listed preconditions for that location:
+///
+/// ```rust,ignore
+/// #[forward(checked for (valid_ptr(p, r)))]
+/// #[forward(documented for ("`p` points to the answer"))]
+/// #[assure(valid_ptr(p, r), reason = "a reference is a valid pointer")]
+/// #[assure("`p` points to the answer", reason = "`answer` is the answer")]
+/// read(&answer);
+/// ```
+///
+/// A precondition can be listed for multiple locations. At most one `forward` attribute of a call
+/// can be written without a list, and it receives all preconditions that are not listed anywhere.
+/// Without such an attribute, every assured precondition must be listed at least once and every
+/// listed precondition must be assured. The call is made the same way as with only the first
+/// `forward` attribute, the other locations are only checked by the compiler.
 pub use pre_proc_macro::forward;
 
 /// Provide preconditions for items in a different crate.
@@ -989,6 +2902,125 @@ pub use pre_proc_macro::forward;
 /// Note the use of the [`forward` attribute](attr.forward.html) above. For more information about
 /// it and its use, you can read [its documentation](attr.forward.html).
 ///
+/// Functions declared as `async` are forwarded by an `async` function that awaits the future
+/// returned by the original function, so they are used exactly like the original.
+///
+/// # Calling functions in `impl` blocks directly
+///
+/// Every function in an `impl` block also gets a free wrapper function in the module of the `impl`
+/// block, named after the type and the function, such as `MaybeUninit_assume_init`. It calls the
+/// original associated function or method, so it can be used instead of the `forward` attribute.
+/// A receiver becomes an ordinary first parameter named `this`, which is also how the
+/// preconditions refer to it, and `Self` is replaced by the type of the `impl` block:
+///
+/// ```rust
+/// use pre::pre;
+///
+/// #[pre::extern_crate(std)]
+/// mod new_std {
+///     mod vec {
+///         impl<T> Vec<T> {
+///             #[pre(new_len <= self.capacity())]
+///             #[pre("the elements at `old_len..new_len` are initialized")]
+///             unsafe fn set_len(&mut self, new_len: usize);
+///         }
+///     }
+/// }
+///
+/// #[pre]
+/// fn main() {
+///     let mut v = vec![1, 2, 3];
+///
+///     // This calls `Vec::set_len(&mut v, 1)`.
+///     #[assure(new_len <= this.capacity(), reason = "the vector only gets shorter")]
+///     #[assure(
+///         "the elements at `old_len..new_len` are initialized",
+///         reason = "there are no elements in the range"
+///     )]
+///     unsafe { new_std::vec::Vec_set_len(&mut v, 1) };
+///     assert_eq!(v, [1]);
+/// }
+/// ```
+///
+/// The wrappers are only generated for `impl` blocks of a type with a single path segment, such as
+/// `Vec<T>`, and they are also generated with the `strip` feature.
+///
+/// # Checking the signatures
+///
+/// By default the signatures in the module are only used to call the original functions, so a
+/// signature that differs slightly from the original one may go unnoticed, for example when an
+/// argument can be coerced to the type of the original parameter. Adding `check_signatures` to the
+/// attribute checks that each function signature exactly matches the signature of the original
+/// function:
+///
+/// ```rust
+/// # use pre::pre;
+/// #[pre::extern_crate(core, check_signatures)]
+/// mod new_core {
+///     mod str {
+///         #[pre("`v` must contain valid UTF-8")]
+///         unsafe fn from_utf8_unchecked(v: &[u8]) -> &str;
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// A mismatch in the number or the types of the parameters is then reported at the declaration in
+/// the module, instead of only when the function is called.
+///
+/// Generic functions are checked by letting the compiler infer the generic arguments of the
+/// original function from the declared signature. For this to work, the declared function must
+/// have the same generic parameters with the same bounds as the original function, although they
+/// may be named differently.
+///
+/// This check has some limitations:
+///
+/// - `async` and variadic functions and functions using `impl Trait` types are not checked,
+///   because they cannot be converted to a function pointer without additional information.
+/// - Functions in `impl` blocks are not checked.
+///
+/// # Finding the functions by their original name
+///
+/// Adding `doc_alias` to the attribute adds a
+/// [`doc(alias)`](https://doc.rust-lang.org/rustdoc/advanced-features.html#add-aliases-for-an-item-in-documentation-search)
+/// with the path of the original function to every function in the module, such as
+/// `#[doc(alias = "core::ptr::read")]`. Searching for the original function in the documentation
+/// then also finds the function with preconditions. The wrappers of functions in `impl` blocks get
+/// the path of the original associated function, such as `std::vec::Vec::set_len`.
+/// The options can be combined: `#[pre::extern_crate(core, check_signatures, doc_alias)]`.
+///
+/// # Reading the items from a file
+///
+/// Long lists of signatures can be kept in a separate file with
+/// `#[pre::extern_crate(core, from = "sigs/core.pre")]`. The path is relative to the file
+/// containing the attribute. The file contains the same items that can be written inside of the
+/// module and they are added to the items written inline. Modules with the same name in the file
+/// and in the module are merged. If the file cannot be parsed, the error names the line of the
+/// first item that fails to parse. The crate is recompiled when the file changes.
+///
+/// # Exporting the signatures with the `export-signatures` feature
+///
+/// If the `export-signatures` feature is enabled, the signatures of the generated wrapper
+/// functions are written to `pre-signatures.txt` in the `OUT_DIR` of the crate being compiled, so
+/// the crate needs a build script for the file to be created. This is useful for FFI bindings,
+/// where the file can be compared with the signatures generated by a tool like `bindgen` to notice
+/// when the declared signatures no longer match the bound library. All modules of a crate share
+/// one file, which is locked while it is updated. If the file cannot be written, a warning is
+/// emitted and the compilation continues.
+///
+/// The file contains one line per function with the following tab-separated fields:
+///
+/// 1. the full path of the wrapper module, such as `my_crate::ffi::pre_core::str`, derived like
+///    the paths in the [coverage report](attr.pre.html#recording-precondition-coverage-with-the-coverage-feature)
+/// 2. the path of the original function, such as `core::str::from_utf8_unchecked`
+/// 3. the signature of the wrapper with a single space between each token, except after
+///    punctuation that is joined with the next token, such as
+///    `unsafe fn from_utf8_unchecked ( v : & [ u8 ] ) -> & str`
+///
+/// The lines are sorted and the lines of an annotated module are replaced when it is compiled
+/// again. The wrappers of functions in `impl` blocks are included with their generated signature,
+/// where the receiver is an ordinary parameter named `this`.
+///
 /// # Visibility
 ///
 /// Visibility modifiers on inner items of the module are ignored.
@@ -1027,20 +3059,387 @@ pub use libs::std;
 
 cfg_if::cfg_if! {
     if #[cfg(nightly)] {
-        // *WARNING* These types are not considered to be part of the public API and may change at
-        // any time without notice.
+        // These types are used to pass the markers by hand, see the documentation of the `pre`
+        // attribute for how they are used.
 
         /// A condition that the pointer of name `PTR` is valid for `ACCESS_TYPE` accesses.
-        #[doc(hidden)]
+        ///
+        /// `ACCESS_TYPE` is one of `"r"`, `"w"` or `"r+w"`.
         pub struct ValidPtrCondition<const PTR: &'static str, const ACCESS_TYPE: &'static str>;
 
         /// A boolean condition.
-        #[doc(hidden)]
         pub struct BooleanCondition<const CONDITION: &'static str>;
 
         /// A custom condition.
-        #[doc(hidden)]
         pub struct CustomCondition<const CONDITION: &'static str>;
 
+        /// A custom condition described by the documentation of the item at `PATH`.
+        pub struct SeeCondition<const PATH: &'static str>;
+
+        /// A condition that the value of name `VALUE` lies in `RANGE`.
+        pub struct InRangeCondition<const VALUE: &'static str, const RANGE: &'static str>;
+
+        /// A condition of the user-defined kind at `KIND` for the values named in `ARGUMENTS`.
+        ///
+        /// `ARGUMENTS` contains the names separated by `", "`.
+        pub struct KindCondition<const KIND: &'static str, const ARGUMENTS: &'static str>;
+
+        /// A condition that the value named `LEFT` relates to the value named `RIGHT` by
+        /// `OPERATOR`.
+        pub struct RelationCondition<
+            const OPERATOR: &'static str,
+            const LEFT: &'static str,
+            const RIGHT: &'static str,
+        >;
+
+        /// A condition that the lock protecting the value named `VALUE` is held.
+        pub struct LockedCondition<const VALUE: &'static str>;
+
+        /// A condition that the pointers named `FIRST` and `SECOND` are derived from the same
+        /// allocation.
+        ///
+        /// `FIRST` is the name that sorts first.
+        pub struct SameProvenanceCondition<const FIRST: &'static str, const SECOND: &'static str>;
+
+        /// A condition that the pointer named `PTR` is not null.
+        pub struct NonNullCondition<const PTR: &'static str>;
+
+        /// A condition that the pointer named `PTR` is properly aligned.
+        pub struct AlignedCondition<const PTR: &'static str>;
+
+        /// A condition that the pointer named `PTR` points to initialized memory.
+        pub struct InitializedCondition<const PTR: &'static str>;
+
+        /// A condition that the `COUNT` elements behind the pointers named `FIRST` and `SECOND` do
+        /// not overlap.
+        ///
+        /// `FIRST` is the name that sorts first.
+        pub struct NonOverlappingCondition<
+            const FIRST: &'static str,
+            const SECOND: &'static str,
+            const COUNT: &'static str,
+        >;
+
+        /// A condition that the value named `VALUE` has a length of exactly `LENGTH`.
+        pub struct LenCondition<const VALUE: &'static str, const LENGTH: &'static str>;
+
+        /// A condition that the value named `VALUE` has a length of at least `LENGTH`.
+        pub struct MinLenCondition<const VALUE: &'static str, const LENGTH: &'static str>;
+
+        /// A condition that `LENGTH` elements can be read through the pointer named `PTR`.
+        pub struct ReadableLenCondition<const PTR: &'static str, const LENGTH: &'static str>;
+
+        /// A condition that `LENGTH` elements can be written through the pointer named `PTR`.
+        pub struct WritableLenCondition<const PTR: &'static str, const LENGTH: &'static str>;
+
+        /// A condition that the integer named `VALUE` is not zero.
+        pub struct NonZeroCondition<const VALUE: &'static str>;
+
+        /// A condition that the slice named `VALUE` is sorted by `ORDER`.
+        ///
+        /// `ORDER` is empty for a slice sorted in ascending order.
+        pub struct SortedCondition<const VALUE: &'static str, const ORDER: &'static str>;
+
+        /// A condition that the pointer named `PTR` is properly aligned for the type `TYPE`.
+        pub struct AlignForTypeConditionHolds<const PTR: &'static str, const TYPE: &'static str>;
+
+        /// A condition that the pointer named `PTR` is the only way to access the memory behind it.
+        pub struct ExclusiveConditionHolds<const PTR: &'static str>;
+
+        /// A condition that the bytes named `BYTES` are valid UTF-8.
+        pub struct Utf8ConditionHolds<const BYTES: &'static str>;
+
+        /// A condition that the function is called on the thread named `THREAD`.
+        pub struct OnThreadConditionHolds<const THREAD: &'static str>;
+
+    }
+}
+
+/// The contract of a function with preconditions, as registered by the `registry` feature.
+///
+/// See the [documentation of the `pre` attribute](attr.pre.html#enumerating-contracts-at-runtime-with-the-registry-feature)
+/// for how contracts are registered.
+#[cfg(feature = "registry")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Contract {
+    /// The path of the module containing the function, as returned by `module_path!()`.
+    pub module_path: &'static str,
+    /// The name of the function.
+    pub function: &'static str,
+    /// The preconditions of the function, written the same way as in a `pre` attribute.
+    ///
+    /// The preconditions are sorted in the same order as in the markers.
+    pub preconditions: &'static [&'static str],
+    /// The address of the function, if it is known.
+    ///
+    /// It is `None` for generic functions, functions in `impl` blocks and functions taking `self` or
+    /// mentioning `Self`. See
+    /// [`preconditions_of`](fn.preconditions_of.html) for looking up a contract by the address.
+    pub address: Option<*const ()>,
+}
+
+// The address is only compared and never dereferenced, so sharing it between threads is fine.
+#[cfg(feature = "registry")]
+unsafe impl Sync for Contract {}
+
+// See the `Sync` implementation.
+#[cfg(feature = "registry")]
+unsafe impl Send for Contract {}
+
+/// The contracts of all functions with preconditions in the binary.
+///
+/// This is only available with the `registry` feature. The slice contains the contracts of all
+/// crates in the binary that were compiled with the feature. The order of the contracts is
+/// unspecified.
+#[cfg(feature = "registry")]
+#[linkme::distributed_slice]
+pub static CONTRACTS: [Contract] = [..];
+
+/// Returns the preconditions of the function at the given address, if it is registered.
+///
+/// This is only available with the `registry` feature. The address is obtained by casting the
+/// function, such as `preconditions_of(foo as *const ())`, which makes it possible to look up the
+/// contract of a function that is only known as a function pointer.
+///
+/// Only functions whose [`address`](struct.Contract.html#structfield.address) is known can be
+/// found, so this returns `None` for generic functions, functions in `impl` blocks, functions taking
+/// `self` or mentioning `Self` and functions without preconditions. The compiler may also merge functions with identical code into one, in
+/// which case the contract of any of them may be returned.
+///
+/// ```rust,ignore
+/// #[pre(x > 0)]
+/// fn foo(x: i32) {}
+///
+/// assert_eq!(pre::preconditions_of(foo as *const ()), Some(&["x > 0"][..]));
+/// ```
+#[cfg(feature = "registry")]
+pub fn preconditions_of(function: *const ()) -> Option<&'static [&'static str]> {
+    CONTRACTS
+        .iter()
+        .find(|contract| contract.address == Some(function))
+        .map(|contract| contract.preconditions)
+}
+
+/// The dependencies and helpers used by the generated code.
+///
+/// *WARNING* This module is not considered to be part of the public API and may change at any
+/// time without notice.
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "registry")]
+    pub use linkme;
+
+    #[cfg(nightly)]
+    pub use crate::nightly_markers::AssuredPreconditions;
+
+    /// Checks the bytes of a `utf8` precondition at runtime.
+    ///
+    /// The length of the memory behind a pointer is not known, so pointers are never rejected.
+    pub trait Utf8Bytes {
+        /// Checks whether the bytes are valid UTF-8.
+        fn is_utf8(&self) -> bool;
+    }
+
+    impl Utf8Bytes for [u8] {
+        fn is_utf8(&self) -> bool {
+            core::str::from_utf8(self).is_ok()
+        }
+    }
+
+    impl Utf8Bytes for str {
+        fn is_utf8(&self) -> bool {
+            true
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl Utf8Bytes for std::vec::Vec<u8> {
+        fn is_utf8(&self) -> bool {
+            self[..].is_utf8()
+        }
+    }
+
+    impl<T: Utf8Bytes + ?Sized> Utf8Bytes for &T {
+        fn is_utf8(&self) -> bool {
+            (**self).is_utf8()
+        }
+    }
+
+    impl<T: Utf8Bytes + ?Sized> Utf8Bytes for &mut T {
+        fn is_utf8(&self) -> bool {
+            (**self).is_utf8()
+        }
+    }
+
+    impl<T: ?Sized> Utf8Bytes for *const T {
+        fn is_utf8(&self) -> bool {
+            true
+        }
+    }
+
+    impl<T: ?Sized> Utf8Bytes for *mut T {
+        fn is_utf8(&self) -> bool {
+            true
+        }
+    }
+
+    /// Checks whether the current thread has the name of an `on_thread` precondition.
+    #[cfg(feature = "std")]
+    pub fn is_on_thread(name: &str) -> bool {
+        std::thread::current().name() == Some(name)
+    }
+
+    /// Threads are not known without the `std` feature, so the check always succeeds.
+    #[cfg(not(feature = "std"))]
+    pub fn is_on_thread(_name: &str) -> bool {
+        true
+    }
+}
+
+/// Compares the preconditions assured at a call with those of the called function.
+///
+/// The marker of a call is not written as a `PhantomData` directly, but taken from the `MARKER`
+/// of `AssuredPreconditions`. The preconditions of the called function are inferred from the type
+/// of the marker parameter, so a mismatch is reported as an unsatisfied bound, which carries a
+/// readable message, instead of a mismatch of two tuple types.
+#[cfg(nightly)]
+mod nightly_markers {
+    use core::marker::PhantomData;
+
+    /// Implemented if the preconditions `Self` are exactly the preconditions `Declared`.
+    #[rustc_on_unimplemented(
+        message = "the assured preconditions don't match the preconditions of the called function",
+        label = "the assured preconditions are `{Self}`",
+        note = "the called function has the preconditions `{Declared}`"
+    )]
+    pub trait AssuredPreconditions<Declared> {
+        /// The marker for a call assuring the preconditions `Self`.
+        const MARKER: PhantomData<Declared>;
+    }
+
+    impl<T> AssuredPreconditions<T> for T {
+        const MARKER: PhantomData<T> = PhantomData;
+    }
+
+    /// Never used, but prevents the compiler from inferring `Declared` from `Self`.
+    ///
+    /// With only one implementation, the compiler would infer the preconditions of the called
+    /// function to be the assured ones and report the mismatch of the marker types again.
+    pub struct Unassured<T>(PhantomData<T>);
+
+    impl<T> AssuredPreconditions<Unassured<T>> for T {
+        const MARKER: PhantomData<Unassured<T>> = PhantomData;
+    }
+}
+
+/// Defines a new kind of precondition.
+///
+/// A kind describes a requirement for one or more values in its documentation. Functions can then
+/// use it in a `#[pre(kind = <path>(<arguments>))]` attribute, see the
+/// [precondition syntax](attr.pre.html#precondition-syntax) for details.
+///
+/// The macro takes the documentation of the kind, an optional visibility, the name of the kind and
+/// the names of its parameters. It defines an empty struct with the name of the kind, which serves
+/// as the marker that the kind exists and carries its documentation. The generated documentation
+/// of functions with such a precondition links to this struct.
+///
+/// # Example
+///
+/// ```rust
+/// # use pre::{define_precondition_kind, pre};
+/// #
+/// define_precondition_kind! {
+///     /// The two slices do not overlap in memory.
+///     pub disjoint(a, b)
+/// }
+///
+/// #[pre(kind = disjoint(src, dst))]
+/// fn copy(src: &[u8], dst: &mut [u8]) {}
+///
+/// #[pre]
+/// fn main() {
+///     let src = [1, 2, 3];
+///     let mut dst = [0; 3];
+///
+///     #[assure(kind = disjoint(src, dst), reason = "both are separate local arrays")]
+///     copy(&src, &mut dst);
+/// }
+/// ```
+///
+/// # Limitations
+///
+/// Procedural macros cannot see the items of the crate they are used in, so the kinds are looked
+/// up by the compiler instead of by pre: a function with a user-defined precondition calls a
+/// function generated by this macro, which fails to compile if the kind is not in scope or the
+/// number of arguments does not match. This means that:
+///
+/// - the kind must be in scope at the function with the precondition, either by importing it or by
+///   using its full path
+/// - the arguments must be parameters of the function
+/// - kinds used in [`extern_crate`](attr.extern_crate.html) modules are not checked at all
+/// - kinds are identified by the path as it is written, so `kinds::non_zero` and `non_zero` are
+///   different preconditions, even if they refer to the same kind
+/// - nothing is checked at runtime, the requirements are only documented
+#[macro_export]
+macro_rules! define_precondition_kind {
+    ($(#[$attr:meta])* $vis:vis $name:ident($($argument:ident),* $(,)?)) => {
+        $(#[$attr])*
+        #[allow(non_camel_case_types)]
+        $vis struct $name {}
+
+        impl $name {
+            /// Checks that the kind is used with the right number of arguments.
+            ///
+            /// *WARNING* This function is not considered to be part of the public API and may
+            /// change at any time without notice.
+            #[doc(hidden)]
+            #[allow(non_camel_case_types, dead_code)]
+            #[inline(always)]
+            pub const fn __check_arguments<$($argument: ?Sized),*>($(_: &$argument),*) {}
+        }
+    };
+}
+
+/// Checks that the assured preconditions match the preconditions of the called function.
+///
+/// This is used by the code generated with the `assoc-const` feature. Both lists are expected to
+/// be sorted in the same way.
+///
+/// *WARNING* This function is not considered to be part of the public API and may change at any
+/// time without notice.
+#[doc(hidden)]
+#[cfg(feature = "assoc-const")]
+pub const fn assert_preconditions_match(required: &[&str], assured: &[&str]) {
+    /// Checks if two strings are equal in a `const` context.
+    const fn str_eq(a: &str, b: &str) -> bool {
+        let (a, b) = (a.as_bytes(), b.as_bytes());
+
+        if a.len() != b.len() {
+            return false;
+        }
+
+        let mut i = 0;
+        while i < a.len() {
+            if a[i] != b[i] {
+                return false;
+            }
+            i += 1;
+        }
+
+        true
+    }
+
+    if required.len() != assured.len() {
+        panic!("the number of assured preconditions does not match the called function");
+    }
+
+    let mut i = 0;
+    while i < required.len() {
+        if !str_eq(required[i], assured[i]) {
+            panic!(
+                "the assured preconditions do not match the preconditions of the called function"
+            );
+        }
+        i += 1;
     }
 }
@@ -27,7 +27,7 @@ macro_rules! define_libs {
         /// As a workaround, you can add the preconditions locally in your own crate using the
         /// [`extern_crate` attribute](../attr.extern_crate.html).
         #[cfg(feature = "core")]
-        #[pre::extern_crate(core)]
+        #[pre::extern_crate(core, doc_alias)]
         #[pre::pre(no_doc)]
         pub mod $core_name {
             $($core_item)*
@@ -50,7 +50,7 @@ macro_rules! define_libs {
         /// As a workaround, you can add the preconditions locally in your own crate using the
         /// [`extern_crate` attribute](../attr.extern_crate.html).
         #[cfg(feature = "std")]
-        #[pre::extern_crate(std)]
+        #[pre::extern_crate(std, doc_alias)]
         #[pre::pre(no_doc)]
         pub mod $std_name {
             $($core_item)*
@@ -0,0 +1,203 @@
+//! Implements the procedural macros using an associated constant on a generated struct.
+//!
+//! The struct has the same name as the function to avoid having to know how to import it, the
+//! same way as in the `struct_impl` module. Instead of being passed as an additional argument, the
+//! preconditions are stored as strings in an associated constant of the struct. At the call site
+//! the assured preconditions are compared to the stored ones in a constant evaluation context.
+//!
+//! # Advantages of this approach
+//! - the signature of the function is not changed
+//! - uses only stable features
+//! - supports arbitrarily complex strings out of the box
+//!
+//! # Disadvantages of this approach
+//! - calls without any `assure` attribute are not checked at all
+//! - possible name clashes, because the identifier namespace is limited
+//! - error messages only state that the preconditions do not match, not which ones
+//! - the struct must be defined somewhere, which is not possible for a method
+//! - requires a compiler that supports panics in constant evaluation (1.57 or newer)
+//!
+//! # What the generated code looks like
+//!
+//! ```rust,ignore
+//! #[pre::pre(some_val > 42.0)]
+//! fn has_preconditions(some_val: f32) -> f32 {
+//!     assert!(some_val > 42.0);
+//!
+//!     some_val
+//! }
+//!
+//! #[pre::pre]
+//! fn main() {
+//!     #[assure(some_val > 42.0, reason = "43.0 > 42.0")]
+//!     has_preconditions(43.0);
+//! }
+//! ```
+//!
+//! turns into
+//!
+//! ```rust,ignore
+//! #[allow(non_camel_case_types)]
+//! #[cfg(not(doc))]
+//! struct has_preconditions {}
+//!
+//! #[cfg(not(doc))]
+//! impl has_preconditions {
+//!     #[doc(hidden)]
+//!     const PRECONDITIONS: &'static [&'static str] = &["some_val > 42.0"];
+//! }
+//!
+//! #[doc = "..."]
+//! fn has_preconditions(some_val: f32) -> f32 {
+//!     ::core::debug_assert!(
+//!         some_val > 42.0
+//!         "boolean precondition was wrongly assured: `{}`",
+//!         ::core::stringify!(some_val > 42.0)
+//!     );
+//!     assert!(some_val > 42.0);
+//!
+//!     some_val
+//! }
+//!
+//! fn main() {
+//!     ({
+//!         const _: () = ::pre::assert_preconditions_match(
+//!             has_preconditions::PRECONDITIONS,
+//!             &["some_val > 42.0"],
+//!         );
+//!
+//!         has_preconditions
+//!     })(43.0);
+//! }
+//! ```
+
+use proc_macro2::{Span, TokenStream};
+use proc_macro_error::emit_error;
+use quote::{quote, quote_spanned};
+use syn::{parse2, Ident, ItemFn, LitStr, PathArguments};
+
+use crate::{
+    call::Call,
+    helpers::{add_span_to_signature, contracts_gate, crate_name_ident},
+    precondition::{custom_text, Precondition},
+};
+
+/// Renders a precondition list to a list of string literals uniquely identifying them.
+fn render_condition_list(mut preconditions: Vec<Precondition>, span: Span) -> TokenStream {
+    preconditions.sort_unstable();
+
+    let strings = preconditions.iter().map(|precondition| {
+        let text = match precondition {
+            Precondition::Custom(lit) => format!("{:?}", custom_text(lit)),
+            _ => precondition.to_string(),
+        };
+
+        LitStr::new(&text, span)
+    });
+
+    quote_spanned! { span=>
+        &[#(#strings),*]
+    }
+}
+
+/// Generates the code for the function with the precondition handling added.
+pub(crate) fn render_pre(
+    preconditions: Vec<Precondition>,
+    function: &mut ItemFn,
+    // The preconditions are checked through an associated constant, so there is no marker
+    // parameter to name.
+    _marker_name: Option<&Ident>,
+    span: Span,
+) -> TokenStream {
+    if function.sig.receiver().is_some() {
+        emit_error!(
+            span,
+            "preconditions are not supported for methods with the `assoc-const` feature"
+        );
+        return quote! { #function };
+    }
+
+    let vis = &function.vis;
+    let function_name = function.sig.ident.clone();
+    let preconditions = render_condition_list(preconditions, span);
+
+    // Include the precondition site into the span of the function.
+    // This improves the error messages for the case where no preconditions are specified.
+    add_span_to_signature(span, &mut function.sig);
+
+    let gate = contracts_gate(span);
+
+    quote_spanned! { span=>
+        #[allow(non_camel_case_types)]
+        #[cfg(not(doc))]
+        #gate
+        #vis struct #function_name {}
+
+        #[cfg(not(doc))]
+        #gate
+        impl #function_name {
+            #[doc(hidden)]
+            #vis const PRECONDITIONS: &'static [&'static str] = #preconditions;
+        }
+
+        #function
+    }
+}
+
+/// Generates the code for the call with the precondition handling added.
+pub(crate) fn render_assure(preconditions: Vec<Precondition>, mut call: Call, span: Span) -> Call {
+    let mut path = match &call {
+        Call::Method(_) => {
+            emit_error!(
+                call,
+                "method calls are not supported by `pre` with the `assoc-const` feature"
+            );
+
+            return call;
+        }
+        Call::Function(fn_call) => match call.path() {
+            Some(path) => path,
+            None => {
+                emit_error!(
+                    fn_call.func,
+                    "unable to determine at compile time which function is being called";
+                    help = "use a direct path to the function instead"
+                );
+
+                return call;
+            }
+        },
+    };
+
+    let fn_expr = path.clone();
+
+    if let Some(last_path_segment) = path.path.segments.last_mut() {
+        last_path_segment.arguments = PathArguments::None;
+
+        // Use the precondition span somewhere in the path.
+        // This should improve the error message when no preconditions are present at the
+        // definition, but some were `assure`d.
+        last_path_segment.ident.set_span(span);
+    }
+
+    let crate_name = crate_name_ident(span);
+    let preconditions = render_condition_list(preconditions, span);
+    let gate = contracts_gate(span);
+
+    if let Call::Function(fn_call) = &mut call {
+        *fn_call.func = parse2(quote_spanned! { span=>
+            ({
+                #gate
+                const _: () = ::#crate_name::assert_preconditions_match(
+                    #path::PRECONDITIONS,
+                    #preconditions,
+                );
+
+                #fn_expr
+            })
+        })
+        .expect("parses as an expression");
+    }
+
+    call
+}
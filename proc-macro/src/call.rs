@@ -50,7 +50,13 @@ impl Call {
 }
 
 impl From<ExprCall> for Call {
-    fn from(call: ExprCall) -> Self {
+    fn from(mut call: ExprCall) -> Self {
+        // A function passed to a macro as a `$f:path` fragment is wrapped in an invisible group.
+        // It is removed, so that the path of the called function can be found.
+        while let Expr::Group(group) = *call.func {
+            call.func = group.expr;
+        }
+
         Call::Function(call)
     }
 }
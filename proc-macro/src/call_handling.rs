@@ -1,19 +1,26 @@
 //! Functionality for parsing and visiting `assure` attributes.
 
-use proc_macro2::Span;
+use proc_macro2::{Span, TokenStream};
 use proc_macro_error::{emit_error, emit_warning};
+use quote::quote_spanned;
+use std::{collections::HashMap, env};
 use syn::{
     parse::{Parse, ParseStream},
+    parse_quote_spanned,
     spanned::Spanned,
-    Attribute, Expr, LitStr, Token,
+    visit_mut::VisitMut,
+    Attribute, Expr, ExprCall, ExprClosure, ExprPath, Ident, LitStr, Pat, PatType, Token,
 };
 
-use self::forward::Forward;
+use self::forward::{Forward, ForwardAttr};
 use crate::{
     call::Call,
-    helpers::{is_attr, visit_matching_attrs_parsed, Parenthesized, HINT_REASON},
-    precondition::Precondition,
+    coverage::Coverage,
+    helpers::{contracts_gate, is_attr, visit_matching_attrs_parsed, Parenthesized, HINT_REASON},
+    pre_attr::{runtime_check, Exclusive, ReasonPattern},
+    precondition::{CaseWarnings, Precondition},
     render_assure,
+    safety_comment::render_safety_comment,
 };
 
 mod forward;
@@ -23,6 +30,8 @@ mod custom_keywords {
     use syn::custom_keyword;
 
     custom_keyword!(reason);
+    custom_keyword!(allow_placeholder);
+    custom_keyword!(evidence);
 }
 
 /// An attribute with an assurance that a precondition holds.
@@ -35,6 +44,10 @@ pub(crate) enum AssureAttr {
         _comma: Token![,],
         /// The reason that was stated.
         reason: Reason,
+        /// The `allow_placeholder` flag, if the reason is allowed to be a placeholder.
+        allow_placeholder: Option<(Token![,], custom_keywords::allow_placeholder)>,
+        /// The value that the precondition was verified for, if it was stated.
+        evidence: Option<Evidence>,
         /// The span best representing the whole attribute.
         ///
         /// This is only optional, because it cannot be determined while parsing.
@@ -102,11 +115,24 @@ impl Parse for AssureAttr {
         } else {
             let comma = input.parse()?;
             let reason = input.parse()?;
+            let allow_placeholder =
+                if input.peek(Token![,]) && input.peek2(custom_keywords::allow_placeholder) {
+                    Some((input.parse()?, input.parse()?))
+                } else {
+                    None
+                };
+            let evidence = if input.peek(Token![,]) && input.peek2(custom_keywords::evidence) {
+                Some(input.parse()?)
+            } else {
+                None
+            };
 
             Ok(AssureAttr::WithReason {
                 precondition,
                 _comma: comma,
                 reason,
+                allow_placeholder,
+                evidence,
                 span: None,
             })
         }
@@ -114,8 +140,16 @@ impl Parse for AssureAttr {
 }
 
 impl AssureAttr {
+    /// Returns the precondition that is assured by this attribute.
+    fn precondition(&self) -> &Precondition {
+        match self {
+            AssureAttr::WithReason { precondition, .. }
+            | AssureAttr::WithoutReason { precondition, .. } => precondition,
+        }
+    }
+
     /// Sets the span of this `assure` attribute.
-    fn set_span(&mut self, new_span: Span) {
+    pub(crate) fn set_span(&mut self, new_span: Span) {
         match self {
             AssureAttr::WithReason { span, .. } | AssureAttr::WithoutReason { span, .. } => {
                 span.replace(new_span);
@@ -148,12 +182,94 @@ impl Parse for Reason {
     }
 }
 
+impl Reason {
+    /// Returns the text of the reason with the placeholders replaced for the given precondition.
+    ///
+    /// `{cond}` is replaced by the precondition as it is written in an attribute and `{{` and
+    /// `}}` are replaced by literal braces. Any other text in braces is kept as it is.
+    pub(crate) fn expand(&self, precondition: &Precondition) -> String {
+        expand_reason(&self.reason.value(), &precondition.to_string())
+    }
+}
+
+/// Replaces the placeholders in the text of a reason.
+fn expand_reason(reason: &str, precondition: &str) -> String {
+    let mut expanded = String::with_capacity(reason.len());
+    let mut rest = reason;
+
+    while let Some(index) = rest.find(['{', '}']) {
+        expanded.push_str(&rest[..index]);
+        rest = &rest[index..];
+
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            expanded.push_str(&rest[..1]);
+            rest = &rest[2..];
+        } else if rest.starts_with("{cond}") {
+            expanded.push_str(precondition);
+            rest = &rest["{cond}".len()..];
+        } else {
+            expanded.push_str(&rest[..1]);
+            rest = &rest[1..];
+        }
+    }
+
+    expanded.push_str(rest);
+
+    expanded
+}
+
+/// The value that a precondition was verified for.
+pub(crate) struct Evidence {
+    /// The comma separating the evidence from the previous part of the attribute.
+    _comma: Token![,],
+    /// The `evidence` keyword.
+    _evidence_keyword: custom_keywords::evidence,
+    /// The `=` separating the `evidence` keyword and the value.
+    _eq: Token![=],
+    /// The expression evaluating to the value.
+    value: Box<Expr>,
+}
+
+impl Parse for Evidence {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Evidence {
+            _comma: input.parse()?,
+            _evidence_keyword: input.parse()?,
+            _eq: input.parse()?,
+            value: input.parse()?,
+        })
+    }
+}
+
+/// Information about the surrounding code that is relevant for rendering a call.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct CallContext<'a> {
+    /// The pattern that all reasons must match, if there is one.
+    pub(crate) reason_pattern: Option<&'a ReasonPattern>,
+    /// The preconditions of the functions defined in the surrounding module by function name.
+    ///
+    /// This is only known, if the whole module is annotated with a `pre` attribute.
+    pub(crate) known_functions: Option<&'a HashMap<String, Vec<Precondition>>>,
+    /// The record of assured preconditions, if the `coverage` feature is enabled.
+    pub(crate) coverage: Option<&'a Coverage>,
+    /// The preconditions that must not be assured for the same call.
+    ///
+    /// These are only declared, if the whole module is annotated with a `pre` attribute.
+    pub(crate) exclusions: &'a [Exclusive],
+    /// The custom preconditions that were already warned about while expanding the item.
+    ///
+    /// Custom preconditions are only checked for normalized text, if this is present.
+    pub(crate) case_warnings: Option<&'a CaseWarnings>,
+}
+
 /// The attributes of a call expression.
 pub(crate) struct CallAttributes {
     /// The span best representing all the attributes.
     pub(crate) span: Span,
-    /// The optional `forward` attribute.
+    /// The optional `forward` attribute without a list of preconditions.
     pub(crate) forward: Option<Forward>,
+    /// The `forward` attributes with the preconditions that are routed to them.
+    pub(crate) routed_forwards: Vec<(Forward, Vec<Precondition>)>,
     /// The list of `assure` attributes.
     pub(crate) assure_attributes: Vec<AssureAttr>,
 }
@@ -161,6 +277,7 @@ pub(crate) struct CallAttributes {
 /// Removes and returns all `pre`-related call-site attributes from the given attribute list.
 pub(crate) fn remove_call_attributes(attributes: &mut Vec<Attribute>) -> Option<CallAttributes> {
     let mut forward = None;
+    let mut routed_forwards = Vec::new();
     let mut assure_attributes = Vec::new();
 
     let preconditions_span = visit_matching_attrs_parsed(
@@ -179,17 +296,21 @@ pub(crate) fn remove_call_attributes(attributes: &mut Vec<Attribute>) -> Option<
     let forward_span = visit_matching_attrs_parsed(
         attributes,
         |attr| is_attr("forward", attr),
-        |Parenthesized {
-             content: mut fwd, ..
-         }: Parenthesized<Forward>,
+        |ForwardAttr {
+             forward: mut fwd,
+             routing,
+         },
          span| {
             fwd.set_span(span);
 
-            if let Some(old_forward) = forward.replace(fwd) {
+            if let Some(routing) = routing {
+                routed_forwards.push((fwd, routing.preconditions.into_iter().collect()));
+            } else if let Some(old_forward) = forward.replace(fwd) {
                 emit_error!(
                     span,
                     "duplicate `forward` attribute";
-                    help = old_forward.span() => "there can be just one location, try removing the wrong one"
+                    help = old_forward.span() => "there can be just one location, try removing the wrong one";
+                    help = "to forward different preconditions to different locations, list them after each attribute: `#[forward(path::to::module for (<precondition>, ...))]`"
                 );
             }
         },
@@ -210,6 +331,7 @@ pub(crate) fn remove_call_attributes(attributes: &mut Vec<Attribute>) -> Option<
         Some(CallAttributes {
             span,
             forward,
+            routed_forwards,
             assure_attributes,
         })
     } else {
@@ -222,36 +344,340 @@ pub(crate) fn render_call(
     CallAttributes {
         span,
         forward,
+        routed_forwards,
         assure_attributes,
     }: CallAttributes,
     original_call: Call,
+    context: &CallContext,
 ) -> Expr {
-    let preconditions = check_reasons(assure_attributes);
+    let evidence_checks: Vec<_> = assure_attributes
+        .iter()
+        .filter_map(|assure_attribute| match assure_attribute {
+            AssureAttr::WithReason {
+                precondition,
+                reason,
+                evidence: Some(evidence),
+                ..
+            } => render_evidence_check(precondition, reason, evidence),
+            _ => None,
+        })
+        .collect();
+    let assure_attributes = remove_duplicate_assures(assure_attributes);
+    let safety_comment: Vec<_> = if cfg!(feature = "safety-comments") {
+        assure_attributes
+            .iter()
+            .filter_map(|assure_attribute| match assure_attribute {
+                AssureAttr::WithReason {
+                    precondition,
+                    reason,
+                    ..
+                } => Some((precondition.clone(), reason.expand(precondition))),
+                AssureAttr::WithoutReason { .. } => None,
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let preconditions = check_reasons(assure_attributes, context.reason_pattern);
+    if let Some(case_warnings) = context.case_warnings {
+        case_warnings.check(&preconditions);
+    }
 
-    if let Some(forward) = forward {
+    for exclusive in context.exclusions {
+        if exclusive.is_violated_by(&preconditions) {
+            emit_error!(
+                span,
+                "the preconditions `{}` and `{}` are mutually exclusive", exclusive.first, exclusive.second;
+                note = "they cannot both hold, so they cannot both be assured for the same call";
+                help = exclusive.span() => "the preconditions are declared as exclusive here"
+            );
+        }
+    }
+
+    if forward.is_none() && routed_forwards.is_empty() {
+        if let Some(known_functions) = context.known_functions {
+            check_missing_preconditions(&original_call, &preconditions, known_functions);
+        }
+
+        if let (Some(coverage), Some(function)) = (context.coverage, called_ident(&original_call)) {
+            coverage.record(&function.to_string(), &preconditions);
+        }
+    }
+
+    let output = if !routed_forwards.is_empty() {
+        render_routed_call(forward, routed_forwards, preconditions, original_call, span)
+    } else if let Some(forward) = forward {
         forward.update_call(original_call, |call| {
             render_assure(preconditions, call, span)
         })
     } else {
-        let output = render_assure(preconditions, original_call, span);
+        render_assure(preconditions, original_call, span).into()
+    };
+
+    let output = if evidence_checks.is_empty() {
+        output
+    } else {
+        parse_quote_spanned! { span=>
+            {
+                #(#evidence_checks)*
+                #output
+            }
+        }
+    };
+
+    render_safety_comment(&safety_comment, output, span)
+}
 
-        output.into()
+/// Renders a call with `forward` attributes that have preconditions routed to them.
+///
+/// Every location is checked with the preconditions routed to it. The `forward` attribute without
+/// a list, if there is one, receives the remaining preconditions. Otherwise an error is emitted for
+/// every assured precondition that isn't routed anywhere.
+fn render_routed_call(
+    forward: Option<Forward>,
+    routed_forwards: Vec<(Forward, Vec<Precondition>)>,
+    preconditions: Vec<Precondition>,
+    original_call: Call,
+    span: Span,
+) -> Expr {
+    for (routed_forward, routed_preconditions) in &routed_forwards {
+        for routed_precondition in routed_preconditions {
+            if !preconditions.contains(routed_precondition) {
+                emit_error!(
+                    routed_precondition.span(),
+                    "the precondition `{}` is routed to this location, but not assured", routed_precondition;
+                    help = routed_forward.span() => "add an `assure` attribute for it or remove it from the list"
+                );
+            }
+        }
+    }
+
+    let (routed, remaining): (Vec<_>, Vec<_>) =
+        preconditions.into_iter().partition(|precondition| {
+            routed_forwards
+                .iter()
+                .any(|(_, routed_preconditions)| routed_preconditions.contains(precondition))
+        });
+
+    let mut locations = Vec::with_capacity(routed_forwards.len() + 1);
+    match forward {
+        Some(forward) => locations.push((forward, remaining)),
+        None => {
+            for precondition in remaining {
+                emit_error!(
+                    precondition.span(),
+                    "the precondition `{}` is not routed to any `forward` attribute", precondition;
+                    help = "add it to the list of the attribute with the right location or add a `forward` attribute without a list for the remaining preconditions"
+                );
+            }
+        }
+    }
+    locations.extend(
+        routed_forwards
+            .into_iter()
+            .map(|(routed_forward, routed_preconditions)| {
+                let preconditions = routed
+                    .iter()
+                    .filter(|precondition| routed_preconditions.contains(precondition))
+                    .cloned()
+                    .collect();
+
+                (routed_forward, preconditions)
+            }),
+    );
+
+    // Only the first location is evaluated, the others are only checked by the compiler.
+    locations
+        .into_iter()
+        .map(|(forward, preconditions)| {
+            forward.update_call(original_call.clone(), |call| {
+                render_assure(preconditions, call, span)
+            })
+        })
+        .rev()
+        .reduce(|checked, evaluated| {
+            parse_quote_spanned! { span=>
+                if true {
+                    #evaluated
+                } else {
+                    #checked
+                }
+            }
+        })
+        .expect("there is at least one routed `forward` attribute")
+}
+
+/// Renders a check that the precondition holds for the evidence at runtime.
+///
+/// The evidence is bound to the name of the value that the precondition refers to in a separate
+/// block, so that the arguments of the call are not affected by it. This emits an error and
+/// returns `None`, if the precondition cannot be checked this way.
+fn render_evidence_check(
+    precondition: &Precondition,
+    reason: &Reason,
+    evidence: &Evidence,
+) -> Option<TokenStream> {
+    let subject = match precondition {
+        Precondition::ValidPtr { ident, .. }
+        | Precondition::NonNull { ident, .. }
+        | Precondition::NonZero { ident, .. }
+        | Precondition::Sorted { ident, .. }
+        | Precondition::Aligned { ident, .. }
+        | Precondition::ProperAlign { ident, .. }
+        | Precondition::Initialized { ident, .. }
+        | Precondition::Utf8 { ident, .. }
+        | Precondition::InRange { ident, .. } => Ok(ident.clone()),
+        Precondition::Boolean(expr) => {
+            let mut collector = IdentCollector(Vec::new());
+            collector.visit_expr_mut(&mut expr.clone());
+
+            match &collector.0[..] {
+                [ident] => Ok(ident.clone()),
+                [] => Err("this precondition does not refer to any value".to_string()),
+                idents => {
+                    let names: Vec<_> = idents.iter().map(|ident| format!("`{}`", ident)).collect();
+
+                    Err(format!(
+                        "this precondition refers to multiple values: {}",
+                        names.join(", ")
+                    ))
+                }
+            }
+        }
+        Precondition::NonOverlapping {
+            first,
+            second,
+            count,
+            ..
+        } => Err(format!(
+            "this precondition refers to multiple values: `{}`, `{}`, `{}`",
+            first, second, count
+        )),
+        _ => Err("this precondition cannot be checked at runtime".to_string()),
+    };
+
+    let checked = subject.and_then(|subject| match runtime_check(precondition) {
+        Some((check, description, _, _)) => Ok((subject, check, description)),
+        None => Err("this precondition cannot be checked at runtime".to_string()),
+    });
+
+    match checked {
+        Ok((subject, check, description)) => {
+            let value = &evidence.value;
+            let reason = reason.expand(precondition);
+            let gate = contracts_gate(value.span());
+
+            Some(quote_spanned! { value.span()=>
+                #gate
+                {
+                    let #subject = #value;
+                    ::core::debug_assert!(
+                        #check,
+                        "the evidence does not satisfy the precondition `{}` (reason: {})",
+                        #description,
+                        #reason
+                    );
+                }
+            })
+        }
+        Err(help) => {
+            emit_error!(
+                evidence.value,
+                "`evidence` is only supported for preconditions about a single value that can be checked at runtime";
+                help = precondition.span() => help
+            );
+
+            None
+        }
+    }
+}
+
+/// Collects the single identifiers used as values in an expression.
+///
+/// Functions that are called by a single identifier and the parameters of closures are not
+/// collected.
+pub(crate) struct IdentCollector(pub(crate) Vec<Ident>);
+
+impl VisitMut for IdentCollector {
+    fn visit_expr_path_mut(&mut self, expr: &mut ExprPath) {
+        if let (None, Some(ident)) = (&expr.qself, expr.path.get_ident()) {
+            if !self.0.contains(ident) {
+                self.0.push(ident.clone());
+            }
+        }
+    }
+
+    fn visit_expr_call_mut(&mut self, call: &mut ExprCall) {
+        if !matches!(&*call.func, Expr::Path(_)) {
+            self.visit_expr_mut(&mut call.func);
+        }
+
+        for arg in call.args.iter_mut() {
+            self.visit_expr_mut(arg);
+        }
+    }
+
+    fn visit_expr_closure_mut(&mut self, closure: &mut ExprClosure) {
+        let mut body = IdentCollector(Vec::new());
+        body.visit_expr_mut(&mut closure.body);
+
+        let parameters: Vec<_> = closure
+            .inputs
+            .iter()
+            .filter_map(|input| match input {
+                Pat::Ident(pat) => Some(&pat.ident),
+                Pat::Type(PatType { pat, .. }) => match &**pat {
+                    Pat::Ident(pat) => Some(&pat.ident),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+
+        for ident in body.0 {
+            if !parameters.contains(&&ident) && !self.0.contains(&ident) {
+                self.0.push(ident);
+            }
+        }
     }
 }
 
 /// Checks that all reasons exist and make sense.
 ///
 /// This function emits errors, if appropriate.
-fn check_reasons(assure_attributes: Vec<AssureAttr>) -> Vec<Precondition> {
+fn check_reasons(
+    assure_attributes: Vec<AssureAttr>,
+    reason_pattern: Option<&ReasonPattern>,
+) -> Vec<Precondition> {
     for assure_attribute in assure_attributes.iter() {
         match assure_attribute {
-            AssureAttr::WithReason { reason, .. } => {
-                if let Some(reason) = unfinished_reason(&reason.reason) {
+            AssureAttr::WithReason {
+                reason,
+                allow_placeholder,
+                ..
+            } => {
+                let is_too_short = is_too_short(&reason.reason, min_reason_length());
+
+                if let (Some((_, allow_placeholder)), None, false) = (
+                    allow_placeholder,
+                    unfinished_reason(&reason.reason),
+                    is_too_short,
+                ) {
                     emit_warning!(
-                        reason,
-                        "you should specify a different here";
-                        help = "specifying a meaningful reason will help you and others understand why this is ok in the future"
+                        allow_placeholder,
+                        "`allow_placeholder` has no effect here";
+                        help = reason.reason.span() => "this reason is not a placeholder"
                     )
+                }
+
+                if let Some(reason) = unfinished_reason(&reason.reason) {
+                    if allow_placeholder.is_none() {
+                        emit_warning!(
+                            reason,
+                            "you should specify a different here";
+                            help = "specifying a meaningful reason will help you and others understand why this is ok in the future"
+                        )
+                    }
                 } else if reason.reason.value() == HINT_REASON {
                     let todo_help_msg = if cfg!(nightly) {
                         Some("using `TODO` here will emit a warning, reminding you to fix this later")
@@ -265,6 +691,41 @@ fn check_reasons(assure_attributes: Vec<AssureAttr>) -> Vec<Precondition> {
                         help = "specifying a meaningful reason will help you and others understand why this is ok in the future";
                         help =? todo_help_msg
                     )
+                } else {
+                    if is_too_short && allow_placeholder.is_none() {
+                        emit_warning!(
+                            reason.reason,
+                            "this reason is probably too short to be meaningful";
+                            help = "a reason should explain why the precondition holds, which rarely fits into fewer than {} characters", min_reason_length();
+                            note = "the minimum length can be changed with the `{}` environment variable", MIN_REASON_LENGTH_ENV_VAR
+                        )
+                    }
+
+                    let precondition = assure_attribute.precondition();
+                    if !is_too_short
+                        && allow_placeholder.is_none()
+                        && restates_precondition(&reason.expand(precondition), precondition)
+                    {
+                        emit_warning!(
+                            reason.reason,
+                            "this reason only restates the precondition `{}`", precondition;
+                            help = "a reason should explain why the precondition holds, for example by referring to a check or an invariant that guarantees it"
+                        )
+                    }
+
+                    if let Some(reason_pattern) = reason_pattern {
+                        if !reason_pattern
+                            .pattern
+                            .is_match(&reason.expand(assure_attribute.precondition()))
+                        {
+                            emit_error!(
+                                reason.reason,
+                                "this reason does not match the required pattern `{}`",
+                                reason_pattern.lit.value();
+                                help = reason_pattern.lit.span() => "the pattern is required here"
+                            )
+                        }
+                    }
                 }
             }
             AssureAttr::WithoutReason {
@@ -285,6 +746,135 @@ fn check_reasons(assure_attributes: Vec<AssureAttr>) -> Vec<Precondition> {
         .collect()
 }
 
+/// Removes `assure` attributes for preconditions that are already assured for the same call.
+///
+/// This emits an error for every removed attribute, pointing to the first one.
+fn remove_duplicate_assures(assure_attributes: Vec<AssureAttr>) -> Vec<AssureAttr> {
+    let mut unique: Vec<AssureAttr> = Vec::with_capacity(assure_attributes.len());
+
+    for assure_attribute in assure_attributes {
+        if let Some(first) = unique
+            .iter()
+            .find(|other| other.precondition() == assure_attribute.precondition())
+        {
+            emit_error!(
+                assure_attribute.span(),
+                "the precondition `{}` is assured more than once for this call",
+                assure_attribute.precondition();
+                help = first.span() => "it is first assured here, try removing one of the attributes"
+            );
+        } else {
+            unique.push(assure_attribute);
+        }
+    }
+
+    unique
+}
+
+/// Checks that all preconditions of a function defined in the same module are assured.
+///
+/// This emits an error for every missing precondition, if the called function is known. With the
+/// `diff-errors` feature, a single error showing the differences is emitted instead, which also
+/// includes the preconditions that are assured, but not declared.
+fn check_missing_preconditions(
+    call: &Call,
+    assured_preconditions: &[Precondition],
+    known_functions: &HashMap<String, Vec<Precondition>>,
+) {
+    let (function, declared_preconditions) = match called_ident(call)
+        .and_then(|ident| Some((ident.clone(), known_functions.get(&ident.to_string())?)))
+    {
+        Some(known_function) => known_function,
+        None => return,
+    };
+
+    if cfg!(feature = "diff-errors") {
+        if let Some(diff) = precondition_diff(declared_preconditions, assured_preconditions) {
+            emit_error!(
+                call,
+                "the assured preconditions don't match the preconditions of `{}`", function;
+                note = "the differences are:\n{}", diff;
+                help = "assure the preconditions marked with `-` and remove the ones marked with `+`"
+            );
+        }
+
+        return;
+    }
+
+    for precondition in declared_preconditions {
+        if !assured_preconditions.contains(precondition) {
+            emit_error!(
+                call,
+                "the precondition `{}` is not assured for this call", precondition;
+                help = precondition.span() => "the precondition is declared here"
+            );
+        }
+    }
+}
+
+/// Renders the differences between the declared and the assured preconditions as a unified diff.
+///
+/// The preconditions are listed in the same order as in the markers. Declared preconditions that
+/// are not assured are prefixed with `-`, assured preconditions that are not declared with `+` and
+/// all others with a space. `None` is returned, if both lists contain the same preconditions.
+fn precondition_diff(declared: &[Precondition], assured: &[Precondition]) -> Option<String> {
+    let mut preconditions: Vec<_> = declared
+        .iter()
+        .chain(
+            assured
+                .iter()
+                .filter(|precondition| !declared.contains(precondition)),
+        )
+        .collect();
+    preconditions.sort_unstable();
+
+    if preconditions
+        .iter()
+        .all(|precondition| declared.contains(precondition) && assured.contains(precondition))
+    {
+        return None;
+    }
+
+    let mut diff = vec!["--- declared".to_string(), "+++ assured".to_string()];
+    diff.extend(preconditions.into_iter().map(|precondition| {
+        let prefix = if !assured.contains(precondition) {
+            '-'
+        } else if !declared.contains(precondition) {
+            '+'
+        } else {
+            ' '
+        };
+
+        format!("{} {}", prefix, precondition)
+    }));
+
+    Some(diff.join("\n"))
+}
+
+/// Returns the identifier of the called function, if it is called by a single identifier.
+///
+/// Only such functions can be defined in the surrounding module. The identifier may be preceded by
+/// `self::` and followed by generic arguments, such as in `self::foo::<u8>()`.
+pub(crate) fn called_ident(call: &Call) -> Option<Ident> {
+    let path = match call.path() {
+        Some(path) if path.qself.is_none() && path.path.leading_colon.is_none() => path.path,
+        _ => return None,
+    };
+
+    let mut segments = path.segments.iter();
+    let segment = match (segments.next(), segments.next(), segments.next()) {
+        (Some(segment), None, None) => segment,
+        (Some(module), Some(segment), None)
+            if module.ident == "self" && module.arguments.is_empty() =>
+        {
+            segment
+        }
+        _ => return None,
+    };
+
+    Some(segment.ident.clone())
+}
+
 /// Returns an unfinished reason declaration for the precondition if one exists.
 fn unfinished_reason(reason: &LitStr) -> Option<&LitStr> {
     let mut reason_val = reason.value();
@@ -295,3 +885,213 @@ fn unfinished_reason(reason: &LitStr) -> Option<&LitStr> {
         _ => None,
     }
 }
+
+/// The environment variable specifying the minimum length of a reason.
+///
+/// It is read without telling the compiler, so a change only applies to crates compiled afterwards.
+const MIN_REASON_LENGTH_ENV_VAR: &str = "PRE_MIN_REASON_LENGTH";
+
+/// The minimum length of a reason, if none is specified.
+const DEFAULT_MIN_REASON_LENGTH: usize = 5;
+
+/// Returns the minimum number of characters that a reason should have.
+///
+/// An error is emitted if the `PRE_MIN_REASON_LENGTH` environment variable is not a number.
+fn min_reason_length() -> usize {
+    let value = match env::var(MIN_REASON_LENGTH_ENV_VAR) {
+        Ok(value) => value,
+        Err(_) => return DEFAULT_MIN_REASON_LENGTH,
+    };
+
+    match value.trim().parse() {
+        Ok(min_length) => min_length,
+        Err(_) => {
+            emit_error!(
+                Span::call_site(),
+                "the minimum length of a reason must be a number, found `{}`", value;
+                note = "the minimum length is specified by the `{}` environment variable",
+                MIN_REASON_LENGTH_ENV_VAR
+            );
+
+            DEFAULT_MIN_REASON_LENGTH
+        }
+    }
+}
+
+/// Checks whether the reason has fewer characters than `min_length`, ignoring surrounding
+/// whitespace.
+fn is_too_short(reason: &LitStr, min_length: usize) -> bool {
+    reason.value().trim().chars().count() < min_length
+}
+
+/// Words that can be added to a precondition without adding any information to a reason.
+const FILLER_WORDS: &[&str] = &[
+    "a", "an", "the", "is", "are", "be", "must", "holds", "true", "it", "this", "that", "here",
+    "not", "than", "to", "equal", "greater", "less", "valid", "pointer", "ptr", "for", "r", "w",
+    "reads", "writes",
+];
+
+/// Returns the lowercase words of the text that are not filler words, ignoring all punctuation.
+fn significant_words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .filter(|word| !FILLER_WORDS.contains(&word.as_str()))
+        .collect()
+}
+
+/// Checks whether the reason consists of the same words as the precondition, apart from fillers.
+///
+/// This is deliberately conservative: a reason that mentions anything that is not part of the
+/// precondition or leaves out a part of the precondition is taken as an actual explanation.
+fn restates_precondition(reason: &str, precondition: &Precondition) -> bool {
+    let reason_words = significant_words(reason);
+    let precondition_words = significant_words(&precondition.to_string());
+
+    !reason_words.is_empty()
+        && reason_words
+            .iter()
+            .all(|word| precondition_words.contains(word))
+        && precondition_words
+            .iter()
+            .all(|word| reason_words.contains(word))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+    use syn::parse_quote;
+
+    use super::*;
+
+    #[test]
+    fn called_ident_of_calls_in_the_same_module() {
+        let called = |expr: Expr| {
+            let call: Call = expr.try_into().unwrap_or_else(|_| panic!("not a call"));
+
+            called_ident(&call).map(|ident| ident.to_string())
+        };
+
+        assert_eq!(called(parse_quote! { foo(1) }).as_deref(), Some("foo"));
+        assert_eq!(
+            called(parse_quote! { foo::<u8>(1) }).as_deref(),
+            Some("foo")
+        );
+        assert_eq!(
+            called(parse_quote! { self::foo(1) }).as_deref(),
+            Some("foo")
+        );
+        assert_eq!(called(parse_quote! { other::foo(1) }), None);
+        assert_eq!(called(parse_quote! { ::foo(1) }), None);
+        assert_eq!(called(parse_quote! { <T>::foo(1) }), None);
+        assert_eq!(called(parse_quote! { x.foo(1) }), None);
+    }
+
+    #[test]
+    fn short_reasons_are_detected() {
+        let reason = |text| LitStr::new(text, Span::call_site());
+
+        assert!(is_too_short(&reason("ok"), 5));
+        assert!(is_too_short(&reason("  safe  "), 5));
+        assert!(!is_too_short(&reason("1 > 0"), 5));
+        assert!(!is_too_short(&reason("`x` is checked above"), 5));
+        assert!(is_too_short(&reason("`x` is 3"), 10));
+        assert!(!is_too_short(&reason("äöüßä"), 5));
+        assert!(!is_too_short(&reason(""), 0));
+    }
+
+    #[test]
+    fn restated_preconditions_are_detected() {
+        let valid_ptr: Precondition = parse_quote! { valid_ptr(p, r) };
+        let boolean: Precondition = parse_quote! { x > 0 };
+        let custom: Precondition = parse_quote! { "is a good value" };
+
+        assert!(restates_precondition("p is a valid pointer", &valid_ptr));
+        assert!(restates_precondition(
+            "`p` must be valid for reads.",
+            &valid_ptr
+        ));
+        assert!(restates_precondition("x > 0", &boolean));
+        assert!(restates_precondition("X is greater than 0!", &boolean));
+        assert!(restates_precondition("It is a good value", &custom));
+    }
+
+    #[test]
+    fn detailed_reasons_are_not_restated_preconditions() {
+        let valid_ptr: Precondition = parse_quote! { valid_ptr(p, r) };
+        let boolean: Precondition = parse_quote! { x > 0 };
+        let custom: Precondition = parse_quote! { "`p` points to the answer" };
+
+        assert!(!restates_precondition(
+            "p comes from a reference",
+            &valid_ptr
+        ));
+        assert!(!restates_precondition(
+            "x is the length of a slice",
+            &boolean
+        ));
+        assert!(!restates_precondition("x > 0 is checked above", &boolean));
+        assert!(!restates_precondition("`answer` is the answer", &custom));
+        assert!(!restates_precondition("it is", &custom));
+        assert!(!restates_precondition("", &boolean));
+    }
+
+    #[test]
+    fn expand_reason_replaces_cond() {
+        assert_eq!(
+            expand_reason("{cond} holds, because `x` is 1", "x > 0"),
+            "x > 0 holds, because `x` is 1"
+        );
+        assert_eq!(expand_reason("{cond} and {cond}", "a"), "a and a");
+        assert_eq!(expand_reason("no placeholder", "x > 0"), "no placeholder");
+    }
+
+    #[test]
+    fn expand_reason_escapes_braces() {
+        assert_eq!(
+            expand_reason("`Foo {{ x }}` shows {{cond}}", "x > 0"),
+            "`Foo { x }` shows {cond}"
+        );
+        assert_eq!(expand_reason("{{{cond}}}", "x > 0"), "{x > 0}");
+        assert_eq!(
+            expand_reason("`{}` and {other} are kept", "x > 0"),
+            "`{}` and {other} are kept"
+        );
+        assert_eq!(expand_reason("unclosed {cond", "x > 0"), "unclosed {cond");
+    }
+
+    #[test]
+    fn diff_shows_missing_and_extra_preconditions() {
+        let declared: [Precondition; 3] = [
+            parse_quote! { "is a prime number" },
+            parse_quote! { x > 17 },
+            parse_quote! { valid_ptr(p, r) },
+        ];
+        let assured: [Precondition; 2] = [
+            parse_quote! { "is a prime number" },
+            parse_quote! { "is a good value" },
+        ];
+
+        assert_eq!(
+            precondition_diff(&declared, &assured).expect("the preconditions differ"),
+            [
+                "--- declared",
+                "+++ assured",
+                "- valid_ptr(p, r)",
+                "- x > 17",
+                "+ \"is a good value\"",
+                "  \"is a prime number\"",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn diff_of_matching_preconditions_is_empty() {
+        let declared: [Precondition; 2] = [parse_quote! { x > 17 }, parse_quote! { "foo" }];
+        let assured: [Precondition; 2] = [parse_quote! { "foo" }, parse_quote! { x > 17 }];
+
+        assert!(precondition_diff(&declared, &assured).is_none());
+        assert!(precondition_diff(&[], &[]).is_none());
+    }
+}
@@ -35,19 +35,99 @@
 //!     }
 //! }
 //! ```
+//!
+//! # Routing preconditions to different locations
+//!
+//! A call can have multiple `forward` attributes, if each one lists the preconditions that are
+//! checked against its location: `#[forward(crate::a for (valid_ptr(p, r)))]`. At most one
+//! `forward` attribute of a call can omit the list, which then receives all preconditions that are
+//! not listed by any other attribute. Every location is rendered the same as for a single
+//! `forward` attribute and the results are combined into one expression, which evaluates the
+//! first location:
+//!
+//! ```rust,ignore
+//! if true {
+//!     // The call rendered for the first `forward` attribute.
+//! } else {
+//!     // The call rendered for the second `forward` attribute.
+//! }
+//! ```
 
 use proc_macro2::Span;
 use proc_macro_error::{abort, emit_error};
 use quote::{quote, quote_spanned};
 use syn::{
+    parenthesized,
     parse::{Parse, ParseStream},
     parse2,
-    punctuated::Pair,
+    punctuated::{Pair, Punctuated},
     spanned::Spanned,
-    Expr, ExprCall, ExprPath, Path, Token,
+    token::Paren,
+    Error, Expr, ExprCall, ExprPath, Path, Token,
 };
 
-use crate::{call::Call, extern_crate::impl_block_stub_name};
+use crate::{call::Call, extern_crate::impl_block_stub_name, precondition::Precondition};
+
+/// A `forward` attribute, optionally limited to some of the assured preconditions.
+pub(crate) struct ForwardAttr {
+    /// The location that the call is forwarded to.
+    pub(crate) forward: Forward,
+    /// The preconditions that are checked against the location, if they are listed.
+    pub(crate) routing: Option<Routing>,
+}
+
+impl Parse for ForwardAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+        let forward = content.parse()?;
+        let routing = if content.is_empty() {
+            None
+        } else {
+            Some(content.parse()?)
+        };
+
+        Ok(ForwardAttr { forward, routing })
+    }
+}
+
+/// The list of preconditions after the location: `for (<precondition>, ...)`.
+pub(crate) struct Routing {
+    /// The `for` keyword introducing the list.
+    _for: Token![for],
+    /// The parentheses surrounding the list.
+    _parentheses: Paren,
+    /// The preconditions that are routed to the location.
+    pub(crate) preconditions: Punctuated<Precondition, Token![,]>,
+}
+
+impl Parse for Routing {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let for_token = input.parse()?;
+        let content;
+        let parentheses = parenthesized!(content in input);
+        let preconditions = content.parse_terminated(Precondition::parse)?;
+
+        if preconditions.is_empty() {
+            return Err(Error::new(
+                parentheses.span,
+                "expected at least one precondition to route to this location",
+            ));
+        }
+
+        if !input.is_empty() {
+            return Err(input.error(
+                "expected the end of the `forward` attribute after the list of preconditions",
+            ));
+        }
+
+        Ok(Routing {
+            _for: for_token,
+            _parentheses: parentheses,
+            preconditions,
+        })
+    }
+}
 
 /// The content of a `forward` attribute.
 ///
@@ -93,6 +173,10 @@ pub(crate) enum Forward {
     },
 }
 
+/// The forms a `forward` attribute can take.
+const FORWARD_FORMS: &str =
+    "expected `forward(path::to::module)`, `forward(impl path::to::Type)` or `forward(old::path -> new::path)`";
+
 impl Parse for Forward {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let impl_keyword = if input.peek(Token![impl]) {
@@ -101,9 +185,9 @@ impl Parse for Forward {
             None
         };
 
-        let first_path = input.parse()?;
+        let first_path = parse_path(input)?;
 
-        Ok(if input.is_empty() {
+        Ok(if input.is_empty() || input.peek(Token![for]) {
             if let Some(impl_keyword) = impl_keyword {
                 Forward::ImplBlock {
                     impl_keyword,
@@ -116,9 +200,16 @@ impl Parse for Forward {
                     span: None,
                 }
             }
-        } else {
+        } else if input.peek(Token![->]) {
             let arrow = input.parse()?;
-            let second_path = input.parse()?;
+            let second_path = parse_path(input)?;
+
+            if !input.is_empty() && !input.peek(Token![for]) {
+                return Err(Error::new(
+                    input.span(),
+                    "expected the end of the `forward` attribute after the replacement path",
+                ));
+            }
 
             Forward::Replace {
                 from: first_path,
@@ -126,10 +217,33 @@ impl Parse for Forward {
                 to: second_path,
                 span: None,
             }
+        } else {
+            let mut err = Error::new(input.span(), "expected `->` or the end of the path");
+            err.combine(Error::new(first_path.span(), FORWARD_FORMS));
+
+            return Err(err);
         })
     }
 }
 
+/// Parses a path in a `forward` attribute, reporting missing and malformed paths.
+fn parse_path(input: ParseStream) -> syn::Result<Path> {
+    if input.is_empty() {
+        let mut err = input.error("expected a path in the `forward` attribute");
+        err.combine(Error::new(input.span(), FORWARD_FORMS));
+
+        return Err(err);
+    }
+
+    let start_span = input.span();
+
+    input.parse().map_err(|mut err| {
+        err.combine(Error::new(start_span, FORWARD_FORMS));
+
+        err
+    })
+}
+
 impl Spanned for Forward {
     fn span(&self) -> Span {
         match self {
@@ -47,10 +47,27 @@
 //! fn main() {
 //!     has_preconditions(
 //!         43.0,
-//!         ::core::marker::PhantomData::<(::pre::BooleanCondition<"some_val > 42.0">,)>,
+//!         <(::pre::BooleanCondition<"some_val > 42.0">,)
+//!             as ::pre::__private::AssuredPreconditions<_>>::MARKER,
 //!     );
 //! }
 //! ```
+//!
+//! # Mismatched preconditions
+//!
+//! The marker of a call is the `MARKER` of `AssuredPreconditions`, which is only implemented if
+//! the assured preconditions are the same as the declared ones. Because the declared preconditions
+//! are inferred from the marker parameter, assuring the wrong preconditions results in an error
+//! like this, instead of a mismatch of two `PhantomData` types:
+//!
+//! ```text
+//! error[E0277]: the assured preconditions don't match the preconditions of the called function
+//!   |
+//!   |     #[assure("is bar", reason = "foo is bar")]
+//!   |     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ the assured preconditions are `(pre::CustomCondition<"is bar">,)`
+//!   |
+//!   = note: the called function has the preconditions `(pre::CustomCondition<"is bar">, pre::CustomCondition<"is baz">)`
+//! ```
 
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, quote_spanned, TokenStreamExt};
@@ -58,8 +75,11 @@ use syn::{parse2, spanned::Spanned, Ident, ItemFn, LitStr};
 
 use crate::{
     call::Call,
-    helpers::{add_span_to_signature, CRATE_NAME},
-    precondition::{Precondition, ReadWrite},
+    helpers::{
+        add_marker_argument, add_marker_parameter, add_span_to_signature, crate_name_ident,
+        marker_pattern,
+    },
+    precondition::{custom_text, path_to_string, Precondition, ReadWrite},
 };
 
 /// Renders a precondition list to a token stream.
@@ -67,14 +87,23 @@ fn render_condition_list(mut preconditions: Vec<Precondition>, span: Span) -> To
     preconditions.sort_unstable();
 
     let mut tokens = TokenStream::new();
-    let crate_name = Ident::new(&CRATE_NAME, span);
+    let crate_name = crate_name_ident(span);
 
     for precondition in preconditions {
         match &precondition {
             Precondition::ValidPtr {
-                ident, read_write, ..
+                ident,
+                ty,
+                read_write,
+                ..
             } => {
-                let ident_lit = LitStr::new(&ident.to_string(), ident.span());
+                // The type is part of the pointer string, so that `ValidPtrCondition` does not need
+                // an additional parameter.
+                let ident_str = match ty {
+                    Some((_, ty)) => format!("{}: {}", ident, quote! { #ty }),
+                    None => ident.to_string(),
+                };
+                let ident_lit = LitStr::new(&ident_str, ident.span());
                 let rw_str = match read_write {
                     ReadWrite::Read { .. } => LitStr::new("r", read_write.span()),
                     ReadWrite::Write { .. } => LitStr::new("w", read_write.span()),
@@ -92,10 +121,181 @@ fn render_condition_list(mut preconditions: Vec<Precondition>, span: Span) -> To
                 });
             }
             Precondition::Custom(string) => {
+                let string = LitStr::new(&custom_text(string), string.span());
+
                 tokens.append_all(quote_spanned! { precondition.span()=>
                     ::#crate_name::CustomCondition::<#string>
                 });
             }
+            Precondition::See { path, .. } => {
+                tokens.append_all(quote_spanned! { precondition.span()=>
+                    ::#crate_name::SeeCondition::<#path>
+                });
+            }
+            Precondition::InRange { ident, range, .. } => {
+                let ident_lit = LitStr::new(&ident.to_string(), ident.span());
+                let range_lit = LitStr::new(&quote! { #range }.to_string(), range.span());
+
+                tokens.append_all(quote_spanned! { precondition.span()=>
+                    ::#crate_name::InRangeCondition::<#ident_lit, #range_lit>
+                });
+            }
+            Precondition::Kind {
+                path, arguments, ..
+            } => {
+                let path_lit = LitStr::new(&path_to_string(path), path.span());
+                let arguments: Vec<_> = arguments.iter().map(|arg| arg.to_string()).collect();
+                let arguments_lit = LitStr::new(&arguments.join(", "), precondition.span());
+
+                tokens.append_all(quote_spanned! { precondition.span()=>
+                    ::#crate_name::KindCondition::<#path_lit, #arguments_lit>
+                });
+            }
+            Precondition::Relation {
+                operator,
+                left,
+                right,
+                ..
+            } => {
+                let left_lit = LitStr::new(&left.to_string(), left.span());
+                let right_lit = LitStr::new(&right.to_string(), right.span());
+
+                tokens.append_all(quote_spanned! { precondition.span()=>
+                    ::#crate_name::RelationCondition::<#operator, #left_lit, #right_lit>
+                });
+            }
+            Precondition::Locked { ident, .. } => {
+                let ident_lit = LitStr::new(&ident.to_string(), ident.span());
+
+                tokens.append_all(quote_spanned! { precondition.span()=>
+                    ::#crate_name::LockedCondition::<#ident_lit>
+                });
+            }
+            Precondition::SameProvenance { first, second, .. } => {
+                let first_lit = LitStr::new(&first.to_string(), first.span());
+                let second_lit = LitStr::new(&second.to_string(), second.span());
+
+                tokens.append_all(quote_spanned! { precondition.span()=>
+                    ::#crate_name::SameProvenanceCondition::<#first_lit, #second_lit>
+                });
+            }
+            Precondition::NonNull { ident, .. } => {
+                let ident_lit = LitStr::new(&ident.to_string(), ident.span());
+
+                tokens.append_all(quote_spanned! { precondition.span()=>
+                    ::#crate_name::NonNullCondition::<#ident_lit>
+                });
+            }
+            Precondition::NonZero { ident, .. } => {
+                let ident_lit = LitStr::new(&ident.to_string(), ident.span());
+
+                tokens.append_all(quote_spanned! { precondition.span()=>
+                    ::#crate_name::NonZeroCondition::<#ident_lit>
+                });
+            }
+            Precondition::Sorted { ident, by, .. } => {
+                let ident_lit = LitStr::new(&ident.to_string(), ident.span());
+                let by = by
+                    .clone()
+                    .unwrap_or_else(|| LitStr::new("", precondition.span()));
+
+                tokens.append_all(quote_spanned! { precondition.span()=>
+                    ::#crate_name::SortedCondition::<#ident_lit, #by>
+                });
+            }
+            Precondition::Aligned { ident, .. } => {
+                let ident_lit = LitStr::new(&ident.to_string(), ident.span());
+
+                tokens.append_all(quote_spanned! { precondition.span()=>
+                    ::#crate_name::AlignedCondition::<#ident_lit>
+                });
+            }
+            Precondition::ProperAlign { ident, ty, .. } => {
+                let ident_lit = LitStr::new(&ident.to_string(), ident.span());
+                let ty_lit = LitStr::new(&quote! { #ty }.to_string(), ty.span());
+
+                tokens.append_all(quote_spanned! { precondition.span()=>
+                    ::#crate_name::AlignForTypeConditionHolds::<#ident_lit, #ty_lit>
+                });
+            }
+            Precondition::Initialized { ident, .. } => {
+                let ident_lit = LitStr::new(&ident.to_string(), ident.span());
+
+                tokens.append_all(quote_spanned! { precondition.span()=>
+                    ::#crate_name::InitializedCondition::<#ident_lit>
+                });
+            }
+            Precondition::Exclusive { ident, .. } => {
+                let ident_lit = LitStr::new(&ident.to_string(), ident.span());
+
+                tokens.append_all(quote_spanned! { precondition.span()=>
+                    ::#crate_name::ExclusiveConditionHolds::<#ident_lit>
+                });
+            }
+            Precondition::Utf8 { ident, .. } => {
+                let ident_lit = LitStr::new(&ident.to_string(), ident.span());
+
+                tokens.append_all(quote_spanned! { precondition.span()=>
+                    ::#crate_name::Utf8ConditionHolds::<#ident_lit>
+                });
+            }
+            Precondition::OnThread { name, .. } => {
+                tokens.append_all(quote_spanned! { precondition.span()=>
+                    ::#crate_name::OnThreadConditionHolds::<#name>
+                });
+            }
+            Precondition::NonOverlapping {
+                first,
+                second,
+                count,
+                ..
+            } => {
+                let first_lit = LitStr::new(&first.to_string(), first.span());
+                let second_lit = LitStr::new(&second.to_string(), second.span());
+                let count_lit = LitStr::new(&count.to_string(), count.span());
+
+                tokens.append_all(quote_spanned! { precondition.span()=>
+                    ::#crate_name::NonOverlappingCondition::<#first_lit, #second_lit, #count_lit>
+                });
+            }
+            Precondition::Len {
+                minimum,
+                ident,
+                length,
+                ..
+            } => {
+                let ident_lit = LitStr::new(&ident.to_string(), ident.span());
+                let length_lit = LitStr::new(&length.to_string(), ident.span());
+
+                if *minimum {
+                    tokens.append_all(quote_spanned! { precondition.span()=>
+                        ::#crate_name::MinLenCondition::<#ident_lit, #length_lit>
+                    });
+                } else {
+                    tokens.append_all(quote_spanned! { precondition.span()=>
+                        ::#crate_name::LenCondition::<#ident_lit, #length_lit>
+                    });
+                }
+            }
+            Precondition::AccessLen {
+                writable,
+                ident,
+                length,
+                ..
+            } => {
+                let ident_lit = LitStr::new(&ident.to_string(), ident.span());
+                let length_lit = LitStr::new(&length.to_string(), ident.span());
+
+                if *writable {
+                    tokens.append_all(quote_spanned! { precondition.span()=>
+                        ::#crate_name::WritableLenCondition::<#ident_lit, #length_lit>
+                    });
+                } else {
+                    tokens.append_all(quote_spanned! { precondition.span()=>
+                        ::#crate_name::ReadableLenCondition::<#ident_lit, #length_lit>
+                    });
+                }
+            }
         }
 
         tokens.append_all(quote_spanned! { span=>
@@ -110,18 +310,21 @@ fn render_condition_list(mut preconditions: Vec<Precondition>, span: Span) -> To
 pub(crate) fn render_pre(
     preconditions: Vec<Precondition>,
     function: &mut ItemFn,
+    marker_name: Option<&Ident>,
     span: Span,
 ) -> TokenStream {
     let preconditions = render_condition_list(preconditions, span);
+    let marker = marker_pattern(marker_name, span);
 
     // Include the precondition site into the span of the function.
     // This improves the error messages for the case where no preconditions are specified.
     add_span_to_signature(span, &mut function.sig);
 
-    function.sig.inputs.push(
+    add_marker_parameter(
+        &mut function.sig,
         parse2(quote_spanned! { span=>
             #[cfg(not(doc))]
-            _: ::core::marker::PhantomData<(#preconditions)>
+            #marker: ::core::marker::PhantomData<(#preconditions)>
         })
         .expect("parses as a function argument"),
     );
@@ -134,10 +337,16 @@ pub(crate) fn render_pre(
 /// Generates the code for the call with the precondition handling added.
 pub(crate) fn render_assure(preconditions: Vec<Precondition>, mut call: Call, span: Span) -> Call {
     let preconditions = render_condition_list(preconditions, span);
+    let crate_name = crate_name_ident(span);
 
-    call.args_mut().push(
+    // The type of the marker is inferred from the marker parameter, which never mentions the type
+    // parameters of the function, so the additional argument does not influence their inference.
+    // If the assured preconditions don't match, the bound of `AssuredPreconditions` is not
+    // satisfied, which results in a more readable error than a mismatch of the marker types.
+    add_marker_argument(
+        &mut call,
         parse2(quote_spanned! { span=>
-            ::core::marker::PhantomData::<(#preconditions)>
+            <(#preconditions) as ::#crate_name::__private::AssuredPreconditions<_>>::MARKER
         })
         .expect("parses as an expression"),
     );
@@ -0,0 +1,337 @@
+//! Records how often the preconditions of functions are assured.
+//!
+//! This is used when the `coverage` feature is enabled. For every module annotated with a `pre`
+//! attribute, the number of call sites in that module that assure each precondition of a function
+//! defined directly in the module is counted. The counts are written to a report at
+//! `$OUT_DIR/pre-coverage.txt`, which is shared by all modules of the crate being compiled.
+//!
+//! # Report format
+//!
+//! The report is a text file with one line per declared precondition. Each line consists of the
+//! following fields separated by tabs:
+//!
+//! 1. the full path of the module containing the function, such as `my_crate::parser::inner`
+//! 2. the name of the function
+//! 3. the precondition as it is written in a `pre` attribute
+//! 4. the number of call sites in the module that assure the precondition
+//!
+//! The lines are sorted by the first three fields. When a module is compiled again, all of its
+//! lines are replaced, so the report always reflects the last compilation of every module.
+//!
+//! Problems with writing the report are only reported as warnings, so that they never break the
+//! build.
+
+use proc_macro2::Span;
+use proc_macro_error::emit_warning;
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    env,
+    fs::{self, OpenOptions},
+    io,
+    path::{Component, Path},
+    thread,
+    time::Duration,
+};
+
+use crate::precondition::Precondition;
+
+/// The name of the report file in `OUT_DIR`.
+const REPORT_FILE_NAME: &str = "pre-coverage.txt";
+
+/// The name of the file used to lock the report while it is updated.
+const LOCK_FILE_NAME: &str = "pre-coverage.lock";
+
+/// How often to retry acquiring the lock before giving up.
+const LOCK_ATTEMPTS: usize = 1000;
+
+/// How long to wait between two attempts to acquire the lock.
+const LOCK_RETRY_DELAY: Duration = Duration::from_millis(10);
+
+/// Returns the full path of a module, given its path relative to the annotated item.
+///
+/// The path of the file containing the annotated item is derived from its location in the crate,
+/// the same way the compiler finds the files of modules declared without a `path` attribute. If
+/// the file is not known, the path relative to the annotated item is used.
+pub(crate) fn full_module_path(module_path: &[String]) -> String {
+    let crate_name = env::var("CARGO_CRATE_NAME").unwrap_or_else(|_| "crate".to_string());
+    let file_module_path = Span::call_site()
+        .local_file()
+        .zip(env::var_os("CARGO_MANIFEST_DIR"))
+        .and_then(|(file, manifest_dir)| file.strip_prefix(manifest_dir).ok().map(file_module_path))
+        .unwrap_or_default();
+
+    std::iter::once(crate_name)
+        .chain(file_module_path)
+        .chain(module_path.iter().cloned())
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// Returns the module path of a file, given its path relative to the manifest directory.
+///
+/// Crate roots, such as `src/lib.rs`, `src/bin/tool.rs` or `tests/api.rs`, have an empty path. A
+/// file directly in the `tests`, `examples` or `benches` directory can also be a module of another
+/// crate root there, but it is assumed to be a crate root.
+fn file_module_path(file: &Path) -> Vec<String> {
+    let mut components: Vec<String> = file
+        .with_extension("")
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect();
+
+    // Module files are found relative to the directory of the crate root.
+    let root_is_src = components.first().map(String::as_str) == Some("src")
+        && components.get(1).map(String::as_str) != Some("bin");
+    let root_dir_len = match components.first().map(String::as_str) {
+        Some("src") if !root_is_src => 2,
+        Some("src") | Some("tests") | Some("examples") | Some("benches") => 1,
+        _ => 0,
+    };
+    components.drain(..root_dir_len.min(components.len()));
+
+    let is_crate_root = match components.as_slice() {
+        [file] if root_is_src => file == "lib" || file == "main",
+        [file] => file != "mod",
+        [_, file] if !root_is_src => file == "main",
+        _ => false,
+    };
+    if is_crate_root {
+        return Vec::new();
+    }
+
+    if components.last().map(String::as_str) == Some("mod") {
+        components.pop();
+    }
+
+    components
+}
+
+/// The number of call sites assuring the preconditions of the functions in one module.
+pub(crate) struct Coverage {
+    /// The path of the module.
+    scope: String,
+    /// The number of assuring call sites by function name and precondition.
+    counts: RefCell<BTreeMap<(String, String), usize>>,
+}
+
+impl Coverage {
+    /// Creates a new coverage record for the functions of the module at the given path.
+    pub(crate) fn new(
+        module_path: &[String],
+        known_functions: &HashMap<String, Vec<Precondition>>,
+    ) -> Coverage {
+        let scope = full_module_path(module_path);
+
+        let counts = known_functions
+            .iter()
+            .flat_map(|(function, preconditions)| {
+                preconditions
+                    .iter()
+                    .map(move |precondition| ((function.clone(), precondition.to_string()), 0))
+            })
+            .collect();
+
+        Coverage {
+            scope,
+            counts: RefCell::new(counts),
+        }
+    }
+
+    /// Records a call to the given function, which assures the given preconditions.
+    pub(crate) fn record(&self, function: &str, assured_preconditions: &[Precondition]) {
+        let mut counts = self.counts.borrow_mut();
+
+        for precondition in assured_preconditions {
+            if let Some(count) = counts.get_mut(&(function.to_string(), precondition.to_string())) {
+                *count += 1;
+            }
+        }
+    }
+
+    /// Merges the recorded counts into the report.
+    ///
+    /// Nothing is written if `OUT_DIR` is not set, which is the case for crates without a build
+    /// script.
+    pub(crate) fn write(&self) {
+        let out_dir = match env::var_os("OUT_DIR") {
+            Some(out_dir) => out_dir,
+            None => return,
+        };
+        let out_dir = Path::new(&out_dir);
+
+        if let Err(err) = with_lock(out_dir, LOCK_FILE_NAME, || {
+            let report_path = out_dir.join(REPORT_FILE_NAME);
+            let existing = match fs::read_to_string(&report_path) {
+                Ok(existing) => existing,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+                Err(err) => return Err(err),
+            };
+
+            fs::write(
+                &report_path,
+                merge_report(&existing, &self.scope, &self.counts.borrow()),
+            )
+        }) {
+            emit_warning!(
+                Span::call_site(),
+                "failed to write the coverage report to `{}`: {}",
+                out_dir.join(REPORT_FILE_NAME).display(),
+                err
+            );
+        }
+    }
+}
+
+/// Runs `f` while holding the lock with the given file name in the given directory.
+///
+/// Multiple crates and modules may be compiled at the same time, so the lock prevents lost
+/// updates of a report. The lock is a file that is created atomically, so only one writer can hold
+/// it. If the lock cannot be acquired, an error is returned instead of running `f`. A lock that is
+/// left over from an aborted compilation is never removed automatically, because that cannot be
+/// told apart from a slow writer without a race.
+pub(crate) fn with_lock(
+    dir: &Path,
+    lock_file_name: &str,
+    f: impl FnOnce() -> io::Result<()>,
+) -> io::Result<()> {
+    let lock_path = dir.join(lock_file_name);
+
+    let mut attempts = 0;
+    loop {
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&lock_path)
+        {
+            Ok(_) => break,
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+                attempts += 1;
+                if attempts == LOCK_ATTEMPTS {
+                    return Err(io::Error::new(
+                        io::ErrorKind::TimedOut,
+                        format!(
+                            "the lock `{}` is held by another writer, remove it if no other \
+                             compilation is running",
+                            lock_path.display()
+                        ),
+                    ));
+                }
+
+                thread::sleep(LOCK_RETRY_DELAY);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    let result = f();
+    let _ = fs::remove_file(&lock_path);
+
+    result
+}
+
+/// Replaces the lines of `scope` in the existing report with the given counts.
+///
+/// All lines of `scope` are replaced, so functions and preconditions that were removed from the
+/// module do not remain in the report.
+fn merge_report(existing: &str, scope: &str, counts: &BTreeMap<(String, String), usize>) -> String {
+    let mut lines: Vec<String> = existing
+        .lines()
+        .filter(|line| !line.is_empty() && line.split('\t').next() != Some(scope))
+        .map(|line| line.to_string())
+        .collect();
+
+    lines.extend(counts.iter().map(|((function, precondition), count)| {
+        format!("{}\t{}\t{}\t{}", scope, function, precondition, count)
+    }));
+
+    // The first three fields identify a line and a tab sorts before any character they contain, so
+    // sorting the whole lines sorts them by these fields.
+    lines.sort();
+
+    let mut report = lines.join("\n");
+    report.push('\n');
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(entries: &[(&str, &str, usize)]) -> BTreeMap<(String, String), usize> {
+        entries
+            .iter()
+            .map(|(function, precondition, count)| {
+                ((function.to_string(), precondition.to_string()), *count)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn merge_into_empty_report() {
+        let report = merge_report(
+            "",
+            "krate::a",
+            &counts(&[("foo", "x > 0", 2), ("bar", "\"is bar\"", 0)]),
+        );
+
+        assert_eq!(
+            report,
+            "krate::a\tbar\t\"is bar\"\t0\nkrate::a\tfoo\tx > 0\t2\n"
+        );
+    }
+
+    #[test]
+    fn merge_replaces_lines_of_the_same_scope() {
+        let existing = "krate::a\tfoo\tx > 0\t2\nkrate::b\tbaz\ty > 0\t1\n";
+        let report = merge_report(existing, "krate::a", &counts(&[("foo", "x > 0", 5)]));
+
+        assert_eq!(report, "krate::a\tfoo\tx > 0\t5\nkrate::b\tbaz\ty > 0\t1\n");
+    }
+
+    #[test]
+    fn merge_removes_other_functions_of_the_same_scope() {
+        let existing =
+            "krate::a\tbar\ty > 0\t1\nkrate::a\tfoo\tx > 0\t2\nkrate::a\tfoo\tx < 9\t1\n";
+        let report = merge_report(existing, "krate::a", &counts(&[("foo", "x > 0", 5)]));
+
+        assert_eq!(report, "krate::a\tfoo\tx > 0\t5\n");
+    }
+
+    #[test]
+    fn module_paths_of_files() {
+        let path = |file: &str| file_module_path(Path::new(file)).join("::");
+
+        assert_eq!(path("src/lib.rs"), "");
+        assert_eq!(path("src/main.rs"), "");
+        assert_eq!(path("src/parser.rs"), "parser");
+        assert_eq!(path("src/parser/mod.rs"), "parser");
+        assert_eq!(path("src/parser/inner.rs"), "parser::inner");
+        assert_eq!(path("src/bin/tool.rs"), "");
+        assert_eq!(path("src/bin/tool/main.rs"), "");
+        assert_eq!(path("tests/api.rs"), "");
+        assert_eq!(path("tests/common/mod.rs"), "common");
+    }
+
+    #[test]
+    fn locks_are_released() {
+        let dir = env::temp_dir().join(format!("pre-coverage-lock-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let lock_path = dir.join(LOCK_FILE_NAME);
+
+        let mut held = false;
+        with_lock(&dir, LOCK_FILE_NAME, || {
+            held = lock_path.exists();
+            Ok(())
+        })
+        .unwrap();
+
+        assert!(held);
+        assert!(!lock_path.exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
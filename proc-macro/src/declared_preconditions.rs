@@ -0,0 +1,184 @@
+//! Defines the `declared_preconditions` macro and the lists of preconditions it refers to.
+//!
+//! A macro cannot look at the declaration of a function it is given, so the `pre` attribute
+//! generates a hidden constant listing the preconditions next to every function with preconditions.
+//! The macro replaces the name of the function in the path with the name of that constant, so the
+//! compiler resolves it the same way as the function itself.
+//!
+//! # What the generated code looks like
+//!
+//! ```rust,ignore
+//! #[pre(x > 0)]
+//! #[pre("`x` is small")]
+//! fn foo(x: i32) -> i32 {
+//!     x * 2
+//! }
+//! ```
+//!
+//! additionally generates
+//!
+//! ```rust,ignore
+//! #[doc(hidden)]
+//! #[allow(non_upper_case_globals)]
+//! #[allow(dead_code)]
+//! const __pre_preconditions_of_foo: &[&str] = &["x > 0", "\"`x` is small\""];
+//! ```
+//!
+//! and `declared_preconditions!(path::to::foo)` expands to
+//! `path::to::__pre_preconditions_of_foo`.
+//!
+//! The preconditions are sorted in the same order as in the markers and written the same way as in
+//! the documentation of the markers, so the list doesn't depend on the order of the attributes.
+
+use proc_macro2::{Span, TokenStream};
+use proc_macro_error::abort;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+    parse::{Parse, ParseStream},
+    ExprPath, ItemFn, LitStr, PathArguments, Token,
+};
+
+use crate::{helpers::is_attr, precondition::Precondition};
+
+/// The prefix of the names of the generated constants.
+const LIST_PREFIX: &str = "__pre_preconditions_of_";
+
+/// Renders the constant listing the preconditions of the given function.
+///
+/// The `cfg` attributes of the function are copied, so that functions with the same name for
+/// different configurations don't get conflicting constants.
+pub(crate) fn render_precondition_list(
+    function: &ItemFn,
+    preconditions: &[Precondition],
+    span: Span,
+) -> TokenStream {
+    let mut preconditions = preconditions.to_vec();
+    preconditions.sort_unstable();
+
+    let vis = &function.vis;
+    let name = format_ident!("{}{}", LIST_PREFIX, function.sig.ident, span = span);
+    let cfg_attrs = function.attrs.iter().filter(|attr| is_attr("cfg", attr));
+    let preconditions = preconditions
+        .iter()
+        .map(|precondition| LitStr::new(&precondition.to_string(), span));
+
+    quote_spanned! { span=>
+        #(#cfg_attrs)*
+        #[doc(hidden)]
+        #[allow(non_upper_case_globals)]
+        #[allow(dead_code)]
+        #vis const #name: &[&str] = &[#(#preconditions),*];
+    }
+}
+
+/// The input to a `declared_preconditions` macro.
+pub(crate) struct DeclaredPreconditions {
+    /// The path to the function to list the preconditions of.
+    path: ExprPath,
+}
+
+impl Parse for DeclaredPreconditions {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+        }
+
+        if input.is_empty() {
+            Ok(DeclaredPreconditions { path })
+        } else {
+            Err(input.error("expected the path to a single function"))
+        }
+    }
+}
+
+impl DeclaredPreconditions {
+    /// Renders the path to the list of preconditions of the function.
+    pub(crate) fn render(self) -> TokenStream {
+        if cfg!(feature = "strip") {
+            abort!(
+                self.path,
+                "the preconditions of functions are not known with the `strip` feature enabled"
+            );
+        }
+
+        let mut path = self.path;
+        let last_segment = path
+            .path
+            .segments
+            .last_mut()
+            .expect("paths have at least one segment");
+
+        // The name keeps the span of the function name, so that an error about a missing
+        // constant points to the function.
+        last_segment.ident = format_ident!(
+            "{}{}",
+            LIST_PREFIX,
+            last_segment.ident,
+            span = last_segment.ident.span()
+        );
+        last_segment.arguments = PathArguments::None;
+
+        quote! { #path }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse2, parse_quote};
+
+    use super::*;
+
+    #[test]
+    fn path_refers_to_the_list_next_to_the_function() {
+        for (input, expected) in [
+            (quote! { foo }, quote! { __pre_preconditions_of_foo }),
+            (
+                quote! { crate::inner::foo::<u8>, },
+                quote! { crate::inner::__pre_preconditions_of_foo },
+            ),
+            (
+                quote! { Vec::<u8>::set_len },
+                quote! { Vec::<u8>::__pre_preconditions_of_set_len },
+            ),
+        ] {
+            let declared_preconditions: DeclaredPreconditions = parse2(input).expect("valid input");
+
+            assert_eq!(
+                declared_preconditions.render().to_string(),
+                expected.to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn list_is_sorted_like_the_markers() {
+        let function: ItemFn = parse_quote! {
+            #[cfg(unix)]
+            pub fn foo(x: i32) {}
+        };
+        let preconditions: [Precondition; 2] =
+            [parse_quote! { "`x` is small" }, parse_quote! { x > 0 }];
+        let list: syn::ItemConst = parse2(render_precondition_list(
+            &function,
+            &preconditions,
+            Span::call_site(),
+        ))
+        .expect("renders a constant");
+
+        assert_eq!(list.ident, "__pre_preconditions_of_foo");
+        assert!(is_attr("cfg", &list.attrs[0]));
+        assert_eq!(
+            quote! { #list }.to_string(),
+            quote! {
+                #[cfg(unix)]
+                #[doc(hidden)]
+                #[allow(non_upper_case_globals)]
+                #[allow(dead_code)]
+                pub const __pre_preconditions_of_foo: &[&str] = &["x > 0", "\"`x` is small\""];
+            }
+            .to_string()
+        );
+    }
+}
@@ -12,7 +12,7 @@ use syn::{
 use crate::{
     extern_crate::{ImplBlock, Module},
     helpers::HINT_REASON,
-    precondition::Precondition,
+    precondition::{path_to_string, Precondition, RELATION_OPERATORS},
 };
 
 /// Evaluates to the base URL of the documentation for the `pre` crate.
@@ -188,20 +188,7 @@ pub(crate) fn generate_docs(
         }
         doc!(docs);
 
-        for precondition in preconditions {
-            match precondition {
-                Precondition::ValidPtr {
-                    ident, read_write, ..
-                } => doc!(
-                    docs,
-                    "- the pointer `{}` must be valid for {}",
-                    ident.to_string(),
-                    read_write.doc_description()
-                ),
-                Precondition::Boolean(expr) => doc!(docs, "- `{}`", quote! { #expr }),
-                Precondition::Custom(text) => doc!(docs, "- {}", text.value()),
-            }
-        }
+        describe_preconditions(&mut docs, preconditions);
 
         doc!(docs);
         if plural {
@@ -254,7 +241,19 @@ pub(crate) fn generate_docs(
         } else {
             "/* parameters omitted */"
         };
-        doc!(docs, "{}{}({});", receiver, function.ident, parameters);
+        let awaited = if function.asyncness.is_some() {
+            ".await"
+        } else {
+            ""
+        };
+        doc!(
+            docs,
+            "{}{}({}){};",
+            receiver,
+            function.ident,
+            parameters,
+            awaited
+        );
 
         doc!(docs, "```");
     }
@@ -271,6 +270,204 @@ pub(crate) fn generate_docs(
     }
 }
 
+/// Generates documentation of the preconditions for a type alias of a function pointer.
+pub(crate) fn generate_fn_pointer_docs(preconditions: &[Precondition], span: Span) -> Attribute {
+    let mut docs = String::new();
+
+    doc!(docs, "# Functions of this type have preconditions");
+    doc!(docs);
+    if preconditions.len() == 1 {
+        doc!(docs, "The functions stored in values of this type have the following precondition generated by the [`pre` attribute]({}):", PRE_LINK);
+    } else {
+        doc!(docs, "The functions stored in values of this type have the following preconditions generated by [`pre` attributes]({}):", PRE_LINK);
+    }
+    doc!(docs);
+
+    describe_preconditions(&mut docs, preconditions);
+
+    doc!(docs);
+    doc!(docs, "Parameters without a name are referred to by their position, starting with `arg0` for the first parameter.");
+    doc!(docs, "The preconditions are not checked for calls through the function pointer, they only document the contract of the functions.");
+
+    let docs = LitStr::new(&docs, span);
+    Attribute {
+        pound_token: Pound { spans: [span] },
+        style: AttrStyle::Outer,
+        bracket_token: Bracket { span },
+        path: Ident::new("doc", span).into(),
+        tokens: quote_spanned! { span=>
+            = #docs
+        },
+    }
+}
+
+/// Writes a list item describing each of the preconditions to the documentation.
+fn describe_preconditions(docs: &mut String, preconditions: &[Precondition]) {
+    for precondition in preconditions {
+        match precondition {
+            Precondition::ValidPtr {
+                ident, read_write, ..
+            } => doc!(
+                docs,
+                "- the pointer `{}` must be valid for {}",
+                ident.to_string(),
+                read_write.doc_description()
+            ),
+            Precondition::Boolean(expr) => doc!(docs, "- `{}`", quote! { #expr }),
+            Precondition::Custom(text) => doc!(docs, "- {}", text.value()),
+            Precondition::See { path, .. } => doc!(
+                docs,
+                "- the requirements described in [`{}`]({}) must hold",
+                path.value(),
+                path.value()
+            ),
+            Precondition::InRange { ident, range, .. } => doc!(
+                docs,
+                "- `{}` must lie in the range `{}`",
+                ident.to_string(),
+                quote! { #range }
+            ),
+            Precondition::Kind {
+                path, arguments, ..
+            } => {
+                let path = path_to_string(path);
+                let arguments: Vec<_> = arguments.iter().map(|arg| format!("`{}`", arg)).collect();
+
+                if arguments.is_empty() {
+                    doc!(docs, "- the [`{}`]({}) precondition must hold", path, path)
+                } else {
+                    doc!(
+                        docs,
+                        "- the [`{}`]({}) precondition must hold for {}",
+                        path,
+                        path,
+                        arguments.join(", ")
+                    )
+                }
+            }
+            Precondition::Relation {
+                operator,
+                left,
+                right,
+                ..
+            } => {
+                let description = RELATION_OPERATORS
+                    .iter()
+                    .find(|(allowed, _)| *allowed == operator.value())
+                    .map(|(_, description)| *description)
+                    .expect("the operator is checked while parsing");
+
+                doc!(
+                    docs,
+                    "- `{}` must be {} `{}`",
+                    left.to_string(),
+                    description,
+                    right.to_string()
+                )
+            }
+            Precondition::Locked { ident, .. } => doc!(
+                docs,
+                "- the lock protecting `{}` must be held",
+                ident.to_string()
+            ),
+            Precondition::SameProvenance { first, second, .. } => doc!(
+                docs,
+                "- `{}` and `{}` must be derived from the same allocation",
+                first.to_string(),
+                second.to_string()
+            ),
+            Precondition::NonNull { ident, .. } => doc!(
+                docs,
+                "- the pointer `{}` must not be null",
+                ident.to_string()
+            ),
+            Precondition::AccessLen {
+                writable,
+                ident,
+                length,
+                ..
+            } => doc!(
+                docs,
+                "- the pointer `{}` must be valid for {} of `{}` elements",
+                ident.to_string(),
+                if *writable { "writes" } else { "reads" },
+                length.to_string()
+            ),
+            Precondition::NonZero { ident, .. } => {
+                doc!(docs, "- `{}` must not be zero", ident.to_string())
+            }
+            Precondition::Sorted {
+                ident, by: None, ..
+            } => doc!(docs, "- `{}` must be sorted", ident.to_string()),
+            Precondition::Sorted {
+                ident,
+                by: Some(by),
+                ..
+            } => doc!(
+                docs,
+                "- `{}` must be sorted by {}",
+                ident.to_string(),
+                by.value()
+            ),
+            Precondition::Aligned { ident, .. } => doc!(
+                docs,
+                "- the pointer `{}` must be properly aligned",
+                ident.to_string()
+            ),
+            Precondition::ProperAlign { ident, ty, .. } => doc!(
+                docs,
+                "- the pointer `{}` must be properly aligned for `{}`",
+                ident.to_string(),
+                quote! { #ty }
+            ),
+            Precondition::Initialized { ident, .. } => doc!(
+                docs,
+                "- the pointer `{}` must point to initialized memory",
+                ident.to_string()
+            ),
+            Precondition::Exclusive { ident, .. } => doc!(
+                docs,
+                "- the pointer `{}` must be the only way to access the memory behind it",
+                ident.to_string()
+            ),
+            Precondition::Utf8 { ident, .. } => doc!(
+                docs,
+                "- the bytes `{}` must be valid UTF-8",
+                ident.to_string()
+            ),
+            Precondition::OnThread { name, .. } => doc!(
+                docs,
+                "- the function must be called on the thread named `{}`",
+                name.value()
+            ),
+            Precondition::NonOverlapping {
+                first,
+                second,
+                count,
+                ..
+            } => doc!(
+                docs,
+                "- the regions of `{}` elements behind `{}` and `{}` must not overlap",
+                count.to_string(),
+                first.to_string(),
+                second.to_string()
+            ),
+            Precondition::Len {
+                minimum,
+                ident,
+                length,
+                ..
+            } => doc!(
+                docs,
+                "- `{}` must have a length of {} `{}`",
+                ident.to_string(),
+                if *minimum { "at least" } else { "exactly" },
+                length.to_string()
+            ),
+        }
+    }
+}
+
 /// Generates documentation of the preconditions for a `extern_crate` module.
 pub(crate) fn generate_module_docs(module: &Module, path: &Path) -> Attribute {
     let span = module.span();
@@ -340,12 +537,7 @@ pub(crate) fn generate_extern_crate_fn_docs(
     span: Span,
 ) -> Attribute {
     let mut docs = String::new();
-
-    let mut path_str = String::new();
-    for segment in path.segments.iter() {
-        doc_inline!(path_str, "{}::", segment.ident);
-    }
-    doc_inline!(path_str, "{}", function.ident);
+    let path_str = original_fn_path(path, function);
 
     if cfg!(nightly) {
         doc!(docs, "[`{}`]({}) with preconditions.", path_str, path_str);
@@ -388,3 +580,130 @@ pub(crate) fn generate_extern_crate_fn_docs(
         },
     }
 }
+
+/// Generates a summary of all preconditions of a function.
+///
+/// This is emitted as a diagnostic with the `verbose` feature, so that the author can confirm the
+/// full set of preconditions. They are listed in the order used for the markers.
+pub(crate) fn generate_precondition_summary(
+    function_name: &Ident,
+    preconditions: &[Precondition],
+) -> String {
+    let mut preconditions = preconditions.to_vec();
+    preconditions.sort_unstable();
+
+    let mut summary = String::new();
+
+    if preconditions.len() == 1 {
+        doc_inline!(summary, "`{}` has 1 precondition:", function_name);
+    } else {
+        doc_inline!(
+            summary,
+            "`{}` has {} preconditions:",
+            function_name,
+            preconditions.len()
+        );
+    }
+
+    for precondition in preconditions {
+        doc_inline!(summary, "\n- `{}`", precondition);
+    }
+
+    summary
+}
+
+/// Generates a `doc(alias)` attribute for a function in an `extern_crate` module.
+///
+/// The alias is the path of the original function, so that searching for it also finds the
+/// wrapper.
+pub(crate) fn generate_extern_crate_fn_alias(
+    path: &Path,
+    function: &Signature,
+    span: Span,
+) -> Attribute {
+    let alias = LitStr::new(&original_fn_path(path, function), span);
+
+    Attribute {
+        pound_token: Pound { spans: [span] },
+        style: AttrStyle::Outer,
+        bracket_token: Bracket { span },
+        path: Ident::new("doc", span).into(),
+        tokens: quote_spanned! { span=>
+            (alias = #alias)
+        },
+    }
+}
+
+/// Returns the path of the original function wrapped by a function in an `extern_crate` module.
+fn original_fn_path(path: &Path, function: &Signature) -> String {
+    let mut path_str = String::new();
+    for segment in path.segments.iter() {
+        doc_inline!(path_str, "{}::", segment.ident);
+    }
+    doc_inline!(path_str, "{}", function.ident);
+
+    path_str
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    #[test]
+    fn alias_is_the_original_path() {
+        let path: Path = parse_quote! { std::ptr };
+        let signature: Signature = parse_quote! { unsafe fn read<T>(src: *const T) -> T };
+
+        let alias = generate_extern_crate_fn_alias(&path, &signature, Span::call_site());
+
+        assert!(alias.path.is_ident("doc"));
+        assert_eq!(
+            alias.tokens.to_string(),
+            quote! { (alias = "std::ptr::read") }.to_string()
+        );
+    }
+
+    #[test]
+    fn see_renders_link() {
+        let signature: Signature = parse_quote! { fn foo() };
+        let preconditions: Vec<Precondition> = vec![parse_quote! { see = "crate::safety::rules" }];
+
+        let docs = generate_docs(&signature, &preconditions, None);
+
+        assert!(docs
+            .tokens
+            .to_string()
+            .contains("[`crate::safety::rules`](crate::safety::rules)"));
+    }
+
+    #[test]
+    fn summary_lists_all_preconditions() {
+        let function_name: Ident = parse_quote! { foo };
+        let preconditions: Vec<Precondition> = vec![
+            parse_quote! { "is called after `init`" },
+            parse_quote! { x > 0 },
+            parse_quote! { valid_ptr(p, r) },
+        ];
+
+        assert_eq!(
+            generate_precondition_summary(&function_name, &preconditions),
+            "`foo` has 3 preconditions:\n\
+             - `valid_ptr(p, r)`\n\
+             - `x > 0`\n\
+             - `\"is called after `init`\"`"
+        );
+    }
+
+    #[test]
+    fn summary_of_single_precondition() {
+        let function_name: Ident = parse_quote! { foo };
+        let preconditions: Vec<Precondition> = vec![parse_quote! { kind = in_range(x, 0..10) }];
+
+        assert_eq!(
+            generate_precondition_summary(&function_name, &preconditions),
+            "`foo` has 1 precondition:\n- `kind = in_range(x, 0 .. 10)`"
+        );
+    }
+}
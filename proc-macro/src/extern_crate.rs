@@ -38,6 +38,14 @@
 //!
 //!         #[doc = "..."]
 //!         #[pre(!ptr.is_null())]
+//!         #[inline(always)]
+//!         #[allow(non_snake_case, dead_code)]
+//!         pub(crate) const unsafe fn NonNull_new_unchecked<T>(ptr: *mut T) -> NonNull<T> {
+//!             <std::ptr::NonNull<T>>::new_unchecked(ptr)
+//!         }
+//!
+//!         #[doc = "..."]
+//!         #[pre(!ptr.is_null())]
 //!         #[pre(no_doc)]
 //!         #[pre(no_debug_assert)]
 //!         #[inline(always)]
@@ -54,7 +62,8 @@
 //! ```
 
 use proc_macro2::{Span, TokenStream};
-use quote::{quote, quote_spanned, TokenStreamExt};
+use proc_macro_error::emit_error;
+use quote::{format_ident, quote, quote_spanned, TokenStreamExt};
 use std::fmt;
 use syn::{
     braced,
@@ -62,24 +71,44 @@ use syn::{
     parse2,
     spanned::Spanned,
     token::Brace,
-    Attribute, FnArg, ForeignItemFn, Ident, ItemUse, Path, PathArguments, PathSegment, Token,
-    Visibility,
+    visit_mut::VisitMut,
+    Attribute, FnArg, ForeignItemFn, Ident, ItemUse, LitStr, Path, PathArguments, PathSegment,
+    Signature, Token, TypeImplTrait, Visibility,
 };
 
 use crate::{
-    documentation::{generate_extern_crate_fn_docs, generate_module_docs},
-    helpers::{is_attr, visit_matching_attrs_parsed, Parenthesized, CRATE_NAME},
+    documentation::{
+        generate_extern_crate_fn_alias, generate_extern_crate_fn_docs, generate_module_docs,
+    },
+    helpers::{crate_name_ident, is_attr, visit_matching_attrs_parsed, Parenthesized},
     pre_attr::PreAttr,
+    signatures::SignatureExport,
 };
 
 pub(crate) use impl_block::{impl_block_stub_name, ImplBlock};
 
+mod external;
 mod impl_block;
 
+/// The custom keywords used in the `extern_crate` attribute.
+mod custom_keywords {
+    use syn::custom_keyword;
+
+    custom_keyword!(check_signatures);
+    custom_keyword!(doc_alias);
+    custom_keyword!(from);
+}
+
 /// The parsed version of the `extern_crate` attribute content.
 pub(crate) struct Attr {
     /// The path of the crate/module to which function calls will be forwarded.
     path: Path,
+    /// A request to check the signatures of the functions against the original functions.
+    check_signatures: Option<custom_keywords::check_signatures>,
+    /// A request to add the paths of the original functions as `doc(alias)` to the functions.
+    doc_alias: Option<custom_keywords::doc_alias>,
+    /// The path of a file containing additional items of the module.
+    from: Option<LitStr>,
 }
 
 impl fmt::Display for Attr {
@@ -94,14 +123,52 @@ impl fmt::Display for Attr {
             write!(f, "{}", segment.ident)?;
         }
 
+        if self.check_signatures.is_some() {
+            write!(f, ", check_signatures")?;
+        }
+
+        if self.doc_alias.is_some() {
+            write!(f, ", doc_alias")?;
+        }
+
+        if let Some(from) = &self.from {
+            write!(f, ", from = {:?}", from.value())?;
+        }
+
         write!(f, ")]")
     }
 }
 
 impl Parse for Attr {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.call(Path::parse_mod_style)?;
+
+        let mut check_signatures = None;
+        let mut doc_alias = None;
+        let mut from = None;
+
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+
+            let lookahead = input.lookahead1();
+            if lookahead.peek(custom_keywords::check_signatures) && check_signatures.is_none() {
+                check_signatures = Some(input.parse()?);
+            } else if lookahead.peek(custom_keywords::doc_alias) && doc_alias.is_none() {
+                doc_alias = Some(input.parse()?);
+            } else if lookahead.peek(custom_keywords::from) && from.is_none() {
+                input.parse::<custom_keywords::from>()?;
+                input.parse::<Token![=]>()?;
+                from = Some(input.parse()?);
+            } else {
+                return Err(lookahead.error());
+            }
+        }
+
         Ok(Attr {
-            path: input.call(Path::parse_mod_style)?,
+            path,
+            check_signatures,
+            doc_alias,
+            from,
         })
     }
 }
@@ -152,7 +219,41 @@ impl Parse for Module {
 
         let content;
         let braces = braced!(content in input);
+        let ModuleContent {
+            impl_blocks,
+            imports,
+            functions,
+            modules,
+        } = content.parse()?;
 
+        Ok(Module {
+            attrs,
+            visibility,
+            mod_token,
+            ident,
+            braces,
+            impl_blocks,
+            imports,
+            functions,
+            modules,
+        })
+    }
+}
+
+/// The items inside of an `extern_crate` module.
+pub(crate) struct ModuleContent {
+    /// The impl blocks contained in the module.
+    impl_blocks: Vec<ImplBlock>,
+    /// The imports contained in the module.
+    imports: Vec<ItemUse>,
+    /// The functions contained in the module.
+    functions: Vec<ForeignItemFn>,
+    /// The submodules contained in the module.
+    modules: Vec<Module>,
+}
+
+impl Parse for ModuleContent {
+    fn parse(content: ParseStream) -> syn::Result<Self> {
         let mut impl_blocks = Vec::new();
         let mut imports = Vec::new();
         let mut functions = Vec::new();
@@ -164,7 +265,19 @@ impl Parse for Module {
             } else if <ItemUse as Parse>::parse(&content.fork()).is_ok() {
                 imports.push(content.parse()?);
             } else if <ForeignItemFn as Parse>::parse(&content.fork()).is_ok() {
-                functions.push(content.parse()?);
+                let function: ForeignItemFn = content.parse()?;
+
+                // The wrapper of a function forwards all of its parameters, which is not possible
+                // for a receiver outside of an `impl` block.
+                if let Some(receiver) = function.sig.receiver() {
+                    emit_error!(
+                        receiver,
+                        "`self` parameters are only allowed in `impl` blocks";
+                        help = "declare `{}` inside of an `impl` block of its type", function.sig.ident
+                    );
+                } else {
+                    functions.push(function);
+                }
             } else {
                 modules.push(content.parse().map_err(|err| {
                     syn::Error::new(
@@ -175,12 +288,7 @@ impl Parse for Module {
             }
         }
 
-        Ok(Module {
-            attrs,
-            visibility,
-            mod_token,
-            ident,
-            braces,
+        Ok(ModuleContent {
             impl_blocks,
             imports,
             functions,
@@ -191,14 +299,107 @@ impl Parse for Module {
 
 impl Module {
     /// Renders this `extern_crate` annotated module to its final result.
-    pub(crate) fn render(&self, attr: Attr) -> TokenStream {
+    pub(crate) fn render(mut self, attr: Attr) -> TokenStream {
         let mut tokens = TokenStream::new();
 
-        self.render_inner(attr.path, &mut tokens, None, &self.ident);
+        let external_file = attr.from.as_ref().and_then(|from| {
+            let (path, content) = external::load(from)?;
+            self.extend(content);
+
+            Some(path)
+        });
+
+        if cfg!(feature = "export-signatures") {
+            let mut export = SignatureExport::new(&self.ident);
+            self.export_signatures(attr.path.clone(), &mut Vec::new(), &mut export);
+            export.write();
+        }
+
+        self.render_inner(
+            attr.path,
+            &mut tokens,
+            None,
+            &self.ident,
+            attr.check_signatures.is_some(),
+            attr.doc_alias.is_some(),
+        );
+
+        if let Some(path) = external_file {
+            tokens.append_all(external::render_dependency(&path, self.ident.span()));
+        }
 
         tokens
     }
 
+    /// Adds the given items to this module.
+    ///
+    /// Submodules with the same name as an existing submodule are merged into it.
+    fn extend(&mut self, content: ModuleContent) {
+        self.impl_blocks.extend(content.impl_blocks);
+        self.imports.extend(content.imports);
+        self.functions.extend(content.functions);
+
+        for module in content.modules {
+            match self
+                .modules
+                .iter_mut()
+                .find(|existing| existing.ident == module.ident)
+            {
+                Some(existing) => existing.extend(module.into_content()),
+                None => self.modules.push(module),
+            }
+        }
+    }
+
+    /// Returns the items contained in this module.
+    fn into_content(self) -> ModuleContent {
+        ModuleContent {
+            impl_blocks: self.impl_blocks,
+            imports: self.imports,
+            functions: self.functions,
+            modules: self.modules,
+        }
+    }
+
+    /// Adds the signatures of the wrapper functions in this module and its submodules.
+    ///
+    /// `module_path` contains the names of the modules from the annotated module to this module,
+    /// which is empty for the annotated module itself.
+    fn export_signatures(
+        &self,
+        path: Path,
+        module_path: &mut Vec<String>,
+        export: &mut SignatureExport,
+    ) {
+        for function in &self.functions {
+            let mut original = path.clone();
+            original.segments.push(PathSegment {
+                ident: function.sig.ident.clone(),
+                arguments: PathArguments::None,
+            });
+
+            export.add(module_path, &original, &function.sig);
+        }
+
+        for impl_block in &self.impl_blocks {
+            for (original, signature) in impl_block.wrapper_signatures(&path) {
+                export.add(module_path, &original, &signature);
+            }
+        }
+
+        for module in &self.modules {
+            let mut path = path.clone();
+            path.segments.push(PathSegment {
+                ident: module.ident.clone(),
+                arguments: PathArguments::None,
+            });
+
+            module_path.push(module.ident.to_string());
+            module.export_signatures(path, module_path, export);
+            module_path.pop();
+        }
+    }
+
     /// A helper function to generate the final token stream.
     ///
     /// This allows passing the top level visibility and the updated path into recursive calls.
@@ -208,6 +409,8 @@ impl Module {
         tokens: &mut TokenStream,
         visibility: Option<&TokenStream>,
         top_level_module: &Ident,
+        check_signatures: bool,
+        doc_alias: bool,
     ) {
         if visibility.is_some() {
             // Update the path only in recursive calls.
@@ -234,7 +437,7 @@ impl Module {
             |_: Parenthesized<PreAttr>, _| render_docs = false,
         );
 
-        if render_docs {
+        if render_docs && !cfg!(feature = "strip") {
             let docs = generate_module_docs(self, &path);
             tokens.append_all(quote! { #docs });
         }
@@ -270,7 +473,7 @@ impl Module {
 
         let mut brace_content = TokenStream::new();
 
-        let crate_name = Ident::new(&CRATE_NAME, Span::call_site());
+        let crate_name = crate_name_ident(Span::call_site());
         brace_content.append_all(quote! {
             #[allow(unused_imports)]
             #[doc(no_inline)]
@@ -280,16 +483,31 @@ impl Module {
             use #crate_name::pre;
         });
 
-        for impl_block in &self.impl_blocks {
-            impl_block.render(&mut brace_content, &path, &visibility, top_level_module);
-        }
-
         for import in &self.imports {
             brace_content.append_all(quote! { #import });
         }
 
-        for function in &self.functions {
-            render_function(function, &mut brace_content, &path, &visibility);
+        // The functions of `impl` blocks have no original function with the same name, so their
+        // wrappers are needed even with the `strip` feature.
+        for impl_block in &self.impl_blocks {
+            impl_block.render(
+                &mut brace_content,
+                &path,
+                &visibility,
+                top_level_module,
+                doc_alias,
+            );
+        }
+
+        // With the `strip` feature, the original functions are used through the glob import.
+        if !cfg!(feature = "strip") {
+            for function in &self.functions {
+                render_function(function, &mut brace_content, &path, &visibility, doc_alias);
+
+                if check_signatures {
+                    render_signature_check(function, &mut brace_content, &path);
+                }
+            }
         }
 
         for module in &self.modules {
@@ -298,6 +516,8 @@ impl Module {
                 &mut brace_content,
                 Some(&visibility),
                 top_level_module,
+                check_signatures,
+                doc_alias,
             );
         }
 
@@ -337,10 +557,15 @@ fn render_function(
     tokens: &mut TokenStream,
     path: &Path,
     visibility: &TokenStream,
+    doc_alias: bool,
 ) {
     tokens.append_all(&function.attrs);
     let doc_header = generate_extern_crate_fn_docs(path, &function.sig, function.span());
     tokens.append_all(quote! { #doc_header });
+    if doc_alias {
+        let alias = generate_extern_crate_fn_alias(path, &function.sig, function.span());
+        tokens.append_all(quote! { #alias });
+    }
     tokens.append_all(quote_spanned! { function.span()=> #[inline(always)] });
     tokens.append_all(visibility.clone().into_iter().map(|mut token| {
         token.set_span(function.span());
@@ -371,5 +596,84 @@ fn render_function(
         }),
         quote_spanned! { function.span()=> , },
     );
-    tokens.append_all(quote_spanned! { function.span()=> { #path(#args_list) } });
+
+    // An `async` wrapper returns the output of the original function, so the future returned by
+    // it needs to be awaited.
+    if function.sig.asyncness.is_some() {
+        tokens.append_all(quote_spanned! { function.span()=> { #path(#args_list).await } });
+    } else {
+        tokens.append_all(quote_spanned! { function.span()=> { #path(#args_list) } });
+    }
+}
+
+/// Generates a check that the function signature matches the signature of the original function.
+///
+/// The function is coerced to a function pointer of the declared signature, so differences in the
+/// number or the types of the parameters are reported here instead of where the function is
+/// called. Generic functions are checked inside of a function with the same generic parameters,
+/// which lets the compiler infer the generic arguments of the original function.
+///
+/// This is only possible for non-async and non-variadic functions without `impl Trait` types,
+/// because only those can be coerced to a function pointer without further information.
+fn render_signature_check(function: &ForeignItemFn, tokens: &mut TokenStream, path: &Path) {
+    let signature = &function.sig;
+
+    if signature.asyncness.is_some()
+        || signature.variadic.is_some()
+        || contains_impl_trait(signature)
+    {
+        return;
+    }
+
+    let unsafety = &signature.unsafety;
+    let abi = &signature.abi;
+    let input_types = signature.inputs.iter().map(|arg| match arg {
+        FnArg::Receiver(_) => unreachable!("receiver is not valid in a function argument list"),
+        FnArg::Typed(pat) => &pat.ty,
+    });
+    let output = &signature.output;
+
+    let mut path = path.clone();
+    path.segments.push(PathSegment {
+        ident: signature.ident.clone(),
+        arguments: PathArguments::None,
+    });
+
+    // Point errors at the function instead of the attribute, where the path was specified.
+    for segment in path.segments.iter_mut() {
+        segment.ident.set_span(signature.ident.span());
+    }
+
+    if signature.generics.params.is_empty() {
+        tokens.append_all(quote_spanned! { signature.span()=>
+            const _: #unsafety #abi fn(#(#input_types),*) #output = #path;
+        });
+    } else {
+        let check_name = format_ident!("__check_signature_of_{}", signature.ident);
+        let (impl_generics, _, where_clause) = signature.generics.split_for_impl();
+
+        tokens.append_all(quote_spanned! { signature.span()=>
+            #[allow(dead_code)]
+            fn #check_name #impl_generics () #where_clause {
+                let _: #unsafety #abi fn(#(#input_types),*) #output = #path;
+            }
+        });
+    }
+}
+
+/// Checks whether an `impl Trait` type is used anywhere in the signature.
+fn contains_impl_trait(signature: &Signature) -> bool {
+    /// Searches for `impl Trait` types.
+    struct ImplTraitFinder(bool);
+
+    impl VisitMut for ImplTraitFinder {
+        fn visit_type_impl_trait_mut(&mut self, _: &mut TypeImplTrait) {
+            self.0 = true;
+        }
+    }
+
+    let mut finder = ImplTraitFinder(false);
+    finder.visit_signature_mut(&mut signature.clone());
+
+    finder.0
 }
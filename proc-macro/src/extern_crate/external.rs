@@ -0,0 +1,345 @@
+//! Loads additional items of an `extern_crate` module from an external file.
+//!
+//! The file is specified with `#[extern_crate(<path>, from = "<file>")]`, where the file is
+//! relative to the file containing the attribute. It contains the same items that can be written
+//! inside of the module, such as function signatures with `pre` attributes, `impl` blocks and
+//! submodules.
+
+use proc_macro2::{Group, Span, TokenStream, TokenTree};
+use proc_macro_error::emit_error;
+use quote::quote_spanned;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use syn::{parse2, LitStr};
+
+use super::ModuleContent;
+use crate::helpers::resolve_relative_path;
+
+/// Loads the items in the file specified by `from`.
+///
+/// This returns the resolved path of the file together with its items. An error is emitted, if the
+/// file cannot be read or parsed.
+pub(super) fn load(from: &LitStr) -> Option<(PathBuf, ModuleContent)> {
+    let name = from.value();
+    let path = resolve_relative_path(from);
+
+    let source = match fs::read_to_string(&path) {
+        Ok(source) => source,
+        Err(err) => {
+            emit_error!(from, "failed to read the signatures at `{}`: {}", name, err);
+
+            return None;
+        }
+    };
+
+    match parse_items(&source, from.span()) {
+        Ok(content) => Some((path, content)),
+        Err(err) => {
+            let location = match locate_error(&source, from.span()) {
+                Some((line, msg)) => format!("at line {}: {}", line, msg),
+                None => err,
+            };
+            emit_error!(
+                from,
+                "failed to parse the signatures in `{}` {}", name, location;
+                note = "the file must only contain items that are allowed inside of an `extern_crate` module"
+            );
+
+            None
+        }
+    }
+}
+
+/// Renders an item that makes the compiler recompile the module when the file changes.
+pub(super) fn render_dependency(path: &Path, span: Span) -> TokenStream {
+    let path = LitStr::new(&path.to_string_lossy(), span);
+
+    quote_spanned! { span=>
+        const _: &[u8] = ::core::include_bytes!(#path);
+    }
+}
+
+/// Parses the items in `source`, giving all tokens the given span.
+fn parse_items(source: &str, span: Span) -> Result<ModuleContent, String> {
+    let tokens: TokenStream = source
+        .parse()
+        .map_err(|_| "because it contains invalid tokens".to_string())?;
+
+    parse2(respan(tokens, span)).map_err(|err| format!("because of an error: {}", err))
+}
+
+/// Sets the span of all tokens in the stream.
+///
+/// Tokens parsed from a string don't have a useful location, so they are shown at the attribute
+/// instead.
+fn respan(tokens: TokenStream, span: Span) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|mut token| {
+            if let TokenTree::Group(group) = &token {
+                let mut new_group = Group::new(group.delimiter(), respan(group.stream(), span));
+                new_group.set_span(span);
+                token = TokenTree::Group(new_group);
+            } else {
+                token.set_span(span);
+            }
+
+            token
+        })
+        .collect()
+}
+
+/// Finds the line and the message of the first error in `source`.
+///
+/// The tokens don't know their location in the file, so the items are parsed one after another to
+/// find the first item that fails to parse.
+fn locate_error(source: &str, span: Span) -> Option<(usize, String)> {
+    let items = match split_items(source) {
+        Ok(items) => items,
+        Err(line) => return Some((line, "unbalanced delimiter".to_string())),
+    };
+
+    items.into_iter().find_map(|(line, item)| {
+        let tokens: TokenStream = match item.parse() {
+            Ok(tokens) => tokens,
+            Err(_) => return Some((line, "invalid tokens".to_string())),
+        };
+
+        parse2::<ModuleContent>(respan(tokens, span))
+            .err()
+            .map(|err| (line, err.to_string()))
+    })
+}
+
+/// Splits the source into its top-level items, together with the line each item starts on.
+///
+/// Only enough of the syntax is understood to find the end of each item: delimiters, comments and
+/// string and character literals. If a delimiter is unbalanced, its line is returned as an error.
+fn split_items(source: &str) -> Result<Vec<(usize, &str)>, usize> {
+    let bytes = source.as_bytes();
+    let mut items = Vec::new();
+    let mut open_delimiters = Vec::new();
+    let mut item_start = None;
+    let mut line = 1;
+    let mut i = 0;
+
+    // Advances `i` to `end`, counting the lines in between.
+    let skip_to = |i: &mut usize, line: &mut usize, end: usize| {
+        *line += bytes[*i..end].iter().filter(|&&byte| byte == b'\n').count();
+        *i = end;
+    };
+
+    while i < bytes.len() {
+        let rest = &bytes[i..];
+
+        if bytes[i].is_ascii_whitespace() {
+            let end = i + 1;
+            skip_to(&mut i, &mut line, end);
+            continue;
+        } else if rest.starts_with(b"//") {
+            let end = rest
+                .iter()
+                .position(|&byte| byte == b'\n')
+                .map_or(bytes.len(), |offset| i + offset);
+            skip_to(&mut i, &mut line, end);
+            continue;
+        } else if rest.starts_with(b"/*") {
+            let end = block_comment_end(rest).map_or(bytes.len(), |offset| i + offset);
+            skip_to(&mut i, &mut line, end);
+            continue;
+        }
+
+        if item_start.is_none() {
+            item_start = Some((i, line));
+        }
+
+        let mut end_of_item = false;
+        match bytes[i] {
+            b'"' => {
+                let end = string_end(rest).map_or(bytes.len(), |offset| i + offset);
+                skip_to(&mut i, &mut line, end);
+                continue;
+            }
+            b'r' if is_token_start(bytes, i) && raw_string_end(rest).is_some() => {
+                let end = i + raw_string_end(rest).expect("checked above");
+                skip_to(&mut i, &mut line, end);
+                continue;
+            }
+            b'\'' => {
+                if let Some(offset) = char_end(&source[i..]) {
+                    i += offset;
+                    continue;
+                }
+            }
+            b'(' | b'[' | b'{' => open_delimiters.push(line),
+            b')' | b']' | b'}' => {
+                if open_delimiters.pop().is_none() {
+                    return Err(line);
+                }
+
+                end_of_item = bytes[i] == b'}' && open_delimiters.is_empty();
+            }
+            b';' => end_of_item = open_delimiters.is_empty(),
+            _ => (),
+        }
+        i += 1;
+
+        if end_of_item {
+            if let Some((start, start_line)) = item_start.take() {
+                items.push((start_line, &source[start..i]));
+            }
+        }
+    }
+
+    if let Some(&line) = open_delimiters.last() {
+        return Err(line);
+    }
+
+    if let Some((start, start_line)) = item_start {
+        items.push((start_line, &source[start..]));
+    }
+
+    Ok(items)
+}
+
+/// Checks that the byte at `i` does not continue an identifier.
+fn is_token_start(bytes: &[u8], i: usize) -> bool {
+    i == 0 || !(bytes[i - 1].is_ascii_alphanumeric() || bytes[i - 1] == b'_')
+}
+
+/// Returns the offset after the block comment at the start of `rest`.
+fn block_comment_end(rest: &[u8]) -> Option<usize> {
+    let mut depth = 0;
+    let mut i = 0;
+
+    while i < rest.len() {
+        if rest[i..].starts_with(b"/*") {
+            depth += 1;
+            i += 2;
+        } else if rest[i..].starts_with(b"*/") {
+            depth -= 1;
+            i += 2;
+
+            if depth == 0 {
+                return Some(i);
+            }
+        } else {
+            i += 1;
+        }
+    }
+
+    None
+}
+
+/// Returns the offset after the string literal at the start of `rest`.
+fn string_end(rest: &[u8]) -> Option<usize> {
+    let mut i = 1;
+
+    while i < rest.len() {
+        match rest[i] {
+            b'\\' => i += 2,
+            b'"' => return Some(i + 1),
+            _ => i += 1,
+        }
+    }
+
+    None
+}
+
+/// Returns the offset after the raw string literal at the start of `rest`, if there is one.
+fn raw_string_end(rest: &[u8]) -> Option<usize> {
+    let hashes = rest[1..].iter().take_while(|&&byte| byte == b'#').count();
+    if rest.get(1 + hashes) != Some(&b'"') {
+        return None;
+    }
+
+    let content_start = 2 + hashes;
+    let mut terminator = vec![b'"'];
+    terminator.resize(1 + hashes, b'#');
+
+    rest[content_start..]
+        .windows(terminator.len())
+        .position(|window| window == &terminator[..])
+        .map(|offset| content_start + offset + terminator.len())
+}
+
+/// Returns the offset after the character literal at the start of `rest`.
+///
+/// If it is a lifetime instead, `None` is returned.
+fn char_end(rest: &str) -> Option<usize> {
+    let mut chars = rest[1..].char_indices();
+
+    match chars.next() {
+        Some((_, '\\')) => rest[2..].find('\'').map(|offset| offset + 3),
+        Some((_, c)) => match chars.next() {
+            Some((offset, '\'')) if offset == c.len_utf8() => Some(offset + 2),
+            _ => None,
+        },
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn items_are_split_at_their_ends() {
+        let source = r##"
+            // A comment with a `;` and a `}`.
+            #[pre(valid_ptr(src, r))]
+            unsafe fn read<T>(src: *const T) -> T;
+
+            /* A block /* nested */ comment; */
+            mod inner {
+                #[pre("a string with a `;`, a `}` and a \" quote")]
+                unsafe fn foo<'a>(x: &'a [u8; 4]) -> char;
+            }
+
+            #[pre(r#"a raw string with a `;`"#)]
+            unsafe fn bar(c: char) -> bool;
+        "##;
+
+        let items = split_items(source).expect("the delimiters are balanced");
+        let lines: Vec<_> = items.iter().map(|(line, _)| *line).collect();
+
+        assert_eq!(lines, [3, 7, 12]);
+        assert!(items[0].1.ends_with("-> T;"));
+        assert!(items[1].1.starts_with("mod inner"));
+        assert!(items[2].1.ends_with("-> bool;"));
+    }
+
+    #[test]
+    fn trailing_incomplete_item_is_kept() {
+        let items = split_items("fn foo();\n\nfn bar()").expect("the delimiters are balanced");
+
+        assert_eq!(items, [(1, "fn foo();"), (3, "fn bar()")]);
+    }
+
+    #[test]
+    fn unbalanced_delimiters_are_reported() {
+        assert_eq!(split_items("fn foo();\nmod a {\n").err(), Some(2));
+        assert_eq!(split_items("fn foo();\n}\n").err(), Some(2));
+    }
+
+    #[test]
+    fn non_ascii_text_is_skipped() {
+        let source = "// Prüfung: ü\n/* ä */ fn föö(x: &'ä u8) -> char;\nfn bar();";
+
+        let items = split_items(source).expect("the delimiters are balanced");
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].0, 2);
+        assert_eq!(items[1], (3, "fn bar();"));
+    }
+
+    #[test]
+    fn char_literals_and_lifetimes() {
+        assert_eq!(char_end("'a'"), Some(3));
+        assert_eq!(char_end("'\\n'"), Some(4));
+        assert_eq!(char_end("'ä'"), Some(4));
+        assert_eq!(char_end("'a str"), None);
+    }
+}
@@ -1,20 +1,24 @@
 //! Handles impl blocks in `extern_crate` modules.
 
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::{Group, Span, TokenStream, TokenTree};
 use proc_macro_error::emit_error;
-use quote::{quote, quote_spanned, TokenStreamExt};
+use quote::{quote, quote_spanned, ToTokens, TokenStreamExt};
 use syn::{
     braced,
     parse::{Parse, ParseStream},
-    parse2,
+    parse2, parse_quote,
     spanned::Spanned,
     token::Brace,
-    ForeignItemFn, Generics, Ident, Path, PathArguments, PathSegment, Token, Type,
+    FnArg, ForeignItemFn, GenericParam, Generics, Ident, Pat, PatType, Path, PathArguments,
+    PathSegment, Signature, Token, Type,
 };
 
 use crate::{
-    documentation::{generate_docs, ImplBlockContext},
-    helpers::{is_attr, Parenthesized},
+    documentation::{
+        generate_docs, generate_extern_crate_fn_alias, generate_extern_crate_fn_docs,
+        ImplBlockContext,
+    },
+    helpers::{gate_attribute, is_attr, Parenthesized},
     pre_attr::PreAttr,
 };
 
@@ -155,12 +159,16 @@ impl ImplBlock {
     }
 
     /// Generates the code for an impl block inside a `extern_crate` module.
+    ///
+    /// With the `strip` feature, only the wrappers are generated, because `forward(impl ...)` has
+    /// no effect then.
     pub(crate) fn render(
         &self,
         tokens: &mut TokenStream,
         path: &Path,
         visibility: &TokenStream,
         top_level_module: &Ident,
+        doc_alias: bool,
     ) {
         let ty = if let Some(ty) = self.ty() {
             ty
@@ -169,8 +177,14 @@ impl ImplBlock {
         };
 
         for function in &self.items {
+            self.render_wrapper(function, ty, tokens, path, visibility, doc_alias);
+
+            if cfg!(feature = "strip") {
+                continue;
+            }
+
             let docs = {
-                let mut render_docs = true;
+                let mut render_docs = cfg!(feature = "doc");
                 let mut preconditions = Vec::new();
 
                 for attr in &function.attrs {
@@ -190,7 +204,7 @@ impl ImplBlock {
                 }
 
                 if render_docs {
-                    Some(generate_docs(
+                    Some(gate_attribute(generate_docs(
                         &function.sig,
                         &preconditions,
                         Some(ImplBlockContext {
@@ -198,7 +212,7 @@ impl ImplBlock {
                             path,
                             top_level_module,
                         }),
-                    ))
+                    )))
                 } else {
                     None
                 }
@@ -223,6 +237,222 @@ impl ImplBlock {
             });
         }
     }
+
+    /// Generates a wrapper function for the given function of the impl block.
+    ///
+    /// The wrapper is a free function, which calls `<path::Type>::function` with its arguments.
+    /// A receiver is passed explicitly as the first parameter, which is named `this`, so that it
+    /// can be referred to in the preconditions.
+    fn render_wrapper(
+        &self,
+        function: &ForeignItemFn,
+        ty: &PathSegment,
+        tokens: &mut TokenStream,
+        path: &Path,
+        visibility: &TokenStream,
+        doc_alias: bool,
+    ) {
+        let span = function.span();
+        let signature = match self.wrapper_signature(ty, &function.sig) {
+            Some(signature) => signature,
+            None => return,
+        };
+
+        let mut original_path = path.clone();
+        original_path.segments.push(PathSegment {
+            ident: ty.ident.clone(),
+            arguments: PathArguments::None,
+        });
+        let doc_header = generate_extern_crate_fn_docs(&original_path, &function.sig, span);
+        tokens.append_all(quote! { #doc_header });
+        if doc_alias {
+            let alias = generate_extern_crate_fn_alias(&original_path, &function.sig, span);
+            tokens.append_all(quote! { #alias });
+        }
+
+        let self_ty = self.self_ty.to_token_stream();
+        for attr in &function.attrs {
+            let mut attr = attr.clone();
+            if is_attr("pre", &attr) {
+                attr.tokens = replace_self(attr.tokens, &self_ty, true);
+            }
+
+            tokens.append_all(quote! { #attr });
+        }
+
+        let mut args_list = TokenStream::new();
+        args_list.append_separated(
+            signature.inputs.iter().map(|arg| match arg {
+                FnArg::Receiver(_) => unreachable!("the receiver is replaced in the wrapper"),
+                FnArg::Typed(pat) => &pat.pat,
+            }),
+            quote_spanned! { span=> , },
+        );
+        let name = &function.sig.ident;
+        let call = quote_spanned! { span=> <#path::#self_ty>::#name(#args_list) };
+        let body = if signature.asyncness.is_some() {
+            quote_spanned! { span=> { #call.await } }
+        } else {
+            quote_spanned! { span=> { #call } }
+        };
+
+        // Unused wrappers are not reported, because the stub of the function is reported already.
+        tokens.append_all(quote_spanned! { span=>
+            #[inline(always)]
+            #[allow(non_snake_case, dead_code)]
+            #visibility #signature #body
+        });
+    }
+
+    /// Returns the path of the original function and the signature of the wrapper for every
+    /// function in the impl block.
+    ///
+    /// Nothing is returned for an unsupported type, which is reported when the block is rendered.
+    pub(crate) fn wrapper_signatures(&self, path: &Path) -> Vec<(Path, Signature)> {
+        let ty = match &*self.self_ty {
+            Type::Path(ty) if ty.qself.is_none() && ty.path.segments.len() == 1 => {
+                &ty.path.segments[0]
+            }
+            _ => return Vec::new(),
+        };
+
+        self.items
+            .iter()
+            .filter_map(|function| {
+                let mut original = path.clone();
+                original.segments.push(PathSegment {
+                    ident: ty.ident.clone(),
+                    arguments: PathArguments::None,
+                });
+                original.segments.push(PathSegment {
+                    ident: function.sig.ident.clone(),
+                    arguments: PathArguments::None,
+                });
+
+                Some((original, self.wrapper_signature(ty, &function.sig)?))
+            })
+            .collect()
+    }
+
+    /// Returns the signature of the wrapper for the function with the given signature.
+    ///
+    /// The generic parameters of the impl block are added to those of the function and every
+    /// `Self` is replaced with the type of the impl block.
+    fn wrapper_signature(&self, ty: &PathSegment, signature: &Signature) -> Option<Signature> {
+        let self_ty = &self.self_ty;
+        let mut signature = signature.clone();
+        signature.ident = impl_block_wrapper_name(ty, &signature.ident, signature.ident.span());
+
+        for input in signature.inputs.iter_mut() {
+            match input {
+                FnArg::Receiver(receiver) => {
+                    let this = Ident::new(RECEIVER_NAME, receiver.self_token.span);
+                    let mutability = &receiver.mutability;
+
+                    *input = match &receiver.reference {
+                        Some((and, lifetime)) => parse_quote! {
+                            #this: #and #lifetime #mutability #self_ty
+                        },
+                        None => parse_quote! { #mutability #this: #self_ty },
+                    };
+                }
+                FnArg::Typed(PatType { pat, .. }) => {
+                    if let Pat::Ident(pat) = &mut **pat {
+                        if pat.ident == "self" {
+                            pat.ident = Ident::new(RECEIVER_NAME, pat.ident.span());
+                        }
+                    }
+                }
+            }
+        }
+
+        // Lifetimes must be declared before the other generic parameters.
+        let (lifetimes, others): (Vec<_>, Vec<_>) = self
+            .generics
+            .params
+            .iter()
+            .chain(signature.generics.params.iter())
+            .cloned()
+            .partition(|param| matches!(param, GenericParam::Lifetime(_)));
+        let predicates = self
+            .generics
+            .where_clause
+            .iter()
+            .chain(signature.generics.where_clause.iter())
+            .flat_map(|where_clause| where_clause.predicates.iter().cloned());
+        let predicates: Vec<_> = predicates.collect();
+        signature.generics = if lifetimes.is_empty() && others.is_empty() {
+            Generics::default()
+        } else {
+            parse_quote! { <#(#lifetimes,)* #(#others),*> }
+        };
+        if !predicates.is_empty() {
+            signature.generics.where_clause = Some(parse_quote! { where #(#predicates),* });
+        }
+
+        let tokens = replace_self(
+            signature.to_token_stream(),
+            &self_ty.to_token_stream(),
+            false,
+        );
+        match parse2(tokens) {
+            Ok(signature) => Some(signature),
+            Err(err) => {
+                emit_error!(err);
+
+                None
+            }
+        }
+    }
+}
+
+/// The name of the parameter that the receiver is passed as to a wrapper.
+const RECEIVER_NAME: &str = "this";
+
+/// Replaces every `Self` with `self_ty`.
+///
+/// If `replace_receiver` is set, every `self` is replaced with the name of the explicit receiver
+/// as well.
+fn replace_self(tokens: TokenStream, self_ty: &TokenStream, replace_receiver: bool) -> TokenStream {
+    let mut replaced = TokenStream::new();
+    let mut tokens = tokens.into_iter().peekable();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Group(group) => {
+                let stream = replace_self(group.stream(), self_ty, replace_receiver);
+                let mut new_group = Group::new(group.delimiter(), stream);
+                new_group.set_span(group.span());
+
+                replaced.append(new_group);
+            }
+            TokenTree::Ident(ident) if ident == "Self" => {
+                // `Self::Item` must become `<Type>::Item`, which is not necessary for `Self` alone.
+                let is_path = matches!(tokens.peek(), Some(TokenTree::Punct(punct)) if punct.as_char() == ':');
+
+                if is_path {
+                    replaced.append_all(quote_spanned! { ident.span()=> <#self_ty> });
+                } else {
+                    replaced.append_all(self_ty.clone());
+                }
+            }
+            TokenTree::Ident(ident) if replace_receiver && ident == "self" => {
+                replaced.append(Ident::new(RECEIVER_NAME, ident.span()));
+            }
+            token => replaced.append(token),
+        }
+    }
+
+    replaced
+}
+
+/// Generates the name of the wrapper for a function in an impl block.
+pub(crate) fn impl_block_wrapper_name(
+    ty: &PathSegment,
+    fn_name: &impl std::fmt::Display,
+    span: Span,
+) -> Ident {
+    Ident::new(&format!("{}_{}", ty.ident, fn_name), span)
 }
 
 /// Generates a name to use for an impl block stub function.
@@ -240,3 +470,23 @@ pub(crate) fn impl_block_stub_name(
     // context.
     Ident::new(&format!("{}__impl__{}__", ty.ident, fn_name), span)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn self_is_replaced_with_the_type() {
+        let self_ty = quote! { Vec<T> };
+        let tokens = quote! { fn foo(self, x: Self::Item) -> Option<Self> };
+
+        assert_eq!(
+            replace_self(tokens.clone(), &self_ty, false).to_string(),
+            quote! { fn foo(self, x: <Vec<T> >::Item) -> Option<Vec<T> > }.to_string()
+        );
+        assert_eq!(
+            replace_self(tokens, &self_ty, true).to_string(),
+            quote! { fn foo(this, x: <Vec<T> >::Item) -> Option<Vec<T> > }.to_string()
+        );
+    }
+}
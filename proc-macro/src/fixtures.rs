@@ -0,0 +1,190 @@
+//! Generates compile-fail fixtures calling functions without assuring their preconditions.
+//!
+//! This is used when the `compile-fail-fixtures` feature is enabled. For every public function
+//! with preconditions in a module annotated with a `pre` attribute, a fixture is written that
+//! calls the function without any `assure` attributes. The fixture is meant to be run as a
+//! compile-fail test with [`trybuild`](https://docs.rs/trybuild), which proves that the
+//! preconditions of the function are actually enforced at its call sites.
+//!
+//! The fixtures are written to the directory in the `PRE_FIXTURES_DIR` environment variable,
+//! relative to the manifest of the crate being compiled. If it is not set, they are written to
+//! `$OUT_DIR/pre-fixtures`. The variable is not tracked by the compiler, so a changed directory
+//! only receives the fixtures of crates that are compiled again.
+//!
+//! # What the generated fixtures look like
+//!
+//! For a function `divide` in a module `math` of the crate `my_crate`, the file
+//! `math__divide.rs` is written:
+//!
+//! ```rust,ignore
+//! // This fixture was generated by `pre` for `my_crate::math::divide`.
+//! // It calls the function without assuring its preconditions, so it must fail to compile:
+//! // - divisor != 0
+//!
+//! #[allow(unreachable_code)]
+//! fn main() {
+//!     my_crate::math::divide(::core::todo!(), ::core::todo!());
+//! }
+//! ```
+//!
+//! The arguments are `todo!()`, so that the types of the parameters don't need to be known. Like
+//! the paths in the coverage report, the path of the function consists of the crate name followed
+//! by the modules from the annotated item to the function.
+
+use proc_macro2::Span;
+use proc_macro_error::emit_error;
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+use syn::{FnArg, Signature, Visibility};
+
+use crate::{precondition::Precondition, registry::has_known_address};
+
+/// The environment variable containing the directory that the fixtures are written to.
+const FIXTURES_DIR_ENV_VAR: &str = "PRE_FIXTURES_DIR";
+
+/// The name of the directory in `OUT_DIR` that the fixtures are written to by default.
+const DEFAULT_FIXTURES_DIR_NAME: &str = "pre-fixtures";
+
+/// Writes the fixture for the function with the given signature.
+///
+/// `module_path` contains the names of the modules from the annotated item to the function.
+/// Nothing is written for functions that cannot be called from another crate without generic
+/// arguments or a receiver, or if the calls are not checked at all.
+pub(crate) fn write_fixture(
+    module_path: &[String],
+    visibility: &Visibility,
+    signature: &Signature,
+    preconditions: &[Precondition],
+) {
+    // Calls without any `assure` attributes are not checked with the `assoc-const` feature.
+    if cfg!(feature = "assoc-const")
+        || preconditions.is_empty()
+        || !matches!(visibility, Visibility::Public(_))
+        || !has_known_address(signature, false)
+    {
+        return;
+    }
+
+    let dir = match fixtures_dir() {
+        Some(dir) => dir,
+        None => return,
+    };
+    let crate_name = env::var("CARGO_CRATE_NAME").unwrap_or_else(|_| "crate".to_string());
+    let function_path = std::iter::once(crate_name)
+        .chain(module_path.iter().cloned())
+        .chain(std::iter::once(signature.ident.to_string()))
+        .collect::<Vec<_>>()
+        .join("::");
+    let file_name = module_path
+        .iter()
+        .cloned()
+        .chain(std::iter::once(signature.ident.to_string()))
+        .collect::<Vec<_>>()
+        .join("__");
+    let path = dir.join(format!("{}.rs", file_name));
+
+    let fixture = render_fixture(&function_path, signature, preconditions);
+    if let Err(err) = write_if_changed(&dir, &path, &fixture) {
+        emit_error!(
+            Span::call_site(),
+            "failed to write the compile-fail fixture to `{}`: {}",
+            path.display(),
+            err
+        );
+    }
+}
+
+/// Returns the directory that the fixtures are written to, if it is known.
+fn fixtures_dir() -> Option<PathBuf> {
+    match env::var_os(FIXTURES_DIR_ENV_VAR) {
+        Some(dir) => {
+            let manifest_dir = env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from);
+
+            Some(manifest_dir.unwrap_or_default().join(dir))
+        }
+        None => env::var_os("OUT_DIR")
+            .map(|out_dir| Path::new(&out_dir).join(DEFAULT_FIXTURES_DIR_NAME)),
+    }
+}
+
+/// Writes the fixture, unless the file already has the same content.
+///
+/// This keeps the modification time of unchanged fixtures, so that they are not compiled again.
+fn write_if_changed(dir: &Path, path: &Path, fixture: &str) -> io::Result<()> {
+    match fs::read_to_string(path) {
+        Ok(existing) if existing == fixture => return Ok(()),
+        Ok(_) => (),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => (),
+        Err(err) => return Err(err),
+    }
+
+    fs::create_dir_all(dir)?;
+    fs::write(path, fixture)
+}
+
+/// Renders the source of the fixture calling the function at `function_path`.
+fn render_fixture(
+    function_path: &str,
+    signature: &Signature,
+    preconditions: &[Precondition],
+) -> String {
+    let mut fixture = format!(
+        "// This fixture was generated by `pre` for `{}`.\n\
+         // It calls the function without assuring its preconditions, so it must fail to compile:\n",
+        function_path
+    );
+    for precondition in preconditions {
+        fixture.push_str(&format!("// - {}\n", precondition));
+    }
+
+    let args = signature
+        .inputs
+        .iter()
+        .filter(|input| matches!(input, FnArg::Typed(_)))
+        .map(|_| "::core::todo!()")
+        .collect::<Vec<_>>()
+        .join(", ");
+    let call = format!("{}({})", function_path, args);
+    let statement = if signature.unsafety.is_some() {
+        format!("unsafe {{ {} }};", call)
+    } else {
+        format!("{};", call)
+    };
+
+    fixture.push_str(&format!(
+        "\n#[allow(unreachable_code)]\nfn main() {{\n    {}\n}}\n",
+        statement
+    ));
+
+    fixture
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn fixture_calls_the_function_without_assuring() {
+        let signature: Signature = parse_quote! { unsafe fn read(ptr: *const u8, len: usize) };
+        let preconditions = [
+            parse_quote! { valid_ptr(ptr, r) },
+            parse_quote! { "`len` is small" },
+        ];
+
+        assert_eq!(
+            render_fixture("my_crate::io::read", &signature, &preconditions),
+            "// This fixture was generated by `pre` for `my_crate::io::read`.\n\
+             // It calls the function without assuring its preconditions, so it must fail to compile:\n\
+             // - valid_ptr(ptr, r)\n\
+             // - \"`len` is small\"\n\
+             \n\
+             #[allow(unreachable_code)]\n\
+             fn main() {\n    \
+             unsafe { my_crate::io::read(::core::todo!(), ::core::todo!()) };\n\
+             }\n"
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! Renders helpers that call functions with preconditions without assuring them.
+//!
+//! This is used when the `fuzzing` feature is enabled. Fuzz harnesses call functions with
+//! arbitrary inputs, so they cannot state a reason for why the preconditions hold. For every
+//! function with preconditions, a helper is generated next to it, which takes the same arguments
+//! and calls the function with all of its preconditions assured.
+//!
+//! The helper is only present when compiling with `--cfg fuzzing`, which is set by `cargo fuzz`.
+//! This prevents it from being used in regular builds, because it bypasses the contract checking.
+//!
+//! # What the generated code looks like
+//!
+//! ```rust,ignore
+//! #[pre(x > 0)]
+//! fn foo(x: i32) -> i32 {
+//!     x * 2
+//! }
+//! ```
+//!
+//! additionally generates
+//!
+//! ```rust,ignore
+//! #[cfg(fuzzing)]
+//! #[doc(hidden)]
+//! #[allow(dead_code)]
+//! fn fuzz_foo(__pre_arg_0: i32) -> i32 {
+//!     // The call is rendered as if all preconditions of `foo` were assured.
+//!     foo(__pre_arg_0)
+//! }
+//! ```
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{parse2, punctuated::Punctuated, ExprCall, FnArg, ItemFn, Pat, PatIdent, Token};
+
+use crate::{call::Call, precondition::Precondition, render_assure};
+
+/// The prefix of the names of the generated helpers.
+const HELPER_PREFIX: &str = "fuzz_";
+
+/// Renders the helper for calling the given function with all preconditions assured.
+///
+/// The function must not be rendered yet. Methods taking `self` don't get a helper. For other
+/// associated functions, `is_associated` must be set, so that the function is called through
+/// `Self`.
+pub(crate) fn render_fuzzing_helper(
+    function: &ItemFn,
+    preconditions: &[Precondition],
+    is_associated: bool,
+    span: Span,
+) -> Option<TokenStream> {
+    if function.sig.receiver().is_some() {
+        return None;
+    }
+
+    let mut sig = function.sig.clone();
+    let function_name = &function.sig.ident;
+    sig.ident = format_ident!("{}{}", HELPER_PREFIX, function_name, span = span);
+
+    let mut args = Punctuated::<_, Token![,]>::new();
+    for (index, input) in sig.inputs.iter_mut().enumerate() {
+        if let FnArg::Typed(input) = input {
+            let ident = format_ident!("__pre_arg_{}", index, span = span);
+
+            input.attrs.clear();
+            *input.pat = Pat::Ident(PatIdent {
+                attrs: Vec::new(),
+                by_ref: None,
+                mutability: None,
+                ident: ident.clone(),
+                subpat: None,
+            });
+            args.push(ident);
+        }
+    }
+
+    let path = if is_associated {
+        quote_spanned! { span=> Self::#function_name }
+    } else {
+        quote! { #function_name }
+    };
+    let call: Call = parse2::<ExprCall>(quote_spanned! { span=> #path(#args) })
+        .expect("parses as a call expression")
+        .into();
+    let call = render_assure(preconditions.to_vec(), call, span);
+    let call = if sig.asyncness.is_some() {
+        quote_spanned! { span=> #call.await }
+    } else {
+        quote! { #call }
+    };
+
+    let vis = &function.vis;
+
+    Some(quote_spanned! { span=>
+        #[cfg(fuzzing)]
+        #[doc(hidden)]
+        #[allow(dead_code)]
+        #vis #sig {
+            #call
+        }
+    })
+}
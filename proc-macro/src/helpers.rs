@@ -1,37 +1,125 @@
 //! Allows retrieving the name of the main crate.
 
 use lazy_static::lazy_static;
-use proc_macro2::Span;
+use proc_macro2::{Span, TokenStream};
 use proc_macro_error::{abort_call_site, emit_error};
-use std::env;
+use quote::quote_spanned;
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
 use syn::{
     parenthesized,
     parse::{Parse, ParseStream},
+    parse_quote_spanned,
     spanned::Spanned,
     token::Paren,
-    Attribute, Expr, Signature,
+    Attribute, Expr, FnArg, Ident, LitStr, Signature,
 };
 
+use crate::call::Call;
+
 /// The reason to display in examples on how to use reasons.
 pub(crate) const HINT_REASON: &str = "<specify the reason why you can assure this here>";
 
+/// The environment variable that overrides the name of the main `pre` crate.
+///
+/// It is not tracked as an input of the compilation, so changing it requires a clean rebuild.
+const CRATE_NAME_ENV_VAR: &str = "PRE_CRATE_NAME";
+
 lazy_static! {
     /// Returns the name of the main `pre` crate.
     pub(crate) static ref CRATE_NAME: String = {
-        match proc_macro_crate::crate_name("pre") {
+        let override_name = env::var(CRATE_NAME_ENV_VAR).ok();
+        let package_name = env::var("CARGO_PKG_NAME").ok();
+
+        match resolve_crate_name(
+            override_name,
+            || proc_macro_crate::crate_name("pre"),
+            package_name.as_deref(),
+        ) {
             Ok(name) => name,
-            Err(err) => match env::var("CARGO_PKG_NAME") {
-                // This allows for writing documentation tests on the functions themselves.
-                //
-                // This *may* lead to false positives, if someone also names their crate `pre`, however
-                // it will very likely fail to compile at a later stage then.
-                Ok(val) if val == "pre" => "pre".into(),
-                _ => abort_call_site!("crate `pre` must be imported: {}", err),
-            },
+            Err(msg) => abort_call_site!(
+                "{}", msg;
+                help = "if the dependencies cannot be read, set the `{}` environment variable to the name of the crate",
+                CRATE_NAME_ENV_VAR
+            ),
         }
     };
 }
 
+/// Resolves the name of the main `pre` crate.
+///
+/// The sources are tried in this order:
+///
+/// 1. `override_name`, the value of the `PRE_CRATE_NAME` environment variable, for build systems
+///    where the manifest of the crate cannot be read
+/// 2. the dependencies in the manifest of the crate, as found by `find_in_manifest`
+/// 3. `pre` itself, if `package_name` is `pre`
+fn resolve_crate_name(
+    override_name: Option<String>,
+    find_in_manifest: impl FnOnce() -> Result<String, String>,
+    package_name: Option<&str>,
+) -> Result<String, String> {
+    if let Some(name) = override_name.filter(|name| !name.is_empty()) {
+        return match syn::parse_str::<Ident>(&name) {
+            Ok(_) => Ok(name),
+            Err(_) => Err(format!(
+                "the name of the `pre` crate must be an identifier, found `{}` in the `{}` environment variable",
+                name, CRATE_NAME_ENV_VAR
+            )),
+        };
+    }
+
+    match find_in_manifest() {
+        Ok(name) => Ok(name),
+        Err(err) => match package_name {
+            // This allows for writing documentation tests on the functions themselves.
+            //
+            // This *may* lead to false positives, if someone also names their crate `pre`, however
+            // it will very likely fail to compile at a later stage then.
+            Some("pre") => Ok("pre".into()),
+            _ => Err(format!("crate `pre` must be imported: {}", err)),
+        },
+    }
+}
+
+/// Creates the identifier of the main `pre` crate for paths in generated code.
+///
+/// The name was looked up for the crate being compiled, so the identifier always resolves at the
+/// call site. Only its location is taken from `span`, which may come from the expansion of a
+/// different macro with its own hygiene.
+pub(crate) fn crate_name_ident(span: Span) -> Ident {
+    Ident::new(&CRATE_NAME, span.resolved_at(Span::call_site()))
+}
+
+/// Checks whether spans can be joined with the compiler in use.
+///
+/// `Span::join` is only implemented on the nightly compiler. On the stable compiler it always
+/// returns `None`, so diagnostics can only point to the first token of a larger span.
+pub(crate) fn can_join_spans() -> bool {
+    let span = Span::call_site();
+
+    span.join(span).is_some()
+}
+
+/// Emits an error, if the `strict-spans` feature is enabled, but spans cannot be joined.
+///
+/// The `pre` crate itself is exempt, so that the errors are reported in the crate using `pre`
+/// instead of for the wrappers of the standard library.
+pub(crate) fn check_strict_spans() {
+    let is_main_crate = env::var("CARGO_PKG_NAME").as_deref() == Ok("pre");
+
+    if cfg!(feature = "strict-spans") && !is_main_crate && !can_join_spans() {
+        emit_error!(
+            Span::call_site(),
+            "the `strict-spans` feature of `pre` requires the nightly compiler";
+            note = "spans cannot be joined with the stable compiler";
+            help = "use the nightly compiler or disable the `strict-spans` feature"
+        );
+    }
+}
+
 /// Checks if the given attribute is an `attr_to_check` attribute of the main crate.
 pub(crate) fn is_attr(attr_to_check: &str, attr: &Attribute) -> bool {
     let path = &attr.path;
@@ -130,6 +218,27 @@ pub(crate) fn attributes_of_expression(expr: &mut Expr) -> Option<&mut Vec<Attri
     )
 }
 
+/// Resolves the path in the given literal, as it is written in an attribute.
+///
+/// A relative path is relative to the file containing the attribute, like for `include_str!`. If
+/// that file is not known, the manifest directory is used instead.
+pub(crate) fn resolve_relative_path(lit: &LitStr) -> PathBuf {
+    let path = PathBuf::from(lit.value());
+
+    if path.is_absolute() {
+        return path;
+    }
+
+    let base_dir = lit
+        .span()
+        .local_file()
+        .and_then(|file| file.parent().map(Path::to_path_buf))
+        .or_else(|| env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from))
+        .unwrap_or_default();
+
+    base_dir.join(path)
+}
+
 /// Incorporates the given span into the signature.
 ///
 /// Ideally both are shown, when the function definition is shown.
@@ -152,3 +261,163 @@ pub(crate) fn add_span_to_signature(span: Span, signature: &mut Signature) {
         abi.extern_token.span = abi.extern_token.span.join(span).unwrap_or_else(|| span);
     }
 }
+
+/// Checks whether the marker is passed as the first argument instead of the last one.
+///
+/// This is controlled by the `marker-first` feature. It must be the same for the definition and
+/// all calls of a function, which is why it is a build-wide setting.
+fn marker_first() -> bool {
+    cfg!(feature = "marker-first")
+}
+
+/// The environment variable that specifies the feature that the contracts are gated behind.
+///
+/// Changes of the variable are not noticed by Cargo, so crates that were compiled before keep the
+/// old setting until they are cleaned.
+const CONTRACTS_FEATURE_ENV_VAR: &str = "PRE_CONTRACTS_FEATURE";
+
+/// Returns the name of the feature that the contracts are gated behind, if there is one.
+///
+/// Like the position of the marker, this must be the same for the definition and all calls of a
+/// function, which is why it is a build-wide setting. The `pre` crate itself cannot declare the
+/// feature, so the functions in its `core` and `std` modules are never gated.
+fn contracts_feature() -> Option<String> {
+    if matches!(env::var("CARGO_PKG_NAME").as_deref(), Ok("pre")) {
+        return None;
+    }
+
+    env::var(CONTRACTS_FEATURE_ENV_VAR)
+        .ok()
+        .filter(|name| !name.is_empty())
+}
+
+/// Returns an attribute that removes the item it is attached to, unless the contracts are enabled.
+///
+/// If no feature is configured, the contracts are always enabled and `None` is returned.
+pub(crate) fn contracts_gate(span: Span) -> Option<Attribute> {
+    contracts_feature().map(|name| {
+        let name = LitStr::new(&name, span);
+
+        parse_quote_spanned! { span=> #[cfg(feature = #name)] }
+    })
+}
+
+/// Makes the attribute only apply if the contracts are enabled.
+pub(crate) fn gate_attribute(attr: Attribute) -> Attribute {
+    match contracts_feature() {
+        Some(name) => {
+            let span = attr.span();
+            let name = LitStr::new(&name, span);
+            let path = &attr.path;
+            let tokens = &attr.tokens;
+
+            parse_quote_spanned! { span=> #[cfg_attr(feature = #name, #path #tokens)] }
+        }
+        None => attr,
+    }
+}
+
+/// Returns the pattern of the marker parameter.
+///
+/// Without a name, the marker parameter is bound to the wildcard pattern `_`. This never clashes
+/// with a parameter of the function, not even with another parameter bound to `_`.
+#[allow(dead_code)]
+pub(crate) fn marker_pattern(name: Option<&Ident>, span: Span) -> TokenStream {
+    match name {
+        Some(name) => quote_spanned! { span=>
+            #[allow(unused_variables)]
+            #name
+        },
+        None => quote_spanned! { span=> _ },
+    }
+}
+
+/// Adds the marker parameter to the signature at the configured position.
+///
+/// A receiver always stays the first parameter, so a prepended marker is placed after it.
+#[allow(dead_code)]
+pub(crate) fn add_marker_parameter(signature: &mut Signature, mut marker: FnArg) {
+    if let (FnArg::Typed(marker), Some(gate)) = (&mut marker, contracts_gate(signature.span())) {
+        marker.attrs.push(gate);
+    }
+
+    if marker_first() {
+        let index = match signature.inputs.first() {
+            Some(FnArg::Receiver(_)) => 1,
+            _ => 0,
+        };
+
+        signature.inputs.insert(index, marker);
+    } else {
+        signature.inputs.push(marker);
+    }
+}
+
+/// Adds the marker argument to the call at the configured position.
+#[allow(dead_code)]
+pub(crate) fn add_marker_argument(call: &mut Call, mut marker: Expr) {
+    if let Some(gate) = contracts_gate(marker.span()) {
+        attributes_of_expression(&mut marker)
+            .expect("markers are expressions that can have attributes")
+            .push(gate);
+    }
+
+    if marker_first() {
+        call.args_mut().insert(0, marker);
+    } else {
+        call.args_mut().push(marker);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fails like `proc_macro_crate` does if the manifest cannot be read.
+    fn missing_manifest() -> Result<String, String> {
+        Err("could not find `Cargo.toml`".to_string())
+    }
+
+    #[test]
+    fn override_is_used_first() {
+        let resolved = resolve_crate_name(
+            Some("renamed_pre".to_string()),
+            || panic!("the manifest is not read with an override"),
+            Some("user"),
+        );
+
+        assert_eq!(resolved.as_deref(), Ok("renamed_pre"));
+    }
+
+    #[test]
+    fn manifest_is_used_without_override() {
+        for override_name in [None, Some(String::new())] {
+            let resolved = resolve_crate_name(
+                override_name,
+                || Ok("renamed_pre".to_string()),
+                Some("user"),
+            );
+
+            assert_eq!(resolved.as_deref(), Ok("renamed_pre"));
+        }
+    }
+
+    #[test]
+    fn package_name_is_used_last() {
+        assert_eq!(
+            resolve_crate_name(None, missing_manifest, Some("pre")).as_deref(),
+            Ok("pre")
+        );
+        assert!(resolve_crate_name(None, missing_manifest, Some("user")).is_err());
+        assert!(resolve_crate_name(None, missing_manifest, None).is_err());
+    }
+
+    #[test]
+    fn override_must_be_an_identifier() {
+        let resolved = resolve_crate_name(Some("renamed-pre".to_string()), missing_manifest, None);
+
+        assert!(resolved
+            .expect_err("the override is not an identifier")
+            .contains(CRATE_NAME_ENV_VAR));
+    }
+}
@@ -0,0 +1,145 @@
+//! Allows handling `let ... else` statements.
+//!
+//! The version of `syn` in use has no representation for `let ... else` statements, so it parses
+//! them as verbatim tokens. To still allow attributes on them, the tokens are parsed again here.
+
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse2,
+    punctuated::Punctuated,
+    Attribute, Block, Expr, Pat, PatOr, PatType, Token, Type,
+};
+
+/// A `let ... else` statement without the trailing semicolon.
+pub(crate) struct LetElse {
+    /// The attributes of the statement.
+    pub(crate) attrs: Vec<Attribute>,
+    /// The `let` keyword.
+    let_token: Token![let],
+    /// The pattern that the value is matched against.
+    pat: Pat,
+    /// The `=` token.
+    eq_token: Token![=],
+    /// The expression producing the value.
+    pub(crate) init: Expr,
+    /// The `else` keyword.
+    else_token: Token![else],
+    /// The block that is executed if the pattern does not match.
+    pub(crate) diverge: Block,
+}
+
+impl Parse for LetElse {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let let_token = input.parse()?;
+
+        let leading_vert: Option<Token![|]> = input.parse()?;
+        let mut cases = Punctuated::new();
+        cases.push_value(input.parse()?);
+        while input.peek(Token![|]) && !input.peek(Token![||]) {
+            cases.push_punct(input.parse()?);
+            cases.push_value(input.parse()?);
+        }
+
+        let mut pat = if leading_vert.is_none() && cases.len() == 1 {
+            cases.into_iter().next().expect("one pattern was parsed")
+        } else {
+            Pat::Or(PatOr {
+                attrs: Vec::new(),
+                leading_vert,
+                cases,
+            })
+        };
+
+        if input.peek(Token![:]) {
+            let colon_token = input.parse()?;
+            let ty: Type = input.parse()?;
+            pat = Pat::Type(PatType {
+                attrs: Vec::new(),
+                pat: Box::new(pat),
+                colon_token,
+                ty: Box::new(ty),
+            });
+        }
+
+        Ok(LetElse {
+            attrs,
+            let_token,
+            pat,
+            eq_token: input.parse()?,
+            init: input.parse()?,
+            else_token: input.parse()?,
+            diverge: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for LetElse {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.append_all(&self.attrs);
+        self.let_token.to_tokens(tokens);
+        self.pat.to_tokens(tokens);
+        self.eq_token.to_tokens(tokens);
+        self.init.to_tokens(tokens);
+        self.else_token.to_tokens(tokens);
+        self.diverge.to_tokens(tokens);
+    }
+}
+
+impl LetElse {
+    /// Parses the `let ... else` statement represented by the given expression, if it is one.
+    pub(crate) fn from_expr(expr: &Expr) -> Option<LetElse> {
+        match expr {
+            Expr::Verbatim(tokens) => parse2(tokens.clone()).ok(),
+            _ => None,
+        }
+    }
+
+    /// Converts the statement back to the expression representing it.
+    pub(crate) fn into_expr(self) -> Expr {
+        Expr::Verbatim(self.into_token_stream())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+    use syn::Stmt;
+
+    use super::*;
+
+    /// Parses the single statement in `stmt` the same way `syn` parses it in a block.
+    fn parse_stmt(stmt: TokenStream) -> Expr {
+        let block: Block = parse2(quote! { { #stmt } }).expect("parses as a block");
+
+        match block.stmts.into_iter().next() {
+            Some(Stmt::Semi(expr, _)) => expr,
+            _ => panic!("expected a `let ... else` statement"),
+        }
+    }
+
+    #[test]
+    fn let_else_round_trips() {
+        let stmt = quote! {
+            #[assure(x > 0, reason = "1 > 0")]
+            let Ok(x) | Err(x): Result<i32, i32> = foo(1) else { return; }
+        };
+        let let_else = LetElse::from_expr(&parse_stmt(quote! { #stmt; }))
+            .expect("parses as a `let ... else` statement");
+
+        assert_eq!(let_else.attrs.len(), 1);
+        assert_eq!(
+            let_else.into_expr().into_token_stream().to_string(),
+            stmt.to_string()
+        );
+    }
+
+    #[test]
+    fn other_verbatim_expressions_are_not_let_else() {
+        let expr = Expr::Verbatim(quote! { let x = foo(); });
+
+        assert!(LetElse::from_expr(&expr).is_none());
+    }
+}
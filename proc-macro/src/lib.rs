@@ -13,18 +13,37 @@ use proc_macro_error::{abort_call_site, proc_macro_error};
 use quote::quote;
 use syn::{parse_macro_input, visit_mut::VisitMut, File};
 
-use crate::pre_attr::PreAttrVisitor;
+use crate::{helpers::check_strict_spans, pre_attr::PreAttrVisitor, strip::StripVisitor};
 
 mod call;
 mod call_handling;
+mod coverage;
+mod declared_preconditions;
 mod documentation;
 mod extern_crate;
+mod fixtures;
+mod fuzzing;
 mod helpers;
+mod let_else;
+mod pattern;
 mod pre_attr;
+mod pre_call;
+mod pre_for_each;
 mod precondition;
+mod precondition_alias;
+mod registry;
+mod safety_comment;
+mod safety_section;
+mod signatures;
+mod strip;
+mod tool_annotation;
+mod vocabulary;
 
 cfg_if::cfg_if! {
-    if #[cfg(nightly)] {
+    if #[cfg(feature = "assoc-const")] {
+        mod assoc_const_impl;
+        pub(crate) use crate::assoc_const_impl::{render_assure, render_pre};
+    } else if #[cfg(nightly)] {
         mod const_generics_impl;
         pub(crate) use crate::const_generics_impl::{render_assure, render_pre};
     } else {
@@ -36,6 +55,8 @@ cfg_if::cfg_if! {
 #[proc_macro_attribute]
 #[proc_macro_error]
 pub fn pre(attr: TokenStream, file: TokenStream) -> TokenStream {
+    check_strict_spans();
+
     let dummy_file: TokenStream2 = file.clone().into();
     proc_macro_error::set_dummy(quote! {
         #dummy_file
@@ -43,7 +64,11 @@ pub fn pre(attr: TokenStream, file: TokenStream) -> TokenStream {
 
     let mut file = parse_macro_input!(file as File);
 
-    PreAttrVisitor::new(attr.into()).visit_file_mut(&mut file);
+    if cfg!(feature = "strip") {
+        StripVisitor.visit_file_mut(&mut file);
+    } else {
+        PreAttrVisitor::new(attr.into()).visit_file_mut(&mut file);
+    }
 
     let output = quote! {
         #file
@@ -86,9 +111,75 @@ pub fn forward(_: TokenStream, _: TokenStream) -> TokenStream {
     )
 }
 
+#[proc_macro_attribute]
+#[proc_macro_error]
+pub fn forbid_pre(_: TokenStream, _: TokenStream) -> TokenStream {
+    // This macro currently only has two purposes:
+    // - Exist as a place to put documentation for the actual `forbid_pre` attribute, which is
+    // implemented inside the `pre` attribute.
+    // - Emit an error with a more helpful message than "attribute not found", if the user uses
+    // `forbid_pre` in the wrong place.
+    abort_call_site!(
+        "this attribute by itself is currently non-functional";
+        help = "use it on a module, a function or an `impl` block inside of a module with a `pre` attribute"
+    )
+}
+
+#[proc_macro]
+#[proc_macro_error]
+pub fn pre_call(input: TokenStream) -> TokenStream {
+    check_strict_spans();
+
+    let pre_call = parse_macro_input!(input as pre_call::PreCall);
+
+    let output = pre_call.render();
+
+    // Reset the dummy here, in case errors were emitted while generating the code.
+    // This will use the most up-to-date version of the generated code.
+    proc_macro_error::set_dummy(quote! {
+        #output
+    });
+
+    output.into()
+}
+
+#[proc_macro]
+#[proc_macro_error]
+pub fn declared_preconditions(input: TokenStream) -> TokenStream {
+    check_strict_spans();
+
+    let declared_preconditions =
+        parse_macro_input!(input as declared_preconditions::DeclaredPreconditions);
+
+    declared_preconditions.render().into()
+}
+
+#[proc_macro]
+#[proc_macro_error]
+pub fn preconditions_of(input: TokenStream) -> TokenStream {
+    check_strict_spans();
+
+    let declared_preconditions =
+        parse_macro_input!(input as declared_preconditions::DeclaredPreconditions);
+
+    declared_preconditions.render().into()
+}
+
+#[proc_macro]
+#[proc_macro_error]
+pub fn precondition_alias(input: TokenStream) -> TokenStream {
+    check_strict_spans();
+
+    let aliases = parse_macro_input!(input as precondition_alias::PreconditionAliases);
+
+    aliases.render().into()
+}
+
 #[proc_macro_attribute]
 #[proc_macro_error]
 pub fn extern_crate(attr: TokenStream, module: TokenStream) -> TokenStream {
+    check_strict_spans();
+
     let attr = parse_macro_input!(attr as extern_crate::Attr);
     let module = parse_macro_input!(module as extern_crate::Module);
 
@@ -102,3 +193,21 @@ pub fn extern_crate(attr: TokenStream, module: TokenStream) -> TokenStream {
 
     output.into()
 }
+
+#[proc_macro]
+#[proc_macro_error]
+pub fn pre_for_each(input: TokenStream) -> TokenStream {
+    check_strict_spans();
+
+    let pre_for_each = parse_macro_input!(input as pre_for_each::PreForEach);
+
+    let output = pre_for_each.render();
+
+    // Reset the dummy here, in case errors were emitted while generating the code.
+    // This will use the most up-to-date version of the generated code.
+    proc_macro_error::set_dummy(quote! {
+        #output
+    });
+
+    output.into()
+}
@@ -0,0 +1,303 @@
+//! A small regular expression engine used to check reasons against a required pattern.
+//!
+//! Only a subset of the usual regular expression syntax is supported:
+//!
+//! - literal characters and escaped special characters (e.g. `\.` or `\\`)
+//! - `.` matching any character
+//! - `\d`, `\w` and `\s` matching digits, word characters and whitespace
+//! - character classes like `[A-Z0-9_]` or `[^ ]`
+//! - the quantifiers `*`, `+` and `?`
+//! - `^` and `$` anchoring the pattern at the start or the end of the text
+//!
+//! Unless anchored, a pattern matches if it matches anywhere in the text.
+
+use std::fmt;
+
+/// A single item in a character class.
+#[derive(Debug, Clone, PartialEq)]
+enum ClassItem {
+    /// A single character.
+    Char(char),
+    /// An inclusive range of characters.
+    Range(char, char),
+    /// Any ASCII digit (`\d`).
+    Digit,
+    /// Any alphanumeric character or `_` (`\w`).
+    Word,
+    /// Any whitespace character (`\s`).
+    Space,
+}
+
+impl ClassItem {
+    /// Checks if the character matches the class item.
+    fn matches(&self, c: char) -> bool {
+        match self {
+            ClassItem::Char(expected) => c == *expected,
+            ClassItem::Range(start, end) => *start <= c && c <= *end,
+            ClassItem::Digit => c.is_ascii_digit(),
+            ClassItem::Word => c.is_alphanumeric() || c == '_',
+            ClassItem::Space => c.is_whitespace(),
+        }
+    }
+}
+
+/// Something that matches a single character.
+#[derive(Debug, Clone, PartialEq)]
+enum Atom {
+    /// Matches any character.
+    Any,
+    /// Matches a character, if any of the items match it (or none, if `negated` is set).
+    Class {
+        /// Whether the class is negated.
+        negated: bool,
+        /// The items of the class.
+        items: Vec<ClassItem>,
+    },
+}
+
+impl Atom {
+    /// Checks if the character matches the atom.
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Atom::Any => true,
+            Atom::Class { negated, items } => items.iter().any(|item| item.matches(c)) != *negated,
+        }
+    }
+}
+
+/// An atom with the number of times it may be repeated.
+#[derive(Debug, Clone, PartialEq)]
+struct Node {
+    /// The repeated atom.
+    atom: Atom,
+    /// The minimum number of repetitions.
+    min: usize,
+    /// The maximum number of repetitions, if there is one.
+    max: Option<usize>,
+}
+
+/// A parsed pattern.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Pattern {
+    /// The nodes of the pattern.
+    nodes: Vec<Node>,
+    /// Whether the pattern must match at the start of the text.
+    anchored_start: bool,
+    /// Whether the pattern must match at the end of the text.
+    anchored_end: bool,
+}
+
+/// An error that occurred while parsing a pattern.
+#[derive(Debug, PartialEq)]
+pub(crate) struct PatternError(String);
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Pattern {
+    /// Parses a pattern from a string.
+    pub(crate) fn new(pattern: &str) -> Result<Pattern, PatternError> {
+        let mut chars = pattern.chars().peekable();
+        let mut nodes = Vec::new();
+        let mut anchored_start = false;
+        let mut anchored_end = false;
+
+        if chars.peek() == Some(&'^') {
+            chars.next();
+            anchored_start = true;
+        }
+
+        while let Some(c) = chars.next() {
+            let atom = match c {
+                '$' if chars.peek().is_none() => {
+                    anchored_end = true;
+                    break;
+                }
+                '.' => Atom::Any,
+                '\\' => match chars.next() {
+                    Some(escaped) => escape_class(escaped),
+                    None => return Err(PatternError("the pattern ends with a `\\`".into())),
+                },
+                '[' => {
+                    let negated = if chars.peek() == Some(&'^') {
+                        chars.next();
+                        true
+                    } else {
+                        false
+                    };
+                    let mut items = Vec::new();
+
+                    loop {
+                        let item = match chars.next() {
+                            Some(']') if !items.is_empty() => break,
+                            Some('\\') => match chars.next().map(escape_class) {
+                                Some(Atom::Class { mut items, .. }) => items.remove(0),
+                                _ => {
+                                    return Err(PatternError("the pattern ends with a `\\`".into()))
+                                }
+                            },
+                            Some(start) => {
+                                let mut lookahead = chars.clone();
+                                match (lookahead.next(), lookahead.next()) {
+                                    (Some('-'), Some(end)) if end != ']' => {
+                                        chars.next();
+                                        chars.next();
+
+                                        if end < start {
+                                            return Err(PatternError(format!(
+                                                "the range `{}-{}` is reversed",
+                                                start, end
+                                            )));
+                                        }
+
+                                        ClassItem::Range(start, end)
+                                    }
+                                    _ => ClassItem::Char(start),
+                                }
+                            }
+                            None => {
+                                return Err(PatternError(
+                                    "the character class is never closed".into(),
+                                ))
+                            }
+                        };
+
+                        items.push(item);
+                    }
+
+                    Atom::Class { negated, items }
+                }
+                '*' | '+' | '?' => {
+                    return Err(PatternError(format!(
+                        "the quantifier `{}` does not follow anything it could repeat",
+                        c
+                    )))
+                }
+                '(' | ')' | '|' | '{' | '}' | '^' | '$' => {
+                    return Err(PatternError(format!(
+                        "`{}` is not supported in patterns, use `\\{}` to match it literally",
+                        c, c
+                    )))
+                }
+                other => Atom::Class {
+                    negated: false,
+                    items: vec![ClassItem::Char(other)],
+                },
+            };
+
+            let (min, max) = match chars.peek() {
+                Some('*') => (0, None),
+                Some('+') => (1, None),
+                Some('?') => (0, Some(1)),
+                _ => (1, Some(1)),
+            };
+            if (min, max) != (1, Some(1)) {
+                chars.next();
+            }
+
+            nodes.push(Node { atom, min, max });
+        }
+
+        Ok(Pattern {
+            nodes,
+            anchored_start,
+            anchored_end,
+        })
+    }
+
+    /// Checks if the pattern matches the given text.
+    pub(crate) fn is_match(&self, text: &str) -> bool {
+        let text: Vec<char> = text.chars().collect();
+
+        if self.anchored_start {
+            self.match_at(&self.nodes, &text)
+        } else {
+            (0..=text.len()).any(|start| self.match_at(&self.nodes, &text[start..]))
+        }
+    }
+
+    /// Checks if the nodes match at the start of the text.
+    fn match_at(&self, nodes: &[Node], text: &[char]) -> bool {
+        let (node, rest) = match nodes.split_first() {
+            Some(split) => split,
+            None => return !self.anchored_end || text.is_empty(),
+        };
+
+        let max = node.max.unwrap_or(text.len()).min(text.len());
+        let matching = text[..max]
+            .iter()
+            .take_while(|&&c| node.atom.matches(c))
+            .count();
+
+        // Try the longest repetition first and backtrack from there.
+        (node.min..=matching)
+            .rev()
+            .any(|count| self.match_at(rest, &text[count..]))
+    }
+}
+
+/// Returns the atom corresponding to an escaped character.
+fn escape_class(escaped: char) -> Atom {
+    let item = match escaped {
+        'd' => ClassItem::Digit,
+        'w' => ClassItem::Word,
+        's' => ClassItem::Space,
+        other => ClassItem::Char(other),
+    };
+
+    Atom::Class {
+        negated: false,
+        items: vec![item],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_match(pattern: &str, text: &str) -> bool {
+        Pattern::new(pattern).expect("valid pattern").is_match(text)
+    }
+
+    #[test]
+    fn literal_and_escapes() {
+        assert!(is_match("JIRA-\\d+", "verified in JIRA-1234"));
+        assert!(!is_match("JIRA-\\d+", "verified in JIRA-"));
+        assert!(is_match("a\\.b", "a.b"));
+        assert!(!is_match("a\\.b", "axb"));
+        assert!(is_match("a.b", "axb"));
+    }
+
+    #[test]
+    fn classes_and_quantifiers() {
+        assert!(is_match("#[0-9]+", "see #42"));
+        assert!(!is_match("#[0-9]+", "see #"));
+        assert!(is_match("colou?r", "color"));
+        assert!(is_match("colou?r", "colour"));
+        assert!(is_match("ab*c", "ac"));
+        assert!(is_match("[^ ]+-\\w", "x-y"));
+        assert!(!is_match("^[^ ]+$", "x y"));
+    }
+
+    #[test]
+    fn anchors() {
+        assert!(is_match("^abc", "abcdef"));
+        assert!(!is_match("^abc", "xabc"));
+        assert!(is_match("def$", "abcdef"));
+        assert!(!is_match("def$", "defx"));
+        assert!(is_match("^a.*z$", "abcz"));
+    }
+
+    #[test]
+    fn invalid_patterns() {
+        assert!(Pattern::new("+abc").is_err());
+        assert!(Pattern::new("abc\\").is_err());
+        assert!(Pattern::new("[abc").is_err());
+        assert!(Pattern::new("[z-a]").is_err());
+        assert!(Pattern::new("(abc)").is_err());
+        assert!(Pattern::new("a|b").is_err());
+    }
+}
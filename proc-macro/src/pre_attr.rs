@@ -1,29 +1,55 @@
 //! Defines the `pre` attribute and how it is handled.
 
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::{Span, TokenStream, TokenTree};
 use proc_macro_error::{emit_error, emit_warning};
 use quote::{quote, quote_spanned};
+use std::{collections::HashMap, path::PathBuf};
 use syn::{
+    parenthesized,
     parse::{Parse, ParseStream},
-    parse2,
+    parse2, parse_quote_spanned,
     spanned::Spanned,
+    token::Paren,
     visit_mut::{
-        visit_expr_mut, visit_file_mut, visit_item_fn_mut, visit_item_mut, visit_local_mut,
-        VisitMut,
+        visit_expr_mut, visit_file_mut, visit_impl_item_mut, visit_item_fn_mut, visit_item_mut,
+        visit_local_mut, VisitMut,
     },
-    Expr, File, Item, ItemFn, Local,
+    Attribute, BinOp, Expr, File, FnArg, Ident, ImplItem, Item, ItemFn, ItemMod, LitStr, Local,
+    Macro, Pat, PatType, RangeLimits, Signature, Stmt, Token,
 };
 
-use self::expr_handling::render_expr;
+pub(crate) use self::expr_handling::render_expr;
+use self::fn_pointer_alias::{has_pre_attrs, render_fn_pointer_alias};
+use self::forbid_pre::{
+    check_unassured_calls, take_forbid_pre, AssureRequirement, ModuleFunctions,
+};
+use self::macro_arguments::MacroArguments;
 use crate::{
-    call_handling::remove_call_attributes,
-    documentation::generate_docs,
-    helpers::{attributes_of_expression, is_attr, visit_matching_attrs_parsed, Parenthesized},
-    precondition::Precondition,
+    call_handling::{remove_call_attributes, CallContext, IdentCollector},
+    coverage::Coverage,
+    declared_preconditions::render_precondition_list,
+    documentation::{generate_docs, generate_precondition_summary},
+    fixtures::write_fixture,
+    fuzzing::render_fuzzing_helper,
+    helpers::{
+        attributes_of_expression, contracts_gate, crate_name_ident, gate_attribute, is_attr,
+        visit_matching_attrs_parsed, Parenthesized,
+    },
+    let_else::LetElse,
+    pattern::Pattern,
+    precondition::{CaseWarnings, Length, Precondition, PreconditionList},
+    precondition_alias::{aliases_of, PreconditionAlias},
+    registry::render_registration,
     render_pre,
+    safety_section::suggest_preconditions,
+    tool_annotation::render_tool_annotation,
+    vocabulary::{render_dependencies, Vocabulary},
 };
 
 mod expr_handling;
+mod fn_pointer_alias;
+mod forbid_pre;
+mod macro_arguments;
 
 /// The custom keywords used for `pre` attributes.
 mod custom_keywords {
@@ -31,6 +57,12 @@ mod custom_keywords {
 
     custom_keyword!(no_doc);
     custom_keyword!(no_debug_assert);
+    custom_keyword!(track_caller);
+    custom_keyword!(reason_pattern);
+    custom_keyword!(exclusive);
+    custom_keyword!(vocabulary);
+    custom_keyword!(marker);
+    custom_keyword!(alias);
 }
 
 /// A `pre` attribute.
@@ -41,6 +73,18 @@ pub(crate) enum PreAttr {
     NoDoc(custom_keywords::no_doc),
     /// A request not to generate `debug_assert` statements for boolean expressions.
     NoDebugAssert(custom_keywords::no_debug_assert),
+    /// A request to report the location of the caller when a `debug_assert` fails.
+    TrackCaller(custom_keywords::track_caller),
+    /// A pattern that the reasons of all contained `assure` attributes must match.
+    ReasonPattern(ReasonPattern),
+    /// Two preconditions that must not be assured for the same call.
+    Exclusive(Exclusive),
+    /// The approved phrases for the custom preconditions of all contained functions.
+    Vocabulary(Vocabulary),
+    /// The name of the marker parameter that is added to the contained function.
+    Marker(Marker),
+    /// A group of preconditions defined by a `precondition_alias` macro.
+    Alias(AliasReference),
     /// A precondition that needs to hold for the contained item.
     Precondition(Precondition),
 }
@@ -53,21 +97,550 @@ impl Parse for PreAttr {
             Ok(PreAttr::NoDoc(input.parse()?))
         } else if input.peek(custom_keywords::no_debug_assert) {
             Ok(PreAttr::NoDebugAssert(input.parse()?))
+        } else if input.peek(custom_keywords::track_caller) {
+            Ok(PreAttr::TrackCaller(input.parse()?))
+        } else if input.peek(custom_keywords::reason_pattern) && input.peek2(Token![=]) {
+            Ok(PreAttr::ReasonPattern(input.parse()?))
+        } else if input.peek(custom_keywords::exclusive)
+            && input.peek2(Paren)
+            && !is_exclusive_call(input)
+        {
+            Ok(PreAttr::Exclusive(input.parse()?))
+        } else if input.peek(custom_keywords::vocabulary) && input.peek2(Token![=]) {
+            Ok(PreAttr::Vocabulary(input.parse()?))
+        } else if input.peek(custom_keywords::marker) && input.peek2(Token![=]) {
+            Ok(PreAttr::Marker(input.parse()?))
+        } else if input.peek(custom_keywords::alias) && input.peek2(Ident) {
+            Ok(PreAttr::Alias(input.parse()?))
         } else {
             Ok(PreAttr::Precondition(input.parse()?))
         }
     }
 }
 
+/// Checks whether the input is a call of a function named `exclusive` instead of an `exclusive`
+/// declaration.
+///
+/// Both start with the `exclusive` keyword, but the call has a single argument, while the
+/// declaration lists two preconditions. Such a call is a boolean precondition, because the built-in
+/// precondition is written as `kind = exclusive(<ptr_name>)`.
+fn is_exclusive_call(input: ParseStream) -> bool {
+    let fork = input.fork();
+    let parse_precondition = |input: ParseStream| -> syn::Result<()> {
+        input.parse::<custom_keywords::exclusive>()?;
+        let content;
+        parenthesized!(content in input);
+        content.parse::<Ident>()?;
+
+        if content.is_empty() {
+            Ok(())
+        } else {
+            Err(content.error("unexpected token"))
+        }
+    };
+
+    parse_precondition(&fork).is_ok()
+}
+
+/// A pattern that the reasons of `assure` attributes must match.
+#[derive(Clone)]
+pub(crate) struct ReasonPattern {
+    /// The `reason_pattern` keyword.
+    _reason_pattern_keyword: custom_keywords::reason_pattern,
+    /// The `=` separating the `reason_pattern` keyword and the pattern.
+    _eq: Token![=],
+    /// The literal that the pattern was parsed from.
+    pub(crate) lit: LitStr,
+    /// The parsed pattern.
+    pub(crate) pattern: Pattern,
+}
+
+impl Parse for ReasonPattern {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let reason_pattern_keyword = input.parse()?;
+        let eq = input.parse()?;
+        let lit: LitStr = input.parse()?;
+        let pattern = Pattern::new(&lit.value())
+            .map_err(|err| syn::Error::new(lit.span(), format!("invalid pattern: {}", err)))?;
+
+        Ok(ReasonPattern {
+            _reason_pattern_keyword: reason_pattern_keyword,
+            _eq: eq,
+            lit,
+            pattern,
+        })
+    }
+}
+
+/// The name of the marker parameter of a function.
+///
+/// The marker parameter is unnamed by default, so that it can never clash with a parameter of the
+/// function. Naming it helps with code that refers to the parameters by name, such as macros that
+/// generate the body of the function.
+pub(crate) struct Marker {
+    /// The `marker` keyword.
+    _marker_keyword: custom_keywords::marker,
+    /// The `=` separating the `marker` keyword and the name.
+    _eq: Token![=],
+    /// The literal that the name was parsed from.
+    pub(crate) lit: LitStr,
+    /// The name of the marker parameter.
+    pub(crate) name: Ident,
+}
+
+impl Parse for Marker {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let marker_keyword = input.parse()?;
+        let eq = input.parse()?;
+        let lit: LitStr = input.parse()?;
+        let name = lit
+            .parse()
+            .map_err(|_| syn::Error::new(lit.span(), "expected an identifier"))?;
+
+        Ok(Marker {
+            _marker_keyword: marker_keyword,
+            _eq: eq,
+            lit,
+            name,
+        })
+    }
+}
+
+/// A use of a precondition alias: `alias <name> for <ident>`.
+pub(crate) struct AliasReference {
+    /// The `alias` keyword.
+    _alias_keyword: custom_keywords::alias,
+    /// The name of the alias.
+    pub(crate) name: Ident,
+    /// The `for` separating the name and the identifier.
+    _for: Token![for],
+    /// The identifier that replaces the one in the preconditions of the alias.
+    pub(crate) ident: Ident,
+}
+
+impl Parse for AliasReference {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(AliasReference {
+            _alias_keyword: input.parse()?,
+            name: input.parse()?,
+            _for: input.parse()?,
+            ident: input.parse()?,
+        })
+    }
+}
+
+impl AliasReference {
+    /// Returns the alias that is referred to, if it is defined.
+    ///
+    /// Aliases that are defined later, such as in a nested module, take precedence.
+    fn find_in<'a>(&self, aliases: &'a [PreconditionAlias]) -> Option<&'a PreconditionAlias> {
+        aliases.iter().rev().find(|alias| alias.name == self.name)
+    }
+
+    /// Returns the preconditions of the alias for the identifier.
+    ///
+    /// An error is emitted, if the alias is not defined or cannot be expanded.
+    fn expand(&self, aliases: &[PreconditionAlias]) -> Vec<Precondition> {
+        let alias = match self.find_in(aliases) {
+            Some(alias) => alias,
+            None => {
+                emit_error!(
+                    self.name,
+                    "the precondition alias `{}` is not defined", self.name;
+                    help = "define it with `pre::precondition_alias!` in a module with a `pre` attribute that contains this item"
+                );
+
+                return Vec::new();
+            }
+        };
+
+        match alias.expand(&self.ident) {
+            Ok(preconditions) => preconditions,
+            Err(err) => {
+                emit_error!(
+                    self.ident,
+                    "the precondition alias `{}` cannot be used for `{}`: {}",
+                    self.name,
+                    self.ident,
+                    err
+                );
+
+                Vec::new()
+            }
+        }
+    }
+}
+
+/// Two preconditions that must not be assured for the same call.
+#[derive(Clone)]
+pub(crate) struct Exclusive {
+    /// The `exclusive` keyword.
+    exclusive_keyword: custom_keywords::exclusive,
+    /// The parentheses surrounding the preconditions.
+    parentheses: Paren,
+    /// The first precondition.
+    pub(crate) first: Precondition,
+    /// The comma separating the preconditions.
+    _comma: Token![,],
+    /// The second precondition.
+    pub(crate) second: Precondition,
+}
+
+impl Parse for Exclusive {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let exclusive_keyword = input.parse()?;
+        let content;
+        let parentheses = parenthesized!(content in input);
+        let first = content.parse()?;
+        let comma = content.parse()?;
+        let second = content.parse()?;
+
+        if content.peek(Token![,]) {
+            content.parse::<Token![,]>()?;
+        }
+
+        if !content.is_empty() {
+            return Err(content.error("expected exactly two preconditions"));
+        }
+
+        Ok(Exclusive {
+            exclusive_keyword,
+            parentheses,
+            first,
+            _comma: comma,
+            second,
+        })
+    }
+}
+
+impl Exclusive {
+    /// Returns the span of the whole declaration.
+    pub(crate) fn span(&self) -> Span {
+        self.exclusive_keyword
+            .span
+            .join(self.parentheses.span)
+            .unwrap_or(self.exclusive_keyword.span)
+    }
+
+    /// Checks whether both preconditions are contained in the given assured preconditions.
+    pub(crate) fn is_violated_by(&self, assured_preconditions: &[Precondition]) -> bool {
+        assured_preconditions.contains(&self.first) && assured_preconditions.contains(&self.second)
+    }
+}
+
+/// Removes the `exclusive` declarations from the given attributes and returns them.
+fn take_exclusions(attrs: &mut Vec<Attribute>) -> Vec<Exclusive> {
+    let mut exclusions = Vec::new();
+
+    visit_matching_attrs_parsed(
+        attrs,
+        |attr| {
+            is_attr("pre", attr)
+                && matches!(
+                    parse2(attr.tokens.clone()),
+                    Ok(Parenthesized {
+                        content: PreAttr::Exclusive(_),
+                        ..
+                    })
+                )
+        },
+        |parsed_attr: Parenthesized<PreAttr>, _span| {
+            if let PreAttr::Exclusive(exclusive) = parsed_attr.content {
+                exclusions.push(exclusive);
+            }
+        },
+    );
+
+    exclusions
+}
+
+/// Removes the preconditions from the attributes of an `impl` block and returns them.
+///
+/// These preconditions apply to every method in the `impl` block. Errors are emitted for
+/// preconditions that are declared more than once.
+fn take_impl_preconditions(
+    attrs: &mut Vec<Attribute>,
+    vocabulary: Option<&Vocabulary>,
+    aliases: &[PreconditionAlias],
+) -> Vec<Precondition> {
+    let mut declared_preconditions = Vec::new();
+
+    visit_matching_attrs_parsed(
+        attrs,
+        |attr| {
+            is_attr("pre", attr)
+                && matches!(
+                    parse2(attr.tokens.clone()),
+                    Ok(Parenthesized {
+                        content: PreAttr::Precondition(_) | PreAttr::Alias(_),
+                        ..
+                    })
+                )
+        },
+        |parsed_attr: Parenthesized<PreAttr>, _span| match parsed_attr.content {
+            PreAttr::Precondition(precondition) => {
+                check_precondition(&precondition, vocabulary);
+                declared_preconditions.push(precondition);
+            }
+            PreAttr::Alias(reference) => {
+                for precondition in reference.expand(aliases) {
+                    check_precondition(&precondition, vocabulary);
+                    declared_preconditions.push(precondition);
+                }
+            }
+            _ => (),
+        },
+    );
+
+    let mut preconditions = PreconditionList::new();
+    for duplicate in preconditions.merge(declared_preconditions) {
+        emit_duplicate_error(&preconditions, &duplicate);
+    }
+
+    preconditions.into_vec()
+}
+
+/// Returns the preconditions of all functions in the given items, including nested modules.
+fn declared_preconditions_of(items: &[Item], aliases: &[PreconditionAlias]) -> Vec<Precondition> {
+    let mut preconditions: Vec<_> = known_functions_of(items, aliases)
+        .into_values()
+        .flatten()
+        .collect();
+
+    for item in items {
+        if let Item::Mod(ItemMod {
+            content: Some((_, items)),
+            ..
+        }) = item
+        {
+            let mut aliases = aliases.to_vec();
+            aliases.extend(aliases_of(items));
+
+            preconditions.extend(declared_preconditions_of(items, &aliases));
+        }
+    }
+
+    preconditions
+}
+
+/// Checks that the preconditions of the exclusions are declared by functions in the given items.
+fn check_exclusions(exclusions: &[Exclusive], items: &[Item], aliases: &[PreconditionAlias]) {
+    if exclusions.is_empty() {
+        return;
+    }
+
+    let declared_preconditions = declared_preconditions_of(items, aliases);
+
+    for exclusive in exclusions {
+        if exclusive.first == exclusive.second {
+            emit_error!(
+                exclusive.span(),
+                "the precondition `{}` cannot be exclusive with itself",
+                exclusive.first
+            );
+
+            continue;
+        }
+
+        for precondition in &[&exclusive.first, &exclusive.second] {
+            if !declared_preconditions.contains(precondition) {
+                emit_error!(
+                    precondition.span(),
+                    "the precondition `{}` is not declared by any function in this module",
+                    precondition;
+                    help = "only preconditions of functions in the module can be exclusive"
+                );
+            }
+        }
+    }
+}
+
+/// Returns the reason pattern specified in the given attributes, if there is one.
+fn reason_pattern_of(attrs: &[Attribute]) -> Option<ReasonPattern> {
+    attrs
+        .iter()
+        .filter(|attr| is_attr("pre", attr))
+        .find_map(|attr| match parse2(attr.tokens.clone()) {
+            Ok(Parenthesized {
+                content: PreAttr::ReasonPattern(reason_pattern),
+                ..
+            }) => Some(reason_pattern),
+            _ => None,
+        })
+}
+
+/// Removes the `pre` attributes that `setting_of` returns a setting for from the given attributes
+/// and returns the first setting, if there is one.
+///
+/// This is used for the reason pattern and vocabulary of modules and `impl` blocks, whose
+/// attributes are not rendered by `render_function`.
+fn take_setting<T>(
+    attrs: &mut Vec<Attribute>,
+    setting_of: impl Fn(PreAttr) -> Option<T>,
+) -> Option<T> {
+    let mut setting = None;
+
+    visit_matching_attrs_parsed(
+        attrs,
+        |attr| {
+            is_attr("pre", attr)
+                && match parse2::<Parenthesized<PreAttr>>(attr.tokens.clone()) {
+                    Ok(parsed_attr) => setting_of(parsed_attr.content).is_some(),
+                    Err(_) => false,
+                }
+        },
+        |parsed_attr: Parenthesized<PreAttr>, _span| {
+            if setting.is_none() {
+                setting = setting_of(parsed_attr.content);
+            }
+        },
+    );
+
+    setting
+}
+
+/// Removes the reason pattern from the given attributes and returns it, if there is one.
+fn take_reason_pattern(attrs: &mut Vec<Attribute>) -> Option<ReasonPattern> {
+    take_setting(attrs, |attr| match attr {
+        PreAttr::ReasonPattern(reason_pattern) => Some(reason_pattern),
+        _ => None,
+    })
+}
+
+/// Removes the vocabulary from the given attributes and returns it, if there is one.
+fn take_vocabulary(attrs: &mut Vec<Attribute>) -> Option<Vocabulary> {
+    take_setting(attrs, |attr| match attr {
+        PreAttr::Vocabulary(vocabulary) => Some(vocabulary),
+        _ => None,
+    })
+}
+
+/// Returns the vocabulary specified in the given attributes, if there is one.
+fn vocabulary_of(attrs: &[Attribute]) -> Option<Vocabulary> {
+    attrs
+        .iter()
+        .filter(|attr| is_attr("pre", attr))
+        .find_map(|attr| match parse2(attr.tokens.clone()) {
+            Ok(Parenthesized {
+                content: PreAttr::Vocabulary(vocabulary),
+                ..
+            }) => Some(vocabulary),
+            _ => None,
+        })
+}
+
+/// Returns the preconditions of all functions in the given items by function name.
+///
+/// Errors in the uses of aliases are not reported here, but when the functions are rendered.
+fn known_functions_of(
+    items: &[Item],
+    aliases: &[PreconditionAlias],
+) -> HashMap<String, Vec<Precondition>> {
+    let mut known_functions = HashMap::new();
+
+    for item in items {
+        if let Item::Fn(function) = item {
+            let preconditions: PreconditionList = function
+                .attrs
+                .iter()
+                .filter(|attr| is_attr("pre", attr))
+                .flat_map(|attr| match parse2(attr.tokens.clone()) {
+                    Ok(Parenthesized {
+                        content: PreAttr::Precondition(precondition),
+                        ..
+                    }) => vec![precondition],
+                    Ok(Parenthesized {
+                        content: PreAttr::Alias(reference),
+                        ..
+                    }) => reference
+                        .find_in(aliases)
+                        .and_then(|alias| alias.expand(&reference.ident).ok())
+                        .unwrap_or_default(),
+                    _ => Vec::new(),
+                })
+                .collect();
+
+            known_functions.insert(function.sig.ident.to_string(), preconditions.into_vec());
+        }
+    }
+
+    known_functions
+}
+
+/// Adds the preconditions of all functions in the given items and their nested modules to
+/// `modules`.
+///
+/// `path` is the path of the module containing the items.
+fn module_functions_of(
+    items: &[Item],
+    aliases: &[PreconditionAlias],
+    path: &mut Vec<String>,
+    modules: &mut ModuleFunctions,
+) {
+    modules.insert(path.clone(), known_functions_of(items, aliases));
+
+    for item in items {
+        if let Item::Mod(ItemMod {
+            ident,
+            content: Some((_, items)),
+            ..
+        }) = item
+        {
+            let mut aliases = aliases.to_vec();
+            aliases.extend(aliases_of(items));
+
+            path.push(ident.to_string());
+            module_functions_of(items, &aliases, path, modules);
+            path.pop();
+        }
+    }
+}
+
 /// Applies and removes all visited pre attributes.
 pub(crate) struct PreAttrVisitor {
     /// The original attribute that started the visitor.
     original_attr: Option<PreAttr>,
+    /// The pattern that reasons in the currently visited item must match.
+    reason_pattern: Option<ReasonPattern>,
+    /// The approved phrases for custom preconditions in the currently visited item.
+    vocabulary: Option<Vocabulary>,
+    /// The files of all vocabularies used in the annotated item.
+    vocabulary_files: Vec<PathBuf>,
+    /// The preconditions of the functions in the currently visited module, if they are known.
+    known_functions: Option<HashMap<String, Vec<Precondition>>>,
+    /// The preconditions of the functions in all modules of the annotated item.
+    ///
+    /// This is only filled, if the preconditions of the functions are known.
+    module_functions: ModuleFunctions,
+    /// The path of the currently visited module relative to the annotated item.
+    module_path: Vec<String>,
+    /// The record of assured preconditions in the currently visited module.
+    ///
+    /// This is only present, if the `coverage` feature is enabled and the module is annotated.
+    coverage: Option<Coverage>,
+    /// The preconditions that must not be assured for the same call in the currently visited
+    /// module.
+    exclusions: Vec<Exclusive>,
+    /// The precondition aliases defined in the currently visited module and its parents.
+    aliases: Vec<PreconditionAlias>,
+    /// The span of the `forbid_pre` attribute of the currently visited item, if there is one.
+    ///
+    /// If it is present, all calls of known functions with preconditions must be assured.
+    forbid_pre: Option<Span>,
+    /// The preconditions declared on the currently visited `impl` block.
+    ///
+    /// They are added to every method in the `impl` block.
+    impl_preconditions: Vec<Precondition>,
+    /// The custom preconditions that were already warned about in the annotated item.
+    case_warnings: CaseWarnings,
+    /// The tokens of the original attribute that started the visitor.
+    original_attr_tokens: TokenStream,
 }
 
 impl PreAttrVisitor {
     /// Creates a new visitor for the syntax tree that `original_attr` was attached to.
     pub(crate) fn new(original_attr: TokenStream) -> PreAttrVisitor {
+        let original_attr_tokens = original_attr.clone();
         let original_attr = if !original_attr.is_empty() {
             let span = original_attr.span();
 
@@ -87,7 +660,40 @@ impl PreAttrVisitor {
             None
         };
 
-        PreAttrVisitor { original_attr }
+        let mut visitor = PreAttrVisitor {
+            original_attr,
+            reason_pattern: None,
+            vocabulary: None,
+            vocabulary_files: Vec::new(),
+            known_functions: None,
+            module_functions: ModuleFunctions::new(),
+            module_path: Vec::new(),
+            coverage: None,
+            exclusions: Vec::new(),
+            aliases: Vec::new(),
+            forbid_pre: None,
+            impl_preconditions: Vec::new(),
+            case_warnings: CaseWarnings::default(),
+            original_attr_tokens,
+        };
+        if let Some(vocabulary) = Vocabulary::from_env() {
+            visitor.enter_vocabulary(vocabulary);
+        }
+
+        visitor
+    }
+
+    /// Uses the given vocabulary for the currently visited item.
+    fn enter_vocabulary(&mut self, vocabulary: Vocabulary) {
+        if !self
+            .vocabulary_files
+            .iter()
+            .any(|file| file == vocabulary.path())
+        {
+            self.vocabulary_files.push(vocabulary.path().to_path_buf());
+        }
+
+        self.vocabulary = Some(vocabulary);
     }
 }
 
@@ -96,20 +702,112 @@ impl VisitMut for PreAttrVisitor {
         let original_attr = self.original_attr.take();
 
         if let [Item::Fn(function)] = &mut file.items[..] {
+            self.reason_pattern = match &original_attr {
+                Some(PreAttr::ReasonPattern(reason_pattern)) => Some(reason_pattern.clone()),
+                _ => reason_pattern_of(&function.attrs),
+            };
+            if let Some(vocabulary) = match &original_attr {
+                Some(PreAttr::Vocabulary(vocabulary)) => Some(vocabulary.clone()),
+                _ => vocabulary_of(&function.attrs),
+            } {
+                self.enter_vocabulary(vocabulary);
+            }
+
+            if let Some(span) = take_forbid_pre(&mut function.attrs) {
+                self.enter_forbid_pre(span, false);
+            }
+
             // Use `visit_item_fn_mut ` here, so that the function remains an `ItemFn` that can be
             // passed to `render_function`. Using `visit_item_mut` here would result in an
-            // `Item::Verbatim` instead.
+            // `Item::Verbatim` instead. This still visits the whole body, including a call that
+            // is the tail expression of the function.
             visit_item_fn_mut(self, function);
 
-            file.items[0] = Item::Verbatim(render_function(function, original_attr));
+            // The dependencies are placed in the body, so that the function can also be a method.
+            function.block.stmts.insert(
+                0,
+                Stmt::Item(Item::Verbatim(render_dependencies(&self.vocabulary_files))),
+            );
+
+            file.items[0] = Item::Verbatim(render_function(
+                function,
+                original_attr,
+                &[],
+                self.vocabulary.as_ref(),
+                &self.aliases,
+                &self.case_warnings,
+                false,
+            ));
+        } else if let [Item::Type(alias)] = &mut file.items[..] {
+            file.items[0] = Item::Verbatim(render_fn_pointer_alias(
+                alias,
+                original_attr,
+                self.vocabulary.as_ref(),
+                &self.aliases,
+            ));
+            file.items
+                .push(Item::Verbatim(render_dependencies(&self.vocabulary_files)));
         } else {
+            let original_attr = match (original_attr, &mut file.items[..]) {
+                // A precondition on an `impl` block is handled like the other attributes of the
+                // block, so that it is added to all of its methods.
+                (Some(PreAttr::Precondition(_)), [Item::Impl(impl_block)]) => {
+                    let tokens = &self.original_attr_tokens;
+                    impl_block.attrs.insert(
+                        0,
+                        parse_quote_spanned! { tokens.span()=>
+                            #[pre(#tokens)]
+                        },
+                    );
+
+                    None
+                }
+                (original_attr, _) => original_attr,
+            };
+
+            match &original_attr {
+                Some(PreAttr::ReasonPattern(reason_pattern)) => {
+                    self.reason_pattern = Some(reason_pattern.clone())
+                }
+                Some(PreAttr::Exclusive(exclusive)) => self.exclusions.push(exclusive.clone()),
+                Some(PreAttr::Vocabulary(vocabulary)) => self.enter_vocabulary(vocabulary.clone()),
+                _ => (),
+            }
+
+            self.aliases.extend(aliases_of(&file.items));
+            check_exclusions(&self.exclusions, &file.items, &self.aliases);
+
+            let known_functions = known_functions_of(&file.items, &self.aliases);
+            if cfg!(feature = "coverage") {
+                self.coverage = Some(Coverage::new(&self.module_path, &known_functions));
+            }
+            self.known_functions = Some(known_functions);
+            module_functions_of(
+                &file.items,
+                &self.aliases,
+                &mut self.module_path.clone(),
+                &mut self.module_functions,
+            );
+
             visit_file_mut(self, file);
+            file.items
+                .push(Item::Verbatim(render_dependencies(&self.vocabulary_files)));
+
+            if let Some(coverage) = self.coverage.take() {
+                coverage.write();
+            }
 
             if let Some(original_attr) = original_attr {
                 if let Some(span) = match original_attr {
                     PreAttr::Empty => None,
                     PreAttr::NoDoc(no_doc) => Some(no_doc.span()),
                     PreAttr::NoDebugAssert(no_debug_assert) => Some(no_debug_assert.span()),
+                    PreAttr::TrackCaller(track_caller) => Some(track_caller.span()),
+                    PreAttr::ReasonPattern(_) | PreAttr::Exclusive(_) | PreAttr::Vocabulary(_) => {
+                        None
+                    }
+                    PreAttr::Marker(marker) => Some(marker.lit.span()),
+                    PreAttr::Alias(reference) => Some(reference.name.span()),
                     PreAttr::Precondition(precondition) => Some(precondition.span()),
                 } {
                     emit_warning!(span, "this is ignored in this context")
@@ -119,20 +817,242 @@ impl VisitMut for PreAttrVisitor {
     }
 
     fn visit_item_mut(&mut self, item: &mut Item) {
+        let outer_reason_pattern = self.reason_pattern.clone();
+        let outer_vocabulary = self.vocabulary.clone();
+        let outer_forbid_pre = self.forbid_pre;
+        let attrs = match item {
+            Item::Mod(module) => Some(&mut module.attrs),
+            Item::Fn(function) => Some(&mut function.attrs),
+            Item::Impl(impl_block) => Some(&mut impl_block.attrs),
+            _ => None,
+        };
+        if let Some(span) = attrs.and_then(take_forbid_pre) {
+            self.enter_forbid_pre(span, matches!(item, Item::Mod(_)));
+        }
+        if let Some(reason_pattern) = match item {
+            Item::Fn(function) => reason_pattern_of(&function.attrs),
+            Item::Mod(module) => take_reason_pattern(&mut module.attrs),
+            Item::Impl(impl_block) => take_reason_pattern(&mut impl_block.attrs),
+            _ => None,
+        } {
+            self.reason_pattern = Some(reason_pattern);
+        }
+        if let Some(vocabulary) = match item {
+            Item::Fn(function) => vocabulary_of(&function.attrs),
+            Item::Mod(module) => take_vocabulary(&mut module.attrs),
+            Item::Impl(impl_block) => take_vocabulary(&mut impl_block.attrs),
+            _ => None,
+        } {
+            self.enter_vocabulary(vocabulary);
+        }
+
+        let outer_known_functions = match item {
+            Item::Mod(ItemMod {
+                attrs,
+                ident,
+                content: Some((_, items)),
+                ..
+            }) if self.known_functions.is_some() => {
+                let outer_alias_count = self.aliases.len();
+                self.aliases.extend(aliases_of(items));
+                let known_functions = known_functions_of(items, &self.aliases);
+
+                self.module_path.push(ident.to_string());
+                let outer_coverage = self.coverage.take();
+                if outer_coverage.is_some() {
+                    self.coverage = Some(Coverage::new(&self.module_path, &known_functions));
+                }
+
+                let exclusions = take_exclusions(attrs);
+                check_exclusions(&exclusions, items, &self.aliases);
+                let outer_exclusion_count = self.exclusions.len();
+                self.exclusions.extend(exclusions);
+
+                Some((
+                    self.known_functions.replace(known_functions),
+                    outer_coverage,
+                    outer_exclusion_count,
+                    outer_alias_count,
+                ))
+            }
+            _ => None,
+        };
+
+        if let (Item::Fn(function), Some(requirement)) = (&*item, self.assure_requirement()) {
+            check_unassured_calls(
+                &function.block,
+                &self.module_functions,
+                &self.module_path,
+                requirement,
+            );
+        }
+
+        let outer_impl_preconditions = match item {
+            Item::Impl(impl_block) => {
+                let preconditions = take_impl_preconditions(
+                    &mut impl_block.attrs,
+                    self.vocabulary.as_ref(),
+                    &self.aliases,
+                );
+
+                Some(std::mem::replace(
+                    &mut self.impl_preconditions,
+                    preconditions,
+                ))
+            }
+            _ => None,
+        };
+
         visit_item_mut(self, item);
 
+        if let Some(outer_impl_preconditions) = outer_impl_preconditions {
+            self.impl_preconditions = outer_impl_preconditions;
+        }
+        self.reason_pattern = outer_reason_pattern;
+        if let Some((
+            outer_known_functions,
+            outer_coverage,
+            outer_exclusion_count,
+            outer_alias_count,
+        )) = outer_known_functions
+        {
+            if let Some(coverage) = std::mem::replace(&mut self.coverage, outer_coverage) {
+                coverage.write();
+            }
+            self.known_functions = outer_known_functions;
+            self.exclusions.truncate(outer_exclusion_count);
+            self.aliases.truncate(outer_alias_count);
+            self.module_path.pop();
+        }
+
+        if let Item::Type(alias) = item {
+            if has_pre_attrs(alias) {
+                *item = Item::Verbatim(render_fn_pointer_alias(
+                    alias,
+                    None,
+                    self.vocabulary.as_ref(),
+                    &self.aliases,
+                ));
+            }
+        }
+
         if let Item::Fn(function) = item {
-            let rendered_function = render_function(function, None);
+            if cfg!(feature = "compile-fail-fixtures") {
+                if let Some(preconditions) =
+                    self.known_functions.as_ref().and_then(|known_functions| {
+                        known_functions.get(&function.sig.ident.to_string())
+                    })
+                {
+                    write_fixture(
+                        &self.module_path,
+                        &function.vis,
+                        &function.sig,
+                        preconditions,
+                    );
+                }
+            }
+
+            let rendered_function = render_function(
+                function,
+                None,
+                &[],
+                self.vocabulary.as_ref(),
+                &self.aliases,
+                &self.case_warnings,
+                false,
+            );
             *item = Item::Verbatim(rendered_function);
         }
+        self.vocabulary = outer_vocabulary;
+        self.forbid_pre = outer_forbid_pre;
+    }
+
+    fn visit_impl_item_mut(&mut self, impl_item: &mut ImplItem) {
+        let outer_reason_pattern = self.reason_pattern.clone();
+        let outer_vocabulary = self.vocabulary.clone();
+        let outer_forbid_pre = self.forbid_pre;
+        if let ImplItem::Method(method) = impl_item {
+            if let Some(reason_pattern) = reason_pattern_of(&method.attrs) {
+                self.reason_pattern = Some(reason_pattern);
+            }
+            if let Some(vocabulary) = vocabulary_of(&method.attrs) {
+                self.enter_vocabulary(vocabulary);
+            }
+            if let Some(span) = take_forbid_pre(&mut method.attrs) {
+                self.enter_forbid_pre(span, false);
+            }
+
+            if let Some(requirement) = self.assure_requirement() {
+                check_unassured_calls(
+                    &method.block,
+                    &self.module_functions,
+                    &self.module_path,
+                    requirement,
+                );
+            }
+        }
+
+        visit_impl_item_mut(self, impl_item);
+
+        self.reason_pattern = outer_reason_pattern;
+
+        // An `ItemFn` cannot represent `default` methods, so they keep their attributes and are
+        // rendered by their own `pre` attribute instead.
+        if let ImplItem::Method(method) = impl_item {
+            if let (Some(default), Some(precondition)) =
+                (method.defaultness, self.impl_preconditions.first())
+            {
+                emit_error!(
+                    default,
+                    "the preconditions of the `impl` block cannot be added to `default` methods";
+                    help = precondition.span() => "add the preconditions to the method itself instead"
+                );
+            }
+
+            if method.defaultness.is_none() {
+                let mut function = ItemFn {
+                    attrs: std::mem::take(&mut method.attrs),
+                    vis: method.vis.clone(),
+                    sig: method.sig.clone(),
+                    block: Box::new(method.block.clone()),
+                };
+
+                *impl_item = ImplItem::Verbatim(render_function(
+                    &mut function,
+                    None,
+                    &self.impl_preconditions,
+                    self.vocabulary.as_ref(),
+                    &self.aliases,
+                    &self.case_warnings,
+                    true,
+                ));
+            }
+        }
+        self.vocabulary = outer_vocabulary;
+        self.forbid_pre = outer_forbid_pre;
     }
 
     fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        if let Some(mut let_else) = LetElse::from_expr(expr) {
+            self.visit_expr_mut(&mut let_else.init);
+            self.visit_block_mut(&mut let_else.diverge);
+
+            if let Some(call_attrs) = remove_call_attributes(&mut let_else.attrs) {
+                render_expr(&mut let_else.init, call_attrs, &self.call_context());
+            }
+
+            *expr = let_else.into_expr();
+            return;
+        }
+
+        // Render nested expressions first. This way an attribute on a nested call, such as
+        // `inner` in `outer(#[assure(...)] inner(x))`, is already removed when the outer call is
+        // processed.
         visit_expr_mut(self, expr);
 
         if let Some(attrs) = attributes_of_expression(expr) {
             if let Some(call_attrs) = remove_call_attributes(attrs) {
-                render_expr(expr, call_attrs);
+                render_expr(expr, call_attrs, &self.call_context());
             }
         }
     }
@@ -142,44 +1062,128 @@ impl VisitMut for PreAttrVisitor {
 
         if let Some((_, expr)) = &mut local.init {
             if let Some(call_attrs) = remove_call_attributes(&mut local.attrs) {
-                render_expr(expr, call_attrs);
+                render_expr(expr, call_attrs, &self.call_context());
             }
         }
     }
+
+    fn visit_macro_mut(&mut self, mac: &mut Macro) {
+        // The arguments of macros are opaque tokens, so only the arguments that are known to be
+        // expressions are visited.
+        if let Some(mut arguments) = MacroArguments::of(mac) {
+            for argument in arguments.exprs_mut() {
+                self.visit_expr_mut(argument);
+            }
+
+            // Only replace the tokens if an attribute was rendered, so that all other macros
+            // are left exactly as they were written.
+            let tokens = quote! { #arguments };
+            if tokens.to_string() != mac.tokens.to_string() {
+                mac.tokens = tokens;
+            }
+        }
+    }
+}
+
+impl PreAttrVisitor {
+    /// Requires the calls in the currently visited item to be assured because of the `forbid_pre`
+    /// attribute at `span`.
+    ///
+    /// The functions with preconditions are only known inside of a module with a `pre` attribute,
+    /// so an error is emitted for other items.
+    fn enter_forbid_pre(&mut self, span: Span, is_module: bool) {
+        if self.known_functions.is_some() && (is_module || !self.module_path.is_empty()) {
+            self.forbid_pre = Some(span);
+        } else {
+            emit_error!(
+                span,
+                "`forbid_pre` can only be used inside of a module with a `pre` attribute";
+                help = "add `#[pre]` to the surrounding module, so that its functions with preconditions are known"
+            );
+        }
+    }
+
+    /// Returns what requires the calls in the currently visited item to be assured, if anything.
+    ///
+    /// With the `assoc-const` feature, the compiler cannot reject calls without `assure`
+    /// attributes, so they are always checked inside of modules with a `pre` attribute.
+    fn assure_requirement(&self) -> Option<AssureRequirement> {
+        match self.forbid_pre {
+            Some(span) => Some(AssureRequirement::ForbidPre(span)),
+            None if cfg!(feature = "assoc-const") && self.known_functions.is_some() => {
+                Some(AssureRequirement::AssocConst)
+            }
+            None => None,
+        }
+    }
+
+    /// Returns the context for rendering calls in the currently visited item.
+    fn call_context(&self) -> CallContext<'_> {
+        CallContext {
+            reason_pattern: self.reason_pattern.as_ref(),
+            known_functions: self.known_functions.as_ref(),
+            coverage: self.coverage.as_ref(),
+            exclusions: &self.exclusions,
+            case_warnings: Some(&self.case_warnings),
+        }
+    }
 }
 
 /// Renders the given function and applies all `pre` attributes to it.
-fn render_function(function: &mut ItemFn, first_attr: Option<PreAttr>) -> TokenStream {
+///
+/// If a vocabulary is given, the custom preconditions of the function are checked against it.
+/// `is_associated` specifies whether the function is defined in an `impl` block.
+fn render_function(
+    function: &mut ItemFn,
+    first_attr: Option<PreAttr>,
+    impl_preconditions: &[Precondition],
+    vocabulary: Option<&Vocabulary>,
+    aliases: &[PreconditionAlias],
+    case_warnings: &CaseWarnings,
+    is_associated: bool,
+) -> TokenStream {
     let first_attr_span = first_attr.as_ref().and_then(|attr| match attr {
         PreAttr::Empty => None,
         PreAttr::NoDoc(no_doc) => Some(no_doc.span()),
         PreAttr::NoDebugAssert(no_debug_assert) => Some(no_debug_assert.span()),
+        PreAttr::TrackCaller(track_caller) => Some(track_caller.span()),
+        PreAttr::ReasonPattern(reason_pattern) => Some(reason_pattern.lit.span()),
+        PreAttr::Exclusive(exclusive) => Some(exclusive.span()),
+        PreAttr::Vocabulary(_) => None,
+        PreAttr::Marker(marker) => Some(marker.lit.span()),
+        PreAttr::Alias(reference) => Some(reference.name.span()),
         PreAttr::Precondition(precondition) => Some(precondition.span()),
     });
 
-    let mut preconditions = Vec::new();
+    let mut declared_preconditions = Vec::new();
 
-    let mut render_docs = true;
+    let mut render_docs = cfg!(feature = "doc");
     let mut debug_assert = true;
+    let mut track_caller = None;
+    let mut marker_name = None;
 
     let mut handle_attr = |attr| match attr {
         PreAttr::Empty => (),
         PreAttr::NoDoc(_) => render_docs = false,
         PreAttr::NoDebugAssert(_) => debug_assert = false,
-        PreAttr::Precondition(precondition) => {
-            if let Precondition::Boolean(boolean_expr) = &precondition {
-                if let Expr::Path(p) = &**boolean_expr {
-                    if let (None, Some(ident)) = (&p.qself, p.path.get_ident()) {
-                        emit_error!(
-                            ident.span(),
-                            "keyword `{}` is not recognized by pre", ident;
-                            help = "if you wanted to use a boolean expression, try `{} == true`",
-                            ident
-                        );
-                    }
-                }
+        PreAttr::TrackCaller(keyword) => track_caller = Some(keyword.span),
+        // These are already handled while visiting the function.
+        PreAttr::ReasonPattern(_) | PreAttr::Vocabulary(_) => (),
+        PreAttr::Exclusive(exclusive) => emit_error!(
+            exclusive.span(),
+            "exclusive preconditions can only be declared for a module";
+            help = "add this attribute to a module with a `pre` attribute instead"
+        ),
+        PreAttr::Marker(marker) => marker_name = Some(marker.name),
+        PreAttr::Alias(reference) => {
+            for precondition in reference.expand(aliases) {
+                check_precondition(&precondition, vocabulary);
+                declared_preconditions.push(precondition)
             }
-            preconditions.push(precondition)
+        }
+        PreAttr::Precondition(precondition) => {
+            check_precondition(&precondition, vocabulary);
+            declared_preconditions.push(precondition)
         }
     };
 
@@ -193,42 +1197,792 @@ fn render_function(function: &mut ItemFn, first_attr: Option<PreAttr>) -> TokenS
         |parsed_attr: Parenthesized<PreAttr>, _span| handle_attr(parsed_attr.content),
     );
 
+    let preconditions = merge_impl_preconditions(declared_preconditions, impl_preconditions);
+
+    if cfg!(feature = "suggest-preconditions") {
+        suggest_preconditions(&function.attrs, &preconditions);
+    }
+
     let span = match (attr_span, first_attr_span) {
         (Some(attr_span), Some(first_attr_span)) => {
             attr_span.join(first_attr_span).unwrap_or_else(|| attr_span)
         }
         (Some(span), None) => span,
         (None, Some(span)) => span,
-        (None, None) => Span::call_site(), // Should never be the case for non-empty preconditions
+        (None, None) => impl_preconditions
+            .first()
+            .map(Spanned::span)
+            // Should never be the case for non-empty preconditions
+            .unwrap_or_else(Span::call_site),
     };
 
     if !preconditions.is_empty() {
         if render_docs {
-            function
-                .attrs
-                .push(generate_docs(&function.sig, &preconditions, None));
+            function.attrs.push(gate_attribute(generate_docs(
+                &function.sig,
+                &preconditions,
+                None,
+            )));
         }
 
         if debug_assert {
+            check_valid_ptr_idents(&function.sig, &preconditions);
+
+            if cfg!(feature = "runtime-checks") {
+                check_boolean_idents(&function.sig, &preconditions);
+            }
+
             for condition in preconditions.iter() {
-                if let Precondition::Boolean(expr) = condition {
-                    function.block.stmts.insert(
-                        0,
-                        parse2(quote_spanned! { expr.span()=>
-                            ::core::debug_assert!(
-                                #expr,
-                                "boolean precondition was wrongly assured: `{}`",
-                                ::core::stringify!(#expr)
-                            );
-                        })
-                        .expect("valid statement"),
-                    );
+                if let Some(statement) = render_runtime_check(
+                    condition,
+                    track_caller.is_some(),
+                    function.sig.constness.is_some(),
+                ) {
+                    function.block.stmts.insert(0, gate_statement(statement));
                 }
             }
         }
 
-        render_pre(preconditions, function, span)
+        for condition in preconditions.iter() {
+            if let Some(statement) = render_kind_check(condition) {
+                function.block.stmts.insert(0, statement);
+            }
+        }
+
+        if cfg!(feature = "tool-annotations") {
+            function
+                .block
+                .stmts
+                .insert(0, render_tool_annotation(&preconditions, span));
+        }
+
+        if cfg!(feature = "registry") {
+            function.block.stmts.insert(
+                0,
+                render_registration(&function.sig, &preconditions, is_associated, span),
+            );
+        }
+
+        if let Some(track_caller) = track_caller {
+            function
+                .attrs
+                .push(parse_quote_spanned! { track_caller=> #[track_caller] });
+        }
+
+        let mut preconditions = preconditions.into_vec();
+        check_valid_ptr_types(&function.sig, &mut preconditions);
+
+        if cfg!(feature = "verbose") {
+            emit_warning!(
+                function.sig.ident,
+                "{}",
+                generate_precondition_summary(&function.sig.ident, &preconditions);
+                note = "this is shown, because the `verbose` feature of `pre` is enabled"
+            );
+        }
+
+        let fuzzing_helper = if cfg!(feature = "fuzzing") {
+            render_fuzzing_helper(function, &preconditions, is_associated, span)
+        } else {
+            None
+        };
+
+        let precondition_list = render_precondition_list(function, &preconditions, span);
+
+        // A clashing name is reported and replaced by the default, to avoid follow-up errors.
+        let marker_name = marker_name.filter(|name| check_marker_name(&function.sig, name));
+
+        case_warnings.check(&preconditions);
+        let function = render_pre(preconditions, function, marker_name.as_ref(), span);
+
+        quote! {
+            #function
+            #fuzzing_helper
+            #precondition_list
+        }
     } else {
         quote! { #function }
     }
 }
+
+/// Emits errors for a declared precondition that pre does not handle as intended.
+///
+/// A single identifier is most likely a misspelled keyword and a custom precondition must be in
+/// the vocabulary, if one is specified.
+fn check_precondition(precondition: &Precondition, vocabulary: Option<&Vocabulary>) {
+    if let Precondition::Boolean(boolean_expr) = precondition {
+        if let Expr::Path(p) = &**boolean_expr {
+            if let (None, Some(ident)) = (&p.qself, p.path.get_ident()) {
+                emit_error!(
+                    ident.span(),
+                    "keyword `{}` is not recognized by pre", ident;
+                    help = "if you wanted to use a boolean expression, try `{} == true`",
+                    ident
+                );
+            }
+        }
+    }
+    if let Some(vocabulary) = vocabulary {
+        vocabulary.check(precondition);
+    }
+}
+
+/// Merges the preconditions declared on a method with the preconditions of its `impl` block.
+///
+/// Errors are emitted for preconditions that are declared more than once on the method. A
+/// precondition of the `impl` block that is also declared on the method is only listed once.
+fn merge_impl_preconditions(
+    declared_preconditions: Vec<Precondition>,
+    impl_preconditions: &[Precondition],
+) -> PreconditionList {
+    let mut preconditions = PreconditionList::new();
+    for duplicate in preconditions.merge(declared_preconditions) {
+        emit_duplicate_error(&preconditions, &duplicate);
+    }
+    preconditions.merge(impl_preconditions.iter().cloned());
+
+    preconditions
+}
+
+/// Emits an error for a precondition that is declared more than once.
+fn emit_duplicate_error(preconditions: &[Precondition], duplicate: &Precondition) {
+    let first = preconditions
+        .iter()
+        .find(|precondition| *precondition == duplicate)
+        .expect("duplicates are already in the list");
+
+    emit_error!(
+        duplicate.span(),
+        "the precondition `{}` is declared more than once", duplicate;
+        help = first.span() => "it is first declared here, try removing one of the attributes"
+    );
+}
+
+/// Removes the statement, unless the contracts are enabled.
+fn gate_statement(mut statement: Stmt) -> Stmt {
+    let gate = contracts_gate(statement.span());
+    let attrs = match &mut statement {
+        Stmt::Local(local) => Some(&mut local.attrs),
+        Stmt::Expr(expr) | Stmt::Semi(expr, _) => attributes_of_expression(expr),
+        Stmt::Item(_) => None,
+    };
+
+    if let (Some(attrs), Some(gate)) = (attrs, gate) {
+        attrs.push(gate);
+    }
+
+    statement
+}
+
+/// Renders a `debug_assert` statement checking the precondition at runtime, if possible.
+///
+/// Boolean, `non_null`, `in_range`, `relation` and `non_overlapping` preconditions are always
+/// checked. With the `both` feature enabled, `valid_ptr` preconditions are additionally checked to
+/// not be null. With the `runtime-checks` feature enabled, `nonzero`, `utf8` and `on_thread`
+/// preconditions are checked as well, as are `readable_len` and `writable_len` preconditions, whose
+/// pointer must not be null unless the length is zero.
+///
+/// Panics in a `const fn` cannot format their message, so the message is rendered as a single
+/// string literal there. The addresses of pointers are not known during constant evaluation, so
+/// `non_overlapping` preconditions are not checked in a `const fn`. Neither are `utf8` and
+/// `on_thread` preconditions, which are checked through helpers of the `pre` crate.
+fn render_runtime_check(
+    condition: &Precondition,
+    track_caller: bool,
+    constness: bool,
+) -> Option<Stmt> {
+    if constness
+        && matches!(
+            condition,
+            Precondition::NonOverlapping { .. }
+                | Precondition::Utf8 { .. }
+                | Precondition::OnThread { .. }
+        )
+    {
+        return None;
+    }
+
+    let (check, description, span, message) = runtime_check(condition)?;
+
+    let statement = if constness {
+        let description = condition.to_string().replace('{', "{{").replace('}', "}}");
+        let message = LitStr::new(&message.replace("{}", &description), span);
+
+        quote_spanned! { span=>
+            ::core::debug_assert!(#check, #message);
+        }
+    } else if track_caller {
+        quote_spanned! { span=>
+            ::core::debug_assert!(
+                #check,
+                "precondition `{}` violated at {}",
+                #description,
+                ::core::panic::Location::caller()
+            );
+        }
+    } else {
+        quote_spanned! { span=>
+            ::core::debug_assert!(#check, #message, #description);
+        }
+    };
+
+    Some(parse2(statement).expect("valid statement"))
+}
+
+/// Returns the parts of a runtime check for the precondition, if it can be checked at runtime.
+///
+/// The parts are the boolean check, an expression describing the precondition, the span of the
+/// check and the message used when the check fails.
+pub(crate) fn runtime_check(
+    condition: &Precondition,
+) -> Option<(TokenStream, TokenStream, Span, &'static str)> {
+    Some(match condition {
+        Precondition::Boolean(expr) => (
+            quote! { #expr },
+            quote_spanned! { expr.span()=> ::core::stringify!(#expr) },
+            expr.span(),
+            "boolean precondition was wrongly assured: `{}`",
+        ),
+        Precondition::ValidPtr { ident, .. } if cfg!(feature = "both") => {
+            let description = LitStr::new(&condition.to_string(), ident.span());
+
+            (
+                quote_spanned! { ident.span()=> !#ident.is_null() },
+                quote! { #description },
+                ident.span(),
+                "precondition was wrongly assured, the pointer is null: `{}`",
+            )
+        }
+        Precondition::NonNull { ident, .. } => {
+            let description = LitStr::new(&condition.to_string(), ident.span());
+
+            (
+                quote_spanned! { ident.span()=> !#ident.is_null() },
+                quote! { #description },
+                ident.span(),
+                "non_null precondition was wrongly assured: `{}`",
+            )
+        }
+        Precondition::NonZero { ident, .. } if cfg!(feature = "runtime-checks") => {
+            let description = LitStr::new(&condition.to_string(), ident.span());
+
+            (
+                quote_spanned! { ident.span()=> #ident != 0 },
+                quote! { #description },
+                ident.span(),
+                "nonzero precondition was wrongly assured: `{}`",
+            )
+        }
+        Precondition::Utf8 { ident, .. } if cfg!(feature = "runtime-checks") => {
+            let description = LitStr::new(&condition.to_string(), ident.span());
+            let crate_name = crate_name_ident(ident.span());
+
+            (
+                quote_spanned! { ident.span()=>
+                    ::#crate_name::__private::Utf8Bytes::is_utf8(&#ident)
+                },
+                quote! { #description },
+                ident.span(),
+                "utf8 precondition was wrongly assured: `{}`",
+            )
+        }
+        Precondition::OnThread { name, .. } if cfg!(feature = "runtime-checks") => {
+            let description = LitStr::new(&condition.to_string(), name.span());
+            let crate_name = crate_name_ident(name.span());
+
+            (
+                quote_spanned! { name.span()=>
+                    ::#crate_name::__private::is_on_thread(#name)
+                },
+                quote! { #description },
+                name.span(),
+                "on_thread precondition was wrongly assured: `{}`",
+            )
+        }
+        Precondition::AccessLen { ident, length, .. } if cfg!(feature = "runtime-checks") => {
+            let description = LitStr::new(&condition.to_string(), ident.span());
+            let length = match length {
+                Length::Literal(lit) => quote! { #lit },
+                Length::Ident(length) => quote! { #length },
+            };
+
+            (
+                quote_spanned! { ident.span()=> #length == 0 || !#ident.is_null() },
+                quote! { #description },
+                ident.span(),
+                "precondition was wrongly assured, the pointer is null: `{}`",
+            )
+        }
+        Precondition::InRange { ident, range, .. } => {
+            let description = LitStr::new(&condition.to_string(), ident.span());
+            let lower_bound = range
+                .from
+                .as_ref()
+                .map(|from| quote_spanned! { range.span()=> #from <= #ident });
+            let upper_bound = range.to.as_ref().map(|to| match range.limits {
+                RangeLimits::HalfOpen(_) => quote_spanned! { range.span()=> #ident < #to },
+                RangeLimits::Closed(_) => quote_spanned! { range.span()=> #ident <= #to },
+            });
+            let check = match (lower_bound, upper_bound) {
+                (Some(lower_bound), Some(upper_bound)) => {
+                    quote_spanned! { range.span()=> (#lower_bound) && (#upper_bound) }
+                }
+                (Some(bound), None) | (None, Some(bound)) => bound,
+                (None, None) => unreachable!("ranges without bounds are rejected while parsing"),
+            };
+            // A lower bound of `0` is useless for unsigned values, but still clearer to state.
+            let check = quote_spanned! { range.span()=>
+                {
+                    #[allow(unused_comparisons)]
+                    let in_range = #check;
+                    in_range
+                }
+            };
+
+            (
+                check,
+                quote! { #description },
+                ident.span(),
+                "in_range precondition was wrongly assured: `{}`",
+            )
+        }
+        Precondition::Relation {
+            operator,
+            left,
+            right,
+            ..
+        } => {
+            let description = LitStr::new(&condition.to_string(), operator.span());
+            let operator: BinOp = operator
+                .parse()
+                .expect("the operator is checked while parsing");
+
+            (
+                quote_spanned! { condition.span()=> #left #operator #right },
+                quote! { #description },
+                condition.span(),
+                "relation precondition was wrongly assured: `{}`",
+            )
+        }
+        Precondition::NonOverlapping {
+            first,
+            second,
+            count,
+            ..
+        } => {
+            let description = LitStr::new(&condition.to_string(), condition.span());
+
+            // The regions start at the pointers and have the same size, so they are disjoint
+            // exactly if the pointers are at least that size apart.
+            (
+                quote_spanned! { condition.span()=>
+                    {
+                        fn non_overlapping<T>(first: *const T, second: *const T, count: usize) -> bool {
+                            let size = ::core::mem::size_of::<T>().saturating_mul(count);
+                            let (first, second) = (first as usize, second as usize);
+
+                            first.max(second) - first.min(second) >= size
+                        }
+
+                        non_overlapping(#first, #second, #count)
+                    }
+                },
+                quote! { #description },
+                condition.span(),
+                "non_overlapping precondition was wrongly assured: `{}`",
+            )
+        }
+        _ => return None,
+    })
+}
+
+/// Renders a statement checking that the kind of the precondition exists, if it has one.
+///
+/// The check calls a function generated by `define_precondition_kind!`, so an undefined kind or a
+/// wrong number of arguments is reported by the compiler. It has no effect at runtime.
+fn render_kind_check(condition: &Precondition) -> Option<Stmt> {
+    match condition {
+        Precondition::Kind {
+            path, arguments, ..
+        } => {
+            let arguments = arguments.iter();
+
+            Some(parse_quote_spanned! { condition.span()=>
+                #path::__check_arguments(#(&#arguments),*);
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Checks that the types specified in `valid_ptr` preconditions match the parameter types.
+///
+/// Types that depend on generic parameters cannot be stringified the same way at the call site, so
+/// they are only checked here and not encoded in the marker.
+fn check_valid_ptr_types(signature: &Signature, preconditions: &mut [Precondition]) {
+    /// Checks if the tokens contain a lifetime or one of the given identifiers.
+    fn is_generic(tokens: TokenStream, generic_idents: &[String]) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Group(group) => is_generic(group.stream(), generic_idents),
+            TokenTree::Ident(ident) => generic_idents.contains(&ident.to_string()),
+            TokenTree::Punct(punct) => punct.as_char() == '\'',
+            TokenTree::Literal(_) => false,
+        })
+    }
+
+    let mut generic_idents: Vec<_> = signature
+        .generics
+        .type_params()
+        .map(|type_param| type_param.ident.to_string())
+        .collect();
+    generic_idents.push("Self".to_string());
+    generic_idents.push("impl".to_string());
+
+    for precondition in preconditions {
+        if let Precondition::ValidPtr {
+            ident,
+            ty: ty @ Some(_),
+            ..
+        } = precondition
+        {
+            let parameter_ty = signature.inputs.iter().find_map(|arg| match arg {
+                FnArg::Typed(PatType { pat, ty, .. }) => match &**pat {
+                    Pat::Ident(pat_ident) if pat_ident.ident == *ident => Some(ty),
+                    _ => None,
+                },
+                FnArg::Receiver(_) => None,
+            });
+            let parameter_ty = match parameter_ty {
+                Some(parameter_ty) => parameter_ty,
+                None => continue,
+            };
+            let specified_ty = &ty.as_ref().expect("matched `Some` above").1;
+
+            if quote! { #specified_ty }.to_string() != quote! { #parameter_ty }.to_string() {
+                emit_error!(
+                    specified_ty,
+                    "this type does not match the type of the parameter `{}`", ident;
+                    help = parameter_ty.span() => "the parameter is declared with this type"
+                );
+            }
+
+            if is_generic(quote! { #parameter_ty }, &generic_idents) {
+                *ty = None;
+            }
+        }
+    }
+}
+
+/// Checks that the name of the marker parameter is not already used by a parameter of the function.
+///
+/// Returns whether the name can be used.
+fn check_marker_name(signature: &Signature, marker_name: &Ident) -> bool {
+    if let Some(parameter) = parameter_idents(signature)
+        .into_iter()
+        .find(|parameter| *parameter == marker_name)
+    {
+        emit_error!(
+            marker_name,
+            "the marker parameter `{}` has the same name as a parameter of the function", marker_name;
+            help = "choose a different name for the marker parameter";
+            note = parameter.span() => "the parameter is declared here"
+        );
+
+        false
+    } else {
+        true
+    }
+}
+
+/// Returns the names of the parameters of the function that are bound to a single identifier.
+fn parameter_idents(signature: &Signature) -> Vec<&Ident> {
+    signature
+        .inputs
+        .iter()
+        .filter_map(|arg| match arg {
+            FnArg::Typed(PatType { pat, .. }) => match &**pat {
+                Pat::Ident(pat_ident) => Some(&pat_ident.ident),
+                _ => None,
+            },
+            FnArg::Receiver(_) => None,
+        })
+        .collect()
+}
+
+/// Returns a help message listing the parameters that a precondition could refer to.
+///
+/// `referrer` describes what refers to the parameters, such as "`valid_ptr`".
+fn parameter_help(parameters: &[&Ident], referrer: &str) -> String {
+    if parameters.is_empty() {
+        format!(
+            "this function has no parameters that {} could refer to",
+            referrer
+        )
+    } else {
+        let names: Vec<_> = parameters
+            .iter()
+            .map(|parameter| format!("`{}`", parameter))
+            .collect();
+
+        format!("valid parameter names are: {}", names.join(", "))
+    }
+}
+
+/// Checks that boolean preconditions only refer to the parameters of the function.
+///
+/// This is used with the `runtime-checks` feature, because boolean preconditions are checked at
+/// the start of the function body. Identifiers starting with an uppercase letter are assumed to be
+/// constants and are not checked. Expressions that may bind names in a way that is not tracked
+/// here are not checked at all, see `BindingFinder`.
+fn check_boolean_idents(signature: &Signature, preconditions: &[Precondition]) {
+    let parameters = parameter_idents(signature);
+    let has_receiver = signature.receiver().is_some();
+
+    for precondition in preconditions {
+        if let Precondition::Boolean(expr) = precondition {
+            if BindingFinder::binds_names(expr) {
+                continue;
+            }
+
+            let mut collector = IdentCollector(Vec::new());
+            collector.visit_expr_mut(&mut expr.clone());
+
+            for ident in collector.0 {
+                let name = ident.to_string();
+                let is_constant = name.starts_with(|c: char| c.is_uppercase());
+                let is_receiver = has_receiver && name == "self";
+
+                if is_constant || is_receiver || parameters.contains(&&ident) {
+                    continue;
+                }
+
+                emit_error!(
+                    ident,
+                    "`{}` is not a parameter of this function", ident;
+                    help = parameter_help(&parameters, "the precondition");
+                    note = "the `runtime-checks` feature requires boolean preconditions to only refer to parameters, because they are checked at the start of the function"
+                );
+            }
+        }
+    }
+}
+
+/// Finds the parts of an expression that `IdentCollector` cannot see through.
+///
+/// These are macros, whose arguments are not visited, and everything that binds names other than
+/// closure parameters of a single identifier: `let` statements, `if let` and `while let`
+/// expressions, `match` expressions, `for` loops, items in blocks and closure parameters with
+/// other patterns.
+struct BindingFinder(bool);
+
+impl BindingFinder {
+    /// Checks whether `expr` contains any of the parts that `IdentCollector` cannot see through.
+    fn binds_names(expr: &Expr) -> bool {
+        let mut finder = BindingFinder(false);
+        finder.visit_expr_mut(&mut expr.clone());
+
+        finder.0
+    }
+}
+
+impl VisitMut for BindingFinder {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Macro(_) | Expr::Let(_) | Expr::Match(_) | Expr::ForLoop(_) => self.0 = true,
+            Expr::Closure(closure)
+                if !closure.inputs.iter().all(|input| match input {
+                    Pat::Ident(_) => true,
+                    Pat::Type(PatType { pat, .. }) => matches!(&**pat, Pat::Ident(_)),
+                    _ => false,
+                }) =>
+            {
+                self.0 = true
+            }
+            _ => visit_expr_mut(self, expr),
+        }
+    }
+
+    fn visit_stmt_mut(&mut self, stmt: &mut Stmt) {
+        match stmt {
+            Stmt::Local(_) | Stmt::Item(_) => self.0 = true,
+            Stmt::Expr(expr) | Stmt::Semi(expr, _) => self.visit_expr_mut(expr),
+        }
+    }
+}
+
+/// Checks that all pointers in `valid_ptr`, `non_null`, `aligned`, `proper_align`, `init`,
+/// `exclusive` and `non_overlapping` preconditions refer to parameters of the function.
+///
+/// The same is checked for the count of `non_overlapping` preconditions, for the value and a
+/// named length of `len` and `min_len` preconditions, for the pointer and a named length of
+/// `readable_len` and `writable_len` preconditions, for the integer of `nonzero` preconditions,
+/// for the slice of `sorted` preconditions and for the bytes of `utf8` preconditions. This emits errors listing the available
+/// parameters, if that is not the case.
+fn check_valid_ptr_idents(signature: &Signature, preconditions: &[Precondition]) {
+    check_parameter_idents(&parameter_idents(signature), preconditions);
+}
+
+/// Checks that the identifiers of the preconditions checked by `check_valid_ptr_idents` are in
+/// `parameters`.
+fn check_parameter_idents(parameters: &[&Ident], preconditions: &[Precondition]) {
+    for precondition in preconditions {
+        let (keyword, idents) = match subject_idents_of(precondition) {
+            Some(subject_idents) => subject_idents,
+            None => continue,
+        };
+
+        for ident in idents {
+            if parameters.contains(&ident) {
+                continue;
+            }
+
+            emit_error!(
+                ident,
+                "`{}` is not a parameter of this function", ident;
+                help = parameter_help(parameters, &format!("`{}`", keyword))
+            );
+        }
+    }
+}
+
+/// Returns the keyword of the precondition and the identifiers it refers to.
+///
+/// `None` is returned for preconditions that don't refer to identifiers in a known way.
+pub(crate) fn subject_idents_of(
+    precondition: &Precondition,
+) -> Option<(&'static str, Vec<&Ident>)> {
+    Some(match precondition {
+        Precondition::ValidPtr { ident, .. } => ("valid_ptr", vec![ident]),
+        Precondition::NonNull { ident, .. } => ("non_null", vec![ident]),
+        Precondition::NonZero { ident, .. } => ("nonzero", vec![ident]),
+        Precondition::Sorted { ident, .. } => ("sorted", vec![ident]),
+        Precondition::Aligned { ident, .. } => ("aligned", vec![ident]),
+        Precondition::ProperAlign { ident, .. } => ("proper_align", vec![ident]),
+        Precondition::Initialized { ident, .. } => ("init", vec![ident]),
+        Precondition::Exclusive { ident, .. } => ("exclusive", vec![ident]),
+        Precondition::Utf8 { ident, .. } => ("utf8", vec![ident]),
+        Precondition::NonOverlapping {
+            first,
+            second,
+            count,
+            ..
+        } => ("non_overlapping", vec![first, second, count]),
+        Precondition::Len {
+            minimum,
+            ident,
+            length,
+            ..
+        } => {
+            let keyword = if *minimum { "min_len" } else { "len" };
+
+            match length {
+                Length::Ident(length) => (keyword, vec![ident, length]),
+                Length::Literal(_) => (keyword, vec![ident]),
+            }
+        }
+        Precondition::AccessLen {
+            writable,
+            ident,
+            length,
+            ..
+        } => {
+            let keyword = if *writable {
+                "writable_len"
+            } else {
+                "readable_len"
+            };
+
+            match length {
+                Length::Ident(length) => (keyword, vec![ident, length]),
+                Length::Literal(_) => (keyword, vec![ident]),
+            }
+        }
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, ItemImpl};
+
+    use super::*;
+
+    #[test]
+    fn impl_preconditions_are_taken_from_the_attributes() {
+        let mut impl_block: ItemImpl = parse_quote! {
+            #[pre("on the impl block")]
+            #[doc = "X"]
+            #[pre(x > 0)]
+            impl X {}
+        };
+        let expected: Vec<Precondition> =
+            vec![parse_quote! { "on the impl block" }, parse_quote! { x > 0 }];
+
+        assert_eq!(
+            take_impl_preconditions(&mut impl_block.attrs, None, &[]),
+            expected
+        );
+        assert_eq!(impl_block.attrs, [parse_quote! { #[doc = "X"] }]);
+    }
+
+    #[test]
+    fn impl_preconditions_are_merged_with_the_method() {
+        let declared: Vec<Precondition> =
+            vec![parse_quote! { "on the method" }, parse_quote! { "on both" }];
+        let impl_preconditions: Vec<Precondition> = vec![
+            parse_quote! { "on both" },
+            parse_quote! { "on the impl block" },
+        ];
+        let expected: Vec<Precondition> = vec![
+            parse_quote! { "on the method" },
+            parse_quote! { "on both" },
+            parse_quote! { "on the impl block" },
+        ];
+
+        assert_eq!(
+            merge_impl_preconditions(declared, &impl_preconditions).into_vec(),
+            expected
+        );
+        assert_eq!(
+            merge_impl_preconditions(Vec::new(), &impl_preconditions).into_vec(),
+            impl_preconditions
+        );
+    }
+
+    #[test]
+    fn exclusive_call_is_a_boolean_precondition() {
+        let attr: PreAttr = parse_quote! { exclusive(ptr) };
+        assert!(matches!(
+            attr,
+            PreAttr::Precondition(Precondition::Boolean(_))
+        ));
+
+        let attr: PreAttr = parse_quote! { exclusive(x > 0, x < 0) };
+        assert!(matches!(attr, PreAttr::Exclusive(_)));
+    }
+
+    #[test]
+    fn only_untracked_bindings_are_found() {
+        let tracked: Vec<Expr> = vec![
+            parse_quote! { x > LIMIT },
+            parse_quote! { values.iter().all(|value: &usize| *value < limit(x)) },
+            parse_quote! { if x > 0 { y > 0 } else { true } },
+        ];
+        let untracked: Vec<Expr> = vec![
+            parse_quote! { matches!(x, Some(_)) },
+            parse_quote! { pairs.iter().all(|(a, b)| a < b) },
+            parse_quote! { match x { Some(y) => y > 0, None => true } },
+            parse_quote! { if let Some(y) = x { y > 0 } else { true } },
+            parse_quote! { { let y = x; y > 0 } },
+            parse_quote! { { for y in x {} true } },
+        ];
+
+        for expr in tracked {
+            assert!(!BindingFinder::binds_names(&expr), "{:?}", expr);
+        }
+
+        for expr in untracked {
+            assert!(BindingFinder::binds_names(&expr), "{:?}", expr);
+        }
+    }
+}
@@ -5,26 +5,37 @@ use proc_macro_error::emit_error;
 use std::convert::TryInto;
 use syn::{spanned::Spanned, Block, Expr, Local, Stmt};
 
-use crate::call_handling::{render_call, CallAttributes};
+use crate::call_handling::{render_call, CallAttributes, CallContext};
 
 /// Renders the contained call in the given expression.
 ///
 /// This only works, if the call can be unambiguosly determined.
 /// Otherwise errors are printed.
-pub(crate) fn render_expr(expr: &mut Expr, attrs: CallAttributes) {
-    if let Some(expr) = extract_call_expr(expr) {
+pub(crate) fn render_expr(expr: &mut Expr, attrs: CallAttributes, context: &CallContext) {
+    if let Some(mut expr) = extract_call_expr(expr) {
+        if attrs.forward.is_none() && attrs.routed_forwards.is_empty() {
+            expr = call_in_constructor(expr);
+        }
+
         let call = expr
             .clone()
             .try_into()
             .expect("`extract_call_expr` should only return call expressions");
 
-        *expr = render_call(attrs, call);
+        *expr = render_call(attrs, call, context);
     } else {
+        let help = if is_operator_without_call(expr) {
+            "operators cannot be assured, try calling a method with the preconditions explicitly, such as `a.add(b)`"
+        } else if matches!(expr, Expr::Macro(_)) {
+            "the arguments of macros are not searched, try placing it on the call inside of the macro instead: `assert!(#[assure(...)] foo())`"
+        } else {
+            "try moving it closer to the call it should apply to"
+        };
         let emit_err = |span: Span| {
             emit_error!(
                 span,
                 "could not find an unambiguos call to apply this to";
-                help = "try moving it closer to the call it should apply to"
+                help = help
             )
         };
 
@@ -32,17 +43,92 @@ pub(crate) fn render_expr(expr: &mut Expr, attrs: CallAttributes) {
             emit_err(forward.span());
         }
 
+        for (forward, _) in attrs.routed_forwards {
+            emit_err(forward.span());
+        }
+
         for assure_attribute in attrs.assure_attributes {
             emit_err(assure_attribute.span());
         }
     }
 }
 
+/// The smart pointers whose constructors are looked through.
+const SMART_POINTERS: [&str; 3] = ["Box", "Rc", "Arc"];
+
+/// The enum variants whose constructors are looked through.
+const VARIANTS: [&str; 3] = ["Ok", "Err", "Some"];
+
+/// Returns the call inside of a constructor, if the expression constructs a value from a call.
+///
+/// The constructors of smart pointers and the enum variants never have preconditions, so for
+/// `Box::new(foo())` or `Ok(foo())`, the attributes are meant for `foo`. This is also the case for
+/// `return Ok(foo())`, because the attributes are applied to the returned expression. Nested
+/// constructors, such as `Ok(Box::new(foo()))`, are looked through as well. Other expressions are
+/// returned unchanged.
+pub(super) fn call_in_constructor(expr: &mut Expr) -> &mut Expr {
+    if !is_constructor_wrapping_call(expr) {
+        return expr;
+    }
+
+    match expr {
+        Expr::Call(call) => call_in_constructor(
+            extract_call_expr(&mut call.args[0]).expect("the constructor wraps a call"),
+        ),
+        _ => unreachable!("only calls are constructors"),
+    }
+}
+
+/// Checks whether the expression is a constructor wrapping a call.
+fn is_constructor_wrapping_call(expr: &Expr) -> bool {
+    let call = match expr {
+        Expr::Call(call) if call.args.len() == 1 => call,
+        _ => return false,
+    };
+    let is_constructor = match &*call.func {
+        Expr::Path(path) if path.qself.is_none() => {
+            let mut segments = path.path.segments.iter().rev();
+
+            match (segments.next(), segments.next()) {
+                (Some(function), Some(ty)) if function.ident == "new" => {
+                    SMART_POINTERS.iter().any(|name| ty.ident == name)
+                }
+                (Some(variant), _) => VARIANTS.iter().any(|name| variant.ident == name),
+                _ => false,
+            }
+        }
+        _ => false,
+    };
+
+    // `extract_call_expr` needs mutable access, so a copy of the argument is searched.
+    is_constructor && extract_call_expr(&mut call.args[0].clone()).is_some()
+}
+
+/// Checks whether the expression is an operator expression that doesn't contain a call.
+///
+/// Operators such as `a + b` or `a[i]` may be desugared to calls of trait methods, but there is
+/// no call expression that the precondition handling could be added to.
+fn is_operator_without_call(expr: &Expr) -> bool {
+    let mut operands = match expr {
+        Expr::Binary(expr) => vec![(*expr.left).clone(), (*expr.right).clone()],
+        Expr::AssignOp(expr) => vec![(*expr.left).clone(), (*expr.right).clone()],
+        Expr::Index(expr) => vec![(*expr.expr).clone(), (*expr.index).clone()],
+        Expr::Unary(expr) => vec![(*expr.expr).clone()],
+        Expr::Paren(expr) => return is_operator_without_call(&expr.expr),
+        Expr::Group(expr) => return is_operator_without_call(&expr.expr),
+        _ => return false,
+    };
+
+    operands
+        .iter_mut()
+        .all(|operand| extract_call_expr(operand).is_none())
+}
+
 /// Extracts an expression that is a valid call from the given expression.
 ///
 /// This may descend into nested expressions, if it would be obvious which nested expression is
 /// meant.
-fn extract_call_expr(expr: &mut Expr) -> Option<&mut Expr> {
+pub(super) fn extract_call_expr(expr: &mut Expr) -> Option<&mut Expr> {
     fn extract_from_block(block: &mut Block) -> Option<&mut Expr> {
         if block.stmts.len() == 1 {
             match &mut block.stmts[0] {
@@ -149,6 +235,82 @@ fn extract_call_expr(expr: &mut Expr) -> Option<&mut Expr> {
             TryBlock.block,
             Unsafe.block;
         manual:
-            Expr::Tuple(expr) if expr.elems.len() == 1 => extract_call_expr(&mut expr.elems[0]);
+            // A tuple is searched like a binary expression, so that the call is found in the
+            // initializer of `let (x, y) = (foo(), 0)`.
+            Expr::Tuple(expr) => extract_unique_call_expr(expr.elems.iter_mut()),
+            // For `if` and `if let`, the call in the condition is the most obvious target.
+            // The same is true for the scrutinee of `while let`.
+            Expr::If(expr) => extract_call_expr(&mut expr.cond),
+            Expr::While(expr) if matches!(*expr.cond, Expr::Let(_)) =>
+                extract_call_expr(&mut expr.cond);
+    }
+}
+
+/// Extracts the call from the only one of the given expressions that contains a call.
+///
+/// If multiple expressions contain a call, it is ambiguous which one is meant, so `None` is
+/// returned.
+fn extract_unique_call_expr<'a>(
+    exprs: impl IntoIterator<Item = &'a mut Expr>,
+) -> Option<&'a mut Expr> {
+    let mut found = None;
+
+    for expr in exprs {
+        if let Some(call) = extract_call_expr(expr) {
+            if found.is_some() {
+                return None;
+            }
+
+            found = Some(call);
+        }
+    }
+
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::{quote, ToTokens};
+    use syn::parse_quote;
+
+    use super::*;
+    use crate::{call::Call, helpers::add_marker_argument};
+
+    #[test]
+    fn method_call_after_try_is_extracted() {
+        let mut expr: Expr = parse_quote!(foo()?.bar(x));
+
+        let call = extract_call_expr(&mut expr).expect("contains a call");
+        let mut call: Call = call.clone().try_into().expect("is a call");
+        add_marker_argument(&mut call, parse_quote!(marker));
+
+        let expected = if cfg!(feature = "marker-first") {
+            quote!(foo()?.bar(marker, x))
+        } else {
+            quote!(foo()?.bar(x, marker))
+        };
+        assert_eq!(call.into_token_stream().to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn only_call_in_tuple_is_extracted() {
+        let mut expr: Expr = parse_quote!((foo(x), 0, y));
+        match extract_call_expr(&mut expr) {
+            Some(Expr::Call(call)) => assert_eq!(call.args.len(), 1),
+            _ => panic!("expected the call of `foo`"),
+        }
+
+        let mut expr: Expr = parse_quote!((foo(x), bar(y)));
+        assert!(extract_call_expr(&mut expr).is_none());
+    }
+
+    #[test]
+    fn try_after_method_call_is_traversed() {
+        let mut expr: Expr = parse_quote!(foo()?.bar(x)?);
+
+        match extract_call_expr(&mut expr) {
+            Some(Expr::MethodCall(call)) => assert_eq!(call.method, "bar"),
+            _ => panic!("expected the call of `bar`"),
+        }
     }
 }
@@ -0,0 +1,153 @@
+//! Handles `pre` attributes on type aliases of function pointers.
+//!
+//! A function pointer type cannot have preconditions that are checked, because the functions
+//! stored in its values are not known. The preconditions only document the contract that callers
+//! of such a function pointer must uphold:
+//!
+//! ```rust,ignore
+//! #[pre(valid_ptr(arg0, r))]
+//! type Callback = fn(*const u8);
+//! ```
+//!
+//! The parameters of a function pointer type usually have no names, so they are referred to by
+//! their position: `arg0` is the first parameter, `arg1` the second and so on. A parameter that
+//! has a name in the type can be referred to by that name as well.
+
+use proc_macro2::{Span, TokenStream};
+use proc_macro_error::emit_error;
+use quote::{format_ident, quote};
+use syn::{spanned::Spanned, BareFnArg, Ident, ItemType, Type};
+
+use super::{check_parameter_idents, check_precondition, emit_duplicate_error, PreAttr};
+use crate::{
+    documentation::generate_fn_pointer_docs,
+    helpers::{gate_attribute, is_attr, visit_matching_attrs_parsed, Parenthesized},
+    precondition::PreconditionList,
+    precondition_alias::PreconditionAlias,
+    vocabulary::Vocabulary,
+};
+
+/// Checks whether the type alias has any `pre` attributes.
+pub(super) fn has_pre_attrs(alias: &ItemType) -> bool {
+    alias.attrs.iter().any(|attr| is_attr("pre", attr))
+}
+
+/// Renders the type alias of a function pointer and documents the preconditions of its
+/// `pre` attributes.
+pub(super) fn render_fn_pointer_alias(
+    alias: &mut ItemType,
+    first_attr: Option<PreAttr>,
+    vocabulary: Option<&Vocabulary>,
+    aliases: &[PreconditionAlias],
+) -> TokenStream {
+    let mut declared_preconditions = Vec::new();
+    let mut render_docs = true;
+    let name = alias.ident.clone();
+
+    let mut handle_attr = |attr| match attr {
+        PreAttr::Empty => (),
+        PreAttr::NoDoc(_) => render_docs = false,
+        PreAttr::Precondition(precondition) => {
+            check_precondition(&precondition, vocabulary);
+            declared_preconditions.push(precondition)
+        }
+        PreAttr::Alias(reference) => {
+            for precondition in reference.expand(aliases) {
+                check_precondition(&precondition, vocabulary);
+                declared_preconditions.push(precondition)
+            }
+        }
+        PreAttr::NoDebugAssert(_)
+        | PreAttr::TrackCaller(_)
+        | PreAttr::ReasonPattern(_)
+        | PreAttr::Exclusive(_)
+        | PreAttr::Vocabulary(_)
+        | PreAttr::Marker(_) => emit_error!(
+            name,
+            "only preconditions and `no_doc` are supported on type aliases";
+            help = "the preconditions of a function pointer type only document its contract"
+        ),
+    };
+
+    if let Some(first_attr) = first_attr {
+        handle_attr(first_attr);
+    }
+
+    let attr_span = visit_matching_attrs_parsed(
+        &mut alias.attrs,
+        |attr| is_attr("pre", attr),
+        |parsed_attr: Parenthesized<PreAttr>, _span| handle_attr(parsed_attr.content),
+    );
+
+    let mut preconditions = PreconditionList::new();
+    for duplicate in preconditions.merge(declared_preconditions) {
+        emit_duplicate_error(&preconditions, &duplicate);
+    }
+
+    if preconditions.is_empty() {
+        return quote! { #alias };
+    }
+
+    let bare_fn = match &*alias.ty {
+        Type::BareFn(bare_fn) => bare_fn,
+        ty => {
+            emit_error!(
+                ty,
+                "preconditions are only supported on type aliases of function pointers";
+                help = "the type must be a function pointer type, such as `fn(*const u8)`"
+            );
+
+            return quote! { #alias };
+        }
+    };
+
+    let parameters = positional_parameters(bare_fn.inputs.iter());
+    check_parameter_idents(&parameters.iter().collect::<Vec<_>>(), &preconditions);
+
+    if render_docs {
+        let span = attr_span.unwrap_or_else(Span::call_site);
+        alias.attrs.push(gate_attribute(generate_fn_pointer_docs(
+            &preconditions,
+            span,
+        )));
+    }
+
+    quote! { #alias }
+}
+
+/// Returns the names that the parameters of a function pointer type can be referred to by.
+///
+/// Every parameter can be referred to by its position, such as `arg0`, and by its name, if it has
+/// one.
+fn positional_parameters<'a>(inputs: impl Iterator<Item = &'a BareFnArg>) -> Vec<Ident> {
+    let mut parameters = Vec::new();
+
+    for (index, input) in inputs.enumerate() {
+        parameters.push(format_ident!("arg{}", index, span = input.span()));
+
+        match &input.name {
+            Some((name, _)) if name != "_" => parameters.push(name.clone()),
+            _ => (),
+        }
+    }
+
+    parameters
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, TypeBareFn};
+
+    use super::*;
+
+    #[test]
+    fn parameters_are_named_by_position() {
+        let bare_fn: TypeBareFn = parse_quote! { fn(*const u8, len: usize, _: u32) };
+        let names: Vec<_> = positional_parameters(bare_fn.inputs.iter())
+            .iter()
+            .map(Ident::to_string)
+            .collect();
+
+        assert_eq!(names, ["arg0", "arg1", "len", "arg2"]);
+    }
+}
@@ -0,0 +1,284 @@
+//! Rejects calls of functions with preconditions that are not assured.
+//!
+//! This is enabled by a `forbid_pre` attribute on a module, a function or an `impl` block inside
+//! of a module with a `pre` attribute. With the `assoc-const` feature, it is enabled in all modules
+//! with a `pre` attribute, because the signatures of the functions are not changed, so the
+//! compiler cannot reject such calls.
+//!
+//! Only calls of functions that are defined in the item with the `pre` attribute are recognized.
+//! Their preconditions are known from the `pre` attributes of the definitions, so no other
+//! function is mistaken for one with preconditions, even if it has a parameter that looks like a
+//! marker. The paths of the calls are resolved relative to the surrounding module, so
+//! `inner::foo()` and `super::foo()` are recognized as well. Method calls, functions imported with
+//! `use` and functions of `extern_crate` modules are not recognized, because the function that is
+//! called cannot be known without type information or name resolution.
+
+use proc_macro2::Span;
+use proc_macro_error::emit_error;
+use std::{collections::HashMap, convert::TryInto};
+use syn::{
+    spanned::Spanned,
+    visit_mut::{visit_expr_mut, visit_local_mut, VisitMut},
+    Attribute, Block, Expr, Item, Local, Macro,
+};
+
+use super::{
+    expr_handling::{call_in_constructor, extract_call_expr},
+    macro_arguments::MacroArguments,
+};
+use crate::{
+    call::Call,
+    helpers::{attributes_of_expression, is_attr},
+    let_else::LetElse,
+    precondition::Precondition,
+};
+
+/// Removes the `forbid_pre` attributes from the given attributes and returns the span of the first.
+pub(super) fn take_forbid_pre(attrs: &mut Vec<Attribute>) -> Option<Span> {
+    let mut span = None;
+
+    attrs.retain(|attr| {
+        if !is_attr("forbid_pre", attr) {
+            return true;
+        }
+
+        if !attr.tokens.is_empty() {
+            emit_error!(attr.tokens, "`forbid_pre` does not take any arguments");
+        }
+        span.get_or_insert_with(|| attr.span());
+
+        false
+    });
+
+    span
+}
+
+/// What requires the calls of functions with preconditions to be assured.
+#[derive(Clone, Copy)]
+pub(super) enum AssureRequirement {
+    /// A `forbid_pre` attribute with the given span.
+    ForbidPre(Span),
+    /// The `assoc-const` feature, which keeps the signatures of the functions.
+    AssocConst,
+}
+
+/// The preconditions of the functions in the annotated item by module path and function name.
+///
+/// The module paths are relative to the annotated item.
+pub(super) type ModuleFunctions = HashMap<Vec<String>, HashMap<String, Vec<Precondition>>>;
+
+/// Emits an error for every call in the block of a known function with preconditions that is not
+/// assured.
+///
+/// `module_path` is the path of the module containing the block.
+pub(super) fn check_unassured_calls(
+    block: &Block,
+    module_functions: &ModuleFunctions,
+    module_path: &[String],
+    requirement: AssureRequirement,
+) {
+    // `extract_call_expr` needs mutable access, so a copy of the block is visited.
+    ForbidPreVisitor {
+        module_functions,
+        module_path,
+        requirement,
+    }
+    .visit_block_mut(&mut block.clone());
+}
+
+/// Returns the path of the module and the name of the function that the call refers to.
+///
+/// The path of the call is resolved relative to `module_path`. `None` is returned, if the call is
+/// not a call of a path, or if the path leaves the annotated item or starts at the crate root.
+fn resolve_call(call: &Call, module_path: &[String]) -> Option<(Vec<String>, String)> {
+    let path = call.path()?;
+    if path.qself.is_some() || path.path.leading_colon.is_some() {
+        return None;
+    }
+
+    let mut segments: Vec<_> = path.path.segments.iter().collect();
+    let function = segments.pop()?;
+    let mut resolved = module_path.to_vec();
+    for module in segments {
+        if !module.arguments.is_empty() {
+            return None;
+        }
+
+        if module.ident == "super" {
+            resolved.pop()?;
+        } else if module.ident == "crate" {
+            return None;
+        } else if module.ident != "self" {
+            resolved.push(module.ident.to_string());
+        }
+    }
+
+    Some((resolved, function.ident.to_string()))
+}
+
+/// Visits all calls in a block and checks that those with preconditions are assured.
+struct ForbidPreVisitor<'a> {
+    /// The preconditions of the functions in the annotated item.
+    module_functions: &'a ModuleFunctions,
+    /// The path of the module containing the block.
+    module_path: &'a [String],
+    /// What requires the calls to be assured.
+    requirement: AssureRequirement,
+}
+
+impl ForbidPreVisitor<'_> {
+    /// Visits an expression with `assure` or `forward` attributes.
+    ///
+    /// The call that the attributes apply to is assured, so only the expressions inside of it are
+    /// checked.
+    fn visit_assured_expr(&mut self, expr: &mut Expr) {
+        match extract_call_expr(expr) {
+            Some(call) => visit_expr_mut(self, call_in_constructor(call)),
+            // The `pre` attribute reports that the call could not be found.
+            None => visit_expr_mut(self, expr),
+        }
+    }
+
+    /// Emits an error, if the expression calls a known function with preconditions.
+    fn check_call(&self, expr: &Expr) {
+        let call: Call = match expr.clone().try_into() {
+            Ok(call) => call,
+            Err(_) => return,
+        };
+        let precondition = match resolve_call(&call, self.module_path)
+            .and_then(|(path, name)| self.module_functions.get(&path)?.get(&name)?.first())
+        {
+            Some(precondition) => precondition,
+            None => return,
+        };
+        // `resolve_call` only succeeds for calls of paths.
+        let ident = call
+            .path()
+            .map(|path| {
+                path.path
+                    .segments
+                    .iter()
+                    .map(|segment| segment.ident.to_string())
+                    .collect::<Vec<_>>()
+                    .join("::")
+            })
+            .unwrap_or_default();
+
+        match self.requirement {
+            AssureRequirement::ForbidPre(forbid_pre) => emit_error!(
+                call,
+                "the call of `{}` is not assured", ident;
+                note = precondition.span() => "`{}` has preconditions and calls of it must be assured here", ident;
+                help = forbid_pre => "`forbid_pre` is specified here";
+                help = "add an `assure` attribute for each precondition: `#[assure({}, reason = ...)]`", precondition
+            ),
+            AssureRequirement::AssocConst => emit_error!(
+                call,
+                "the call of `{}` is not assured", ident;
+                note = precondition.span() => "`{}` has preconditions, so calls of it must be assured", ident;
+                note = "the `assoc-const` feature of `pre` keeps the signature of `{}`, so the compiler cannot check this", ident;
+                help = "add an `assure` attribute for each precondition: `#[assure({}, reason = ...)]`", precondition
+            ),
+        }
+    }
+}
+
+/// Checks whether the attributes contain an `assure` or a `forward` attribute.
+fn has_call_attributes(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .any(|attr| is_attr("assure", attr) || is_attr("forward", attr))
+}
+
+impl VisitMut for ForbidPreVisitor<'_> {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        if let Some(mut let_else) = LetElse::from_expr(expr) {
+            if has_call_attributes(&let_else.attrs) {
+                self.visit_assured_expr(&mut let_else.init);
+            } else {
+                self.visit_expr_mut(&mut let_else.init);
+            }
+            self.visit_block_mut(&mut let_else.diverge);
+
+            return;
+        }
+
+        if matches!(attributes_of_expression(expr), Some(attrs) if has_call_attributes(attrs)) {
+            self.visit_assured_expr(expr);
+        } else {
+            self.check_call(expr);
+
+            visit_expr_mut(self, expr);
+        }
+    }
+
+    fn visit_local_mut(&mut self, local: &mut Local) {
+        match &mut local.init {
+            Some((_, expr)) if has_call_attributes(&local.attrs) => self.visit_assured_expr(expr),
+            _ => visit_local_mut(self, local),
+        }
+    }
+
+    fn visit_macro_mut(&mut self, mac: &mut Macro) {
+        // Only the macro arguments that are searched for `assure` attributes are checked. Calls in
+        // `pre_call!` and `pre_for_each!` are assured.
+        if let Some(mut arguments) = MacroArguments::of(mac) {
+            for argument in arguments.exprs_mut() {
+                self.visit_expr_mut(argument);
+            }
+        }
+    }
+
+    fn visit_item_mut(&mut self, _: &mut Item) {
+        // Nested items are checked when the `pre` attribute visits them.
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+    use syn::{parse_quote, Expr};
+
+    use super::*;
+
+    /// Resolves the call expression in the module `a::b`.
+    fn resolved(expr: Expr) -> Option<(Vec<String>, String)> {
+        let call: Call = expr.try_into().unwrap_or_else(|_| panic!("not a call"));
+
+        resolve_call(&call, &["a".to_string(), "b".to_string()])
+    }
+
+    #[test]
+    fn paths_are_resolved_relative_to_the_module() {
+        let path = |modules: &[&str], name: &str| {
+            Some((
+                modules.iter().map(ToString::to_string).collect(),
+                name.to_string(),
+            ))
+        };
+
+        assert_eq!(resolved(parse_quote! { foo() }), path(&["a", "b"], "foo"));
+        assert_eq!(
+            resolved(parse_quote! { self::foo::<u8>() }),
+            path(&["a", "b"], "foo")
+        );
+        assert_eq!(
+            resolved(parse_quote! { c::foo() }),
+            path(&["a", "b", "c"], "foo")
+        );
+        assert_eq!(resolved(parse_quote! { super::foo() }), path(&["a"], "foo"));
+        assert_eq!(
+            resolved(parse_quote! { super::super::c::foo() }),
+            path(&["c"], "foo")
+        );
+    }
+
+    #[test]
+    fn paths_outside_of_the_annotated_item_are_not_resolved() {
+        assert_eq!(resolved(parse_quote! { super::super::super::foo() }), None);
+        assert_eq!(resolved(parse_quote! { crate::foo() }), None);
+        assert_eq!(resolved(parse_quote! { ::c::foo() }), None);
+        assert_eq!(resolved(parse_quote! { <T>::foo() }), None);
+        assert_eq!(resolved(parse_quote! { x.foo() }), None);
+    }
+}
@@ -0,0 +1,161 @@
+//! Finds the arguments of macro invocations that can contain `assure` attributes.
+//!
+//! The arguments of a macro are opaque tokens, so calls in them are normally not visited. For the
+//! assertion macros of the standard library, the arguments are known to be expressions. With the
+//! `macro-arguments` feature, the arguments of all other macros are parsed as expressions as well,
+//! as far as that is possible.
+//!
+//! # Failure modes
+//!
+//! This is a best-effort approach, because the meaning of the tokens is only known to the macro:
+//!
+//! - Leading arguments are parsed as expressions, as long as each of them is followed by a comma or
+//!   the end of the input. All tokens from the first argument that is not an expression onwards
+//!   are kept as they are, so `matches!(foo(), Some(x) if x > 0)` visits `foo()`, but not the
+//!   pattern with its guard.
+//! - Macros whose arguments are not separated by commas, such as `vec![x; 4]`, are not visited at
+//!   all, because no argument is followed by a comma.
+//! - Tokens that parse as an expression, but are used differently by the macro, such as the
+//!   pattern `Some(_) | None` or the name of a generated item, are visited as well. This only
+//!   makes a difference, if they contain `pre`-related attributes.
+//! - The arguments of `macro_rules!`, `pre_call!` and `pre_for_each!` are never visited. The
+//!   calls in `pre_call!` and `pre_for_each!` are assured by the macros themselves.
+//! - The tokens of a macro are only replaced, if an attribute was rendered. All other macros are
+//!   left exactly as they were written.
+
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+use syn::{
+    parse::{ParseStream, Parser},
+    punctuated::Punctuated,
+    Expr, Macro, Token,
+};
+
+/// The macros of the standard library whose arguments are visited for `assure` attributes.
+pub(super) const ASSERTION_MACROS: &[&str] = &[
+    "assert",
+    "assert_eq",
+    "assert_ne",
+    "debug_assert",
+    "debug_assert_eq",
+    "debug_assert_ne",
+];
+
+/// The macros whose arguments are never visited, because they are not expressions or are handled
+/// by the macro itself.
+const OPAQUE_MACROS: &[&str] = &["macro_rules", "pre_call", "pre_for_each"];
+
+/// The arguments of a macro invocation that are visited for `assure` attributes.
+pub(super) struct MacroArguments {
+    /// The arguments that are expressions, each with the comma following it.
+    arguments: Vec<(Expr, Option<Token![,]>)>,
+    /// The tokens after the last argument that is an expression.
+    rest: TokenStream,
+}
+
+impl MacroArguments {
+    /// Returns the arguments of the macro that should be visited.
+    ///
+    /// `None` is returned, if the arguments of the macro are not visited.
+    pub(super) fn of(mac: &Macro) -> Option<MacroArguments> {
+        let name = &mac.path.segments.last()?.ident;
+
+        if ASSERTION_MACROS.iter().any(|assertion| name == assertion) {
+            let arguments = Punctuated::<Expr, Token![,]>::parse_terminated
+                .parse2(mac.tokens.clone())
+                .ok()?;
+
+            Some(MacroArguments {
+                arguments: arguments
+                    .into_pairs()
+                    .map(|pair| pair.into_tuple())
+                    .collect(),
+                rest: TokenStream::new(),
+            })
+        } else if cfg!(feature = "macro-arguments")
+            && !OPAQUE_MACROS.iter().any(|opaque| name == opaque)
+        {
+            let arguments = parse_leading_expressions.parse2(mac.tokens.clone()).ok()?;
+
+            if arguments.arguments.is_empty() {
+                None
+            } else {
+                Some(arguments)
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Returns the arguments that are expressions.
+    pub(super) fn exprs_mut(&mut self) -> impl Iterator<Item = &mut Expr> {
+        self.arguments.iter_mut().map(|(expr, _)| expr)
+    }
+}
+
+/// Parses as many leading expressions as possible and keeps the remaining tokens as they are.
+fn parse_leading_expressions(input: ParseStream) -> syn::Result<MacroArguments> {
+    let mut arguments = Vec::new();
+
+    while !input.is_empty() {
+        // The argument is checked on a fork first, so that the input stays at its start, if it
+        // is not an expression.
+        let fork = input.fork();
+        match fork.parse::<Expr>() {
+            Ok(_) if fork.is_empty() || fork.peek(Token![,]) => {}
+            _ => break,
+        }
+
+        arguments.push((input.parse()?, input.parse()?));
+    }
+
+    Ok(MacroArguments {
+        arguments,
+        rest: input.parse()?,
+    })
+}
+
+impl ToTokens for MacroArguments {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        for (expr, comma) in &self.arguments {
+            expr.to_tokens(tokens);
+            comma.to_tokens(tokens);
+        }
+
+        tokens.append_all(self.rest.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+
+    use super::*;
+
+    #[test]
+    fn leading_expressions_are_parsed() {
+        let arguments: MacroArguments = parse_leading_expressions
+            .parse2(quote! { foo(1), Some(x) if x > 0 })
+            .expect("valid tokens");
+
+        assert_eq!(arguments.arguments.len(), 1);
+        assert_eq!(
+            arguments.rest.to_string(),
+            quote! { Some(x) if x > 0 }.to_string()
+        );
+        assert_eq!(
+            quote! { #arguments }.to_string(),
+            quote! { foo(1), Some(x) if x > 0 }.to_string()
+        );
+    }
+
+    #[test]
+    fn arguments_without_commas_are_not_parsed() {
+        let arguments: MacroArguments = parse_leading_expressions
+            .parse2(quote! { foo(1); 4 })
+            .expect("valid tokens");
+
+        assert!(arguments.arguments.is_empty());
+        assert_eq!(arguments.rest.to_string(), quote! { foo(1); 4 }.to_string());
+    }
+}
@@ -0,0 +1,136 @@
+//! Defines the `pre_call` macro and how it is handled.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    visit_mut::VisitMut,
+    Expr, Token,
+};
+
+use crate::{
+    call_handling::{AssureAttr, CallAttributes, CallContext},
+    pre_attr::render_expr,
+    precondition::{CaseWarnings, Precondition},
+    strip::StripVisitor,
+};
+
+/// The custom keywords used in the `pre_call` macro.
+mod custom_keywords {
+    use syn::custom_keyword;
+
+    custom_keyword!(reason);
+}
+
+/// The input to a `pre_call` macro.
+pub(crate) struct PreCall {
+    /// The assurances that preconditions hold for the call.
+    assure_attributes: Vec<AssureAttr>,
+    /// The expression containing the call.
+    expr: Expr,
+}
+
+/// Parses the comma-separated assurances at the start of a macro input.
+///
+/// Everything up to the last comma-separated element is an assurance, but both preconditions and
+/// the following expressions can be arbitrary expressions. The `reason` after a precondition is
+/// used to tell them apart.
+pub(crate) fn parse_assurances(input: ParseStream) -> syn::Result<Vec<AssureAttr>> {
+    let mut assure_attributes = Vec::new();
+
+    loop {
+        let fork = input.fork();
+        let is_assurance = fork.parse::<Precondition>().is_ok()
+            && fork.peek(Token![,])
+            && fork.peek2(custom_keywords::reason);
+
+        if !is_assurance {
+            break;
+        }
+
+        let start_span = input.span();
+        let mut assure_attribute: AssureAttr = input.parse()?;
+        let span = start_span
+            .join(assure_attribute.span())
+            .unwrap_or_else(|| assure_attribute.span());
+        assure_attribute.set_span(span);
+        assure_attributes.push(assure_attribute);
+
+        input.parse::<Token![,]>()?;
+    }
+
+    Ok(assure_attributes)
+}
+
+/// Returns the span covering all assurances, or `fallback` if there are none.
+pub(crate) fn assurances_span(assure_attributes: &[AssureAttr], fallback: Span) -> Span {
+    match (assure_attributes.first(), assure_attributes.last()) {
+        (Some(first), Some(last)) => first
+            .span()
+            .join(last.span())
+            .unwrap_or_else(|| first.span()),
+        _ => fallback,
+    }
+}
+
+impl Parse for PreCall {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let assure_attributes = parse_assurances(input)?;
+
+        let expr = input.parse()?;
+
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+        }
+
+        if !input.is_empty() {
+            let msg = if assure_attributes.is_empty() {
+                "expected the end of the macro input, preconditions must be followed by `reason = \"<reason>\"`"
+            } else {
+                "expected the end of the macro input after the call expression"
+            };
+
+            return Err(input.error(msg));
+        }
+
+        Ok(PreCall {
+            assure_attributes,
+            expr,
+        })
+    }
+}
+
+impl PreCall {
+    /// Renders the call with the precondition handling added.
+    pub(crate) fn render(self) -> TokenStream {
+        let PreCall {
+            assure_attributes,
+            mut expr,
+        } = self;
+
+        if cfg!(feature = "strip") {
+            StripVisitor.visit_expr_mut(&mut expr);
+
+            return quote! { #expr };
+        }
+
+        let span = assurances_span(&assure_attributes, expr.span());
+
+        render_expr(
+            &mut expr,
+            CallAttributes {
+                span,
+                forward: None,
+                routed_forwards: Vec::new(),
+                assure_attributes,
+            },
+            &CallContext {
+                case_warnings: Some(&CaseWarnings::default()),
+                ..CallContext::default()
+            },
+        );
+
+        quote! { #expr }
+    }
+}
@@ -0,0 +1,136 @@
+//! Defines the `pre_for_each` macro and how it is handled.
+
+use proc_macro2::TokenStream;
+use proc_macro_error::emit_error;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    visit_mut::VisitMut,
+    Error, Expr, Pat, Token,
+};
+
+use crate::{
+    call_handling::{AssureAttr, CallAttributes, CallContext},
+    pre_attr::render_expr,
+    pre_call::{assurances_span, parse_assurances},
+    precondition::CaseWarnings,
+    strip::StripVisitor,
+};
+
+/// The input to a `pre_for_each` macro.
+pub(crate) struct PreForEach {
+    /// The assurances that preconditions hold for every call.
+    assure_attributes: Vec<AssureAttr>,
+    /// The expression evaluating to the items to iterate over.
+    iter: Expr,
+    /// The pattern binding each item.
+    pat: Pat,
+    /// The expression containing the call for each item.
+    body: Expr,
+}
+
+impl Parse for PreForEach {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let assure_attributes = parse_assurances(input)?;
+
+        let iter = input.parse()?;
+        input.parse::<Token![=>]>()?;
+
+        let closure = match input.parse()? {
+            Expr::Closure(closure) => closure,
+            expr => {
+                return Err(Error::new(
+                    expr.span(),
+                    "expected a closure with a single parameter, such as `|item| foo(item)`",
+                ))
+            }
+        };
+
+        if closure.inputs.len() != 1 {
+            return Err(Error::new(
+                closure.inputs.span(),
+                "expected a closure with a single parameter for the item",
+            ));
+        }
+
+        if closure.asyncness.is_some() || closure.movability.is_some() || closure.capture.is_some()
+        {
+            return Err(Error::new(
+                closure.span(),
+                "`async`, `static` and `move` closures are not supported here",
+            ));
+        }
+
+        let pat = match closure
+            .inputs
+            .into_iter()
+            .next()
+            .expect("the closure has a single parameter")
+        {
+            Pat::Type(typed) => {
+                emit_error!(
+                    typed.ty,
+                    "type annotations are not supported for the item";
+                    help = "the type of the item is inferred from the iterator"
+                );
+
+                *typed.pat
+            }
+            pat => pat,
+        };
+
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+        }
+
+        if !input.is_empty() {
+            return Err(input.error("expected the end of the macro input after the closure"));
+        }
+
+        Ok(PreForEach {
+            assure_attributes,
+            iter,
+            pat,
+            body: *closure.body,
+        })
+    }
+}
+
+impl PreForEach {
+    /// Renders the loop with the precondition handling added to the call.
+    pub(crate) fn render(self) -> TokenStream {
+        let PreForEach {
+            assure_attributes,
+            iter,
+            pat,
+            mut body,
+        } = self;
+
+        if cfg!(feature = "strip") {
+            StripVisitor.visit_expr_mut(&mut body);
+        } else {
+            let span = assurances_span(&assure_attributes, body.span());
+
+            render_expr(
+                &mut body,
+                CallAttributes {
+                    span,
+                    forward: None,
+                    routed_forwards: Vec::new(),
+                    assure_attributes,
+                },
+                &CallContext {
+                    case_warnings: Some(&CaseWarnings::default()),
+                    ..CallContext::default()
+                },
+            );
+        }
+
+        quote! {
+            for #pat in #iter {
+                #body;
+            }
+        }
+    }
+}
@@ -1,14 +1,18 @@
 //! Defines the different kinds of preconditions.
 
 use proc_macro2::Span;
+use proc_macro_error::emit_warning;
 use quote::quote;
-use std::{cmp::Ordering, fmt};
+use std::{
+    cell::RefCell, cmp::Ordering, collections::HashSet, fmt, iter::FromIterator, ops::Deref,
+};
 use syn::{
     parenthesized,
     parse::{Parse, ParseStream},
+    punctuated::Punctuated,
     spanned::Spanned,
     token::Paren,
-    Error, Expr, Ident, LitStr, Token,
+    Error, Expr, ExprRange, Ident, Lit, LitInt, LitStr, Path, RangeLimits, Token, Type, UnOp,
 };
 
 /// The custom keywords used by the precondition kinds.
@@ -16,6 +20,30 @@ mod custom_keywords {
     use syn::custom_keyword;
 
     custom_keyword!(valid_ptr);
+    custom_keyword!(see);
+    custom_keyword!(in_range);
+    custom_keyword!(kind);
+    custom_keyword!(relation);
+    custom_keyword!(locked);
+    custom_keyword!(same_provenance);
+    custom_keyword!(non_null);
+    custom_keyword!(aligned);
+    custom_keyword!(proper_align);
+    custom_keyword!(init);
+    custom_keyword!(initialized);
+    custom_keyword!(exclusive);
+    custom_keyword!(no_alias);
+    custom_keyword!(utf8);
+    custom_keyword!(on_thread);
+    custom_keyword!(non_overlapping);
+    custom_keyword!(len);
+    custom_keyword!(min_len);
+    custom_keyword!(readable_len);
+    custom_keyword!(writable_len);
+    custom_keyword!(nonzero);
+    custom_keyword!(sorted);
+    custom_keyword!(by);
+    custom_keyword!(condition);
     custom_keyword!(r);
     custom_keyword!(w);
 }
@@ -31,162 +59,1329 @@ pub(crate) enum Precondition {
         parentheses: Paren,
         /// The identifier of the pointer.
         ident: Ident,
-        /// The comma between the identifier and the read/write information.
-        _comma: Token![,],
+        /// The optionally specified type of the pointer.
+        ty: Option<(Token![:], Box<Type>)>,
+        /// The comma between the identifier and the read/write information, if it is specified.
+        _comma: Option<Token![,]>,
         /// Information on what accesses of the pointer must be valid.
+        ///
+        /// If it is not specified, both reads and writes must be valid.
         read_write: ReadWrite,
     },
     /// An expression that should evaluate to a boolean value.
+    ///
+    /// It can also be written as `condition = <expr>`, which results in the same precondition.
+    /// Unlike a parenthesized form, this cannot be confused with a call of a function named
+    /// `condition`.
     Boolean(Box<Expr>),
     /// A custom precondition that is spelled out in a string.
     Custom(LitStr),
+    /// A custom precondition that is described by the documentation of an item.
+    See {
+        /// The `see` keyword.
+        see_keyword: custom_keywords::see,
+        /// The `=` separating the `see` keyword and the path.
+        _eq: Token![=],
+        /// The path of the item describing the precondition.
+        path: LitStr,
+    },
+    /// Requires that the given value lies in a range.
+    InRange {
+        /// The `in_range` keyword.
+        in_range_keyword: custom_keywords::in_range,
+        /// The parentheses following the `in_range` keyword.
+        parentheses: Paren,
+        /// The identifier of the value.
+        ident: Ident,
+        /// The comma between the identifier and the range.
+        _comma: Token![,],
+        /// The range the value must lie in.
+        range: Box<ExprRange>,
+    },
+    /// A precondition of a kind defined with `define_precondition_kind!`.
+    Kind {
+        /// The `kind` keyword.
+        kind_keyword: custom_keywords::kind,
+        /// The `=` separating the `kind` keyword and the path.
+        _eq: Token![=],
+        /// The path of the kind.
+        path: Path,
+        /// The parentheses following the path.
+        parentheses: Paren,
+        /// The identifiers of the arguments of the kind.
+        arguments: Punctuated<Ident, Token![,]>,
+    },
+    /// Requires that two values are related by a comparison.
+    Relation {
+        /// The `relation` keyword.
+        relation_keyword: custom_keywords::relation,
+        /// The parentheses following the `relation` keyword.
+        parentheses: Paren,
+        /// The comparison operator as a string, one of `RELATION_OPERATORS`.
+        operator: LitStr,
+        /// The comma between the operator and the left identifier.
+        _first_comma: Token![,],
+        /// The identifier of the value on the left side of the comparison.
+        left: Ident,
+        /// The comma between the left and the right identifier.
+        _second_comma: Token![,],
+        /// The identifier of the value on the right side of the comparison.
+        right: Ident,
+    },
+    /// Requires that the lock protecting the given value is held.
+    Locked {
+        /// The `locked` keyword.
+        locked_keyword: custom_keywords::locked,
+        /// The parentheses following the `locked` keyword.
+        parentheses: Paren,
+        /// The identifier of the locked value.
+        ident: Ident,
+    },
+    /// Requires that two pointers are derived from the same allocation.
+    SameProvenance {
+        /// The `same_provenance` keyword.
+        same_provenance_keyword: custom_keywords::same_provenance,
+        /// The parentheses following the `same_provenance` keyword.
+        parentheses: Paren,
+        /// The identifier of the pointer that sorts first.
+        ///
+        /// The pointers are sorted while parsing, because the order in which they are written
+        /// doesn't matter.
+        first: Ident,
+        /// The comma between the identifiers.
+        _comma: Token![,],
+        /// The identifier of the pointer that sorts last.
+        second: Ident,
+    },
+    /// Requires that a pointer is not null.
+    NonNull {
+        /// The `non_null` keyword.
+        non_null_keyword: custom_keywords::non_null,
+        /// The parentheses following the `non_null` keyword.
+        parentheses: Paren,
+        /// The identifier of the pointer.
+        ident: Ident,
+    },
+    /// Requires that a pointer is properly aligned.
+    Aligned {
+        /// The `aligned` keyword.
+        aligned_keyword: custom_keywords::aligned,
+        /// The parentheses following the `aligned` keyword.
+        parentheses: Paren,
+        /// The identifier of the pointer.
+        ident: Ident,
+    },
+    /// Requires that a pointer is properly aligned for a given type.
+    ProperAlign {
+        /// The `proper_align` keyword.
+        proper_align_keyword: custom_keywords::proper_align,
+        /// The parentheses following the `proper_align` keyword.
+        parentheses: Paren,
+        /// The identifier of the pointer.
+        ident: Ident,
+        /// The comma between the identifier and the type.
+        _comma: Token![,],
+        /// The type whose alignment the pointer must have.
+        ty: Box<Type>,
+    },
+    /// Requires that a pointer points to initialized memory.
+    Initialized {
+        /// The span of the `init` or `initialized` keyword.
+        keyword_span: Span,
+        /// The parentheses following the keyword.
+        parentheses: Paren,
+        /// The identifier of the pointer.
+        ident: Ident,
+    },
+    /// Requires that a pointer is the only way to access the memory behind it.
+    Exclusive {
+        /// The span of the `exclusive` or `no_alias` keyword.
+        keyword_span: Span,
+        /// The parentheses following the keyword.
+        parentheses: Paren,
+        /// The identifier of the pointer.
+        ident: Ident,
+    },
+    /// Requires that bytes are valid UTF-8.
+    Utf8 {
+        /// The `utf8` keyword.
+        utf8_keyword: custom_keywords::utf8,
+        /// The parentheses following the `utf8` keyword.
+        parentheses: Paren,
+        /// The identifier of the bytes.
+        ident: Ident,
+    },
+    /// Requires that the function is called on a specific thread.
+    OnThread {
+        /// The `on_thread` keyword.
+        on_thread_keyword: custom_keywords::on_thread,
+        /// The parentheses following the `on_thread` keyword.
+        parentheses: Paren,
+        /// The name of the thread.
+        name: LitStr,
+    },
+    /// Requires that the memory regions behind two pointers do not overlap.
+    NonOverlapping {
+        /// The `non_overlapping` keyword.
+        non_overlapping_keyword: custom_keywords::non_overlapping,
+        /// The parentheses following the `non_overlapping` keyword.
+        parentheses: Paren,
+        /// The identifier of the pointer that sorts first.
+        ///
+        /// The pointers are sorted while parsing, because the order in which they are written
+        /// doesn't matter.
+        first: Ident,
+        /// The comma between the identifiers of the pointers.
+        _first_comma: Token![,],
+        /// The identifier of the pointer that sorts last.
+        second: Ident,
+        /// The comma between the second pointer and the count.
+        _second_comma: Token![,],
+        /// The identifier of the number of elements in each region.
+        count: Ident,
+    },
+    /// Requires that a value has a given length.
+    Len {
+        /// The span of the `len` or `min_len` keyword.
+        keyword_span: Span,
+        /// Whether the length is only a lower bound, as written with `min_len`.
+        minimum: bool,
+        /// The parentheses following the keyword.
+        parentheses: Paren,
+        /// The identifier of the value.
+        ident: Ident,
+        /// The comma between the identifier and the length.
+        _comma: Token![,],
+        /// The required length.
+        length: Length,
+    },
+    /// Requires that a number of elements behind a pointer can be read or written.
+    AccessLen {
+        /// The span of the `readable_len` or `writable_len` keyword.
+        keyword_span: Span,
+        /// Whether the elements must be writable, as written with `writable_len`.
+        writable: bool,
+        /// The parentheses following the keyword.
+        parentheses: Paren,
+        /// The identifier of the pointer.
+        ident: Ident,
+        /// The comma between the identifier and the length.
+        _comma: Token![,],
+        /// The number of elements that can be accessed.
+        length: Length,
+    },
+    /// Requires that an integer is not zero.
+    NonZero {
+        /// The `nonzero` keyword.
+        nonzero_keyword: custom_keywords::nonzero,
+        /// The parentheses following the `nonzero` keyword.
+        parentheses: Paren,
+        /// The identifier of the integer.
+        ident: Ident,
+    },
+    /// Requires that a slice is sorted.
+    Sorted {
+        /// The `sorted` keyword.
+        sorted_keyword: custom_keywords::sorted,
+        /// The parentheses following the `sorted` keyword.
+        parentheses: Paren,
+        /// The identifier of the slice.
+        ident: Ident,
+        /// The description of the order, if it is given with `by = "<order>"`.
+        ///
+        /// Without it, the slice must be sorted in ascending order.
+        by: Option<LitStr>,
+    },
+}
+
+/// The length required by a `len` or `min_len` precondition.
+#[derive(Clone)]
+pub(crate) enum Length {
+    /// A length given as an integer literal.
+    Literal(LitInt),
+    /// A length given by another value, such as a parameter.
+    Ident(Ident),
 }
 
+impl fmt::Display for Length {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Length::Literal(lit) => write!(f, "{}", lit.base10_digits()),
+            Length::Ident(ident) => write!(f, "{}", ident),
+        }
+    }
+}
+
+impl Parse for Length {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+
+        if lookahead.peek(LitInt) {
+            let lit: LitInt = input.parse()?;
+            lit.base10_parse::<usize>()?;
+
+            Ok(Length::Literal(lit))
+        } else if lookahead.peek(Ident) {
+            Ok(Length::Ident(input.parse()?))
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+/// The operators allowed in `relation` preconditions and how they are described in documentation.
+///
+/// The description should finish the sentence "`a` must be...`b`".
+pub(crate) const RELATION_OPERATORS: [(&str, &str); 6] = [
+    ("<", "less than"),
+    ("<=", "less than or equal to"),
+    (">", "greater than"),
+    (">=", "greater than or equal to"),
+    ("==", "equal to"),
+    ("!=", "not equal to"),
+];
+
 impl fmt::Display for Precondition {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            Precondition::ValidPtr {
+                ident,
+                ty: Some((_, ty)),
+                read_write,
+                ..
+            } => write!(
+                f,
+                "valid_ptr({}: {}, {})",
+                ident,
+                quote! { #ty },
+                read_write
+            ),
             Precondition::ValidPtr {
                 ident, read_write, ..
             } => write!(f, "valid_ptr({}, {})", ident.to_string(), read_write),
             Precondition::Boolean(expr) => write!(f, "{}", quote! { #expr }),
             Precondition::Custom(lit) => write!(f, "{:?}", lit.value()),
+            Precondition::See { path, .. } => write!(f, "see = {:?}", path.value()),
+            Precondition::InRange { ident, range, .. } => {
+                write!(f, "kind = in_range({}, {})", ident, quote! { #range })
+            }
+            Precondition::Kind {
+                path, arguments, ..
+            } => {
+                let arguments: Vec<_> = arguments.iter().map(|arg| arg.to_string()).collect();
+
+                write!(
+                    f,
+                    "kind = {}({})",
+                    path_to_string(path),
+                    arguments.join(", ")
+                )
+            }
+            Precondition::Relation {
+                operator,
+                left,
+                right,
+                ..
+            } => write!(
+                f,
+                "kind = relation({:?}, {}, {})",
+                operator.value(),
+                left,
+                right
+            ),
+            Precondition::Locked { ident, .. } => write!(f, "kind = locked({})", ident),
+            Precondition::SameProvenance { first, second, .. } => {
+                write!(f, "kind = same_provenance({}, {})", first, second)
+            }
+            Precondition::NonNull { ident, .. } => write!(f, "kind = non_null({})", ident),
+            Precondition::Aligned { ident, .. } => write!(f, "kind = aligned({})", ident),
+            Precondition::ProperAlign { ident, ty, .. } => {
+                write!(f, "kind = proper_align({}, {})", ident, quote! { #ty })
+            }
+            Precondition::Initialized { ident, .. } => write!(f, "kind = init({})", ident),
+            Precondition::Exclusive { ident, .. } => write!(f, "kind = exclusive({})", ident),
+            Precondition::Utf8 { ident, .. } => write!(f, "kind = utf8({})", ident),
+            Precondition::OnThread { name, .. } => {
+                write!(f, "kind = on_thread({:?})", name.value())
+            }
+            Precondition::NonOverlapping {
+                first,
+                second,
+                count,
+                ..
+            } => write!(
+                f,
+                "kind = non_overlapping({}, {}, {})",
+                first, second, count
+            ),
+            Precondition::Len {
+                minimum,
+                ident,
+                length,
+                ..
+            } => {
+                let keyword = if *minimum { "min_len" } else { "len" };
+
+                write!(f, "kind = {}({}, {})", keyword, ident, length)
+            }
+            Precondition::AccessLen {
+                writable,
+                ident,
+                length,
+                ..
+            } => {
+                let keyword = if *writable {
+                    "writable_len"
+                } else {
+                    "readable_len"
+                };
+
+                write!(f, "kind = {}({}, {})", keyword, ident, length)
+            }
+            Precondition::NonZero { ident, .. } => write!(f, "kind = nonzero({})", ident),
+            Precondition::Sorted {
+                ident, by: None, ..
+            } => write!(f, "kind = sorted({})", ident),
+            Precondition::Sorted {
+                ident,
+                by: Some(by),
+                ..
+            } => write!(f, "kind = sorted({}, by = {:?})", ident, by.value()),
         }
     }
 }
 
+impl fmt::Debug for Precondition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Precondition({})", self)
+    }
+}
+
 impl Parse for Precondition {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let start_span = input.span();
 
+        // The built-in kinds are written as `kind = <name>(...)`, so that a call of a function with
+        // the same name, such as `sorted(v)`, remains a boolean precondition.
+        let builtin_kind = input.peek(custom_keywords::kind) && input.peek2(Token![=]) && {
+            let fork = input.fork();
+            fork.parse::<custom_keywords::kind>()?;
+            fork.parse::<Token![=]>()?;
+
+            is_builtin_kind(&fork)
+        };
+        if builtin_kind {
+            input.parse::<custom_keywords::kind>()?;
+            input.parse::<Token![=]>()?;
+        }
+
         if input.peek(custom_keywords::valid_ptr) {
             let valid_ptr_keyword = input.parse()?;
             let content;
             let parentheses = parenthesized!(content in input);
-            let ident = content.parse()?;
-            let comma = content.parse()?;
-            let read_write = content.parse()?;
+            let ident: Ident = content.parse()?;
+            let ty = if content.peek(Token![:]) && !content.peek(Token![::]) {
+                Some((content.parse()?, content.parse()?))
+            } else {
+                None
+            };
+            let (comma, read_write) = if content.is_empty() {
+                (None, ReadWrite::both(ident.span()))
+            } else {
+                (Some(content.parse()?), content.parse()?)
+            };
 
             if content.is_empty() {
                 Ok(Precondition::ValidPtr {
                     valid_ptr_keyword,
                     parentheses,
                     ident,
+                    ty,
                     _comma: comma,
                     read_write,
                 })
             } else {
                 Err(content.error("unexpected token"))
             }
-        } else if input.peek(LitStr) {
-            Ok(Precondition::Custom(input.parse()?))
-        } else {
-            let expr = input.parse();
+        } else if builtin_kind && input.peek(custom_keywords::in_range) {
+            let in_range_keyword = input.parse()?;
+            let content;
+            let parentheses = parenthesized!(content in input);
+            let ident = content.parse()?;
+            let comma = content.parse()?;
+            let range = parse_range(&content)?;
 
-            match expr {
-                Ok(expr) => Ok(Precondition::Boolean(Box::new(expr))),
-                Err(mut err) => {
-                    err.combine(Error::new(
-                        start_span,
-                        "expected `valid_ptr`, a string literal or a boolean expression",
-                    ));
+            if content.is_empty() {
+                Ok(Precondition::InRange {
+                    in_range_keyword,
+                    parentheses,
+                    ident,
+                    _comma: comma,
+                    range: Box::new(range),
+                })
+            } else {
+                Err(content.error("unexpected token"))
+            }
+        } else if builtin_kind && input.peek(custom_keywords::relation) {
+            let relation_keyword = input.parse()?;
+            let content;
+            let parentheses = parenthesized!(content in input);
+            let operator: LitStr = content.parse()?;
 
-                    Err(err)
-                }
+            if !RELATION_OPERATORS
+                .iter()
+                .any(|(allowed, _)| *allowed == operator.value())
+            {
+                let allowed: Vec<_> = RELATION_OPERATORS
+                    .iter()
+                    .map(|(allowed, _)| format!("`{}`", allowed))
+                    .collect();
+
+                return Err(Error::new(
+                    operator.span(),
+                    format!(
+                        "unknown relation `{}`, expected one of {}",
+                        operator.value(),
+                        allowed.join(", ")
+                    ),
+                ));
             }
-        }
-    }
-}
 
-impl Spanned for Precondition {
-    fn span(&self) -> Span {
-        match self {
-            Precondition::ValidPtr {
-                valid_ptr_keyword,
-                parentheses,
-                ..
-            } => valid_ptr_keyword
-                .span()
-                .join(parentheses.span)
-                .unwrap_or_else(|| valid_ptr_keyword.span()),
-            Precondition::Boolean(expr) => expr.span(),
-            Precondition::Custom(lit) => lit.span(),
-        }
-    }
-}
+            let first_comma = content.parse()?;
+            let left = content.parse()?;
+            let second_comma = content.parse()?;
+            let right = content.parse()?;
 
-impl Precondition {
-    /// Returns a unique id for each descriminant.
-    fn descriminant_id(&self) -> usize {
-        match self {
-            Precondition::ValidPtr { .. } => 0,
-            Precondition::Boolean(_) => 1,
-            Precondition::Custom(_) => 2,
-        }
-    }
-}
+            if content.is_empty() {
+                Ok(Precondition::Relation {
+                    relation_keyword,
+                    parentheses,
+                    operator,
+                    _first_comma: first_comma,
+                    left,
+                    _second_comma: second_comma,
+                    right,
+                })
+            } else {
+                Err(content.error("unexpected token"))
+            }
+        } else if input.peek(custom_keywords::condition)
+            && input.peek2(Token![=])
+            && !input.peek2(Token![==])
+        {
+            input.parse::<custom_keywords::condition>()?;
+            input.parse::<Token![=]>()?;
 
-// Define an order for the preconditions here.
-//
-// The exact ordering does not really matter, as long as it is deterministic.
-impl Ord for Precondition {
-    fn cmp(&self, other: &Self) -> Ordering {
-        match (self, other) {
-            (
-                Precondition::ValidPtr {
-                    ident: ident_self, ..
-                },
-                Precondition::ValidPtr {
-                    ident: ident_other, ..
-                },
-            ) => ident_self.cmp(&ident_other),
-            (Precondition::Boolean(expr_self), Precondition::Boolean(expr_other)) => {
-                quote!(#expr_self)
-                    .to_string()
-                    .cmp(&quote!(#expr_other).to_string())
+            Ok(Precondition::Boolean(Box::new(input.parse()?)))
+        } else if builtin_kind && input.peek(custom_keywords::locked) {
+            let locked_keyword = input.parse()?;
+            let content;
+            let parentheses = parenthesized!(content in input);
+            let ident = content.parse()?;
+
+            if content.is_empty() {
+                Ok(Precondition::Locked {
+                    locked_keyword,
+                    parentheses,
+                    ident,
+                })
+            } else {
+                Err(content.error("unexpected token"))
             }
-            (Precondition::Custom(lit_self), Precondition::Custom(lit_other)) => {
-                lit_self.value().cmp(&lit_other.value())
+        } else if builtin_kind && input.peek(custom_keywords::non_null) {
+            let non_null_keyword = input.parse()?;
+            let content;
+            let parentheses = parenthesized!(content in input);
+            let ident = content.parse()?;
+
+            if content.is_empty() {
+                Ok(Precondition::NonNull {
+                    non_null_keyword,
+                    parentheses,
+                    ident,
+                })
+            } else {
+                Err(content.error("unexpected token"))
             }
-            _ => {
-                debug_assert_ne!(self.descriminant_id(), other.descriminant_id());
+        } else if builtin_kind
+            && (input.peek(custom_keywords::readable_len)
+                || input.peek(custom_keywords::writable_len))
+        {
+            let (keyword_span, writable) = if input.peek(custom_keywords::readable_len) {
+                (input.parse::<custom_keywords::readable_len>()?.span, false)
+            } else {
+                (input.parse::<custom_keywords::writable_len>()?.span, true)
+            };
+            let content;
+            let parentheses = parenthesized!(content in input);
+            let ident = content.parse()?;
+            let comma = content.parse()?;
+            let length = content.parse()?;
 
-                self.descriminant_id().cmp(&other.descriminant_id())
+            if content.is_empty() {
+                Ok(Precondition::AccessLen {
+                    keyword_span,
+                    writable,
+                    parentheses,
+                    ident,
+                    _comma: comma,
+                    length,
+                })
+            } else {
+                Err(content.error("unexpected token"))
             }
-        }
-    }
-}
+        } else if builtin_kind && input.peek(custom_keywords::nonzero) {
+            let nonzero_keyword = input.parse()?;
+            let content;
+            let parentheses = parenthesized!(content in input);
+            let ident = content.parse()?;
 
-impl PartialOrd for Precondition {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
+            if content.is_empty() {
+                Ok(Precondition::NonZero {
+                    nonzero_keyword,
+                    parentheses,
+                    ident,
+                })
+            } else {
+                Err(content.error("unexpected token"))
+            }
+        } else if builtin_kind && input.peek(custom_keywords::sorted) {
+            let sorted_keyword = input.parse()?;
+            let content;
+            let parentheses = parenthesized!(content in input);
+            let ident = content.parse()?;
+            let by = if content.is_empty() {
+                None
+            } else {
+                content.parse::<Token![,]>()?;
+                content.parse::<custom_keywords::by>()?;
+                content.parse::<Token![=]>()?;
+                let by: LitStr = content.parse()?;
 
-impl PartialEq for Precondition {
-    fn eq(&self, other: &Self) -> bool {
-        self.cmp(other) == Ordering::Equal
-    }
-}
+                if by.value().trim().is_empty() {
+                    return Err(Error::new(
+                        by.span(),
+                        "the order must be described, omit `by` for an ascending order",
+                    ));
+                }
 
-impl Eq for Precondition {}
+                Some(by)
+            };
 
-/// Whether something is readable, writable or both.
-#[derive(Clone)]
-pub(crate) enum ReadWrite {
-    /// The described thing is only readable.
-    Read {
-        /// The `r` keyword, indicating readability.
-        r_keyword: custom_keywords::r,
-    },
-    /// The described thing is only writable.
-    Write {
-        /// The `w` keyword, indicating writability.
-        w_keyword: custom_keywords::w,
-    },
-    /// The described thing is both readable and writable.
-    Both {
-        /// The `r` keyword, indicating readability.
-        r_keyword: custom_keywords::r,
+            if content.is_empty() {
+                Ok(Precondition::Sorted {
+                    sorted_keyword,
+                    parentheses,
+                    ident,
+                    by,
+                })
+            } else {
+                Err(content.error("unexpected token"))
+            }
+        } else if builtin_kind && input.peek(custom_keywords::aligned) {
+            let aligned_keyword = input.parse()?;
+            let content;
+            let parentheses = parenthesized!(content in input);
+            let ident = content.parse()?;
+
+            if content.is_empty() {
+                Ok(Precondition::Aligned {
+                    aligned_keyword,
+                    parentheses,
+                    ident,
+                })
+            } else {
+                Err(content.error("unexpected token"))
+            }
+        } else if builtin_kind && input.peek(custom_keywords::proper_align) {
+            let proper_align_keyword = input.parse()?;
+            let content;
+            let parentheses = parenthesized!(content in input);
+            let ident = content.parse()?;
+            let comma = content.parse()?;
+            let ty = content.parse()?;
+
+            if content.is_empty() {
+                Ok(Precondition::ProperAlign {
+                    proper_align_keyword,
+                    parentheses,
+                    ident,
+                    _comma: comma,
+                    ty,
+                })
+            } else {
+                Err(content.error("unexpected token"))
+            }
+        } else if builtin_kind
+            && (input.peek(custom_keywords::init) || input.peek(custom_keywords::initialized))
+        {
+            // `initialized` is accepted as a longer spelling of the same precondition.
+            let keyword_span = if input.peek(custom_keywords::init) {
+                input.parse::<custom_keywords::init>()?.span
+            } else {
+                input.parse::<custom_keywords::initialized>()?.span
+            };
+            let content;
+            let parentheses = parenthesized!(content in input);
+            let ident = content.parse()?;
+
+            if content.is_empty() {
+                Ok(Precondition::Initialized {
+                    keyword_span,
+                    parentheses,
+                    ident,
+                })
+            } else {
+                Err(content.error("unexpected token"))
+            }
+        } else if builtin_kind
+            && (input.peek(custom_keywords::exclusive) || input.peek(custom_keywords::no_alias))
+        {
+            // `no_alias` is accepted as another spelling of the same precondition.
+            let keyword_span = if input.peek(custom_keywords::exclusive) {
+                input.parse::<custom_keywords::exclusive>()?.span
+            } else {
+                input.parse::<custom_keywords::no_alias>()?.span
+            };
+            let content;
+            let parentheses = parenthesized!(content in input);
+            let ident = content.parse()?;
+
+            if content.is_empty() {
+                Ok(Precondition::Exclusive {
+                    keyword_span,
+                    parentheses,
+                    ident,
+                })
+            } else {
+                Err(content.error("unexpected token"))
+            }
+        } else if builtin_kind && input.peek(custom_keywords::utf8) {
+            let utf8_keyword = input.parse()?;
+            let content;
+            let parentheses = parenthesized!(content in input);
+            let ident = content.parse()?;
+
+            if content.is_empty() {
+                Ok(Precondition::Utf8 {
+                    utf8_keyword,
+                    parentheses,
+                    ident,
+                })
+            } else {
+                Err(content.error("unexpected token"))
+            }
+        } else if builtin_kind && input.peek(custom_keywords::on_thread) {
+            let on_thread_keyword = input.parse()?;
+            let content;
+            let parentheses = parenthesized!(content in input);
+            let name = content.parse()?;
+
+            if content.is_empty() {
+                Ok(Precondition::OnThread {
+                    on_thread_keyword,
+                    parentheses,
+                    name,
+                })
+            } else {
+                Err(content.error("unexpected token"))
+            }
+        } else if builtin_kind && input.peek(custom_keywords::same_provenance) {
+            let same_provenance_keyword = input.parse()?;
+            let content;
+            let parentheses = parenthesized!(content in input);
+            let first: Ident = content.parse()?;
+            let comma = content.parse()?;
+            let second: Ident = content.parse()?;
+
+            if !content.is_empty() {
+                return Err(content.error("unexpected token"));
+            }
+
+            if first == second {
+                return Err(Error::new(
+                    second.span(),
+                    format!("`{}` trivially has the same provenance as itself", second),
+                ));
+            }
+
+            let (first, second) = if first <= second {
+                (first, second)
+            } else {
+                (second, first)
+            };
+
+            Ok(Precondition::SameProvenance {
+                same_provenance_keyword,
+                parentheses,
+                first,
+                _comma: comma,
+                second,
+            })
+        } else if builtin_kind && input.peek(custom_keywords::non_overlapping) {
+            let non_overlapping_keyword = input.parse()?;
+            let content;
+            let parentheses = parenthesized!(content in input);
+            let first: Ident = content.parse()?;
+            let first_comma = content.parse()?;
+            let second: Ident = content.parse()?;
+            let second_comma = content.parse()?;
+            let count = content.parse()?;
+
+            if !content.is_empty() {
+                return Err(content.error("unexpected token"));
+            }
+
+            if first == second {
+                return Err(Error::new(
+                    second.span(),
+                    format!("the memory behind `{}` always overlaps with itself", second),
+                ));
+            }
+
+            let (first, second) = if first <= second {
+                (first, second)
+            } else {
+                (second, first)
+            };
+
+            Ok(Precondition::NonOverlapping {
+                non_overlapping_keyword,
+                parentheses,
+                first,
+                _first_comma: first_comma,
+                second,
+                _second_comma: second_comma,
+                count,
+            })
+        } else if builtin_kind
+            && (input.peek(custom_keywords::len) || input.peek(custom_keywords::min_len))
+        {
+            let (keyword_span, minimum) = if input.peek(custom_keywords::len) {
+                (input.parse::<custom_keywords::len>()?.span, false)
+            } else {
+                (input.parse::<custom_keywords::min_len>()?.span, true)
+            };
+            let content;
+            let parentheses = parenthesized!(content in input);
+            let ident = content.parse()?;
+            let comma = content.parse()?;
+            let length = content.parse()?;
+
+            if content.is_empty() {
+                Ok(Precondition::Len {
+                    keyword_span,
+                    minimum,
+                    parentheses,
+                    ident,
+                    _comma: comma,
+                    length,
+                })
+            } else {
+                Err(content.error("unexpected token"))
+            }
+        } else if input.peek(custom_keywords::see) && input.peek2(Token![=]) {
+            Ok(Precondition::See {
+                see_keyword: input.parse()?,
+                _eq: input.parse()?,
+                path: input.parse()?,
+            })
+        } else if input.peek(custom_keywords::kind) && input.peek2(Token![=]) {
+            let kind_keyword = input.parse()?;
+            let eq = input.parse()?;
+            let path = Path::parse_mod_style(input)?;
+
+            if !input.peek(Paren) {
+                return Err(input.error(
+                    "expected the arguments of the precondition kind in parentheses, such as `kind = non_zero(x)`",
+                ));
+            }
+
+            let content;
+            let parentheses = parenthesized!(content in input);
+            let arguments = content.parse_terminated(Ident::parse)?;
+
+            Ok(Precondition::Kind {
+                kind_keyword,
+                _eq: eq,
+                path,
+                parentheses,
+                arguments,
+            })
+        } else if input.peek(LitStr) {
+            Ok(Precondition::Custom(input.parse()?))
+        } else {
+            let expr = input.parse();
+
+            match expr {
+                Ok(expr) => Ok(Precondition::Boolean(Box::new(expr))),
+                Err(mut err) => {
+                    err.combine(Error::new(
+                        start_span,
+                        "expected `valid_ptr`, `kind = non_null`, `kind = aligned`, `kind = proper_align`, `kind = init`, `kind = exclusive`, `kind = utf8`, `kind = on_thread`, `kind = in_range`, `kind = relation`, `kind = locked`, `kind = same_provenance`, `kind = non_overlapping`, `kind = len`, `kind = min_len`, `kind = readable_len`, `kind = writable_len`, `kind = nonzero`, `kind = sorted`, `see`, `kind`, a string literal or a boolean expression",
+                    ));
+
+                    Err(err)
+                }
+            }
+        }
+    }
+}
+
+/// Checks if the input starts with a built-in kind that is written as `kind = <name>(...)`.
+///
+/// The `kind =` is expected to be parsed already.
+fn is_builtin_kind(input: ParseStream) -> bool {
+    input.peek2(Paren)
+        && (input.peek(custom_keywords::sorted)
+            || input.peek(custom_keywords::in_range)
+            || input.peek(custom_keywords::relation)
+            || input.peek(custom_keywords::locked)
+            || input.peek(custom_keywords::same_provenance)
+            || input.peek(custom_keywords::non_null)
+            || input.peek(custom_keywords::aligned)
+            || input.peek(custom_keywords::init)
+            || input.peek(custom_keywords::initialized)
+            || input.peek(custom_keywords::non_overlapping)
+            || input.peek(custom_keywords::len)
+            || input.peek(custom_keywords::min_len)
+            || input.peek(custom_keywords::nonzero)
+            || input.peek(custom_keywords::proper_align)
+            || input.peek(custom_keywords::readable_len)
+            || input.peek(custom_keywords::writable_len)
+            || input.peek(custom_keywords::exclusive)
+            || input.peek(custom_keywords::no_alias)
+            || input.peek(custom_keywords::utf8)
+            || input.peek(custom_keywords::on_thread))
+}
+
+impl Spanned for Precondition {
+    fn span(&self) -> Span {
+        match self {
+            Precondition::ValidPtr {
+                valid_ptr_keyword,
+                parentheses,
+                ..
+            } => valid_ptr_keyword
+                .span()
+                .join(parentheses.span)
+                .unwrap_or_else(|| valid_ptr_keyword.span()),
+            Precondition::Boolean(expr) => expr.span(),
+            Precondition::Custom(lit) => lit.span(),
+            Precondition::See {
+                see_keyword, path, ..
+            } => see_keyword
+                .span
+                .join(path.span())
+                .unwrap_or(see_keyword.span),
+            Precondition::InRange {
+                in_range_keyword,
+                parentheses,
+                ..
+            } => in_range_keyword
+                .span
+                .join(parentheses.span)
+                .unwrap_or(in_range_keyword.span),
+            Precondition::Kind {
+                kind_keyword,
+                parentheses,
+                ..
+            } => kind_keyword
+                .span
+                .join(parentheses.span)
+                .unwrap_or(kind_keyword.span),
+            Precondition::Relation {
+                relation_keyword,
+                parentheses,
+                ..
+            } => relation_keyword
+                .span
+                .join(parentheses.span)
+                .unwrap_or(relation_keyword.span),
+            Precondition::Locked {
+                locked_keyword,
+                parentheses,
+                ..
+            } => locked_keyword
+                .span
+                .join(parentheses.span)
+                .unwrap_or(locked_keyword.span),
+            Precondition::SameProvenance {
+                same_provenance_keyword,
+                parentheses,
+                ..
+            } => same_provenance_keyword
+                .span
+                .join(parentheses.span)
+                .unwrap_or(same_provenance_keyword.span),
+            Precondition::NonNull {
+                non_null_keyword,
+                parentheses,
+                ..
+            } => non_null_keyword
+                .span
+                .join(parentheses.span)
+                .unwrap_or(non_null_keyword.span),
+            Precondition::Aligned {
+                aligned_keyword,
+                parentheses,
+                ..
+            } => aligned_keyword
+                .span
+                .join(parentheses.span)
+                .unwrap_or(aligned_keyword.span),
+            Precondition::ProperAlign {
+                proper_align_keyword,
+                parentheses,
+                ..
+            } => proper_align_keyword
+                .span
+                .join(parentheses.span)
+                .unwrap_or(proper_align_keyword.span),
+            Precondition::Initialized {
+                keyword_span,
+                parentheses,
+                ..
+            } => keyword_span.join(parentheses.span).unwrap_or(*keyword_span),
+            Precondition::Exclusive {
+                keyword_span,
+                parentheses,
+                ..
+            } => keyword_span.join(parentheses.span).unwrap_or(*keyword_span),
+            Precondition::Utf8 {
+                utf8_keyword,
+                parentheses,
+                ..
+            } => utf8_keyword
+                .span
+                .join(parentheses.span)
+                .unwrap_or(utf8_keyword.span),
+            Precondition::OnThread {
+                on_thread_keyword,
+                parentheses,
+                ..
+            } => on_thread_keyword
+                .span
+                .join(parentheses.span)
+                .unwrap_or(on_thread_keyword.span),
+            Precondition::NonOverlapping {
+                non_overlapping_keyword,
+                parentheses,
+                ..
+            } => non_overlapping_keyword
+                .span
+                .join(parentheses.span)
+                .unwrap_or(non_overlapping_keyword.span),
+            Precondition::Len {
+                keyword_span,
+                parentheses,
+                ..
+            } => keyword_span.join(parentheses.span).unwrap_or(*keyword_span),
+            Precondition::AccessLen {
+                keyword_span,
+                parentheses,
+                ..
+            } => keyword_span.join(parentheses.span).unwrap_or(*keyword_span),
+            Precondition::NonZero {
+                nonzero_keyword,
+                parentheses,
+                ..
+            } => nonzero_keyword
+                .span
+                .join(parentheses.span)
+                .unwrap_or(nonzero_keyword.span),
+            Precondition::Sorted {
+                sorted_keyword,
+                parentheses,
+                ..
+            } => sorted_keyword
+                .span
+                .join(parentheses.span)
+                .unwrap_or(sorted_keyword.span),
+        }
+    }
+}
+
+impl Precondition {
+    /// Returns a unique id for each descriminant.
+    ///
+    /// Preconditions are sorted by this id first. The resulting order is documented, so that
+    /// markers can be constructed by hand, which means that it must not be changed. New kinds get
+    /// the next free id, so that they are sorted after the existing ones.
+    fn descriminant_id(&self) -> usize {
+        match self {
+            Precondition::ValidPtr { .. } => 0,
+            Precondition::Boolean(_) => 1,
+            Precondition::Custom(_) => 2,
+            Precondition::See { .. } => 3,
+            Precondition::InRange { .. } => 4,
+            Precondition::Kind { .. } => 5,
+            Precondition::Relation { .. } => 6,
+            Precondition::Locked { .. } => 7,
+            Precondition::SameProvenance { .. } => 8,
+            Precondition::NonNull { .. } => 9,
+            Precondition::Aligned { .. } => 10,
+            Precondition::Initialized { .. } => 11,
+            Precondition::NonOverlapping { .. } => 12,
+            Precondition::Len { .. } => 13,
+            Precondition::NonZero { .. } => 14,
+            Precondition::Sorted { .. } => 15,
+            Precondition::ProperAlign { .. } => 16,
+            Precondition::AccessLen { .. } => 17,
+            Precondition::Exclusive { .. } => 18,
+            Precondition::Utf8 { .. } => 19,
+            Precondition::OnThread { .. } => 20,
+        }
+    }
+
+    /// Returns the text that identifies the precondition.
+    ///
+    /// This is the rendered precondition, except that the text of custom preconditions is
+    /// normalized with [`custom_text`].
+    fn canonical_text(&self) -> String {
+        match self {
+            Precondition::Custom(lit) => format!("{:?}", custom_text(lit)),
+            _ => self.to_string(),
+        }
+    }
+}
+
+/// A list of preconditions that contains every precondition only once.
+///
+/// Two preconditions are considered the same, if they are equal in the sense of `PartialEq`,
+/// which is the same comparison that is used to match assured preconditions to the declared ones.
+/// The preconditions are kept in the order in which they were first added.
+#[derive(Clone, Default)]
+pub(crate) struct PreconditionList {
+    /// The preconditions in the list.
+    preconditions: Vec<Precondition>,
+}
+
+impl PreconditionList {
+    /// Creates an empty list.
+    pub(crate) fn new() -> PreconditionList {
+        PreconditionList::default()
+    }
+
+    /// Adds all preconditions of `other` that are not yet in this list.
+    ///
+    /// The preconditions of `other` that were dropped as duplicates are returned. This includes
+    /// preconditions that occur more than once in `other` itself.
+    pub(crate) fn merge(
+        &mut self,
+        other: impl IntoIterator<Item = Precondition>,
+    ) -> Vec<Precondition> {
+        let mut duplicates = Vec::new();
+
+        for precondition in other {
+            if self.contains(&precondition) {
+                duplicates.push(precondition);
+            } else {
+                self.preconditions.push(precondition);
+            }
+        }
+
+        duplicates
+    }
+
+    /// Returns the preconditions in the list.
+    pub(crate) fn into_vec(self) -> Vec<Precondition> {
+        self.preconditions
+    }
+}
+
+impl Deref for PreconditionList {
+    type Target = [Precondition];
+
+    fn deref(&self) -> &Self::Target {
+        &self.preconditions
+    }
+}
+
+impl IntoIterator for PreconditionList {
+    type Item = Precondition;
+    type IntoIter = std::vec::IntoIter<Precondition>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.preconditions.into_iter()
+    }
+}
+
+impl FromIterator<Precondition> for PreconditionList {
+    fn from_iter<I: IntoIterator<Item = Precondition>>(iter: I) -> Self {
+        let mut list = PreconditionList::new();
+        list.merge(iter);
+
+        list
+    }
+}
+
+/// Renders a path as it is written, without any spaces around the `::` separators.
+pub(crate) fn path_to_string(path: &Path) -> String {
+    let segments: Vec<_> = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect();
+    let prefix = if path.leading_colon.is_some() {
+        "::"
+    } else {
+        ""
+    };
+
+    format!("{}{}", prefix, segments.join("::"))
+}
+
+/// Parses the range of an `in_range` precondition.
+///
+/// Ranges with integer literals as bounds are checked to not be reversed or empty.
+fn parse_range(input: ParseStream) -> syn::Result<ExprRange> {
+    /// Returns the value of an integer literal bound, if it is one.
+    fn int_value(expr: &Expr) -> Option<i128> {
+        match expr {
+            Expr::Lit(lit) => match &lit.lit {
+                Lit::Int(int) => int.base10_parse().ok(),
+                _ => None,
+            },
+            Expr::Unary(unary) => match unary.op {
+                UnOp::Neg(_) => int_value(&unary.expr).map(|value: i128| -value),
+                _ => None,
+            },
+            Expr::Paren(paren) => int_value(&paren.expr),
+            _ => None,
+        }
+    }
+
+    let range = match input.parse()? {
+        Expr::Range(range) => range,
+        expr => {
+            return Err(Error::new(
+                expr.span(),
+                "expected a range, such as `0..10` or `0..=10`",
+            ))
+        }
+    };
+
+    if range.from.is_none() && range.to.is_none() {
+        return Err(Error::new(
+            range.span(),
+            "a range without any bounds does not restrict the value",
+        ));
+    }
+
+    if let (Some(from), Some(to)) = (&range.from, &range.to) {
+        if let (Some(start), Some(end)) = (int_value(from), int_value(to)) {
+            match range.limits {
+                RangeLimits::HalfOpen(_) if start == end => {
+                    return Err(Error::new(
+                        range.span(),
+                        format!("the range is empty, try `{}..={}`", start, end),
+                    ))
+                }
+                _ if start > end => {
+                    return Err(Error::new(
+                        range.span(),
+                        "the range is reversed, so no value lies in it",
+                    ))
+                }
+                _ => (),
+            }
+        }
+    }
+
+    Ok(range)
+}
+
+/// Returns the text that identifies a custom precondition.
+///
+/// With the `case-insensitive-custom` feature enabled, the text is converted to lower case, so that
+/// custom preconditions that only differ in case are considered equal.
+pub(crate) fn custom_text(lit: &LitStr) -> String {
+    if cfg!(feature = "case-insensitive-custom") {
+        lit.value().to_lowercase()
+    } else {
+        lit.value()
+    }
+}
+
+/// The custom preconditions that were already warned about while expanding one attribute.
+///
+/// A precondition can be rendered multiple times, such as for every location of a forwarded call,
+/// so the warning is only emitted for the first time.
+#[derive(Default)]
+pub(crate) struct CaseWarnings {
+    /// The locations and texts of the literals that were warned about.
+    warned: RefCell<HashSet<String>>,
+}
+
+impl CaseWarnings {
+    /// Warns once for each custom precondition whose text differs from the originally written text.
+    pub(crate) fn check(&self, preconditions: &[Precondition]) {
+        for precondition in preconditions {
+            if let Precondition::Custom(lit) = precondition {
+                let text = custom_text(lit);
+
+                if text != lit.value()
+                    && self
+                        .warned
+                        .borrow_mut()
+                        .insert(format!("{:?} {:?}", lit.span(), text))
+                {
+                    emit_warning!(
+                        lit,
+                        "this custom precondition is treated as {:?}", text;
+                        help = "the `case-insensitive-custom` feature of `pre` is enabled, consider writing it in lower case"
+                    );
+                }
+            }
+        }
+    }
+}
+
+// Define an order for the preconditions here.
+//
+// The preconditions are ordered by their kind first and then by their canonical text, so two
+// preconditions are equal exactly if they are rendered the same way. This makes the order
+// independent of how the preconditions are written and of the order they are written in.
+impl Ord for Precondition {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.descriminant_id()
+            .cmp(&other.descriminant_id())
+            .then_with(|| self.canonical_text().cmp(&other.canonical_text()))
+    }
+}
+
+impl PartialOrd for Precondition {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Precondition {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Precondition {}
+
+/// Whether something is readable, writable or both.
+#[derive(Clone)]
+pub(crate) enum ReadWrite {
+    /// The described thing is only readable.
+    Read {
+        /// The `r` keyword, indicating readability.
+        r_keyword: custom_keywords::r,
+    },
+    /// The described thing is only writable.
+    Write {
+        /// The `w` keyword, indicating writability.
+        w_keyword: custom_keywords::w,
+    },
+    /// The described thing is both readable and writable.
+    Both {
+        /// The `r` keyword, indicating readability.
+        r_keyword: custom_keywords::r,
         /// The `+` between the `r` and the `w`, if both are present.
         _plus: Token![+],
         /// The `w` keyword, indicating writability.
@@ -194,146 +1389,1396 @@ pub(crate) enum ReadWrite {
     },
 }
 
-impl ReadWrite {
-    /// Generates a short description suitable for usage in generated documentation.
-    ///
-    /// The generated description should finish the sentence
-    /// "The pointer must be valid for...".
-    pub(crate) fn doc_description(&self) -> &str {
-        match self {
-            ReadWrite::Read { .. } => "reads",
-            ReadWrite::Write { .. } => "writes",
-            ReadWrite::Both { .. } => "reads and writes",
+impl ReadWrite {
+    /// Creates the information that both reads and writes must be valid.
+    ///
+    /// This is used when no read/write information was specified.
+    fn both(span: Span) -> ReadWrite {
+        ReadWrite::Both {
+            r_keyword: custom_keywords::r(span),
+            _plus: Token![+](span),
+            w_keyword: custom_keywords::w(span),
+        }
+    }
+
+    /// Generates a short description suitable for usage in generated documentation.
+    ///
+    /// The generated description should finish the sentence
+    /// "The pointer must be valid for...".
+    pub(crate) fn doc_description(&self) -> &str {
+        match self {
+            ReadWrite::Read { .. } => "reads",
+            ReadWrite::Write { .. } => "writes",
+            ReadWrite::Both { .. } => "reads and writes",
+        }
+    }
+}
+
+impl fmt::Display for ReadWrite {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReadWrite::Read { .. } => write!(f, "r"),
+            ReadWrite::Write { .. } => write!(f, "w"),
+            ReadWrite::Both { .. } => write!(f, "r+w"),
+        }
+    }
+}
+
+impl Parse for ReadWrite {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+
+        if lookahead.peek(custom_keywords::w) {
+            Ok(ReadWrite::Write {
+                w_keyword: input.parse()?,
+            })
+        } else if lookahead.peek(custom_keywords::r) {
+            let r_keyword = input.parse()?;
+
+            if input.peek(Token![+]) {
+                let plus = input.parse()?;
+                let w_keyword = input.parse()?;
+
+                Ok(ReadWrite::Both {
+                    r_keyword,
+                    _plus: plus,
+                    w_keyword,
+                })
+            } else {
+                Ok(ReadWrite::Read { r_keyword })
+            }
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+impl Spanned for ReadWrite {
+    fn span(&self) -> Span {
+        match self {
+            ReadWrite::Read { r_keyword } => r_keyword.span,
+            ReadWrite::Write { w_keyword } => w_keyword.span,
+            ReadWrite::Both {
+                r_keyword,
+                w_keyword,
+                ..
+            } => r_keyword
+                .span
+                .join(w_keyword.span)
+                .unwrap_or_else(|| r_keyword.span),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::TokenStream;
+    use quote::quote;
+    use syn::parse2;
+
+    use super::*;
+
+    #[test]
+    fn parse_correct_custom() {
+        let result: Result<Precondition, _> = parse2(quote! {
+            "foo"
+        });
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn parse_correct_valid_ptr() {
+        {
+            let result: Result<Precondition, _> = parse2(quote! {
+                valid_ptr(foo, r)
+            });
+            assert!(result.is_ok());
+        }
+
+        {
+            let result: Result<Precondition, _> = parse2(quote! {
+                valid_ptr(foo, r+w)
+            });
+            assert!(result.is_ok());
+        }
+
+        {
+            let result: Result<Precondition, _> = parse2(quote! {
+                valid_ptr(foo, w)
+            });
+            assert!(result.is_ok());
+        }
+    }
+
+    #[test]
+    fn valid_ptr_defaults_to_reads_and_writes() {
+        let precondition = |tokens| parse2::<Precondition>(tokens).expect("valid precondition");
+
+        assert_eq!(
+            precondition(quote! { valid_ptr(foo) }).to_string(),
+            precondition(quote! { valid_ptr(foo, r+w) }).to_string()
+        );
+        assert_eq!(
+            precondition(quote! { valid_ptr(foo: *const u8) }).to_string(),
+            "valid_ptr(foo: * const u8, r+w)"
+        );
+
+        // The default is spelled out, so that the output can be parsed again.
+        let default = precondition(quote! { valid_ptr(foo) });
+        assert_eq!(
+            format!("{:?}", default),
+            "Precondition(valid_ptr(foo, r+w))"
+        );
+        let reparsed: Precondition =
+            syn::parse_str(&default.to_string()).expect("valid precondition");
+        assert_eq!(reparsed.to_string(), default.to_string());
+    }
+
+    #[test]
+    fn parse_correct_valid_ptr_with_type() {
+        {
+            let result: Result<Precondition, _> = parse2(quote! {
+                valid_ptr(foo: *const u8, r)
+            });
+            assert!(result.is_ok());
+        }
+
+        {
+            let result: Result<Precondition, _> = parse2(quote! {
+                valid_ptr(foo: *mut ::core::ffi::c_void, r+w)
+            });
+            assert!(result.is_ok());
+        }
+    }
+
+    #[test]
+    fn valid_ptr_type_is_compared() {
+        let precondition = |tokens| parse2::<Precondition>(tokens).expect("valid precondition");
+
+        assert!(
+            precondition(quote! { valid_ptr(foo: *const u8, r) })
+                == precondition(quote! { valid_ptr(foo: *const u8, r) })
+        );
+        assert!(
+            precondition(quote! { valid_ptr(foo: *const u8, r) })
+                != precondition(quote! { valid_ptr(foo: *const u16, r) })
+        );
+        assert!(
+            precondition(quote! { valid_ptr(foo: *const u8, r) })
+                != precondition(quote! { valid_ptr(foo, r) })
+        );
+    }
+
+    #[test]
+    fn valid_ptr_access_is_compared() {
+        let precondition = |tokens| parse2::<Precondition>(tokens).expect("valid precondition");
+
+        assert!(
+            precondition(quote! { valid_ptr(p, r) }) != precondition(quote! { valid_ptr(p, w) })
+        );
+        assert!(
+            precondition(quote! { valid_ptr(p) }) == precondition(quote! { valid_ptr(p, r+w) })
+        );
+
+        let preconditions = list(&[quote! { valid_ptr(p, r) }, quote! { valid_ptr(p, w) }]);
+        assert_eq!(
+            rendered(&preconditions),
+            ["valid_ptr(p, r)", "valid_ptr(p, w)"]
+        );
+    }
+
+    #[test]
+    fn parse_correct_see() {
+        let result: Result<Precondition, _> = parse2(quote! {
+            see = "crate::safety::rules"
+        });
+        assert!(matches!(result, Ok(Precondition::See { .. })));
+    }
+
+    #[test]
+    fn condition_is_a_boolean_precondition() {
+        let precondition = |tokens| parse2::<Precondition>(tokens).expect("valid precondition");
+
+        assert!(matches!(
+            precondition(quote! { condition = x > 0 }),
+            Precondition::Boolean(_)
+        ));
+        assert_eq!(
+            precondition(quote! { condition = x > 0 }),
+            precondition(quote! { x > 0 })
+        );
+        assert_eq!(
+            precondition(quote! { condition = x > 0 }).to_string(),
+            "x > 0"
+        );
+
+        // Calls of functions named `condition` and comparisons with a value named `condition`
+        // keep their meaning.
+        assert!(matches!(
+            precondition(quote! { condition(x > 0) }),
+            Precondition::Boolean(expr) if matches!(*expr, Expr::Call(_))
+        ));
+        assert!(matches!(
+            precondition(quote! { condition == x }),
+            Precondition::Boolean(expr) if matches!(*expr, Expr::Binary(_))
+        ));
+
+        for tokens in [quote! { condition = }, quote! { condition = x > 0, y > 0 }] {
+            let result: Result<Precondition, _> = parse2(tokens);
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn parse_wrong_expr() {
+        {
+            let result: Result<Precondition, _> = parse2(quote! {
+                a ++ b
+            });
+            assert!(result.is_err());
+        }
+
+        {
+            let result: Result<Precondition, _> = parse2(quote! {
+                17 - + -- + []
+            });
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn parse_extra_tokens() {
+        {
+            let result: Result<Precondition, _> = parse2(quote! {
+                "foo" bar
+            });
+            assert!(result.is_err());
+        }
+
+        {
+            let result: Result<Precondition, _> = parse2(quote! {
+                valid_ptr(foo, r+w+x)
+            });
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn parse_correct_in_range() {
+        for tokens in [
+            quote! { kind = in_range(x, 0..10) },
+            quote! { kind = in_range(x, 0..=10) },
+            quote! { kind = in_range(x, -5..) },
+            quote! { kind = in_range(x, ..10) },
+            quote! { kind = in_range(x, a..b) },
+            quote! { kind = in_range(x, 5..=5) },
+        ] {
+            let result: Result<Precondition, _> = parse2(tokens);
+            assert!(result.is_ok());
+        }
+    }
+
+    #[test]
+    fn in_range_function_call_is_boolean() {
+        let result: Result<Precondition, _> = parse2(quote! { in_range(x, 0..10) });
+        assert!(matches!(result, Ok(Precondition::Boolean(_))));
+    }
+
+    #[test]
+    fn parse_invalid_in_range() {
+        for tokens in [
+            quote! { kind = in_range(x, 10..0) },
+            quote! { kind = in_range(x, 5..5) },
+            quote! { kind = in_range(x, 1..=-1) },
+            quote! { kind = in_range(x, ..) },
+            quote! { kind = in_range(x, 10) },
+            quote! { kind = in_range(x) },
+        ] {
+            let result: Result<Precondition, _> = parse2(tokens);
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn parse_correct_kind() {
+        for tokens in [
+            quote! { kind = non_zero(x) },
+            quote! { kind = crate::kinds::disjoint(a, b) },
+            quote! { kind = self::initialized() },
+        ] {
+            let result: Result<Precondition, _> = parse2(tokens);
+            assert!(matches!(result, Ok(Precondition::Kind { .. })));
+        }
+    }
+
+    #[test]
+    fn parse_invalid_kind() {
+        for tokens in [
+            quote! { kind = non_zero },
+            quote! { kind = non_zero(x + 1) },
+            quote! { kind = non_zero::<u8>(x) },
+            quote! { kind = "non_zero" },
+        ] {
+            let result: Result<Precondition, _> = parse2(tokens);
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn parse_correct_relation() {
+        for tokens in [
+            quote! { kind = relation("<", a, b) },
+            quote! { kind = relation("<=", a, b) },
+            quote! { kind = relation(">", a, b) },
+            quote! { kind = relation(">=", a, b) },
+            quote! { kind = relation("==", a, b) },
+            quote! { kind = relation("!=", a, b) },
+        ] {
+            let result: Result<Precondition, _> = parse2(tokens);
+            assert!(matches!(result, Ok(Precondition::Relation { .. })));
+        }
+    }
+
+    #[test]
+    fn relation_function_call_is_boolean() {
+        let result: Result<Precondition, _> = parse2(quote! { relation("<", a, b) });
+        assert!(matches!(result, Ok(Precondition::Boolean(_))));
+    }
+
+    #[test]
+    fn parse_invalid_relation() {
+        for tokens in [
+            quote! { kind = relation("=<", a, b) },
+            quote! { kind = relation("", a, b) },
+            quote! { kind = relation(<, a, b) },
+            quote! { kind = relation("<", a) },
+            quote! { kind = relation("<", a, b, c) },
+            quote! { kind = relation("<", a + 1, b) },
+        ] {
+            let result: Result<Precondition, _> = parse2(tokens);
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn parse_correct_locked() {
+        let result: Result<Precondition, _> = parse2(quote! {
+            kind = locked(guard)
+        });
+        assert!(matches!(result, Ok(Precondition::Locked { .. })));
+    }
+
+    #[test]
+    fn parse_invalid_locked() {
+        for tokens in [
+            quote! { kind = locked() },
+            quote! { kind = locked(a, b) },
+            quote! { kind = locked(self.mutex) },
+            quote! { kind = locked("guard") },
+        ] {
+            let result: Result<Precondition, _> = parse2(tokens);
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn locked_function_call_is_boolean() {
+        let result: Result<Precondition, _> = parse2(quote! { locked() });
+        assert!(matches!(result, Ok(Precondition::Boolean(_))));
+    }
+
+    #[test]
+    fn locked_is_distinct_from_other_kinds() {
+        let precondition = |tokens| parse2::<Precondition>(tokens).expect("valid precondition");
+
+        assert!(
+            precondition(quote! { kind = locked(a) }) == precondition(quote! { kind = locked(a) })
+        );
+        assert!(
+            precondition(quote! { kind = locked(a) }) != precondition(quote! { kind = locked(b) })
+        );
+        assert!(
+            precondition(quote! { kind = locked(a) })
+                != precondition(quote! { kind = self::locked(a) })
+        );
+        assert!(precondition(quote! { kind = locked(a) }) != precondition(quote! { locked }));
+    }
+
+    #[test]
+    fn parse_correct_non_null() {
+        let result: Result<Precondition, _> = parse2(quote! {
+            kind = non_null(ptr)
+        });
+        assert!(matches!(result, Ok(Precondition::NonNull { .. })));
+    }
+
+    #[test]
+    fn parse_invalid_non_null() {
+        for tokens in [
+            quote! { kind = non_null() },
+            quote! { kind = non_null(a, b) },
+            quote! { kind = non_null(a, r) },
+            quote! { kind = non_null(self.ptr) },
+            quote! { kind = non_null("ptr") },
+        ] {
+            let result: Result<Precondition, _> = parse2(tokens);
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn non_null_function_call_is_boolean() {
+        let result: Result<Precondition, _> = parse2(quote! { non_null() });
+        assert!(matches!(result, Ok(Precondition::Boolean(_))));
+    }
+
+    #[test]
+    fn non_null_is_distinct_from_valid_ptr() {
+        let precondition = |tokens| parse2::<Precondition>(tokens).expect("valid precondition");
+
+        assert_eq!(
+            precondition(quote! { kind = non_null(p) }),
+            precondition(quote! { kind = non_null(p) })
+        );
+        assert_ne!(
+            precondition(quote! { kind = non_null(p) }),
+            precondition(quote! { kind = non_null(q) })
+        );
+        assert_ne!(
+            precondition(quote! { kind = non_null(p) }),
+            precondition(quote! { valid_ptr(p, r) })
+        );
+        assert_ne!(
+            precondition(quote! { kind = non_null(p) }),
+            precondition(quote! { kind = self::non_null(p) })
+        );
+        assert_eq!(
+            format!("{:?}", precondition(quote! { kind = non_null(p) })),
+            "Precondition(kind = non_null(p))"
+        );
+    }
+
+    #[test]
+    fn parse_correct_nonzero() {
+        let result: Result<Precondition, _> = parse2(quote! {
+            kind = nonzero(divisor)
+        });
+        assert!(matches!(result, Ok(Precondition::NonZero { .. })));
+    }
+
+    #[test]
+    fn parse_invalid_nonzero() {
+        for tokens in [
+            quote! { kind = nonzero() },
+            quote! { kind = nonzero(a, b) },
+            quote! { kind = nonzero(0) },
+            quote! { kind = nonzero(self.divisor) },
+        ] {
+            let result: Result<Precondition, _> = parse2(tokens);
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn nonzero_function_call_is_boolean() {
+        let result: Result<Precondition, _> = parse2(quote! { nonzero() });
+        assert!(matches!(result, Ok(Precondition::Boolean(_))));
+    }
+
+    #[test]
+    fn nonzero_sorts_after_the_other_kinds() {
+        let precondition = |tokens| parse2::<Precondition>(tokens).expect("valid precondition");
+
+        assert_ne!(
+            precondition(quote! { kind = nonzero(x) }),
+            precondition(quote! { kind = nonzero(y) })
+        );
+        assert_ne!(
+            precondition(quote! { kind = nonzero(x) }),
+            precondition(quote! { x != 0 })
+        );
+        assert_eq!(
+            format!("{:?}", precondition(quote! { kind = nonzero(x) })),
+            "Precondition(kind = nonzero(x))"
+        );
+
+        let mut preconditions = [
+            precondition(quote! { kind = nonzero(y) }),
+            precondition(quote! { kind = len(v, 4) }),
+            precondition(quote! { kind = nonzero(x) }),
+            precondition(quote! { x > 1 }),
+        ];
+        preconditions.sort();
+        let rendered: Vec<_> = preconditions.iter().map(ToString::to_string).collect();
+
+        assert_eq!(
+            rendered,
+            [
+                "x > 1",
+                "kind = len(v, 4)",
+                "kind = nonzero(x)",
+                "kind = nonzero(y)"
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_correct_sorted() {
+        for tokens in [
+            quote! { kind = sorted(v) },
+            quote! { kind = sorted(v, by = "key") },
+        ] {
+            let result: Result<Precondition, _> = parse2(tokens);
+            assert!(matches!(result, Ok(Precondition::Sorted { .. })));
+        }
+    }
+
+    #[test]
+    fn sorted_function_call_is_boolean() {
+        let result: Result<Precondition, _> = parse2(quote! { sorted(v) });
+        assert!(matches!(result, Ok(Precondition::Boolean(_))));
+    }
+
+    #[test]
+    fn parse_invalid_sorted() {
+        for tokens in [
+            quote! { kind = sorted() },
+            quote! { kind = sorted(a, b) },
+            quote! { kind = sorted(v, by = key) },
+            quote! { kind = sorted(v, by = "") },
+            quote! { kind = sorted(v, by = "key", extra) },
+        ] {
+            let result: Result<Precondition, _> = parse2(tokens);
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn sorted_orders_are_different_preconditions() {
+        let precondition = |tokens| parse2::<Precondition>(tokens).expect("valid precondition");
+
+        assert_ne!(
+            precondition(quote! { kind = sorted(v) }),
+            precondition(quote! { kind = sorted(v, by = "key") })
+        );
+        assert_ne!(
+            precondition(quote! { kind = sorted(v, by = "key") }),
+            precondition(quote! { kind = sorted(v, by = "reverse key") })
+        );
+        assert_ne!(
+            precondition(quote! { kind = sorted(v) }),
+            precondition(quote! { "`v` is sorted" })
+        );
+        assert_eq!(
+            format!("{:?}", precondition(quote! { kind = sorted(v) })),
+            "Precondition(kind = sorted(v))"
+        );
+        assert_eq!(
+            precondition(quote! { kind = sorted(v, by = "key") }).to_string(),
+            "kind = sorted(v, by = \"key\")"
+        );
+    }
+
+    #[test]
+    fn parse_correct_aligned() {
+        let result: Result<Precondition, _> = parse2(quote! {
+            kind = aligned(ptr)
+        });
+        assert!(matches!(result, Ok(Precondition::Aligned { .. })));
+    }
+
+    #[test]
+    fn parse_invalid_aligned() {
+        for tokens in [
+            quote! { kind = aligned() },
+            quote! { kind = aligned(a, b) },
+            quote! { kind = aligned(a, 8) },
+            quote! { kind = aligned(self.ptr) },
+        ] {
+            let result: Result<Precondition, _> = parse2(tokens);
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn aligned_function_call_is_boolean() {
+        let result: Result<Precondition, _> = parse2(quote! { aligned() });
+        assert!(matches!(result, Ok(Precondition::Boolean(_))));
+    }
+
+    #[test]
+    fn aligned_is_distinct_from_other_pointer_preconditions() {
+        let precondition = |tokens| parse2::<Precondition>(tokens).expect("valid precondition");
+
+        assert_eq!(
+            precondition(quote! { kind = aligned(p) }),
+            precondition(quote! { kind = aligned(p) })
+        );
+        assert_ne!(
+            precondition(quote! { kind = aligned(p) }),
+            precondition(quote! { kind = aligned(q) })
+        );
+        assert_ne!(
+            precondition(quote! { kind = aligned(p) }),
+            precondition(quote! { valid_ptr(p, r) })
+        );
+        assert_ne!(
+            precondition(quote! { kind = aligned(p) }),
+            precondition(quote! { kind = non_null(p) })
+        );
+        assert_eq!(
+            format!("{:?}", precondition(quote! { kind = aligned(p) })),
+            "Precondition(kind = aligned(p))"
+        );
+    }
+
+    #[test]
+    fn aligned_and_valid_ptr_are_not_duplicates() {
+        let mut preconditions = list(&[quote! { valid_ptr(p, r) }, quote! { kind = non_null(p) }]);
+        let duplicates = preconditions.merge(list(&[quote! { kind = aligned(p) }]));
+        assert!(duplicates.is_empty());
+
+        let mut preconditions = preconditions.into_vec();
+        preconditions.sort_unstable();
+        assert_eq!(
+            rendered(&preconditions),
+            ["valid_ptr(p, r)", "kind = non_null(p)", "kind = aligned(p)"]
+        );
+    }
+
+    #[test]
+    fn parse_correct_proper_align() {
+        for tokens in [
+            quote! { kind = proper_align(ptr, u64) },
+            quote! { kind = proper_align(ptr, [u8; 16]) },
+            quote! { kind = proper_align(ptr, Option<&'static str>) },
+        ] {
+            let result: Result<Precondition, _> = parse2(tokens);
+            assert!(matches!(result, Ok(Precondition::ProperAlign { .. })));
+        }
+    }
+
+    #[test]
+    fn proper_align_function_call_is_boolean() {
+        let result: Result<Precondition, _> = parse2(quote! { proper_align(ptr, u64) });
+        assert!(matches!(result, Ok(Precondition::Boolean(_))));
+    }
+
+    #[test]
+    fn parse_invalid_proper_align() {
+        for tokens in [
+            quote! { kind = proper_align() },
+            quote! { kind = proper_align(ptr) },
+            quote! { kind = proper_align(ptr, ) },
+            quote! { kind = proper_align(self.ptr, u64) },
+            quote! { kind = proper_align(ptr, u64, u32) },
+        ] {
+            let result: Result<Precondition, _> = parse2(tokens);
+            assert!(result.is_err());
         }
     }
-}
 
-impl fmt::Display for ReadWrite {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            ReadWrite::Read { .. } => write!(f, "r"),
-            ReadWrite::Write { .. } => write!(f, "w"),
-            ReadWrite::Both { .. } => write!(f, "r+w"),
+    #[test]
+    fn proper_align_types_are_different_preconditions() {
+        let precondition = |tokens| parse2::<Precondition>(tokens).expect("valid precondition");
+
+        assert_eq!(
+            precondition(quote! { kind = proper_align(p, u64) }),
+            precondition(quote! { kind = proper_align(p, u64) })
+        );
+        assert_ne!(
+            precondition(quote! { kind = proper_align(p, u64) }),
+            precondition(quote! { kind = proper_align(p, u32) })
+        );
+        assert_ne!(
+            precondition(quote! { kind = proper_align(p, u64) }),
+            precondition(quote! { kind = aligned(p) })
+        );
+        assert_eq!(
+            format!("{:?}", precondition(quote! { kind = proper_align(p, u64) })),
+            "Precondition(kind = proper_align(p, u64))"
+        );
+    }
+
+    #[test]
+    fn parse_correct_init() {
+        for tokens in [
+            quote! { kind = init(ptr) },
+            quote! { kind = initialized(ptr) },
+        ] {
+            let result: Result<Precondition, _> = parse2(tokens);
+            assert!(matches!(result, Ok(Precondition::Initialized { .. })));
         }
     }
-}
 
-impl Parse for ReadWrite {
-    fn parse(input: ParseStream) -> syn::Result<Self> {
-        let lookahead = input.lookahead1();
+    #[test]
+    fn initialized_function_call_is_boolean() {
+        let result: Result<Precondition, _> = parse2(quote! { initialized(ptr) });
+        assert!(matches!(result, Ok(Precondition::Boolean(_))));
+    }
 
-        if lookahead.peek(custom_keywords::w) {
-            Ok(ReadWrite::Write {
-                w_keyword: input.parse()?,
-            })
-        } else if lookahead.peek(custom_keywords::r) {
-            let r_keyword = input.parse()?;
+    #[test]
+    fn init_function_call_is_boolean() {
+        let result: Result<Precondition, _> = parse2(quote! { init(ptr) });
+        assert!(matches!(result, Ok(Precondition::Boolean(_))));
+    }
 
-            if input.peek(Token![+]) {
-                let plus = input.parse()?;
-                let w_keyword = input.parse()?;
+    #[test]
+    fn parse_invalid_init() {
+        for tokens in [
+            quote! { kind = init() },
+            quote! { kind = init(a, b) },
+            quote! { kind = initialized(a, r) },
+            quote! { kind = init(self.ptr) },
+        ] {
+            let result: Result<Precondition, _> = parse2(tokens);
+            assert!(result.is_err());
+        }
+    }
 
-                Ok(ReadWrite::Both {
-                    r_keyword,
-                    _plus: plus,
-                    w_keyword,
-                })
-            } else {
-                Ok(ReadWrite::Read { r_keyword })
-            }
-        } else {
-            Err(lookahead.error())
+    #[test]
+    fn init_spellings_are_the_same_precondition() {
+        let precondition = |tokens| parse2::<Precondition>(tokens).expect("valid precondition");
+
+        assert_eq!(
+            precondition(quote! { kind = init(p) }),
+            precondition(quote! { kind = initialized(p) })
+        );
+        assert_ne!(
+            precondition(quote! { kind = init(p) }),
+            precondition(quote! { kind = init(q) })
+        );
+        assert_eq!(
+            format!("{:?}", precondition(quote! { kind = initialized(p) })),
+            "Precondition(kind = init(p))"
+        );
+    }
+
+    #[test]
+    fn init_combines_with_other_pointer_preconditions() {
+        let mut preconditions = list(&[quote! { valid_ptr(p, r) }, quote! { kind = aligned(p) }]);
+        let duplicates = preconditions.merge(list(&[quote! { kind = init(p) }]));
+        assert!(duplicates.is_empty());
+
+        let mut preconditions = preconditions.into_vec();
+        preconditions.sort_unstable();
+        assert_eq!(
+            rendered(&preconditions),
+            ["valid_ptr(p, r)", "kind = aligned(p)", "kind = init(p)"]
+        );
+    }
+
+    #[test]
+    fn parse_correct_exclusive() {
+        for tokens in [
+            quote! { kind = exclusive(ptr) },
+            quote! { kind = no_alias(ptr) },
+        ] {
+            let result: Result<Precondition, _> = parse2(tokens);
+            assert!(matches!(result, Ok(Precondition::Exclusive { .. })));
         }
     }
-}
 
-impl Spanned for ReadWrite {
-    fn span(&self) -> Span {
-        match self {
-            ReadWrite::Read { r_keyword } => r_keyword.span,
-            ReadWrite::Write { w_keyword } => w_keyword.span,
-            ReadWrite::Both {
-                r_keyword,
-                w_keyword,
-                ..
-            } => r_keyword
-                .span
-                .join(w_keyword.span)
-                .unwrap_or_else(|| r_keyword.span),
+    #[test]
+    fn no_alias_function_call_is_boolean() {
+        let result: Result<Precondition, _> = parse2(quote! { no_alias(ptr) });
+        assert!(matches!(result, Ok(Precondition::Boolean(_))));
+    }
+
+    #[test]
+    fn exclusive_function_call_is_boolean() {
+        let result: Result<Precondition, _> = parse2(quote! { exclusive(ptr) });
+        assert!(matches!(result, Ok(Precondition::Boolean(_))));
+    }
+
+    #[test]
+    fn parse_invalid_exclusive() {
+        for tokens in [
+            quote! { kind = exclusive() },
+            quote! { kind = exclusive(a, b) },
+            quote! { kind = no_alias(a, w) },
+            quote! { kind = exclusive(self.ptr) },
+        ] {
+            let result: Result<Precondition, _> = parse2(tokens);
+            assert!(result.is_err());
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use quote::quote;
-    use syn::parse2;
+    #[test]
+    fn exclusive_is_distinct_from_valid_ptr() {
+        let precondition = |tokens| parse2::<Precondition>(tokens).expect("valid precondition");
 
-    use super::*;
+        assert_eq!(
+            precondition(quote! { kind = exclusive(p) }),
+            precondition(quote! { kind = no_alias(p) })
+        );
+        assert_ne!(
+            precondition(quote! { kind = exclusive(p) }),
+            precondition(quote! { valid_ptr(p, w) })
+        );
+        assert_eq!(
+            format!("{:?}", precondition(quote! { kind = no_alias(p) })),
+            "Precondition(kind = exclusive(p))"
+        );
+
+        let mut preconditions = list(&[quote! { kind = exclusive(p) }, quote! { valid_ptr(p, w) }]);
+        let duplicates = preconditions.merge(list(&[quote! { kind = init(p) }]));
+        assert!(duplicates.is_empty());
+
+        let mut preconditions = preconditions.into_vec();
+        preconditions.sort_unstable();
+        assert_eq!(
+            rendered(&preconditions),
+            ["valid_ptr(p, w)", "kind = init(p)", "kind = exclusive(p)"]
+        );
+    }
 
     #[test]
-    fn parse_correct_custom() {
+    fn parse_correct_utf8() {
         let result: Result<Precondition, _> = parse2(quote! {
-            "foo"
+            kind = utf8(bytes)
         });
-        assert!(result.is_ok());
+        assert!(matches!(result, Ok(Precondition::Utf8 { .. })));
     }
 
     #[test]
-    fn parse_correct_valid_ptr() {
-        {
-            let result: Result<Precondition, _> = parse2(quote! {
-                valid_ptr(foo, r)
-            });
-            assert!(result.is_ok());
+    fn parse_invalid_utf8() {
+        for tokens in [
+            quote! { kind = utf8() },
+            quote! { kind = utf8(ptr, len) },
+            quote! { kind = utf8("bytes") },
+            quote! { kind = utf8(self.bytes) },
+        ] {
+            let result: Result<Precondition, _> = parse2(tokens);
+            assert!(result.is_err());
         }
+    }
 
-        {
-            let result: Result<Precondition, _> = parse2(quote! {
-                valid_ptr(foo, r+w)
-            });
-            assert!(result.is_ok());
-        }
+    #[test]
+    fn utf8_function_call_is_boolean() {
+        let result: Result<Precondition, _> = parse2(quote! { utf8() });
+        assert!(matches!(result, Ok(Precondition::Boolean(_))));
+    }
 
-        {
-            let result: Result<Precondition, _> = parse2(quote! {
-                valid_ptr(foo, w)
-            });
-            assert!(result.is_ok());
+    #[test]
+    fn utf8_is_independent_from_valid_ptr() {
+        let precondition = |tokens| parse2::<Precondition>(tokens).expect("valid precondition");
+
+        assert_ne!(
+            precondition(quote! { kind = utf8(p) }),
+            precondition(quote! { valid_ptr(p, r) })
+        );
+        assert_eq!(
+            format!("{:?}", precondition(quote! { kind = utf8(p) })),
+            "Precondition(kind = utf8(p))"
+        );
+
+        let mut preconditions = list(&[quote! { kind = utf8(p) }, quote! { valid_ptr(p, r) }]);
+        let duplicates = preconditions.merge(list(&[quote! { kind = exclusive(p) }]));
+        assert!(duplicates.is_empty());
+
+        let mut preconditions = preconditions.into_vec();
+        preconditions.sort_unstable();
+        assert_eq!(
+            rendered(&preconditions),
+            ["valid_ptr(p, r)", "kind = exclusive(p)", "kind = utf8(p)"]
+        );
+    }
+
+    #[test]
+    fn parse_correct_on_thread() {
+        let result: Result<Precondition, _> = parse2(quote! {
+            kind = on_thread("main")
+        });
+        assert!(matches!(result, Ok(Precondition::OnThread { .. })));
+    }
+
+    #[test]
+    fn parse_invalid_on_thread() {
+        for tokens in [
+            quote! { kind = on_thread() },
+            quote! { kind = on_thread(main) },
+            quote! { kind = on_thread("main", "ui") },
+        ] {
+            let result: Result<Precondition, _> = parse2(tokens);
+            assert!(result.is_err());
         }
     }
 
     #[test]
-    fn parse_wrong_expr() {
-        {
-            let result: Result<Precondition, _> = parse2(quote! {
-                a ++ b
-            });
+    fn on_thread_function_call_is_boolean() {
+        let result: Result<Precondition, _> = parse2(quote! { on_thread() });
+        assert!(matches!(result, Ok(Precondition::Boolean(_))));
+    }
+
+    #[test]
+    fn on_thread_is_compared_by_name() {
+        let precondition = |tokens| parse2::<Precondition>(tokens).expect("valid precondition");
+
+        assert_ne!(
+            precondition(quote! { kind = on_thread("main") }),
+            precondition(quote! { kind = on_thread("ui") })
+        );
+        assert_ne!(
+            precondition(quote! { kind = on_thread("main") }),
+            precondition(quote! { "main" })
+        );
+        assert_eq!(
+            format!("{:?}", precondition(quote! { kind = on_thread("main") })),
+            "Precondition(kind = on_thread(\"main\"))"
+        );
+
+        let mut preconditions = [
+            precondition(quote! { kind = on_thread("ui") }),
+            precondition(quote! { kind = utf8(b) }),
+            precondition(quote! { kind = on_thread("main") }),
+        ];
+        preconditions.sort();
+        assert_eq!(
+            rendered(&preconditions),
+            [
+                "kind = utf8(b)",
+                "kind = on_thread(\"main\")",
+                "kind = on_thread(\"ui\")"
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_correct_same_provenance() {
+        let result: Result<Precondition, _> = parse2(quote! {
+            kind = same_provenance(start, end)
+        });
+        assert!(matches!(result, Ok(Precondition::SameProvenance { .. })));
+    }
+
+    #[test]
+    fn parse_invalid_same_provenance() {
+        for tokens in [
+            quote! { kind = same_provenance() },
+            quote! { kind = same_provenance(a) },
+            quote! { kind = same_provenance(a, b, c) },
+            quote! { kind = same_provenance(a, a) },
+            quote! { kind = same_provenance(a.ptr, b) },
+            quote! { kind = same_provenance("a", "b") },
+        ] {
+            let result: Result<Precondition, _> = parse2(tokens);
             assert!(result.is_err());
         }
+    }
 
-        {
-            let result: Result<Precondition, _> = parse2(quote! {
-                17 - + -- + []
-            });
+    #[test]
+    fn same_provenance_function_call_is_boolean() {
+        let result: Result<Precondition, _> = parse2(quote! { same_provenance() });
+        assert!(matches!(result, Ok(Precondition::Boolean(_))));
+    }
+
+    #[test]
+    fn parse_correct_non_overlapping() {
+        let result: Result<Precondition, _> = parse2(quote! {
+            kind = non_overlapping(dst, src, count)
+        });
+        assert!(matches!(result, Ok(Precondition::NonOverlapping { .. })));
+    }
+
+    #[test]
+    fn parse_invalid_non_overlapping() {
+        for tokens in [
+            quote! { kind = non_overlapping() },
+            quote! { kind = non_overlapping(dst, src) },
+            quote! { kind = non_overlapping(dst, src, count, len) },
+            quote! { kind = non_overlapping(dst, dst, count) },
+            quote! { kind = non_overlapping(self.dst, src, count) },
+            quote! { kind = non_overlapping(dst, src, 4) },
+        ] {
+            let result: Result<Precondition, _> = parse2(tokens);
             assert!(result.is_err());
         }
     }
 
     #[test]
-    fn parse_extra_tokens() {
-        {
-            let result: Result<Precondition, _> = parse2(quote! {
-                "foo" bar
-            });
+    fn non_overlapping_function_call_is_boolean() {
+        let result: Result<Precondition, _> = parse2(quote! { non_overlapping() });
+        assert!(matches!(result, Ok(Precondition::Boolean(_))));
+    }
+
+    #[test]
+    fn non_overlapping_is_symmetric_in_the_pointers() {
+        let precondition = |tokens| parse2::<Precondition>(tokens).expect("valid precondition");
+
+        assert_eq!(
+            precondition(quote! { kind = non_overlapping(dst, src, count) }),
+            precondition(quote! { kind = non_overlapping(src, dst, count) })
+        );
+        assert_eq!(
+            format!(
+                "{:?}",
+                precondition(quote! { kind = non_overlapping(src, dst, count) })
+            ),
+            "Precondition(kind = non_overlapping(dst, src, count))"
+        );
+        assert_ne!(
+            precondition(quote! { kind = non_overlapping(dst, src, count) }),
+            precondition(quote! { kind = non_overlapping(dst, src, len) })
+        );
+        assert_ne!(
+            precondition(quote! { kind = non_overlapping(dst, src, count) }),
+            precondition(quote! { kind = same_provenance(dst, src) })
+        );
+    }
+
+    #[test]
+    fn parse_correct_len() {
+        for tokens in [
+            quote! { kind = len(buf, 16) },
+            quote! { kind = len(buf, count) },
+            quote! { kind = min_len(buf, 0x10) },
+            quote! { kind = min_len(buf, count) },
+        ] {
+            let result: Result<Precondition, _> = parse2(tokens);
+            assert!(matches!(result, Ok(Precondition::Len { .. })));
+        }
+    }
+
+    #[test]
+    fn len_function_call_is_boolean() {
+        let result: Result<Precondition, _> = parse2(quote! { len(buf, 16) });
+        assert!(matches!(result, Ok(Precondition::Boolean(_))));
+    }
+
+    #[test]
+    fn min_len_function_call_is_boolean() {
+        let result: Result<Precondition, _> = parse2(quote! { min_len(buf, 0x10) });
+        assert!(matches!(result, Ok(Precondition::Boolean(_))));
+    }
+
+    #[test]
+    fn parse_invalid_len() {
+        for tokens in [
+            quote! { kind = len() },
+            quote! { kind = len(buf) },
+            quote! { kind = len(buf, 16, 32) },
+            quote! { kind = min_len(buf, -1) },
+            quote! { kind = min_len(buf, 1.5) },
+            quote! { kind = len(buf, "16") },
+            quote! { kind = len(self.buf, 16) },
+        ] {
+            let result: Result<Precondition, _> = parse2(tokens);
             assert!(result.is_err());
         }
+    }
 
-        {
-            let result: Result<Precondition, _> = parse2(quote! {
-                valid_ptr(foo, r+w+x)
-            });
+    #[test]
+    fn len_and_min_len_are_distinct() {
+        let precondition = |tokens| parse2::<Precondition>(tokens).expect("valid precondition");
+
+        assert_eq!(
+            format!("{:?}", precondition(quote! { kind = len(buf, 16) })),
+            "Precondition(kind = len(buf, 16))"
+        );
+        assert_eq!(
+            format!("{:?}", precondition(quote! { kind = min_len(buf, 0x10) })),
+            "Precondition(kind = min_len(buf, 16))"
+        );
+        assert_eq!(
+            precondition(quote! { kind = len(buf, 16) }),
+            precondition(quote! { kind = len(buf, 0x10) })
+        );
+        assert_ne!(
+            precondition(quote! { kind = len(buf, 16) }),
+            precondition(quote! { kind = len(buf, count) })
+        );
+
+        let mut preconditions = list(&[quote! { kind = min_len(buf, 16) }]);
+        let duplicates = preconditions.merge(list(&[quote! { kind = len(buf, 16) }]));
+        assert!(duplicates.is_empty());
+
+        let mut preconditions = preconditions.into_vec();
+        preconditions.sort_unstable();
+        assert_eq!(
+            rendered(&preconditions),
+            ["kind = len(buf, 16)", "kind = min_len(buf, 16)"]
+        );
+    }
+
+    #[test]
+    fn parse_correct_access_len() {
+        for tokens in [
+            quote! { kind = readable_len(ptr, 16) },
+            quote! { kind = readable_len(ptr, count) },
+            quote! { kind = writable_len(ptr, 0x10) },
+            quote! { kind = writable_len(ptr, count) },
+        ] {
+            let result: Result<Precondition, _> = parse2(tokens);
+            assert!(matches!(result, Ok(Precondition::AccessLen { .. })));
+        }
+    }
+
+    #[test]
+    fn readable_len_function_call_is_boolean() {
+        let result: Result<Precondition, _> = parse2(quote! { readable_len(ptr, 16) });
+        assert!(matches!(result, Ok(Precondition::Boolean(_))));
+    }
+
+    #[test]
+    fn writable_len_function_call_is_boolean() {
+        let result: Result<Precondition, _> = parse2(quote! { writable_len(ptr, 0x10) });
+        assert!(matches!(result, Ok(Precondition::Boolean(_))));
+    }
+
+    #[test]
+    fn parse_invalid_access_len() {
+        for tokens in [
+            quote! { kind = readable_len() },
+            quote! { kind = readable_len(ptr) },
+            quote! { kind = readable_len(ptr, 16, 32) },
+            quote! { kind = writable_len(ptr, -1) },
+            quote! { kind = writable_len(self.ptr, 16) },
+        ] {
+            let result: Result<Precondition, _> = parse2(tokens);
             assert!(result.is_err());
         }
     }
+
+    #[test]
+    fn readable_and_writable_len_are_distinct() {
+        let precondition = |tokens| parse2::<Precondition>(tokens).expect("valid precondition");
+
+        assert_eq!(
+            format!(
+                "{:?}",
+                precondition(quote! { kind = readable_len(ptr, count) })
+            ),
+            "Precondition(kind = readable_len(ptr, count))"
+        );
+        assert_eq!(
+            format!(
+                "{:?}",
+                precondition(quote! { kind = writable_len(ptr, 0x10) })
+            ),
+            "Precondition(kind = writable_len(ptr, 16))"
+        );
+        assert_ne!(
+            precondition(quote! { kind = readable_len(ptr, count) }),
+            precondition(quote! { kind = writable_len(ptr, count) })
+        );
+        assert_ne!(
+            precondition(quote! { kind = readable_len(ptr, count) }),
+            precondition(quote! { kind = len(ptr, count) })
+        );
+
+        let mut preconditions = list(&[
+            quote! { kind = writable_len(ptr, count) },
+            quote! { valid_ptr(ptr, r+w) },
+        ]);
+        let duplicates = preconditions.merge(list(&[quote! { kind = readable_len(ptr, count) }]));
+        assert!(duplicates.is_empty());
+
+        let mut preconditions = preconditions.into_vec();
+        preconditions.sort_unstable();
+        assert_eq!(
+            rendered(&preconditions),
+            [
+                "valid_ptr(ptr, r+w)",
+                "kind = readable_len(ptr, count)",
+                "kind = writable_len(ptr, count)"
+            ]
+        );
+    }
+
+    #[test]
+    fn same_provenance_is_symmetric() {
+        let precondition = |tokens| parse2::<Precondition>(tokens).expect("valid precondition");
+
+        assert_eq!(
+            precondition(quote! { kind = same_provenance(a, b) }),
+            precondition(quote! { kind = same_provenance(b, a) })
+        );
+        assert_eq!(
+            precondition(quote! { kind = same_provenance(end, start) }).to_string(),
+            "kind = same_provenance(end, start)"
+        );
+        assert_eq!(
+            precondition(quote! { kind = same_provenance(start, end) }).to_string(),
+            "kind = same_provenance(end, start)"
+        );
+        assert_ne!(
+            precondition(quote! { kind = same_provenance(a, b) }),
+            precondition(quote! { kind = same_provenance(a, c) })
+        );
+        assert_ne!(
+            precondition(quote! { kind = same_provenance(a, b) }),
+            precondition(quote! { kind = self::same_provenance(a, b) })
+        );
+    }
+
+    fn list(preconditions: &[TokenStream]) -> PreconditionList {
+        preconditions
+            .iter()
+            .map(|tokens| parse2(tokens.clone()).expect("valid precondition"))
+            .collect()
+    }
+
+    fn rendered(preconditions: &[Precondition]) -> Vec<String> {
+        preconditions
+            .iter()
+            .map(|precondition| precondition.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn merge_disjoint_lists() {
+        let mut first = list(&[quote! { x > 0 }, quote! { valid_ptr(p, r) }]);
+        let duplicates = first.merge(list(&[
+            quote! { "custom" },
+            quote! { kind = in_range(y, 0..4) },
+        ]));
+
+        assert!(duplicates.is_empty());
+        assert_eq!(
+            rendered(&first),
+            [
+                "x > 0",
+                "valid_ptr(p, r)",
+                "\"custom\"",
+                "kind = in_range(y, 0 .. 4)"
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_overlapping_lists() {
+        let mut first = list(&[quote! { x > 0 }, quote! { valid_ptr(p, r) }]);
+        let duplicates = first.merge(list(&[
+            quote! { valid_ptr(p, r) },
+            quote! { valid_ptr(q, w) },
+            quote! { x > 0 },
+        ]));
+
+        assert_eq!(rendered(&duplicates), ["valid_ptr(p, r)", "x > 0"]);
+        assert_eq!(
+            rendered(&first),
+            ["x > 0", "valid_ptr(p, r)", "valid_ptr(q, w)"]
+        );
+    }
+
+    #[test]
+    fn list_drops_duplicates() {
+        let mut preconditions = list(&[quote! { x > 0 }, quote! { x > 0 }, quote! { "a" }]);
+        assert_eq!(rendered(&preconditions), ["x > 0", "\"a\""]);
+
+        let duplicates = preconditions.merge(vec![
+            parse2(quote! { "b" }).expect("valid precondition"),
+            parse2(quote! { "b" }).expect("valid precondition"),
+        ]);
+        assert_eq!(rendered(&duplicates), ["\"b\""]);
+        assert_eq!(rendered(&preconditions), ["x > 0", "\"a\"", "\"b\""]);
+    }
+
+    #[test]
+    fn sorting_order_is_stable() {
+        let mut preconditions: Vec<Precondition> = vec![
+            parse2(quote! { see = "foo" }).expect("valid precondition"),
+            parse2(quote! { "b custom" }).expect("valid precondition"),
+            parse2(quote! { x > 0 }).expect("valid precondition"),
+            parse2(quote! { valid_ptr(q, r) }).expect("valid precondition"),
+            parse2(quote! { "a custom" }).expect("valid precondition"),
+            parse2(quote! { valid_ptr(p, w) }).expect("valid precondition"),
+            parse2(quote! { a < b }).expect("valid precondition"),
+            parse2(quote! { kind = in_range(x, 0..10) }).expect("valid precondition"),
+            parse2(quote! { kind = non_zero(x) }).expect("valid precondition"),
+            parse2(quote! { kind = relation("<", a, b) }).expect("valid precondition"),
+            parse2(quote! { kind = same_provenance(q, p) }).expect("valid precondition"),
+            parse2(quote! { kind = locked(m) }).expect("valid precondition"),
+            parse2(quote! { kind = init(p) }).expect("valid precondition"),
+            parse2(quote! { kind = aligned(p) }).expect("valid precondition"),
+            parse2(quote! { kind = non_null(p) }).expect("valid precondition"),
+        ];
+
+        preconditions.sort_unstable();
+
+        let rendered: Vec<_> = preconditions
+            .iter()
+            .map(|precondition| precondition.to_string())
+            .collect();
+        assert_eq!(
+            rendered,
+            [
+                "valid_ptr(p, w)",
+                "valid_ptr(q, r)",
+                "a < b",
+                "x > 0",
+                "\"a custom\"",
+                "\"b custom\"",
+                "see = \"foo\"",
+                "kind = in_range(x, 0 .. 10)",
+                "kind = non_zero(x)",
+                "kind = relation(\"<\", a, b)",
+                "kind = locked(m)",
+                "kind = same_provenance(p, q)",
+                "kind = non_null(p)",
+                "kind = aligned(p)",
+                "kind = init(p)",
+            ]
+        );
+    }
+
+    #[test]
+    fn written_order_does_not_affect_the_sorted_order() {
+        let sorted = |preconditions: &[TokenStream]| {
+            let mut preconditions = list(preconditions).into_vec();
+            preconditions.sort_unstable();
+
+            rendered(&preconditions)
+        };
+
+        assert_eq!(
+            sorted(&[quote! { "a" }, quote! { valid_ptr(p, r) }]),
+            sorted(&[quote! { valid_ptr(p, r) }, quote! { "a" }])
+        );
+        assert_eq!(
+            sorted(&[
+                quote! { kind = len(x, 4) },
+                quote! { kind = relation("<", a, b) },
+                quote! { x > 0 }
+            ]),
+            sorted(&[
+                quote! { x > 0 },
+                quote! { kind = len(x, 4) },
+                quote! { kind = relation("<", a, b) }
+            ])
+        );
+    }
+
+    #[test]
+    fn equal_preconditions_are_rendered_the_same() {
+        let preconditions = list(&[
+            quote! { valid_ptr(p, r) },
+            quote! { valid_ptr(p: *const u8, r) },
+            quote! { valid_ptr(p, w) },
+            quote! { x > 0 },
+            quote! { "a" },
+            quote! { see = "a" },
+            quote! { kind = in_range(x, 0..4) },
+            quote! { kind = non_zero(x) },
+            quote! { kind = relation("<", a, b) },
+            quote! { kind = relation("<=", a, b) },
+            quote! { kind = same_provenance(a, b) },
+            quote! { kind = non_overlapping(a, b, n) },
+            quote! { kind = len(x, 4) },
+            quote! { kind = min_len(x, 4) },
+        ]);
+
+        for first in preconditions.iter() {
+            for second in preconditions.iter() {
+                assert_eq!(
+                    first == second,
+                    first.to_string() == second.to_string(),
+                    "`{}` and `{}`",
+                    first,
+                    second
+                );
+            }
+        }
+    }
 }
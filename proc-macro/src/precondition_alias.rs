@@ -0,0 +1,314 @@
+//! Defines the `precondition_alias` macro and how the aliases it defines are expanded.
+//!
+//! A macro cannot look at the items around it, so the macro itself only checks the definitions.
+//! The `pre` attribute of a module finds the invocations of the macro in the module and expands
+//! every `#[pre(alias <name> for <ident>)]` attribute of the functions in it.
+//!
+//! # What the expansion looks like
+//!
+//! ```rust,ignore
+//! #[pre]
+//! mod slices {
+//!     pre::precondition_alias! {
+//!         SliceValid = valid_ptr(p, r), kind = aligned(p), kind = init(p);
+//!     }
+//!
+//!     #[pre(alias SliceValid for src)]
+//!     unsafe fn read(src: *const u8) -> u8 {
+//!         *src
+//!     }
+//! }
+//! ```
+//!
+//! declares the same preconditions for `read` as
+//!
+//! ```rust,ignore
+//! #[pre(valid_ptr(src, r))]
+//! #[pre(kind = aligned(src))]
+//! #[pre(kind = init(src))]
+//! ```
+//!
+//! The identifier that is replaced is the one that all preconditions of the alias refer to. It can
+//! also be specified explicitly, such as `Bounded(x) = kind = len(x, n), kind = nonzero(n);`. It
+//! is replaced in all tokens of the preconditions, including boolean expressions, but not inside
+//! of string literals.
+
+use proc_macro2::{Group, TokenStream, TokenTree};
+use proc_macro_error::emit_error;
+use syn::{
+    buffer::Cursor,
+    parenthesized,
+    parse::{Parse, ParseStream},
+    parse2,
+    token::Paren,
+    Ident, Item, Token,
+};
+
+use crate::{pre_attr::subject_idents_of, precondition::Precondition};
+
+/// The name of the macro defining aliases.
+const MACRO_NAME: &str = "precondition_alias";
+
+/// The input to a `precondition_alias` macro.
+pub(crate) struct PreconditionAliases {
+    /// The aliases that are defined.
+    pub(crate) aliases: Vec<PreconditionAlias>,
+}
+
+impl Parse for PreconditionAliases {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut aliases = Vec::new();
+
+        while !input.is_empty() {
+            aliases.push(input.parse()?);
+        }
+
+        Ok(PreconditionAliases { aliases })
+    }
+}
+
+/// A name for a group of preconditions about the same identifier.
+#[derive(Clone)]
+pub(crate) struct PreconditionAlias {
+    /// The name of the alias.
+    pub(crate) name: Ident,
+    /// The identifier that is replaced when the alias is expanded.
+    placeholder: Ident,
+    /// The tokens of the preconditions of the alias.
+    preconditions: Vec<TokenStream>,
+}
+
+impl Parse for PreconditionAlias {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+
+        let explicit_placeholder = if input.peek(Paren) {
+            let content;
+            parenthesized!(content in input);
+
+            Some(content.parse::<Ident>()?)
+        } else {
+            None
+        };
+
+        input.parse::<Token![=]>()?;
+
+        let mut preconditions = Vec::new();
+        let mut parsed = Vec::new();
+        loop {
+            let begin = input.cursor();
+            parsed.push(input.parse::<Precondition>()?);
+            preconditions.push(tokens_between(begin, input.cursor()));
+
+            if input.peek(Token![;]) {
+                input.parse::<Token![;]>()?;
+                break;
+            }
+
+            input.parse::<Token![,]>()?;
+        }
+
+        let placeholder = match explicit_placeholder {
+            Some(placeholder) => placeholder,
+            None => infer_placeholder(&name, &parsed)?,
+        };
+
+        Ok(PreconditionAlias {
+            name,
+            placeholder,
+            preconditions,
+        })
+    }
+}
+
+impl PreconditionAlias {
+    /// Returns the preconditions of the alias for `ident`.
+    ///
+    /// The placeholder is replaced by `ident` with its span, so that errors about the identifier
+    /// point to the use of the alias.
+    pub(crate) fn expand(&self, ident: &Ident) -> syn::Result<Vec<Precondition>> {
+        self.preconditions
+            .iter()
+            .map(|tokens| parse2(replace_ident(tokens.clone(), &self.placeholder, ident)))
+            .collect()
+    }
+}
+
+/// Returns the tokens from `begin` up to, but not including, `end`.
+fn tokens_between(mut begin: Cursor, end: Cursor) -> TokenStream {
+    let mut tokens = TokenStream::new();
+
+    while begin != end {
+        let (token, next) = begin.token_tree().expect("`end` is after `begin`");
+        tokens.extend(Some(token));
+        begin = next;
+    }
+
+    tokens
+}
+
+/// Returns the identifier that all of the preconditions refer to.
+fn infer_placeholder(name: &Ident, preconditions: &[Precondition]) -> syn::Result<Ident> {
+    let mut candidates: Option<Vec<Ident>> = None;
+
+    for precondition in preconditions {
+        if let Some((_, idents)) = subject_idents_of(precondition) {
+            let idents: Vec<Ident> = idents.into_iter().cloned().collect();
+
+            candidates = Some(match candidates {
+                Some(candidates) => candidates
+                    .into_iter()
+                    .filter(|candidate| idents.contains(candidate))
+                    .collect(),
+                None => idents,
+            });
+        }
+    }
+
+    match candidates.as_deref() {
+        Some([placeholder]) => Ok(placeholder.clone()),
+        Some([]) | None => Err(syn::Error::new(
+            name.span(),
+            format!(
+                "the preconditions of `{}` don't all refer to the same identifier",
+                name
+            ),
+        )),
+        Some(candidates) => {
+            let names: Vec<_> = candidates
+                .iter()
+                .map(|candidate| format!("`{}`", candidate))
+                .collect();
+
+            Err(syn::Error::new(
+                name.span(),
+                format!(
+                    "the identifier to replace in `{}` is ambiguous, it could be {}; specify it explicitly, such as `{}({}) = ...`",
+                    name,
+                    names.join(" or "),
+                    name,
+                    candidates[0]
+                ),
+            ))
+        }
+    }
+}
+
+/// Replaces every occurrence of the identifier `from` in the tokens by `to`.
+fn replace_ident(tokens: TokenStream, from: &Ident, to: &Ident) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Ident(ident) if ident == *from => TokenTree::Ident(to.clone()),
+            TokenTree::Group(group) => {
+                let mut replaced =
+                    Group::new(group.delimiter(), replace_ident(group.stream(), from, to));
+                replaced.set_span(group.span());
+
+                TokenTree::Group(replaced)
+            }
+            token => token,
+        })
+        .collect()
+}
+
+/// Returns the aliases defined by `precondition_alias` macros in the given items.
+///
+/// Invalid definitions are skipped here, because the macro reports them itself.
+pub(crate) fn aliases_of(items: &[Item]) -> Vec<PreconditionAlias> {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Macro(item_macro)
+                if matches!(
+                    item_macro.mac.path.segments.last(),
+                    Some(segment) if segment.ident == MACRO_NAME
+                ) =>
+            {
+                parse2::<PreconditionAliases>(item_macro.mac.tokens.clone()).ok()
+            }
+            _ => None,
+        })
+        .flat_map(|aliases| aliases.aliases)
+        .collect()
+}
+
+impl PreconditionAliases {
+    /// Checks the definitions for conflicting names.
+    ///
+    /// The definitions are otherwise checked while parsing, so the macro expands to nothing.
+    pub(crate) fn render(self) -> TokenStream {
+        for (index, alias) in self.aliases.iter().enumerate() {
+            if self.aliases[..index]
+                .iter()
+                .any(|other| other.name == alias.name)
+            {
+                emit_error!(
+                    alias.name,
+                    "the alias `{}` is defined more than once",
+                    alias.name
+                );
+            }
+        }
+
+        TokenStream::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+    use quote::quote;
+
+    use super::*;
+
+    /// Returns the rendered preconditions of the alias for `ident`.
+    fn expanded(alias: TokenStream, ident: &str) -> Vec<String> {
+        let alias: PreconditionAlias = parse2(alias).expect("valid alias");
+
+        alias
+            .expand(&Ident::new(ident, Span::call_site()))
+            .expect("valid expansion")
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn placeholder_is_replaced_in_all_preconditions() {
+        assert_eq!(
+            expanded(
+                quote! { SliceValid = valid_ptr(p, r), kind = aligned(p), kind = init(p), p != q; },
+                "src"
+            ),
+            [
+                "valid_ptr(src, r)",
+                "kind = aligned(src)",
+                "kind = init(src)",
+                "src != q"
+            ]
+        );
+    }
+
+    #[test]
+    fn placeholder_can_be_explicit() {
+        assert_eq!(
+            expanded(
+                quote! { Bounded(n) = kind = len(v, n), kind = nonzero(n); },
+                "count"
+            ),
+            ["kind = len(v, count)", "kind = nonzero(count)"]
+        );
+    }
+
+    #[test]
+    fn ambiguous_placeholder_is_an_error() {
+        for tokens in [
+            quote! { Bounded = kind = len(v, n); },
+            quote! { Unrelated = kind = non_null(p), kind = non_null(q); },
+            quote! { Custom = "some precondition"; },
+        ] {
+            assert!(parse2::<PreconditionAlias>(tokens).is_err());
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Registers the preconditions of functions in a global registry.
+//!
+//! This is used when the `registry` feature is enabled. Every function with preconditions gets a
+//! hidden static at the start of its body, which is added to the distributed slice
+//! `pre::CONTRACTS` using [`linkme`](https://docs.rs/linkme). The linker collects all elements of
+//! the slice, so the contracts of every crate in the final binary can be enumerated at runtime.
+//!
+//! # What the generated code looks like
+//!
+//! ```rust,ignore
+//! #[::pre::__private::linkme::distributed_slice(::pre::CONTRACTS)]
+//! #[linkme(crate = ::pre::__private::linkme)]
+//! static __PRE_CONTRACT: ::pre::Contract = ::pre::Contract {
+//!     module_path: ::core::module_path!(),
+//!     function: "foo",
+//!     preconditions: &["x > 0"],
+//!     address: ::core::option::Option::Some(foo as *const ()),
+//! };
+//! ```
+//!
+//! The preconditions are listed in the same order as in the markers and written the same way as
+//! for the `tool-annotations` feature.
+//!
+//! The address is only known for functions that can be named without any generic arguments from
+//! inside of their body. For functions in `impl` blocks, functions taking `self` or mentioning
+//! `Self` and generic functions, it is `None`.
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{quote, quote_spanned};
+use syn::{parse2, FnArg, GenericParam, Ident, LitStr, PatType, Signature, Stmt};
+
+use crate::{helpers::crate_name_ident, precondition::Precondition};
+
+/// The name of the static containing the registered contract.
+const CONTRACT_NAME: &str = "__PRE_CONTRACT";
+
+/// Renders the statement registering the contract of the function with the given signature.
+///
+/// `is_associated` specifies whether the function is defined in an `impl` block.
+pub(crate) fn render_registration(
+    signature: &Signature,
+    preconditions: &[Precondition],
+    is_associated: bool,
+    span: Span,
+) -> Stmt {
+    let crate_name = crate_name_ident(span);
+    let name = Ident::new(CONTRACT_NAME, span);
+    let function_name = LitStr::new(&signature.ident.to_string(), span);
+    let address = if has_known_address(signature, is_associated) {
+        let ident = &signature.ident;
+
+        quote_spanned! { span=> ::core::option::Option::Some(#ident as *const ()) }
+    } else {
+        quote_spanned! { span=> ::core::option::Option::None }
+    };
+    let mut preconditions = preconditions.to_vec();
+    preconditions.sort_unstable();
+    let preconditions = preconditions
+        .iter()
+        .map(|precondition| LitStr::new(&precondition.to_string(), span));
+
+    parse2(quote_spanned! { span=>
+        #[::#crate_name::__private::linkme::distributed_slice(::#crate_name::CONTRACTS)]
+        #[linkme(crate = ::#crate_name::__private::linkme)]
+        static #name: ::#crate_name::Contract = ::#crate_name::Contract {
+            module_path: ::core::module_path!(),
+            function: #function_name,
+            preconditions: &[#(#preconditions),*],
+            address: #address,
+        };
+    })
+    .expect("parses as a statement")
+}
+
+/// Checks whether the address of the function can be taken in its registration.
+///
+/// The registration is a static inside of the function body. From there, the function can only be
+/// named by its identifier, which is not possible for associated functions. Functions taking `self`
+/// or mentioning `Self` are treated as associated functions as well, because `is_associated` is
+/// not set for a function in an `impl` block that is annotated on its own. Generic functions don't
+/// have a single address.
+pub(crate) fn has_known_address(signature: &Signature, is_associated: bool) -> bool {
+    /// Checks if the tokens contain `impl`, as used by `impl Trait` arguments, or `Self`.
+    fn contains_impl_or_self(tokens: TokenStream) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Group(group) => contains_impl_or_self(group.stream()),
+            TokenTree::Ident(ident) => ident == "impl" || ident == "Self",
+            _ => false,
+        })
+    }
+
+    let is_generic = signature
+        .generics
+        .params
+        .iter()
+        .any(|param| !matches!(param, GenericParam::Lifetime(_)));
+    let output = &signature.output;
+    let mentions_impl_or_self = signature.inputs.iter().any(|arg| match arg {
+        FnArg::Typed(PatType { ty, .. }) => contains_impl_or_self(quote! { #ty }),
+        FnArg::Receiver(_) => true,
+    }) || contains_impl_or_self(quote! { #output });
+
+    !is_associated && !is_generic && !mentions_impl_or_self
+}
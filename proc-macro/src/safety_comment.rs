@@ -0,0 +1,141 @@
+//! Renders the reasons of `assure` attributes as `SAFETY` comments in the expanded code.
+//!
+//! This is used when the `safety-comments` feature is enabled. Comments don't survive in token
+//! streams, so every call with `assure` attributes is wrapped in a parenthesized block that starts
+//! with an unused constant documented with the reasons. Tools that read the expanded code, such as the
+//! output of `cargo expand`, can find the reasons next to the call they belong to.
+//!
+//! # Comment format
+//!
+//! The call
+//!
+//! ```rust,ignore
+//! #[assure(x > 0, reason = "`1` is positive")]
+//! #[assure("`x` is small", reason = "`1` is small")]
+//! foo(1)
+//! ```
+//!
+//! is wrapped like this:
+//!
+//! ```rust,ignore
+//! ({
+//!     #[doc = "SAFETY: x > 0: `1` is positive"]
+//!     #[doc = "SAFETY: \"`x` is small\": `1` is small"]
+//!     const _: () = ();
+//!
+//!     foo(1, /* the marker */)
+//! })
+//! ```
+//!
+//! There is one `doc` attribute per assured precondition, in the order of the `assure`
+//! attributes. It consists of `SAFETY: `, the precondition as it would be written in a `pre`
+//! attribute, `: ` and the reason with its placeholders replaced. Attributes are not allowed on
+//! block expressions on the stable compiler, which is why the constant carries them instead. The
+//! parentheses keep the block from being parsed as the body of an `if` or the `else` of a
+//! `let`-`else` statement.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote_spanned;
+use syn::{Expr, LitStr};
+
+use crate::precondition::Precondition;
+
+/// The prefix of every line of the comment.
+const SAFETY_PREFIX: &str = "SAFETY: ";
+
+/// Wraps the call in a block with the reasons for the assured preconditions in a `doc` attribute.
+///
+/// The call is returned unchanged, if no preconditions were assured.
+pub(crate) fn render_safety_comment(
+    assured: &[(Precondition, String)],
+    call: Expr,
+    span: Span,
+) -> Expr {
+    if assured.is_empty() {
+        return call;
+    }
+
+    let lines = assured.iter().map(|(precondition, reason)| {
+        LitStr::new(
+            &format!("{}{}: {}", SAFETY_PREFIX, precondition, reason),
+            span,
+        )
+    });
+    let tokens: TokenStream = quote_spanned! { span=>
+        ({
+            #(#[doc = #lines])*
+            const _: () = ();
+
+            #call
+        })
+    };
+
+    syn::parse2(tokens).expect("parses as an expression")
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, Item, Lit, Meta, Stmt};
+
+    use super::*;
+
+    /// Returns the block around the call.
+    fn wrapping_block(expr: &Expr) -> &syn::Block {
+        match expr {
+            Expr::Paren(paren) => match &*paren.expr {
+                Expr::Block(block) => &block.block,
+                _ => panic!("the call is not wrapped in a block"),
+            },
+            _ => panic!("the call is not wrapped in parentheses"),
+        }
+    }
+
+    /// Returns the lines of the comment in the block around the call.
+    fn comment_lines(expr: &Expr) -> Vec<String> {
+        let block = wrapping_block(expr);
+        let item = match &block.stmts[0] {
+            Stmt::Item(Item::Const(item)) => item,
+            _ => panic!("the block does not start with a constant"),
+        };
+
+        item.attrs
+            .iter()
+            .map(|attr| match attr.parse_meta() {
+                Ok(Meta::NameValue(meta)) if meta.path.is_ident("doc") => match meta.lit {
+                    Lit::Str(lit) => lit.value(),
+                    _ => panic!("the comment is not a string"),
+                },
+                _ => panic!("the constant has an attribute that is not a comment"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn comment_lists_the_reasons() {
+        let assured = [
+            (parse_quote! { x > 0 }, "`1` is positive".to_string()),
+            (parse_quote! { "`x` is small" }, "`1` is small".to_string()),
+        ];
+        let expr = render_safety_comment(&assured, parse_quote! { foo(1) }, Span::call_site());
+
+        assert_eq!(
+            comment_lines(&expr),
+            [
+                "SAFETY: x > 0: `1` is positive",
+                "SAFETY: \"`x` is small\": `1` is small",
+            ]
+        );
+
+        let block = wrapping_block(&expr);
+        assert_eq!(block.stmts.len(), 2);
+        assert!(matches!(&block.stmts[1], Stmt::Expr(Expr::Call(_))));
+    }
+
+    #[test]
+    fn call_without_assured_preconditions_is_unchanged() {
+        let call: Expr = parse_quote! { foo(1) };
+        let expr = render_safety_comment(&[], call.clone(), Span::call_site());
+
+        assert_eq!(expr, call);
+    }
+}
@@ -0,0 +1,219 @@
+//! Suggests `pre` attributes for the preconditions described in a `# Safety` section.
+//!
+//! This is used when the `suggest-preconditions` feature is enabled. Code that was written before
+//! `pre` was used often describes its preconditions only in the documentation of a function. To
+//! help with migrating such code, a warning is emitted for every function whose `# Safety` section
+//! lists preconditions that are not declared with a `pre` attribute. The warning contains the
+//! attributes that would declare them.
+//!
+//! # Recognized format
+//!
+//! Only the list items in the `# Safety` section are considered. The section ends at the next
+//! heading. An item that starts with a precondition in backticks, optionally followed by a colon
+//! and an explanation, is suggested as that precondition. Every other item is suggested as a
+//! custom precondition with the text of the item.
+//!
+//! ```rust,ignore
+//! /// Reads the value behind the pointer.
+//! ///
+//! /// # Safety
+//! ///
+//! /// - `valid_ptr(ptr, r)`: the pointer must be valid for reads
+//! /// - `ptr` must point to an initialized value
+//! unsafe fn read(ptr: *const u8) -> u8 {
+//!     *ptr
+//! }
+//! ```
+//!
+//! results in the suggestion to add
+//!
+//! ```rust,ignore
+//! #[pre(valid_ptr(ptr, r))]
+//! #[pre("`ptr` must point to an initialized value")]
+//! ```
+
+use proc_macro2::Span;
+use proc_macro_error::emit_warning;
+use syn::{spanned::Spanned, Attribute, Lit, LitStr, Meta};
+
+use crate::precondition::Precondition;
+
+/// The heading of the section that describes the preconditions.
+const SAFETY_HEADING: &str = "# Safety";
+
+/// Emits a warning suggesting `pre` attributes for the undeclared preconditions of the documentation.
+pub(crate) fn suggest_preconditions(attrs: &[Attribute], declared: &[Precondition]) {
+    if let Some((span, suggestions)) = suggested_attributes(attrs, declared) {
+        emit_warning!(
+            span,
+            "the `# Safety` section describes preconditions that are not declared";
+            help = "declare them with these attributes:\n{}", suggestions.join("\n");
+            note = "this is shown, because the `suggest-preconditions` feature of `pre` is enabled"
+        );
+    }
+}
+
+/// Returns the span of the `# Safety` heading and the attributes for the undeclared preconditions.
+///
+/// `None` is returned, if there is no such section or all of its preconditions are declared.
+fn suggested_attributes(
+    attrs: &[Attribute],
+    declared: &[Precondition],
+) -> Option<(Span, Vec<String>)> {
+    let (span, items) = safety_section(attrs)?;
+
+    let suggestions: Vec<_> = items
+        .iter()
+        .map(|item| precondition_for_item(item, span))
+        .filter(|precondition| !declared.contains(precondition))
+        .map(|precondition| format!("#[pre({})]", precondition))
+        .collect();
+
+    if suggestions.is_empty() {
+        None
+    } else {
+        Some((span, suggestions))
+    }
+}
+
+/// Returns the span of the `# Safety` heading and the list items in the section.
+///
+/// An item that spans multiple lines is joined into a single line.
+fn safety_section(attrs: &[Attribute]) -> Option<(Span, Vec<String>)> {
+    let mut lines = attrs.iter().filter_map(|attr| match attr.parse_meta() {
+        Ok(Meta::NameValue(meta)) if meta.path.is_ident("doc") => match meta.lit {
+            Lit::Str(lit) => Some((attr.span(), lit.value())),
+            _ => None,
+        },
+        _ => None,
+    });
+
+    let (span, _) = lines.find(|(_, line)| line.trim() == SAFETY_HEADING)?;
+
+    let mut items: Vec<String> = Vec::new();
+    let mut in_item = false;
+    for (_, line) in lines.take_while(|(_, line)| !line.trim_start().starts_with('#')) {
+        let line = line.trim();
+
+        if let Some(item) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+            items.push(item.trim().to_string());
+            in_item = true;
+        } else if line.is_empty() {
+            in_item = false;
+        } else if in_item {
+            let item = items.last_mut().expect("an item was started");
+            item.push(' ');
+            item.push_str(line);
+        }
+    }
+
+    Some((span, items))
+}
+
+/// Returns the precondition described by the list item.
+fn precondition_for_item(item: &str, span: Span) -> Precondition {
+    if let Some(code) = item.strip_prefix('`') {
+        if let Some((code, rest)) = code.split_once('`') {
+            let rest = rest.trim();
+
+            if rest.is_empty() || rest.starts_with(':') {
+                if let Ok(precondition) = syn::parse_str(code) {
+                    return precondition;
+                }
+            }
+        }
+    }
+
+    Precondition::Custom(LitStr::new(item, span))
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    /// Returns the suggested attributes for the given documentation.
+    fn suggestions(attrs: &[Attribute]) -> Vec<String> {
+        suggested_attributes(attrs, &[])
+            .map(|(_, suggestions)| suggestions)
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn items_in_backticks_are_preconditions() {
+        let attrs: [Attribute; 6] = [
+            parse_quote! { #[doc = " Reads the value behind the pointer."] },
+            parse_quote! { #[doc = ""] },
+            parse_quote! { #[doc = " # Safety"] },
+            parse_quote! { #[doc = ""] },
+            parse_quote! { #[doc = " - `valid_ptr(ptr, r)`: the pointer must be valid for reads"] },
+            parse_quote! { #[doc = " - `len > 0`"] },
+        ];
+
+        assert_eq!(
+            suggestions(&attrs),
+            ["#[pre(valid_ptr(ptr, r))]", "#[pre(len > 0)]"]
+        );
+    }
+
+    #[test]
+    fn other_items_are_custom_preconditions() {
+        let attrs: [Attribute; 6] = [
+            parse_quote! { #[doc = " # Safety"] },
+            parse_quote! { #[doc = " * `ptr` must point to"] },
+            parse_quote! { #[doc = "   an initialized value"] },
+            parse_quote! { #[doc = " - `len` is the number of elements"] },
+            parse_quote! { #[doc = ""] },
+            parse_quote! { #[doc = " Text after the list is ignored."] },
+        ];
+
+        assert_eq!(
+            suggestions(&attrs),
+            [
+                "#[pre(\"`ptr` must point to an initialized value\")]",
+                "#[pre(\"`len` is the number of elements\")]",
+            ]
+        );
+    }
+
+    #[test]
+    fn section_ends_at_the_next_heading() {
+        let attrs: [Attribute; 4] = [
+            parse_quote! { #[doc = " # Safety"] },
+            parse_quote! { #[doc = " - `x > 0`"] },
+            parse_quote! { #[doc = " # Examples"] },
+            parse_quote! { #[doc = " - not a precondition"] },
+        ];
+
+        assert_eq!(suggestions(&attrs), ["#[pre(x > 0)]"]);
+    }
+
+    #[test]
+    fn documentation_without_safety_section_has_no_suggestions() {
+        let attrs: [Attribute; 2] = [
+            parse_quote! { #[doc = " # Panics"] },
+            parse_quote! { #[doc = " - `x > 0`"] },
+        ];
+
+        assert!(safety_section(&attrs).is_none());
+    }
+
+    #[test]
+    fn declared_preconditions_are_not_suggested() {
+        let attrs: [Attribute; 3] = [
+            parse_quote! { #[doc = " # Safety"] },
+            parse_quote! { #[doc = " - `x > 0`"] },
+            parse_quote! { #[doc = " - `x` must be small"] },
+        ];
+
+        let declared: [Precondition; 1] = [parse_quote! { x > 0 }];
+        let (_, suggestions) =
+            suggested_attributes(&attrs, &declared).expect("one precondition is not declared");
+        assert_eq!(suggestions, ["#[pre(\"`x` must be small\")]"]);
+
+        let declared: [Precondition; 2] =
+            [parse_quote! { x > 0 }, parse_quote! { "`x` must be small" }];
+        assert!(suggested_attributes(&attrs, &declared).is_none());
+    }
+}
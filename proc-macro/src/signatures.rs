@@ -0,0 +1,208 @@
+//! Exports the signatures of the functions generated for `extern_crate` modules.
+//!
+//! This is used when the `export-signatures` feature is enabled. For every module annotated with
+//! an `extern_crate` attribute, the signatures of the generated wrapper functions are written to
+//! a report at `$OUT_DIR/pre-signatures.txt`, which is shared by all modules of the crate being
+//! compiled. The report can be compared with the output of tools like `bindgen` to notice when
+//! the declared signatures drift from the original functions.
+//!
+//! # Report format
+//!
+//! The report is a text file with one line per wrapper function. Each line consists of the
+//! following fields separated by tabs:
+//!
+//! 1. the full path of the module containing the wrapper, such as `my_crate::ffi::pre_core::str`
+//! 2. the path of the original function, as it is called by the wrapper
+//! 3. the signature of the wrapper, with a single space between each of its tokens, except after
+//!    punctuation that is joined with the next token, such as the `-` in `->`
+//!
+//! The lines are sorted by all fields. When an annotated module is compiled again, its lines are
+//! replaced, so the report always reflects the last compilation of every module.
+//!
+//! Like for the coverage report, problems with writing the report are only reported as warnings.
+
+use proc_macro2::{Delimiter, Spacing, Span, TokenStream, TokenTree};
+use proc_macro_error::emit_warning;
+use quote::quote;
+use std::{env, fs, io, path::Path as FsPath};
+use syn::{Ident, Path, Signature};
+
+use crate::{
+    coverage::{full_module_path, with_lock},
+    precondition::path_to_string,
+};
+
+/// The name of the report file in `OUT_DIR`.
+const REPORT_FILE_NAME: &str = "pre-signatures.txt";
+
+/// The name of the file used to lock the report while it is updated.
+const LOCK_FILE_NAME: &str = "pre-signatures.lock";
+
+/// The signatures of the wrapper functions generated for one `extern_crate` module.
+pub(crate) struct SignatureExport {
+    /// The path of the annotated module.
+    scope: String,
+    /// The lines of the report for the module.
+    lines: Vec<String>,
+}
+
+impl SignatureExport {
+    /// Creates a new export for the `extern_crate` module with the given name.
+    pub(crate) fn new(module: &Ident) -> SignatureExport {
+        SignatureExport {
+            scope: full_module_path(&[module.to_string()]),
+            lines: Vec::new(),
+        }
+    }
+
+    /// Adds the signature of a wrapper in the given submodule of the annotated module.
+    ///
+    /// `module_path` contains the names of the modules from the annotated module to the wrapper,
+    /// excluding the annotated module itself.
+    pub(crate) fn add(&mut self, module_path: &[String], original: &Path, signature: &Signature) {
+        let module = std::iter::once(self.scope.clone())
+            .chain(module_path.iter().cloned())
+            .collect::<Vec<_>>()
+            .join("::");
+
+        self.lines.push(format!(
+            "{}\t{}\t{}",
+            module,
+            path_to_string(original),
+            tokens_to_string(quote! { #signature })
+        ));
+    }
+
+    /// Merges the signatures into the report.
+    ///
+    /// Nothing is written if `OUT_DIR` is not set, which is the case for crates without a build
+    /// script.
+    pub(crate) fn write(&self) {
+        let out_dir = match env::var_os("OUT_DIR") {
+            Some(out_dir) => out_dir,
+            None => return,
+        };
+        let out_dir = FsPath::new(&out_dir);
+
+        if let Err(err) = with_lock(out_dir, LOCK_FILE_NAME, || {
+            let report_path = out_dir.join(REPORT_FILE_NAME);
+            let existing = match fs::read_to_string(&report_path) {
+                Ok(existing) => existing,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+                Err(err) => return Err(err),
+            };
+
+            fs::write(
+                &report_path,
+                merge_report(&existing, &self.scope, &self.lines),
+            )
+        }) {
+            emit_warning!(
+                Span::call_site(),
+                "failed to write the exported signatures to `{}`: {}",
+                out_dir.join(REPORT_FILE_NAME).display(),
+                err
+            );
+        }
+    }
+}
+
+/// Renders tokens with a single space between each of them.
+///
+/// Punctuation that is joined with the following token, such as the `-` of `->`, is not separated
+/// from it.
+fn tokens_to_string(tokens: TokenStream) -> String {
+    let mut rendered = String::new();
+    let mut separate = false;
+
+    for token in tokens {
+        if separate {
+            rendered.push(' ');
+        }
+        separate = true;
+
+        match token {
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("( ", " )"),
+                    Delimiter::Brace => ("{ ", " }"),
+                    Delimiter::Bracket => ("[ ", " ]"),
+                    Delimiter::None => ("", ""),
+                };
+                let content = tokens_to_string(group.stream());
+
+                if content.is_empty() {
+                    rendered.push_str(open.trim_end());
+                    rendered.push_str(close.trim_start());
+                } else {
+                    rendered.push_str(open);
+                    rendered.push_str(&content);
+                    rendered.push_str(close);
+                }
+            }
+            TokenTree::Punct(punct) => {
+                rendered.push(punct.as_char());
+                separate = punct.spacing() == Spacing::Alone;
+            }
+            token => rendered.push_str(&token.to_string()),
+        }
+    }
+
+    rendered
+}
+
+/// Replaces the lines of `scope` and its submodules in the existing report with the given lines.
+fn merge_report(existing: &str, scope: &str, new_lines: &[String]) -> String {
+    let submodule_prefix = format!("{}::", scope);
+    let mut lines: Vec<String> = existing
+        .lines()
+        .filter(|line| {
+            let module = line.split('\t').next().unwrap_or_default();
+
+            !line.is_empty() && module != scope && !module.starts_with(&submodule_prefix)
+        })
+        .map(|line| line.to_string())
+        .collect();
+
+    lines.extend(new_lines.iter().cloned());
+    lines.sort();
+
+    let mut report = lines.join("\n");
+    report.push('\n');
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signature_tokens_are_separated() {
+        let signature: Signature = syn::parse_quote! {
+            unsafe fn read<'a, T>(src: &'a *const T, _: ()) -> T
+        };
+
+        assert_eq!(
+            tokens_to_string(quote! { #signature }),
+            "unsafe fn read < 'a , T > ( src : & 'a * const T , _ : () ) -> T"
+        );
+    }
+
+    #[test]
+    fn merge_replaces_lines_of_the_module_and_its_submodules() {
+        let existing = "krate::a\ta::f\tfn f ()\n\
+                        krate::a::b\ta::b::g\tfn g ()\n\
+                        krate::ab\tab::h\tfn h ()\n";
+        let report = merge_report(
+            existing,
+            "krate::a",
+            &["krate::a::c\ta::c::i\tfn i ()".to_string()],
+        );
+
+        assert_eq!(
+            report,
+            "krate::a::c\ta::c::i\tfn i ()\nkrate::ab\tab::h\tfn h ()\n"
+        );
+    }
+}
@@ -0,0 +1,90 @@
+//! Removes all `pre` related attributes without rendering them.
+//!
+//! This is used when the `strip` feature is enabled. The resulting code behaves as if `pre` was
+//! never used: no additional parameters, no documentation and no `debug_assert`s are generated.
+
+use syn::{
+    visit_mut::{
+        visit_expr_mut, visit_impl_item_method_mut, visit_item_fn_mut, visit_item_impl_mut,
+        visit_item_mod_mut, visit_item_type_mut, visit_local_mut, visit_trait_item_method_mut,
+        VisitMut,
+    },
+    Attribute, Expr, ImplItemMethod, ItemFn, ItemImpl, ItemMod, ItemType, Local, TraitItemMethod,
+};
+
+use crate::{
+    helpers::{attributes_of_expression, is_attr},
+    let_else::LetElse,
+};
+
+/// The attributes that are removed.
+const STRIPPED_ATTRIBUTES: [&str; 4] = ["pre", "assure", "forward", "forbid_pre"];
+
+/// A visitor that removes all `pre` related attributes.
+pub(crate) struct StripVisitor;
+
+/// Removes all `pre` related attributes from the list.
+fn strip_attributes(attrs: &mut Vec<Attribute>) {
+    attrs.retain(|attr| !STRIPPED_ATTRIBUTES.iter().any(|name| is_attr(name, attr)));
+}
+
+impl VisitMut for StripVisitor {
+    fn visit_item_fn_mut(&mut self, function: &mut ItemFn) {
+        strip_attributes(&mut function.attrs);
+
+        visit_item_fn_mut(self, function);
+    }
+
+    fn visit_impl_item_method_mut(&mut self, method: &mut ImplItemMethod) {
+        strip_attributes(&mut method.attrs);
+
+        visit_impl_item_method_mut(self, method);
+    }
+
+    fn visit_trait_item_method_mut(&mut self, method: &mut TraitItemMethod) {
+        strip_attributes(&mut method.attrs);
+
+        visit_trait_item_method_mut(self, method);
+    }
+
+    fn visit_item_impl_mut(&mut self, impl_block: &mut ItemImpl) {
+        strip_attributes(&mut impl_block.attrs);
+
+        visit_item_impl_mut(self, impl_block);
+    }
+
+    fn visit_item_mod_mut(&mut self, module: &mut ItemMod) {
+        strip_attributes(&mut module.attrs);
+
+        visit_item_mod_mut(self, module);
+    }
+
+    fn visit_item_type_mut(&mut self, alias: &mut ItemType) {
+        strip_attributes(&mut alias.attrs);
+
+        visit_item_type_mut(self, alias);
+    }
+
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        if let Some(mut let_else) = LetElse::from_expr(expr) {
+            strip_attributes(&mut let_else.attrs);
+            self.visit_expr_mut(&mut let_else.init);
+            self.visit_block_mut(&mut let_else.diverge);
+
+            *expr = let_else.into_expr();
+            return;
+        }
+
+        if let Some(attrs) = attributes_of_expression(expr) {
+            strip_attributes(attrs);
+        }
+
+        visit_expr_mut(self, expr);
+    }
+
+    fn visit_local_mut(&mut self, local: &mut Local) {
+        strip_attributes(&mut local.attrs);
+
+        visit_local_mut(self, local);
+    }
+}
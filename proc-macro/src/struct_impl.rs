@@ -71,8 +71,11 @@ use syn::{parse2, spanned::Spanned, Ident, ItemFn, PathArguments};
 
 use crate::{
     call::Call,
-    helpers::add_span_to_signature,
-    precondition::{Precondition, ReadWrite},
+    helpers::{
+        add_marker_argument, add_marker_parameter, add_span_to_signature, contracts_gate,
+        marker_pattern,
+    },
+    precondition::{custom_text, path_to_string, Precondition, ReadWrite},
 };
 
 /// Renders a precondition as a `String` representing an identifier.
@@ -91,23 +94,150 @@ pub(crate) fn render_as_ident(precondition: &Precondition) -> Ident {
 
     let mut ident = match precondition {
         Precondition::ValidPtr {
-            ident, read_write, ..
-        } => format_ident!(
-            "_valid_ptr_{}_{}",
             ident,
-            match read_write {
-                ReadWrite::Read { .. } => "r",
-                ReadWrite::Write { .. } => "w",
-                ReadWrite::Both { .. } => "rw",
+            ty,
+            read_write,
+            ..
+        } => {
+            let ident = format_ident!(
+                "_valid_ptr_{}_{}",
+                ident,
+                match read_write {
+                    ReadWrite::Read { .. } => "r",
+                    ReadWrite::Write { .. } => "w",
+                    ReadWrite::Both { .. } => "rw",
+                }
+            );
+
+            match ty {
+                Some((_, ty)) => format_ident!(
+                    "{}_{}",
+                    ident,
+                    escape_non_ident_chars(quote! { #ty }.to_string())
+                ),
+                None => ident,
             }
-        ),
+        }
         Precondition::Boolean(expr) => format_ident!(
             "_boolean_{}",
             escape_non_ident_chars(quote! { #expr }.to_string())
         ),
         Precondition::Custom(string) => {
-            format_ident!("_custom_{}", escape_non_ident_chars(string.value()))
+            format_ident!("_custom_{}", escape_non_ident_chars(custom_text(string)))
+        }
+        Precondition::See { path, .. } => {
+            format_ident!("_see_{}", escape_non_ident_chars(path.value()))
+        }
+        Precondition::InRange { ident, range, .. } => format_ident!(
+            "_in_range_{}_{}",
+            ident,
+            escape_non_ident_chars(quote! { #range }.to_string())
+        ),
+        Precondition::Kind {
+            path, arguments, ..
+        } => {
+            let arguments: Vec<_> = arguments.iter().map(|arg| arg.to_string()).collect();
+
+            format_ident!(
+                "_kind_{}_{}",
+                escape_non_ident_chars(path_to_string(path)),
+                escape_non_ident_chars(arguments.join(","))
+            )
+        }
+        Precondition::Relation {
+            operator,
+            left,
+            right,
+            ..
+        } => format_ident!(
+            "_relation_{}_{}_{}",
+            escape_non_ident_chars(operator.value()),
+            escape_non_ident_chars(left.to_string()),
+            escape_non_ident_chars(right.to_string())
+        ),
+        Precondition::Locked { ident, .. } => {
+            format_ident!("_locked_{}", escape_non_ident_chars(ident.to_string()))
+        }
+        Precondition::SameProvenance { first, second, .. } => format_ident!(
+            "_same_provenance_{}_{}",
+            escape_non_ident_chars(first.to_string()),
+            escape_non_ident_chars(second.to_string())
+        ),
+        Precondition::NonNull { ident, .. } => {
+            format_ident!("_non_null_{}", escape_non_ident_chars(ident.to_string()))
+        }
+        Precondition::AccessLen {
+            writable,
+            ident,
+            length,
+            ..
+        } => format_ident!(
+            "_{}_{}_{}",
+            if *writable {
+                "writable_len"
+            } else {
+                "readable_len"
+            },
+            escape_non_ident_chars(ident.to_string()),
+            escape_non_ident_chars(length.to_string())
+        ),
+        Precondition::NonZero { ident, .. } => {
+            format_ident!("_nonzero_{}", escape_non_ident_chars(ident.to_string()))
+        }
+        Precondition::Sorted {
+            ident, by: None, ..
+        } => format_ident!("_sorted_{}", escape_non_ident_chars(ident.to_string())),
+        Precondition::Sorted {
+            ident,
+            by: Some(by),
+            ..
+        } => format_ident!(
+            "_sorted_{}_{}",
+            escape_non_ident_chars(ident.to_string()),
+            escape_non_ident_chars(by.value())
+        ),
+        Precondition::Aligned { ident, .. } => {
+            format_ident!("_aligned_{}", escape_non_ident_chars(ident.to_string()))
+        }
+        Precondition::ProperAlign { ident, ty, .. } => format_ident!(
+            "_proper_align_{}_{}",
+            escape_non_ident_chars(ident.to_string()),
+            escape_non_ident_chars(quote! { #ty }.to_string())
+        ),
+        Precondition::Initialized { ident, .. } => {
+            format_ident!("_init_{}", escape_non_ident_chars(ident.to_string()))
         }
+        Precondition::Exclusive { ident, .. } => {
+            format_ident!("_exclusive_{}", escape_non_ident_chars(ident.to_string()))
+        }
+        Precondition::Utf8 { ident, .. } => {
+            format_ident!("_utf8_{}", escape_non_ident_chars(ident.to_string()))
+        }
+        Precondition::OnThread { name, .. } => {
+            format_ident!("_on_thread_{}", escape_non_ident_chars(name.value()))
+        }
+        Precondition::NonOverlapping {
+            first,
+            second,
+            count,
+            ..
+        } => format_ident!(
+            "_non_overlapping_{}_{}_{}",
+            escape_non_ident_chars(first.to_string()),
+            escape_non_ident_chars(second.to_string()),
+            escape_non_ident_chars(count.to_string())
+        ),
+        Precondition::Len {
+            minimum,
+            ident,
+            length,
+            ..
+        } => format_ident!(
+            "_{}_{}_{}",
+            if *minimum { "min_len" } else { "len" },
+            escape_non_ident_chars(ident.to_string()),
+            escape_non_ident_chars(length.to_string())
+        ),
     };
 
     ident.set_span(precondition.span());
@@ -119,6 +249,7 @@ pub(crate) fn render_as_ident(precondition: &Precondition) -> Ident {
 pub(crate) fn render_pre(
     preconditions: Vec<Precondition>,
     function: &mut ItemFn,
+    marker_name: Option<&Ident>,
     span: Span,
 ) -> TokenStream {
     if function.sig.receiver().is_some() {
@@ -139,10 +270,13 @@ pub(crate) fn render_pre(
     );
 
     let function_name = function.sig.ident.clone();
+    let marker = marker_pattern(marker_name, span);
+    let gate = contracts_gate(span);
     let struct_def = quote_spanned! { span=>
         #[allow(non_camel_case_types)]
         #[allow(non_snake_case)]
         #[cfg(not(doc))]
+        #gate
         #vis struct #function_name {
             #preconditions_rendered
         }
@@ -152,10 +286,11 @@ pub(crate) fn render_pre(
     // This improves the error messages for the case where no preconditions are specified.
     add_span_to_signature(span, &mut function.sig);
 
-    function.sig.inputs.push(
+    add_marker_parameter(
+        &mut function.sig,
         parse2(quote_spanned! { span=>
             #[cfg(not(doc))]
-            _: #function_name
+            #marker: #function_name
         })
         .expect("parses as valid function argument"),
     );
@@ -212,7 +347,10 @@ pub(crate) fn render_assure(preconditions: Vec<Precondition>, mut call: Call, sp
             .map(|ident| quote_spanned! { span=> #ident: (), }),
     );
 
-    call.args_mut().push(
+    // The marker struct is never generic, so the additional argument does not influence the
+    // inference of type parameters of the function.
+    add_marker_argument(
+        &mut call,
         parse2(quote_spanned! { span=>
             #path {
                 #preconditions_rendered
@@ -0,0 +1,122 @@
+//! Renders annotations of the preconditions for external verification tools.
+//!
+//! This is used when the `tool-annotations` feature is enabled. Every function with preconditions
+//! gets a hidden constant at the start of its body, which lists the preconditions in a stable
+//! format. Static analyzers can look for the constant in the source code or in the compiled
+//! crate instead of having to understand the `pre` attributes themselves.
+//!
+//! # Annotation format
+//!
+//! The constant looks like this:
+//!
+//! ```rust,ignore
+//! #[doc(hidden)]
+//! #[allow(dead_code)]
+//! const __PRE_TOOL_ANNOTATION: (&str, &[&str]) = ("pre-tool-annotation/1", &["x > 0"]);
+//! ```
+//!
+//! The first element of the tuple names the version of the format. The second element contains
+//! one string per precondition, written the same way as in a `pre` attribute. The preconditions
+//! are listed in the same order as in the markers. The constant is not used by the generated code,
+//! so it has no effect on the compiled function.
+
+use proc_macro2::Span;
+use quote::quote_spanned;
+use syn::{parse2, LitStr, Stmt};
+
+use crate::precondition::Precondition;
+
+/// The name of the constant containing the annotation.
+const ANNOTATION_NAME: &str = "__PRE_TOOL_ANNOTATION";
+
+/// The version of the annotation format.
+const ANNOTATION_VERSION: &str = "pre-tool-annotation/1";
+
+/// Renders the statement annotating a function with the given preconditions.
+pub(crate) fn render_tool_annotation(preconditions: &[Precondition], span: Span) -> Stmt {
+    let name = syn::Ident::new(ANNOTATION_NAME, span);
+    let version = LitStr::new(ANNOTATION_VERSION, span);
+    let mut preconditions = preconditions.to_vec();
+    preconditions.sort_unstable();
+    let preconditions = preconditions
+        .iter()
+        .map(|precondition| LitStr::new(&precondition.to_string(), span));
+
+    parse2(quote_spanned! { span=>
+        #[doc(hidden)]
+        #[allow(dead_code)]
+        const #name: (&str, &[&str]) = (#version, &[#(#preconditions),*]);
+    })
+    .expect("parses as a statement")
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+    use syn::{parse_quote, Expr, Item, Lit};
+
+    use super::*;
+
+    /// Returns the strings contained in the annotation.
+    fn annotation_strings(statement: Stmt) -> (String, Vec<String>) {
+        let item = match statement {
+            Stmt::Item(Item::Const(item)) => item,
+            _ => panic!("the annotation is not a constant"),
+        };
+        assert_eq!(item.ident, ANNOTATION_NAME);
+
+        let string = |expr: &Expr| match expr {
+            Expr::Lit(lit) => match &lit.lit {
+                Lit::Str(lit) => lit.value(),
+                _ => panic!("the annotation contains a non-string literal"),
+            },
+            _ => panic!("the annotation contains a non-literal"),
+        };
+
+        match &*item.expr {
+            Expr::Tuple(tuple) if tuple.elems.len() == 2 => {
+                let version = string(&tuple.elems[0]);
+                let preconditions = match &tuple.elems[1] {
+                    Expr::Reference(reference) => match &*reference.expr {
+                        Expr::Array(array) => array.elems.iter().map(string).collect(),
+                        _ => panic!("the preconditions are not an array"),
+                    },
+                    _ => panic!("the preconditions are not a reference"),
+                };
+
+                (version, preconditions)
+            }
+            _ => panic!("the annotation is not a pair"),
+        }
+    }
+
+    #[test]
+    fn annotation_lists_all_preconditions_in_marker_order() {
+        let preconditions: Vec<Precondition> = vec![
+            parse_quote! { "is \"quoted\"" },
+            parse_quote! { x > 0 },
+            parse_quote! { valid_ptr(p, r) },
+        ];
+        let statement = render_tool_annotation(&preconditions, Span::call_site());
+
+        assert_eq!(
+            annotation_strings(statement),
+            (
+                "pre-tool-annotation/1".to_string(),
+                vec![
+                    "valid_ptr(p, r)".to_string(),
+                    "x > 0".to_string(),
+                    "\"is \\\"quoted\\\"\"".to_string(),
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn annotation_is_hidden_and_unused() {
+        let statement = render_tool_annotation(&[parse_quote! { x > 0 }], Span::call_site());
+        let tokens = quote! { #statement }.to_string();
+
+        assert!(tokens.starts_with("# [doc (hidden)] # [allow (dead_code)] const"));
+    }
+}
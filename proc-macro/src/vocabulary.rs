@@ -0,0 +1,246 @@
+//! Restricts custom preconditions to an approved vocabulary.
+//!
+//! A vocabulary is a text file with one approved phrase per line. Empty lines and lines starting
+//! with `#` are ignored. When a vocabulary applies to a function, every custom precondition of
+//! that function must be one of the approved phrases.
+//!
+//! A vocabulary can be specified for an item with `#[pre(vocabulary = "<path>")]`, where the path is
+//! relative to the file containing the attribute. A default vocabulary for the whole crate can be
+//! specified with the `PRE_VOCABULARY` environment variable, where the path is relative to the
+//! directory containing the `Cargo.toml` of the crate.
+
+use proc_macro2::{Span, TokenStream};
+use proc_macro_error::emit_error;
+use quote::quote;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+use syn::{
+    parse::{Parse, ParseStream},
+    LitStr, Token,
+};
+
+use crate::{
+    helpers::resolve_relative_path,
+    precondition::{custom_text, Precondition},
+};
+
+/// The custom keywords used for vocabularies.
+mod custom_keywords {
+    use syn::custom_keyword;
+
+    custom_keyword!(vocabulary);
+}
+
+/// The environment variable containing the path of the default vocabulary.
+const VOCABULARY_ENV_VAR: &str = "PRE_VOCABULARY";
+
+/// The approved phrases for custom preconditions.
+#[derive(Clone)]
+pub(crate) struct Vocabulary {
+    /// The path of the vocabulary file as it was specified.
+    name: String,
+    /// The resolved path of the vocabulary file.
+    path: PathBuf,
+    /// The approved phrases.
+    phrases: Vec<String>,
+}
+
+impl Parse for Vocabulary {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<custom_keywords::vocabulary>()?;
+        input.parse::<Token![=]>()?;
+        let lit: LitStr = input.parse()?;
+
+        let name = lit.value();
+        let path = resolve_relative_path(&lit);
+
+        Vocabulary::load(&path, name).map_err(|msg| syn::Error::new(lit.span(), msg))
+    }
+}
+
+impl Vocabulary {
+    /// Loads the default vocabulary specified in the `PRE_VOCABULARY` environment variable.
+    ///
+    /// An error is emitted if the vocabulary cannot be loaded.
+    pub(crate) fn from_env() -> Option<Vocabulary> {
+        let name = env::var(VOCABULARY_ENV_VAR).ok()?;
+        let path = match env::var_os("CARGO_MANIFEST_DIR") {
+            Some(manifest_dir) => Path::new(&manifest_dir).join(&name),
+            None => PathBuf::from(&name),
+        };
+
+        match Vocabulary::load(&path, name) {
+            Ok(vocabulary) => Some(vocabulary),
+            Err(msg) => {
+                emit_error!(
+                    Span::call_site(),
+                    "{}", msg;
+                    note = "the vocabulary is specified by the `{}` environment variable",
+                    VOCABULARY_ENV_VAR
+                );
+
+                None
+            }
+        }
+    }
+
+    /// Loads the vocabulary file at the given path, which was specified as `name`.
+    fn load(path: &Path, name: String) -> Result<Vocabulary, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|err| format!("failed to read the vocabulary at `{}`: {}", name, err))?;
+
+        Ok(Vocabulary {
+            name,
+            path: path.to_path_buf(),
+            phrases: parse_phrases(&content),
+        })
+    }
+
+    /// Returns the resolved path of the vocabulary file.
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Checks that the given precondition is approved, if it is a custom precondition.
+    ///
+    /// An error is emitted if it is not approved.
+    pub(crate) fn check(&self, precondition: &Precondition) {
+        let lit = match precondition {
+            Precondition::Custom(lit) => lit,
+            _ => return,
+        };
+
+        let text = custom_text(lit);
+        if self.phrases.iter().any(|phrase| normalize(phrase) == text) {
+            return;
+        }
+
+        let note = if self.phrases.is_empty() {
+            format!("the vocabulary at `{}` is empty", self.name)
+        } else {
+            format!("the approved phrases are listed in `{}`", self.name)
+        };
+        emit_error!(
+            lit,
+            "the custom precondition {:?} is not in the approved vocabulary", lit.value();
+            help =? closest_phrase(&text, &self.phrases)
+                .map(|phrase| format!("the closest approved phrase is {:?}", phrase));
+            note = note
+        );
+    }
+}
+
+/// Renders items that make the compiler recompile the annotated item when one of the given
+/// vocabulary files or the `PRE_VOCABULARY` environment variable changes.
+pub(crate) fn render_dependencies(files: &[PathBuf]) -> TokenStream {
+    let files = files
+        .iter()
+        .map(|file| LitStr::new(&file.to_string_lossy(), Span::call_site()));
+
+    quote! {
+        const _: ::core::option::Option<&str> = ::core::option_env!(#VOCABULARY_ENV_VAR);
+        #(const _: &[u8] = ::core::include_bytes!(#files);)*
+    }
+}
+
+/// Returns the phrases listed in the content of a vocabulary file.
+fn parse_phrases(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Converts a phrase to the text that identifies the equivalent custom precondition.
+fn normalize(phrase: &str) -> String {
+    if cfg!(feature = "case-insensitive-custom") {
+        phrase.to_lowercase()
+    } else {
+        phrase.to_string()
+    }
+}
+
+/// Returns the phrase with the smallest edit distance to `text`.
+///
+/// If multiple phrases have the same distance, the first one is returned.
+fn closest_phrase<'a>(text: &str, phrases: &'a [String]) -> Option<&'a str> {
+    phrases
+        .iter()
+        .enumerate()
+        .min_by_key(|(index, phrase)| (edit_distance(text, &normalize(phrase)), *index))
+        .map(|(_, phrase)| &phrase[..])
+}
+
+/// Computes the Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous_row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current_row = vec![i + 1];
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution_cost = if a_char == *b_char { 0 } else { 1 };
+
+            current_row.push(
+                (previous_row[j] + substitution_cost)
+                    .min(previous_row[j + 1] + 1)
+                    .min(current_row[j] + 1),
+            );
+        }
+
+        previous_row = current_row;
+    }
+
+    previous_row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit_distances() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("abc", ""), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("is valid", "is valid"), 0);
+    }
+
+    #[test]
+    fn phrases_skip_comments_and_empty_lines() {
+        let content = "# approved phrases\n\n  is initialized  \nis aligned\n";
+
+        assert_eq!(
+            parse_phrases(content),
+            vec!["is initialized".to_string(), "is aligned".to_string()]
+        );
+    }
+
+    #[test]
+    fn dependencies_include_the_files() {
+        let rendered = render_dependencies(&[PathBuf::from("/vocabulary.txt")]).to_string();
+
+        assert!(rendered.contains("option_env ! (\"PRE_VOCABULARY\")"));
+        assert!(rendered.contains("include_bytes ! (\"/vocabulary.txt\")"));
+    }
+
+    #[test]
+    fn closest_phrase_is_suggested() {
+        let phrases = vec![
+            "the pointer is aligned".to_string(),
+            "the buffer is initialized".to_string(),
+        ];
+
+        assert_eq!(
+            closest_phrase("the buffer is initialised", &phrases),
+            Some("the buffer is initialized")
+        );
+        assert_eq!(closest_phrase("anything", &[]), None);
+    }
+}
@@ -1,8 +1,15 @@
 use rustc_version::{version_meta, Channel};
+use std::env;
 
 fn main() {
     println!("cargo:rerun-if-changed=build.rs");
 
+    // Compile the crate as `cargo fuzz` would, so that the helpers of the `fuzzing` feature exist.
+    println!("cargo:rustc-check-cfg=cfg(fuzzing)");
+    if env::var_os("CARGO_FEATURE_FUZZING").is_some() {
+        println!("cargo:rustc-cfg=fuzzing");
+    }
+
     match version_meta() {
         Ok(version) if version.channel == Channel::Nightly => {
             println!("cargo:rustc-cfg=nightly");
@@ -0,0 +1,13 @@
+use pre::pre;
+
+#[pre(valid_ptr(ptr, r))]
+unsafe fn read(ptr: *const u8) -> u8 {
+    *ptr
+}
+
+#[pre]
+fn main() {
+    let value = 42;
+
+    unsafe { read(&value) };
+}
@@ -0,0 +1,14 @@
+use pre::pre;
+
+#[pre("Frees Memory")]
+fn foo() {}
+
+#[pre]
+fn main() {
+    // A warning is emitted once for every literal that is not written in lower case.
+    #[assure("Frees Memory", reason = "`foo` frees the memory")]
+    foo();
+
+    #[assure("FREES MEMORY")]
+    foo();
+}
@@ -0,0 +1,35 @@
+use pre::pre;
+
+#[pre(x > 0)]
+#[pre("is a good value")]
+fn foo(x: i32) -> i32 {
+    x
+}
+
+#[pre]
+fn main() {
+    #[assure(x > 0, reason = "`1` is greater than zero")]
+    #[assure("is a good value", reason = "`1` is a good value")]
+    let value = foo(1);
+    assert_eq!(value, 1);
+
+    let result = std::panic::catch_unwind(|| {
+        #[assure(x > 0, reason = "this is wrong on purpose")]
+        #[assure("is a good value", reason = "this is wrong on purpose")]
+        foo(-1)
+    });
+
+    if cfg!(feature = "contracts") {
+        // The contracts are enabled, so the precondition is checked.
+        assert!(result.is_err());
+    } else {
+        // Without the feature, the function has no marker and no `debug_assert`.
+        assert_eq!(result.ok(), Some(-1));
+    }
+
+    #[cfg(not(feature = "contracts"))]
+    {
+        let _: fn(i32) -> i32 = foo;
+        assert_eq!(foo(2), 2);
+    }
+}
@@ -0,0 +1,9 @@
+#[pre::extern_crate(std)]
+mod pre_std {
+    mod ptr {
+        #[pre("`self` is valid")]
+        unsafe fn read(&self) -> u8;
+    }
+}
+
+fn main() {}
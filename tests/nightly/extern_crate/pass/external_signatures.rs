@@ -0,0 +1,25 @@
+use pre::pre;
+
+#[pre::extern_crate(core, from = "../../../signatures/core.pre")]
+mod pre_core {
+    mod ptr {
+        #[pre(valid_ptr(dst, w))]
+        unsafe fn write<T>(dst: *mut T, src: T);
+    }
+}
+
+#[pre]
+fn main() {
+    let mut value = 0;
+
+    #[assure(valid_ptr(dst, w), reason = "the pointer is created from a mutable reference")]
+    unsafe { pre_core::ptr::write(&mut value, 42) };
+
+    #[assure(valid_ptr(src, r), reason = "the pointer is created from a reference")]
+    let read = unsafe { pre_core::ptr::read(&value) };
+    assert_eq!(read, 42);
+
+    #[assure("`v` must contain valid UTF-8", reason = "the bytes are ASCII")]
+    let text = unsafe { pre_core::str::from_utf8_unchecked(b"pre") };
+    assert_eq!(text, "pre");
+}
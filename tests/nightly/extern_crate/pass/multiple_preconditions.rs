@@ -0,0 +1,57 @@
+use pre::pre;
+
+#[pre::extern_crate(core)]
+mod pre_core {
+    mod slice {
+        #[pre(valid_ptr(data, r))]
+        #[pre(len * ::core::mem::size_of::<T>() <= isize::MAX as usize)]
+        #[pre("`data` must point to `len` consecutive properly initialized values of type `T`")]
+        unsafe fn from_raw_parts<'a, T>(data: *const T, len: usize) -> &'a [T];
+    }
+
+    mod ptr {
+        impl<T> NonNull<T> {
+            #[pre(valid_ptr(ptr, r))]
+            #[pre(!ptr.is_null())]
+            const unsafe fn new_unchecked(ptr: *mut T) -> NonNull<T>;
+        }
+    }
+}
+
+#[pre]
+fn main() {
+    let array = [1, 2, 3];
+
+    #[assure(valid_ptr(data, r), reason = "`data` is derived from a reference")]
+    #[assure(
+        len * ::core::mem::size_of::<T>() <= isize::MAX as usize,
+        reason = "the length of an existing array is used"
+    )]
+    #[assure(
+        "`data` must point to `len` consecutive properly initialized values of type `T`",
+        reason = "`data` and `len` are derived from the same array"
+    )]
+    let slice = unsafe { pre_core::slice::from_raw_parts(array.as_ptr(), array.len()) };
+    assert_eq!(slice, &array[..]);
+
+    #[forward(core -> pre_core)]
+    #[assure(valid_ptr(data, r), reason = "`data` is derived from a reference")]
+    #[assure(
+        len * ::core::mem::size_of::<T>() <= isize::MAX as usize,
+        reason = "the length of an existing array is used"
+    )]
+    #[assure(
+        "`data` must point to `len` consecutive properly initialized values of type `T`",
+        reason = "`data` and `len` are derived from the same array"
+    )]
+    let slice = unsafe { core::slice::from_raw_parts(array.as_ptr(), array.len()) };
+    assert_eq!(slice, &array[..]);
+
+    let mut val = 42;
+
+    #[forward(impl pre_core::ptr::NonNull)]
+    #[assure(valid_ptr(ptr, r), reason = "`ptr` is a reference")]
+    #[assure(!ptr.is_null(), reason = "`ptr` is a reference")]
+    let non_null = unsafe { core::ptr::NonNull::new_unchecked(&mut val) };
+    assert_eq!(unsafe { *non_null.as_ptr() }, 42);
+}
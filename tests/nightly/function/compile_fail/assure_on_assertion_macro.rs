@@ -0,0 +1,12 @@
+use pre::pre;
+
+#[pre(x > 0)]
+fn is_even(x: i32) -> bool {
+    x % 2 == 0
+}
+
+#[pre]
+fn main() {
+    #[assure(x > 0, reason = "42 > 0")]
+    assert!(is_even(42));
+}
@@ -0,0 +1,17 @@
+use pre::pre;
+
+fn outer(x: i32) -> i32 {
+    x + 1
+}
+
+#[pre(x > 0)]
+fn inner(x: i32) -> i32 {
+    x * 2
+}
+
+#[pre]
+fn main() {
+    // The attribute applies to the outer call, which does not have the precondition.
+    #[assure(x > 0, reason = "1 > 0")]
+    let _ = outer(inner(1));
+}
@@ -0,0 +1,67 @@
+use pre::pre;
+
+#[pre(x > 0)]
+fn double(x: i32) -> i32 {
+    x * 2
+}
+
+fn setup() {}
+
+#[pre]
+fn main() {
+    let value = {
+        setup();
+        #[assure(x > 0, reason = "1 > 0")]
+        double(1)
+    };
+    assert_eq!(value, 2);
+
+    let nested = {
+        setup();
+        {
+            setup();
+            #[assure(x > 0, reason = "2 > 0")]
+            double(2)
+        }
+    };
+    assert_eq!(nested, 4);
+
+    let in_branch = if value > 0 {
+        setup();
+        #[assure(x > 0, reason = "4 > 0")]
+        double(4)
+    } else {
+        #[assure(x > 0, reason = "5 > 0")]
+        double(5)
+    };
+    assert_eq!(in_branch, 8);
+
+    let in_arm = match value {
+        2 => {
+            setup();
+            #[assure(x > 0, reason = "6 > 0")]
+            double(6)
+        }
+        _ => 0,
+    };
+    assert_eq!(in_arm, 12);
+
+    let labeled = 'block: {
+        if value < 0 {
+            break 'block 0;
+        }
+
+        #[assure(x > 0, reason = "7 > 0")]
+        double(7)
+    };
+    assert_eq!(labeled, 14);
+
+    assert_eq!(
+        {
+            setup();
+            #[assure(x > 0, reason = "8 > 0")]
+            double(8)
+        },
+        16
+    );
+}
@@ -0,0 +1,47 @@
+use pre::pre;
+
+#[pre(x > 0)]
+fn double(x: i32) -> i32 {
+    x * 2
+}
+
+#[pre]
+fn only_call() -> i32 {
+    #[assure(x > 0, reason = "21 > 0")]
+    double(21)
+}
+
+#[pre(x > 0)]
+fn forwarding(x: i32) -> i32 {
+    #[assure(x > 0, reason = "`x > 0` is a precondition of `forwarding`")]
+    double(x)
+}
+
+#[pre]
+fn unit_call() {
+    #[assure(x > 0, reason = "1 > 0")]
+    double(1);
+}
+
+#[pre]
+mod in_module {
+    #[pre(x > 0)]
+    pub(crate) fn triple(x: i32) -> i32 {
+        x * 3
+    }
+
+    pub(crate) fn only_call() -> i32 {
+        #[assure(x > 0, reason = "14 > 0")]
+        triple(14)
+    }
+}
+
+#[pre]
+fn main() {
+    assert_eq!(only_call(), 42);
+    #[assure(x > 0, reason = "4 > 0")]
+    let value = forwarding(4);
+    assert_eq!(value, 8);
+    unit_call();
+    assert_eq!(in_module::only_call(), 42);
+}
@@ -0,0 +1,51 @@
+use pre::pre;
+
+#[pre(x > 0)]
+fn double(x: i32) -> i32 {
+    x * 2
+}
+
+#[pre]
+fn main() {
+    #[assure(x > 0, reason = "1 > 0")]
+    let on_closure = || double(1);
+    assert_eq!(on_closure(), 2);
+
+    let in_block = || {
+        #[assure(x > 0, reason = "2 > 0")]
+        let value = double(2);
+
+        value
+    };
+    assert_eq!(in_block(), 4);
+
+    let as_tail = |x: i32| {
+        #[assure(x > 0, reason = "`x.abs() + 1` is always positive for small values")]
+        double(x.abs() + 1)
+    };
+    assert_eq!(as_tail(-3), 8);
+
+    let offset = 5;
+    #[assure(x > 0, reason = "`offset` is 5")]
+    let moved = move || double(offset);
+    assert_eq!(moved(), 10);
+
+    let nested = || {
+        let inner = || {
+            #[assure(x > 0, reason = "3 > 0")]
+            double(3)
+        };
+
+        inner()
+    };
+    assert_eq!(nested(), 6);
+
+    let mapped: Vec<_> = [1, 2, 3]
+        .iter()
+        .map(|&x| {
+            #[assure(x > 0, reason = "all elements are positive")]
+            double(x)
+        })
+        .collect();
+    assert_eq!(mapped, [2, 4, 6]);
+}
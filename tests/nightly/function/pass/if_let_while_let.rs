@@ -0,0 +1,35 @@
+use pre::pre;
+
+#[pre(x > 17)]
+fn foo(x: i32) -> Option<i32> {
+    Some(x)
+}
+
+#[pre(!v.is_empty())]
+fn pop(v: &mut Vec<i32>) -> Option<i32> {
+    v.pop()
+}
+
+#[pre]
+fn main() {
+    #[assure(x > 17, reason = "42 > 17")]
+    if let Some(x) = foo(42) {
+        assert_eq!(x, 42);
+    } else {
+        unreachable!();
+    }
+
+    let mut v = vec![1, 2, 3];
+    let mut sum = 0;
+
+    #[assure(!v.is_empty(), reason = "the loop is only run while there are elements")]
+    while let Some(x) = pop(&mut v) {
+        sum += x;
+
+        if v.is_empty() {
+            break;
+        }
+    }
+
+    assert_eq!(sum, 6);
+}
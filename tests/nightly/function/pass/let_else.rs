@@ -0,0 +1,32 @@
+use pre::pre;
+
+#[pre(x > 17)]
+fn foo(x: i32) -> Result<i32, ()> {
+    Ok(x)
+}
+
+#[pre(!v.is_empty())]
+fn first(v: &[i32]) -> Option<i32> {
+    v.first().copied()
+}
+
+#[pre]
+fn main() {
+    #[assure(x > 17, reason = "42 > 17")]
+    let Ok(x) = foo(42) else {
+        unreachable!();
+    };
+    assert_eq!(x, 42);
+
+    let v = vec![1, 2, 3];
+
+    #[assure(!v.is_empty(), reason = "`v` has three elements")]
+    let Some(value): Option<i32> = first(&v) else {
+        // Calls inside of the diverging block are handled as well.
+        #[assure(x > 17, reason = "42 > 17")]
+        let _ = foo(42);
+
+        return;
+    };
+    assert_eq!(value, 1);
+}
@@ -0,0 +1,59 @@
+use pre::pre;
+use std::{cell::RefCell, rc::Rc, sync::Arc};
+
+#[pre(x > 0)]
+fn foo(x: i32) -> i32 {
+    x * 2
+}
+
+#[pre(valid_ptr(ptr, r))]
+unsafe fn read(ptr: *const i32) -> i32 {
+    *ptr
+}
+
+#[pre]
+fn main() {
+    let boxed = Box::new(
+        #[assure(x > 0, reason = "1 > 0")]
+        foo(1),
+    );
+    assert_eq!(*boxed, 2);
+
+    let rc = Rc::new(
+        #[assure(x > 0, reason = "2 > 0")]
+        foo(2),
+    );
+    assert_eq!(*rc, 4);
+
+    let arc = Arc::new(
+        #[assure(x > 0, reason = "3 > 0")]
+        foo(3),
+    );
+    assert_eq!(*arc, 6);
+
+    // Constructors can be nested as well.
+    let shared = Rc::new(RefCell::new(
+        #[assure(x > 0, reason = "4 > 0")]
+        foo(4),
+    ));
+    assert_eq!(*shared.borrow(), 8);
+
+    // The attribute is placed on the `unsafe` block containing the call.
+    let value = 5;
+    let boxed = Box::new(
+        #[assure(valid_ptr(ptr, r), reason = "`ptr` is created from a reference")]
+        unsafe {
+            read(&value)
+        },
+    );
+    assert_eq!(*boxed, 5);
+
+    // An attribute on the constructor applies to the call inside of it.
+    #[assure(x > 0, reason = "6 > 0")]
+    let boxed = Box::new(foo(6));
+    assert_eq!(*boxed, 12);
+
+    #[assure(x > 0, reason = "7 > 0")]
+    let shared = Rc::new(Arc::new(foo(7)));
+    assert_eq!(**shared, 14);
+}
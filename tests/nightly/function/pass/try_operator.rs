@@ -0,0 +1,46 @@
+use pre::pre;
+
+#[pre(x > 0)]
+fn checked(x: i32) -> Result<i32, String> {
+    Ok(x)
+}
+
+#[pre(x > 0)]
+fn nested(x: i32) -> Result<Result<i32, String>, String> {
+    #[assure(x > 0, reason = "`x > 0` is a precondition of `nested`")]
+    let inner = checked(x);
+
+    Ok(inner)
+}
+
+#[pre("returns an option")]
+fn optional() -> Option<i32> {
+    Some(42)
+}
+
+#[pre]
+fn results() -> Result<i32, String> {
+    #[assure(x > 0, reason = "1 > 0")]
+    checked(1)?;
+
+    #[assure(x > 0, reason = "2 > 0")]
+    let value = checked(2)?;
+
+    #[assure(x > 0, reason = "3 > 0")]
+    let nested_value = nested(3)??;
+
+    Ok(value + nested_value)
+}
+
+#[pre]
+fn options() -> Option<i32> {
+    #[assure("returns an option", reason = "`optional` always returns an option")]
+    let value = optional()?;
+
+    Some(value)
+}
+
+fn main() {
+    assert_eq!(results(), Ok(5));
+    assert_eq!(options(), Some(42));
+}
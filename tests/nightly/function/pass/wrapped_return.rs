@@ -0,0 +1,69 @@
+use pre::pre;
+
+#[pre(x > 0)]
+fn foo(x: i32) -> i32 {
+    x * 2
+}
+
+#[pre]
+fn checked(x: i32) -> Result<i32, i32> {
+    if x > 0 {
+        return Ok(#[assure(x > 0, reason = "checked by the `if`")]
+        foo(x));
+    }
+
+    Err(x)
+}
+
+#[pre]
+fn checked_err(x: i32) -> Result<(), i32> {
+    if x > 0 {
+        return Err(#[assure(x > 0, reason = "checked by the `if`")]
+        foo(x));
+    }
+
+    Ok(())
+}
+
+#[pre]
+fn checked_some(x: i32) -> Option<i32> {
+    if x <= 0 {
+        return None;
+    }
+
+    // Paths to the variants work the same way.
+    return Option::Some(#[assure(x > 0, reason = "checked by the `if`")]
+    foo(x));
+}
+
+#[pre]
+fn checked_constructor(x: i32) -> Result<Box<i32>, i32> {
+    if x > 0 {
+        // The constructors can also be nested.
+        return Ok(Box::new(#[assure(x > 0, reason = "checked by the `if`")]
+        foo(x)));
+    }
+
+    Err(x)
+}
+
+#[pre]
+fn checked_outer(x: i32) -> Result<i32, i32> {
+    if x > 0 {
+        // An attribute on the whole `return` expression applies to the call as well.
+        #[assure(x > 0, reason = "checked by the `if`")]
+        return Ok(foo(x));
+    }
+
+    Err(x)
+}
+
+fn main() {
+    assert_eq!(checked(2), Ok(4));
+    assert_eq!(checked(0), Err(0));
+    assert_eq!(checked_err(3), Err(6));
+    assert_eq!(checked_some(4), Some(8));
+    assert_eq!(checked_some(-1), None);
+    assert_eq!(checked_constructor(5), Ok(Box::new(10)));
+    assert_eq!(checked_outer(6), Ok(12));
+}
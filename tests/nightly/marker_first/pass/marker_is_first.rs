@@ -0,0 +1,38 @@
+use pre::pre;
+
+#[pre(x > 0)]
+fn foo(x: i32, negate: bool) -> i32 {
+    if negate {
+        -x
+    } else {
+        x
+    }
+}
+
+// Only compiles if the marker is the first parameter, whatever its type is.
+fn marker_first<M>(_: fn(M, i32, bool) -> i32) {}
+
+#[pre]
+fn main() {
+    marker_first(foo);
+
+    #[assure(x > 0, reason = "1 > 0")]
+    let value = foo(1, true);
+    assert_eq!(value, -1);
+
+    // The wrappers of the standard library use the same position.
+    let values = [1, 2, 3];
+    let second = unsafe {
+        #[assure(valid_ptr(src, r), reason = "`values` is a valid array")]
+        #[assure(
+            "`src` points to a properly initialized value of type `T`",
+            reason = "`values` is initialized"
+        )]
+        #[assure(
+            "`T` is `Copy` or the value at `*src` isn't used after this call",
+            reason = "`i32` is `Copy`"
+        )]
+        pre::std::ptr::read_unaligned(values.as_ptr().add(1))
+    };
+    assert_eq!(second, 2);
+}
@@ -0,0 +1,10 @@
+use pre::pre;
+
+#[pre("some condition")]
+fn foo() {}
+
+#[pre]
+fn main() {
+    #[assure("some condition", allow_placeholder, reason = "TODO")]
+    foo();
+}
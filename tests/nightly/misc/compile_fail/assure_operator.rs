@@ -0,0 +1,38 @@
+use pre::pre;
+use std::ops::{Add, Index};
+
+#[derive(Clone, Copy)]
+struct Meters(u32);
+
+impl Add for Meters {
+    type Output = Meters;
+
+    fn add(self, other: Meters) -> Meters {
+        Meters(self.0 + other.0)
+    }
+}
+
+struct Values([u32; 4]);
+
+impl Index<usize> for Values {
+    type Output = u32;
+
+    fn index(&self, index: usize) -> &u32 {
+        &self.0[index]
+    }
+}
+
+#[pre]
+fn main() {
+    let a = Meters(1);
+    let b = Meters(2);
+
+    #[assure("the sum does not overflow", reason = "both values are small")]
+    let _ = a + b;
+
+    let values = Values([1, 2, 3, 4]);
+    let index = 2;
+
+    #[assure(kind = in_range(index, 0..4), reason = "2 is less than 4")]
+    let _ = values[index];
+}
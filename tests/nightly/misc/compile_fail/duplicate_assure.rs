@@ -0,0 +1,13 @@
+use pre::pre;
+
+#[pre(x > 0)]
+#[pre("is called in a test")]
+fn foo(x: i32) {}
+
+#[pre]
+fn main() {
+    #[assure(x > 0, reason = "1 > 0")]
+    #[assure("is called in a test", reason = "this is a test")]
+    #[assure(x > 0, reason = "one is still greater than zero")]
+    foo(1);
+}
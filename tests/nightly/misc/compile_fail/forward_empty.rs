@@ -0,0 +1,11 @@
+use pre::pre;
+
+#[pre("some condition")]
+fn foo() {}
+
+#[pre]
+fn main() {
+    #[forward()]
+    #[assure("some condition", reason = "the condition holds")]
+    foo();
+}
@@ -0,0 +1,42 @@
+use pre::pre;
+
+fn read(p: *const i32) -> i32 {
+    unsafe { *p }
+}
+
+mod checked {
+    use pre::pre;
+
+    #[pre(valid_ptr(p, r))]
+    pub(super) fn read(p: *const i32) -> i32 {
+        unsafe { *p }
+    }
+}
+
+mod documented {
+    use pre::pre;
+
+    #[pre("`p` points to the answer")]
+    pub(super) fn read(p: *const i32) -> i32 {
+        unsafe { *p }
+    }
+}
+
+#[pre]
+fn main() {
+    let answer = 42;
+
+    #[forward(checked for (valid_ptr(p, r)))]
+    #[forward(documented for ("`p` points to the answer"))]
+    #[assure(valid_ptr(p, r), reason = "a reference is a valid pointer")]
+    read(&answer);
+
+    #[forward(checked for (valid_ptr(p, r)))]
+    #[assure(valid_ptr(p, r), reason = "a reference is a valid pointer")]
+    #[assure("`p` points to the answer", reason = "`answer` is the answer")]
+    read(&answer);
+
+    #[forward(checked for ())]
+    #[assure(valid_ptr(p, r), reason = "a reference is a valid pointer")]
+    checked::read(&answer);
+}
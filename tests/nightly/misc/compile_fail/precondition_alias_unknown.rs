@@ -0,0 +1,25 @@
+use pre::pre;
+
+#[pre]
+mod bytes {
+    pre::precondition_alias! {
+        Readable = valid_ptr(p, r), kind = aligned(p);
+    }
+
+    #[pre(alias Writable for dst)]
+    pub unsafe fn write(dst: *mut u8) {
+        *dst = 42;
+    }
+
+    #[pre(alias Readable for src)]
+    pub unsafe fn read(src: *const u8) -> u8 {
+        *src
+    }
+}
+
+#[pre(alias Readable for src)]
+unsafe fn outside(src: *const u8) -> u8 {
+    *src
+}
+
+fn main() {}
@@ -0,0 +1,51 @@
+use pre::pre;
+
+#[pre(exclusive(x > 0, x < 0))]
+mod numbers {
+    #[pre(x > 0)]
+    pub(crate) fn positive(x: i32) -> i32 {
+        x
+    }
+
+    #[pre(x < 0)]
+    pub(crate) fn negative(x: i32) -> i32 {
+        x
+    }
+
+    pub(crate) fn abs(x: i32) -> i32 {
+        if x > 0 {
+            #[assure(x > 0, reason = "checked by the `if`")]
+            positive(x)
+        } else if x < 0 {
+            #[assure(x < 0, reason = "checked by the `if`")]
+            -negative(x)
+        } else {
+            0
+        }
+    }
+
+    #[pre(exclusive("the buffer is empty", "the buffer is full"))]
+    pub(crate) mod buffer {
+        #[pre("the buffer is empty")]
+        pub(crate) fn fill() {}
+
+        #[pre("the buffer is full")]
+        pub(crate) fn drain() {}
+
+        pub(crate) fn cycle() {
+            #[assure("the buffer is empty", reason = "the buffer is created empty")]
+            fill();
+
+            #[assure("the buffer is full", reason = "`fill` fills the buffer")]
+            drain();
+        }
+    }
+}
+
+fn main() {
+    assert_eq!(numbers::abs(-3), 3);
+    assert_eq!(numbers::abs(3), 3);
+    assert_eq!(numbers::abs(0), 0);
+
+    numbers::buffer::cycle();
+}
@@ -0,0 +1,34 @@
+use pre::pre;
+
+#[pre(valid_ptr(arg0, r))]
+#[pre(kind = readable_len(arg0, len))]
+#[pre("the callback is only called once")]
+type Callback = unsafe fn(*const u8, len: usize) -> u8;
+
+#[pre]
+mod callbacks {
+    #[pre(kind = non_null(arg1))]
+    pub(crate) type Write = fn(u8, *mut u8);
+
+    #[pre(no_doc)]
+    #[pre(x > 0)]
+    pub(crate) type Unnamed = fn(x: i32) -> i32;
+}
+
+fn write(value: u8, ptr: *mut u8) {
+    unsafe { *ptr = value };
+}
+
+fn main() {
+    let callback: Callback = |ptr, _| unsafe { *ptr };
+    let data = [42];
+    assert_eq!(unsafe { callback(data.as_ptr(), 1) }, 42);
+
+    let write: callbacks::Write = write;
+    let mut value = 0;
+    write(1, &mut value);
+    assert_eq!(value, 1);
+
+    let double: callbacks::Unnamed = |x| x * 2;
+    assert_eq!(double(2), 4);
+}
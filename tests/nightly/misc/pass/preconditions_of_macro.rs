@@ -0,0 +1,16 @@
+use pre::{pre, preconditions_of};
+
+#[pre("the value is small")]
+#[pre(x > 0)]
+fn double(x: i32) -> i32 {
+    x * 2
+}
+
+fn main() {
+    // The macro is the same as `declared_preconditions`.
+    assert_eq!(
+        preconditions_of!(double),
+        pre::declared_preconditions!(double)
+    );
+    assert_eq!(preconditions_of!(double), ["x > 0", "\"the value is small\""]);
+}
@@ -0,0 +1,54 @@
+use pre::pre;
+
+#[pre("some condition")]
+fn foo() {}
+
+#[pre(reason_pattern = "JIRA-\\d+")]
+fn main() {
+    #[assure("some condition", reason = "checked in JIRA-1234")]
+    foo();
+
+    inner();
+    outer::call_all();
+}
+
+#[pre(reason_pattern = "^see #[0-9]+$")]
+mod inner_module {
+    use super::foo;
+
+    pub(super) fn inner() {
+        #[assure("some condition", reason = "see #42")]
+        foo();
+    }
+}
+
+use inner_module::inner;
+
+#[pre]
+mod outer {
+    #[pre("some condition")]
+    fn foo() {}
+
+    #[pre(reason_pattern = "JIRA-\\d+")]
+    mod inner {
+        pub(super) fn bar() {
+            #[assure("some condition", reason = "checked in JIRA-1234")]
+            super::foo();
+        }
+    }
+
+    pub(super) struct X;
+
+    #[pre(reason_pattern = "JIRA-\\d+")]
+    impl X {
+        pub(super) fn baz() {
+            #[assure("some condition", reason = "checked in JIRA-1234")]
+            foo();
+        }
+    }
+
+    pub(super) fn call_all() {
+        inner::bar();
+        X::baz();
+    }
+}
@@ -0,0 +1,35 @@
+use pre::pre;
+use std::panic;
+
+#[pre(track_caller)]
+#[pre(x > 17)]
+fn foo(x: i32) -> i32 {
+    x
+}
+
+#[pre]
+fn main() {
+    panic::set_hook(Box::new(|_| {}));
+
+    let result = panic::catch_unwind(|| {
+        #[assure(x > 17, reason = "this is wrong on purpose")]
+        foo(3)
+    });
+    let _ = panic::take_hook();
+
+    let payload = result.expect_err("the precondition is violated");
+    let message = payload
+        .downcast_ref::<String>()
+        .expect("the panic message is formatted");
+
+    assert!(
+        message.starts_with("precondition `x > 17` violated at "),
+        "unexpected panic message: {}",
+        message
+    );
+    assert!(
+        message.contains("track_caller.rs:16:"),
+        "unexpected panic message: {}",
+        message
+    );
+}
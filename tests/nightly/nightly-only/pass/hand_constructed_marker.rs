@@ -0,0 +1,27 @@
+use core::marker::PhantomData;
+use pre::{pre, BooleanCondition, CustomCondition, ValidPtrCondition};
+
+#[pre(valid_ptr(p, r))]
+#[pre(x > 0)]
+#[pre("is a custom precondition")]
+unsafe fn foo(p: *const u8, x: usize) -> u8 {
+    *p.add(x - 1)
+}
+
+fn main() {
+    let values = [1, 2, 3];
+
+    let value = unsafe {
+        foo(
+            values.as_ptr(),
+            2,
+            PhantomData::<(
+                ValidPtrCondition<"p", "r">,
+                BooleanCondition<"x > 0">,
+                CustomCondition<"is a custom precondition">,
+            )>,
+        )
+    };
+
+    assert_eq!(value, 2);
+}
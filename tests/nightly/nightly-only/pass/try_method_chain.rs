@@ -0,0 +1,38 @@
+use pre::pre;
+
+struct Buffer {
+    data: [u8; 4],
+}
+
+impl Buffer {
+    #[pre(index < 4)]
+    fn get(&self, index: usize) -> u8 {
+        self.data[index]
+    }
+
+    #[pre(index < 4)]
+    fn checked(&self, index: usize) -> Result<u8, ()> {
+        Ok(self.data[index])
+    }
+}
+
+fn open() -> Result<Buffer, ()> {
+    Ok(Buffer { data: [1, 2, 3, 4] })
+}
+
+#[pre]
+fn read() -> Result<u8, ()> {
+    let index = 2;
+
+    #[assure(index < 4, reason = "2 < 4")]
+    let first = open()?.get(index);
+
+    #[assure(index < 4, reason = "2 < 4")]
+    let second = open()?.checked(index)?;
+
+    Ok(first + second)
+}
+
+fn main() {
+    assert_eq!(read(), Ok(6));
+}
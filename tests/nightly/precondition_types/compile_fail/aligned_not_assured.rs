@@ -0,0 +1,19 @@
+use pre::pre;
+
+#[pre(valid_ptr(ptr, r))]
+#[pre(kind = aligned(ptr))]
+unsafe fn read(ptr: *const u32) -> u32 {
+    *ptr
+}
+
+#[pre]
+fn main() {
+    let value = 42u32;
+
+    #[assure(valid_ptr(ptr, r), reason = "the pointer is created from a reference")]
+    let _ = unsafe { read(&value) };
+
+    #[assure(valid_ptr(ptr, r), reason = "the pointer is created from a reference")]
+    #[assure(kind = aligned(value), reason = "references are always aligned")]
+    let _ = unsafe { read(&value) };
+}
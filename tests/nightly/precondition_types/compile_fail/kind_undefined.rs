@@ -0,0 +1,6 @@
+use pre::pre;
+
+#[pre(kind = non_zero(x))]
+fn foo(x: u32) {}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use pre::pre;
+
+#[pre(kind = len(key, 16))]
+fn checksum(key: &[u8]) -> u8 {
+    key.iter().fold(0, |sum, byte| sum ^ byte)
+}
+
+#[pre(kind = min_len(buf, count))]
+fn not_a_parameter(buf: &[u8]) -> usize {
+    buf.len()
+}
+
+#[pre]
+fn main() {
+    #[assure(kind = min_len(key, 16), reason = "the array has 16 elements")]
+    let _ = checksum(&[0; 16]);
+}
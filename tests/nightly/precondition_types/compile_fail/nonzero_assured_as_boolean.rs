@@ -0,0 +1,12 @@
+use pre::pre;
+
+#[pre(kind = nonzero(divisor))]
+fn divide(dividend: u32, divisor: u32) -> u32 {
+    dividend / divisor
+}
+
+#[pre]
+fn main() {
+    #[assure(divisor != 0, reason = "`4` is not zero")]
+    divide(12, 4);
+}
@@ -0,0 +1,16 @@
+use pre::pre;
+
+#[pre(kind = writable_len(dst, count))]
+unsafe fn zero(dst: *mut u8, count: usize) {
+    std::ptr::write_bytes(dst, 0, count);
+}
+
+#[pre]
+fn main() {
+    let mut buffer = [1; 4];
+
+    #[assure(kind = readable_len(dst, count), reason = "`buffer` has 4 elements")]
+    unsafe {
+        zero(buffer.as_mut_ptr(), 4)
+    };
+}
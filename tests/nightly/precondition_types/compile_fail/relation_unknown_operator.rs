@@ -0,0 +1,6 @@
+use pre::pre;
+
+#[pre(kind = relation("=<", a, b))]
+fn foo(a: i32, b: i32) {}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use pre::pre;
+
+#[pre(valid_ptr(ptr: *const u16, r))]
+unsafe fn foo(ptr: *const u16) -> u16 {
+    *ptr
+}
+
+#[pre]
+fn main() {
+    #[assure(valid_ptr(ptr: *const u8, r), reason = "it is created from a reference")]
+    unsafe {
+        foo(&42)
+    };
+}
@@ -0,0 +1,31 @@
+use pre::pre;
+
+#[pre(kind = aligned(ptr))]
+fn is_null(ptr: *const u64) -> bool {
+    ptr.is_null()
+}
+
+#[pre(valid_ptr(ptr, r))]
+#[pre(kind = aligned(ptr))]
+#[pre(kind = non_null(ptr))]
+unsafe fn read(ptr: *const u32) -> u32 {
+    *ptr
+}
+
+#[pre]
+fn main() {
+    let value = 42u64;
+
+    #[assure(kind = aligned(ptr), reason = "references are always aligned")]
+    let result = is_null(&value);
+    assert!(!result);
+
+    let value = 42u32;
+
+    // The preconditions share the pointer, but all of them must be assured.
+    #[assure(kind = aligned(ptr), reason = "references are always aligned")]
+    #[assure(kind = non_null(ptr), reason = "references are never null")]
+    #[assure(valid_ptr(ptr, r), reason = "the pointer is created from a reference")]
+    let result = unsafe { read(&value) };
+    assert_eq!(result, 42);
+}
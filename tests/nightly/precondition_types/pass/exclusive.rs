@@ -0,0 +1,34 @@
+use pre::pre;
+
+#[pre(valid_ptr(ptr, r+w))]
+#[pre(kind = init(ptr))]
+#[pre(kind = exclusive(ptr))]
+unsafe fn increment(ptr: *mut u32) {
+    *ptr += 1;
+}
+
+#[pre(kind = no_alias(dst))]
+#[pre(valid_ptr(dst, w))]
+unsafe fn write(dst: *mut u32, value: u32) {
+    dst.write(value);
+}
+
+#[pre]
+fn main() {
+    let mut value = 41;
+
+    #[assure(kind = exclusive(ptr), reason = "`value` is not borrowed during the call")]
+    #[assure(valid_ptr(ptr, r+w), reason = "the pointer is created from a reference")]
+    #[assure(kind = init(ptr), reason = "`value` is initialized")]
+    unsafe {
+        increment(&mut value)
+    };
+
+    #[assure(valid_ptr(dst, w), reason = "the pointer is created from a reference")]
+    #[assure(kind = exclusive(dst), reason = "`value` is not borrowed during the call")]
+    unsafe {
+        write(&mut value, 42)
+    };
+
+    assert_eq!(value, 42);
+}
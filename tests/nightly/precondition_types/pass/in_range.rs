@@ -0,0 +1,117 @@
+// A lower bound of `0` for unsigned values must not cause a warning.
+#![deny(unused_comparisons)]
+
+use pre::pre;
+use std::panic;
+
+#[pre(kind = in_range(x, 0..10))]
+fn exclusive(x: i32) -> i32 {
+    x
+}
+
+#[pre(kind = in_range(x, 0..16))]
+fn unsigned(x: usize) -> usize {
+    x
+}
+
+#[pre(kind = in_range(x, 0..=10))]
+fn inclusive(x: i32) -> i32 {
+    x
+}
+
+#[pre(kind = in_range(x, 5..))]
+fn lower_bound(x: i32) -> i32 {
+    x
+}
+
+#[pre(kind = in_range(x, ..=-5))]
+fn upper_bound(x: i32) -> i32 {
+    x
+}
+
+#[pre(kind = in_range(x, low..high))]
+fn variable_bounds(x: i32, low: i32, high: i32) -> i32 {
+    x - low + high
+}
+
+/// Checks whether the closure panics with the given precondition.
+fn violates(precondition: &str, f: impl FnOnce() + panic::UnwindSafe) -> bool {
+    match panic::catch_unwind(f) {
+        Ok(()) => false,
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<String>()
+                .map(String::as_str)
+                .or_else(|| payload.downcast_ref::<&str>().copied())
+                .expect("the panic message is a string");
+
+            assert_eq!(
+                message,
+                format!("in_range precondition was wrongly assured: `{}`", precondition)
+            );
+
+            true
+        }
+    }
+}
+
+#[pre]
+fn main() {
+    panic::set_hook(Box::new(|_| {}));
+
+    #[assure(kind = in_range(x, 0..10), reason = "0 is in the range")]
+    let value = exclusive(0);
+    assert_eq!(value, 0);
+
+    #[assure(kind = in_range(x, 0..16), reason = "15 is in the range")]
+    let value = unsigned(15);
+    assert_eq!(value, 15);
+    let violated = violates("kind = in_range(x, 0 .. 10)", || {
+        #[assure(kind = in_range(x, 0..10), reason = "this is wrong on purpose")]
+        exclusive(10);
+    });
+    assert!(violated);
+    let violated = violates("kind = in_range(x, 0 .. 10)", || {
+        #[assure(kind = in_range(x, 0..10), reason = "this is wrong on purpose")]
+        exclusive(-1);
+    });
+    assert!(violated);
+
+    #[assure(kind = in_range(x, 0..=10), reason = "the end is included")]
+    let value = inclusive(10);
+    assert_eq!(value, 10);
+    let violated = violates("kind = in_range(x, 0 ..= 10)", || {
+        #[assure(kind = in_range(x, 0..=10), reason = "this is wrong on purpose")]
+        inclusive(11);
+    });
+    assert!(violated);
+
+    #[assure(kind = in_range(x, 5..), reason = "5 is the lower bound")]
+    let value = lower_bound(5);
+    assert_eq!(value, 5);
+    let violated = violates("kind = in_range(x, 5 ..)", || {
+        #[assure(kind = in_range(x, 5..), reason = "this is wrong on purpose")]
+        lower_bound(4);
+    });
+    assert!(violated);
+
+    #[assure(kind = in_range(x, ..=-5), reason = "the end is included")]
+    let value = upper_bound(-5);
+    assert_eq!(value, -5);
+    let violated = violates("kind = in_range(x, ..= - 5)", || {
+        #[assure(kind = in_range(x, ..=-5), reason = "this is wrong on purpose")]
+        upper_bound(-4);
+    });
+    assert!(violated);
+
+    #[assure(kind = in_range(x, low..high), reason = "2 is between 1 and 3")]
+    let value = variable_bounds(2, 1, 3);
+    assert_eq!(value, 4);
+    let violated = violates("kind = in_range(x, low .. high)", || {
+        #[assure(kind = in_range(x, low..high), reason = "this is wrong on purpose")]
+        variable_bounds(3, 1, 3);
+    });
+    assert!(violated);
+
+    let _ = panic::take_hook();
+}
@@ -0,0 +1,32 @@
+use pre::pre;
+use std::mem::MaybeUninit;
+
+#[pre(valid_ptr(ptr, w))]
+#[pre(kind = aligned(ptr))]
+unsafe fn write(ptr: *mut u32, value: u32) {
+    ptr.write(value);
+}
+
+#[pre(valid_ptr(ptr, r))]
+#[pre(kind = aligned(ptr))]
+#[pre(kind = initialized(ptr))]
+unsafe fn read(ptr: *const u32) -> u32 {
+    *ptr
+}
+
+#[pre]
+fn main() {
+    let mut slot = MaybeUninit::<u32>::uninit();
+
+    // Writing does not require initialized memory.
+    #[assure(valid_ptr(ptr, w), reason = "the pointer is created from a `MaybeUninit`")]
+    #[assure(kind = aligned(ptr), reason = "`MaybeUninit` has the alignment of `u32`")]
+    unsafe { write(slot.as_mut_ptr(), 42) };
+
+    // Both spellings can be used interchangeably.
+    #[assure(kind = init(ptr), reason = "the value was written above")]
+    #[assure(valid_ptr(ptr, r), reason = "the pointer is created from a `MaybeUninit`")]
+    #[assure(kind = aligned(ptr), reason = "`MaybeUninit` has the alignment of `u32`")]
+    let result = unsafe { read(slot.as_ptr()) };
+    assert_eq!(result, 42);
+}
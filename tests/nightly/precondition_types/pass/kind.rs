@@ -0,0 +1,43 @@
+use pre::{define_precondition_kind, pre};
+
+pub mod kinds {
+    use pre::define_precondition_kind;
+
+    define_precondition_kind! {
+        /// The two buffers do not overlap.
+        pub disjoint(a, b)
+    }
+}
+
+define_precondition_kind! {
+    /// The value is not zero.
+    non_zero(value)
+}
+
+define_precondition_kind! {
+    /// The global state is initialized.
+    initialized()
+}
+
+#[pre(kind = non_zero(divisor))]
+#[pre(kind = kinds::disjoint(src, dst))]
+#[pre(kind = self::initialized())]
+fn foo(divisor: u32, src: &[u8], dst: &mut [u8]) -> u32 {
+    dst.copy_from_slice(src);
+
+    100 / divisor
+}
+
+#[pre]
+fn main() {
+    let src = [1, 2];
+    let mut dst = [0; 2];
+
+    #[assure(kind = self::initialized(), reason = "nothing needs to be initialized in this test")]
+    #[assure(kind = kinds::disjoint(src, dst), reason = "both are separate local arrays")]
+    #[assure(kind = non_zero(divisor), reason = "5 is not zero")]
+    let result = foo(5, &src, &mut dst);
+
+    assert_eq!(result, 20);
+    assert_eq!(dst, src);
+}
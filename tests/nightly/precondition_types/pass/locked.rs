@@ -0,0 +1,44 @@
+use pre::pre;
+use std::sync::Mutex;
+
+#[pre(kind = locked(counter))]
+#[pre(valid_ptr(counter, r+w))]
+unsafe fn increment_unlocked(counter: *mut u32) {
+    *counter += 1;
+}
+
+#[pre(kind = locked(first))]
+#[pre(kind = locked(second))]
+fn swap_locked(first: &mut u32, second: &mut u32) {
+    std::mem::swap(first, second);
+}
+
+#[pre]
+fn main() {
+    let mutex = Mutex::new(1);
+
+    {
+        let mut guard = mutex.lock().unwrap();
+        let counter: *mut u32 = &mut *guard;
+
+        #[assure(kind = locked(counter), reason = "`guard` is held until the end of the block")]
+        #[assure(valid_ptr(counter, r+w), reason = "`counter` comes from a mutable reference")]
+        unsafe {
+            increment_unlocked(counter)
+        };
+    }
+    assert_eq!(*mutex.lock().unwrap(), 2);
+
+    let mut a = 1;
+    let mut b = 2;
+    #[assure(
+        kind = locked(second),
+        reason = "the values are local and cannot be accessed elsewhere"
+    )]
+    #[assure(
+        kind = locked(first),
+        reason = "the values are local and cannot be accessed elsewhere"
+    )]
+    swap_locked(&mut a, &mut b);
+    assert_eq!((a, b), (2, 1));
+}
@@ -0,0 +1,30 @@
+use pre::pre;
+
+#[pre(kind = non_null(name))]
+fn name_len(name: *const u8) -> usize {
+    if name.is_null() {
+        0
+    } else {
+        1
+    }
+}
+
+#[pre(kind = non_null(ptr))]
+#[pre(valid_ptr(ptr, r))]
+unsafe fn read(ptr: *const u8) -> u8 {
+    *ptr
+}
+
+#[pre]
+fn main() {
+    let value = 42u8;
+
+    #[assure(kind = non_null(name), reason = "the pointer is created from a reference")]
+    let len = name_len(&value);
+    assert_eq!(len, 1);
+
+    #[assure(kind = non_null(ptr), reason = "the pointer is created from a reference")]
+    #[assure(valid_ptr(ptr, r), reason = "the pointer is created from a reference")]
+    let result = unsafe { read(&value) };
+    assert_eq!(result, 42);
+}
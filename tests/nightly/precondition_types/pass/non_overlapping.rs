@@ -0,0 +1,53 @@
+use pre::pre;
+
+#[pre(valid_ptr(src, r))]
+#[pre(valid_ptr(dst, w))]
+#[pre(kind = non_overlapping(dst, src, count))]
+unsafe fn copy_nonoverlapping<T>(src: *const T, dst: *mut T, count: usize) {
+    std::ptr::copy_nonoverlapping(src, dst, count)
+}
+
+#[pre]
+fn main() {
+    let mut array = [1u16, 2, 3, 4];
+    let src = array.as_ptr();
+    let dst = unsafe { array.as_mut_ptr().add(2) };
+
+    #[assure(valid_ptr(src, r), reason = "`src` points to the first element")]
+    #[assure(valid_ptr(dst, w), reason = "`dst` points to the third element")]
+    #[assure(
+        kind = non_overlapping(dst, src, count),
+        reason = "the first two and the last two elements are disjoint"
+    )]
+    unsafe {
+        copy_nonoverlapping(src, dst, 2)
+    };
+    assert_eq!(array, [1, 2, 1, 2]);
+
+    // The order of the pointers does not matter.
+    let mut other = [0u16; 2];
+    #[assure(valid_ptr(src, r), reason = "`src` points to `array`")]
+    #[assure(valid_ptr(dst, w), reason = "`dst` points to `other`")]
+    #[assure(kind = non_overlapping(src, dst, count), reason = "the arrays are different")]
+    unsafe {
+        copy_nonoverlapping(array.as_ptr(), other.as_mut_ptr(), 2)
+    };
+    assert_eq!(other, [1, 2]);
+
+    let violated = std::panic::catch_unwind(|| {
+        let mut array = [1u16, 2, 3, 4];
+        let src = array.as_ptr();
+        let dst = unsafe { array.as_mut_ptr().add(1) };
+
+        #[assure(valid_ptr(src, r), reason = "`src` points to the first element")]
+        #[assure(valid_ptr(dst, w), reason = "`dst` points to the second element")]
+        #[assure(kind = non_overlapping(dst, src, count), reason = "this is wrong on purpose")]
+        unsafe {
+            copy_nonoverlapping(src, dst, 2)
+        };
+    });
+
+    if cfg!(debug_assertions) {
+        assert!(violated.is_err());
+    }
+}
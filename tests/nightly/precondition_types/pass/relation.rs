@@ -0,0 +1,41 @@
+use pre::pre;
+
+#[pre(kind = relation("<", start, end))]
+#[pre(kind = relation(">=", len, end))]
+fn slice_len(start: usize, end: usize, len: usize) -> usize {
+    end - start
+}
+
+#[pre(kind = relation("==", a, b))]
+#[pre(kind = relation("!=", a, c))]
+fn all_relations(a: i32, b: i32, c: i32) {}
+
+#[pre(kind = relation("<=", low, high))]
+#[pre(kind = relation(">", high, zero))]
+fn bounds(low: i32, high: i32, zero: i32) {}
+
+#[pre]
+fn main() {
+    #[assure(kind = relation("<", start, end), reason = "2 < 5")]
+    #[assure(kind = relation(">=", len, end), reason = "8 >= 5")]
+    let len = slice_len(2, 5, 8);
+    assert_eq!(len, 3);
+
+    #[assure(kind = relation("==", a, b), reason = "1 == 1")]
+    #[assure(kind = relation("!=", a, c), reason = "1 != 2")]
+    all_relations(1, 1, 2);
+
+    #[assure(kind = relation("<=", low, high), reason = "3 <= 3")]
+    #[assure(kind = relation(">", high, zero), reason = "3 > 0")]
+    bounds(3, 3, 0);
+
+    let violated = std::panic::catch_unwind(|| {
+        #[assure(kind = relation("<=", low, high), reason = "this is wrong on purpose")]
+        #[assure(kind = relation(">", high, zero), reason = "1 > 0")]
+        bounds(2, 1, 0);
+    });
+
+    if cfg!(debug_assertions) {
+        assert!(violated.is_err());
+    }
+}
@@ -0,0 +1,41 @@
+use pre::pre;
+
+#[pre(kind = same_provenance(start, end))]
+unsafe fn distance(start: *const u8, end: *const u8) -> isize {
+    end.offset_from(start)
+}
+
+#[pre(kind = same_provenance(dst, src))]
+#[pre(valid_ptr(src, r))]
+#[pre(valid_ptr(dst, w))]
+unsafe fn copy_within(src: *const u8, dst: *mut u8) {
+    dst.write(src.read());
+}
+
+#[pre]
+fn main() {
+    let array = [1u8, 2, 3, 4];
+    let start = array.as_ptr();
+    let end = unsafe { start.add(3) };
+
+    #[assure(kind = same_provenance(start, end), reason = "both point into `array`")]
+    let result = unsafe { distance(start, end) };
+    assert_eq!(result, 3);
+
+    // The order of the pointers does not matter.
+    #[assure(kind = same_provenance(end, start), reason = "both point into `array`")]
+    let result = unsafe { distance(start, end) };
+    assert_eq!(result, 3);
+
+    let mut array = [1u8, 2, 3, 4];
+    let src = array.as_ptr();
+    let dst = unsafe { array.as_mut_ptr().add(2) };
+
+    #[assure(kind = same_provenance(src, dst), reason = "both point into `array`")]
+    #[assure(valid_ptr(src, r), reason = "`src` points to the first element")]
+    #[assure(valid_ptr(dst, w), reason = "`dst` points to the third element")]
+    unsafe {
+        copy_within(src, dst)
+    };
+    assert_eq!(array, [1, 2, 1, 4]);
+}
@@ -0,0 +1,16 @@
+use pre::pre;
+
+/// Rules that need to be followed when calling `foo`.
+pub mod safety {
+    /// The value passed to `foo` must be a good value.
+    pub struct Rules;
+}
+
+#[pre(see = "safety::Rules")]
+fn foo(_x: i32) {}
+
+#[pre]
+fn main() {
+    #[assure(see = "safety::Rules", reason = "42 is a good value")]
+    foo(42);
+}
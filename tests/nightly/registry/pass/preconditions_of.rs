@@ -0,0 +1,36 @@
+use pre::pre;
+
+#[pre(x > 0)]
+fn foo(x: i32) -> i32 {
+    x * 2
+}
+
+#[pre(!v.is_empty())]
+fn first<T: Copy>(v: &[T]) -> T {
+    v[0]
+}
+
+fn no_preconditions() {}
+
+fn main() {
+    assert_eq!(
+        pre::preconditions_of(foo as *const ()),
+        Some(&["x > 0"][..])
+    );
+
+    let contract = pre::CONTRACTS
+        .iter()
+        .find(|contract| contract.module_path == module_path!() && contract.function == "foo")
+        .expect("`foo` is registered");
+    assert_eq!(contract.address, Some(foo as *const ()));
+
+    // Generic functions have no single address.
+    let generic = pre::CONTRACTS
+        .iter()
+        .find(|contract| contract.module_path == module_path!() && contract.function == "first")
+        .expect("`first` is registered");
+    assert_eq!(generic.address, None);
+    assert_eq!(pre::preconditions_of(first::<i32> as *const ()), None);
+
+    assert_eq!(pre::preconditions_of(no_preconditions as *const ()), None);
+}
@@ -0,0 +1,24 @@
+use pre::pre;
+use std::thread;
+
+#[pre(kind = on_thread("main"))]
+fn redraw() -> u32 {
+    1
+}
+
+#[pre]
+fn main() {
+    #[assure(kind = on_thread("main"), reason = "this is called directly from `main`")]
+    let frames = redraw();
+    assert_eq!(frames, 1);
+
+    let result = thread::Builder::new()
+        .name("worker".to_string())
+        .spawn(|| {
+            #[assure(kind = on_thread("main"), reason = "this is wrong on purpose")]
+            redraw()
+        })
+        .expect("the thread can be spawned")
+        .join();
+    assert!(result.is_err());
+}
@@ -0,0 +1,39 @@
+use pre::pre;
+
+#[pre(x > 0)]
+#[pre("`x` is not too large")]
+const fn double(x: u8) -> u8 {
+    x * 2
+}
+
+#[pre(valid_ptr(ptr, r))]
+unsafe fn read<T: Copy>(ptr: *const T) -> T {
+    *ptr
+}
+
+#[pre]
+const fn doubled_one() -> u8 {
+    #[assure(x > 0, reason = "1 > 0")]
+    #[assure("`x` is not too large", reason = "1 is small")]
+    double(1)
+}
+
+#[pre]
+fn main() {
+    assert_eq!(doubled_one(), 2);
+
+    #[assure(x > 0, reason = "{cond} holds for 2")]
+    #[assure("`x` is not too large", reason = "2 is small")]
+    let value = double(2);
+    assert_eq!(value, 4);
+
+    #[assure(valid_ptr(ptr, r), reason = "`ptr` is a reference")]
+    let value = unsafe { read(&3) };
+    assert_eq!(value, 3);
+
+    let value = 1 + #[assure(valid_ptr(ptr, r), reason = "`ptr` is a reference")]
+    unsafe {
+        read(&4)
+    };
+    assert_eq!(value, 5);
+}
@@ -0,0 +1,12 @@
+use pre::pre;
+
+#[pre(x > 0)]
+fn foo(x: i32) -> i32 {
+    x
+}
+
+#[pre]
+fn main() {
+    #[assure(x > 0, reason = "1 > 0")]
+    foo(1);
+}
@@ -0,0 +1,6 @@
+#[pre::assure("some precondition", reason = "no `pre` attribute is present")]
+fn foo() {}
+
+fn main() {
+    foo();
+}
@@ -0,0 +1,31 @@
+use pre::pre;
+
+#[pre::extern_crate(core)]
+mod pre_core {
+    mod ptr {
+        #[pre(valid_ptr(src, r))]
+        unsafe fn read<T>(src: *const T) -> T;
+
+        impl<T> NonNull<T> {
+            #[pre(!ptr.is_null())]
+            const unsafe fn new_unchecked(ptr: *mut T) -> NonNull<T>;
+        }
+    }
+}
+
+#[pre]
+fn main() {
+    let mut value = 42;
+
+    #[forward(pre_core)]
+    #[assure(valid_ptr(src, r), reason = "a reference is a valid pointer")]
+    let copy = unsafe { core::ptr::read(&value) };
+    assert_eq!(copy, 42);
+
+    let _: unsafe fn(*const i32) -> i32 = pre_core::ptr::read;
+
+    #[forward(impl pre_core::ptr::NonNull)]
+    #[assure(!ptr.is_null(), reason = "a reference is never null")]
+    let ptr = unsafe { core::ptr::NonNull::new_unchecked(&mut value) };
+    assert_eq!(unsafe { *ptr.as_ptr() }, 42);
+}
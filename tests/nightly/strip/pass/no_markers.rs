@@ -0,0 +1,35 @@
+use pre::{pre, pre_call};
+
+#[pre(x > 10)]
+#[pre("some precondition")]
+fn foo(x: i32) -> i32 {
+    x
+}
+
+#[pre(valid_ptr(ptr, r))]
+unsafe fn read(ptr: *const i32) -> i32 {
+    *ptr
+}
+
+#[pre]
+fn main() {
+    // The signatures are unchanged, so the functions can be called without any assurances and
+    // coerced to the original function pointer types.
+    assert_eq!(foo(42), 42);
+    let _: fn(i32) -> i32 = foo;
+    let _: unsafe fn(*const i32) -> i32 = read;
+
+    // The boolean precondition is violated, but no `debug_assert` is generated.
+    #[assure(x > 10, reason = "this is wrong on purpose")]
+    #[assure("some precondition", reason = "the attributes are removed")]
+    let value = foo(3);
+    assert_eq!(value, 3);
+
+    let number = 7;
+    let value = pre_call!(
+        valid_ptr(ptr, r),
+        reason = "the attributes are removed",
+        unsafe { read(&number) }
+    );
+    assert_eq!(value, 7);
+}
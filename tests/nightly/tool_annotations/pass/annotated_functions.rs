@@ -0,0 +1,49 @@
+use pre::pre;
+
+#[pre(x > 0)]
+#[pre("`x` is not too large")]
+fn foo(x: i32) -> i32 {
+    x
+}
+
+#[pre(valid_ptr(ptr, r))]
+unsafe fn read<T: Copy>(ptr: *const T) -> T {
+    *ptr
+}
+
+#[pre(x < 10)]
+#[pre(no_debug_assert)]
+const fn double(x: u8) -> u8 {
+    x * 2
+}
+
+#[pre(kind = in_range(index, 0..4))]
+#[pre(no_debug_assert)]
+fn get(index: usize) -> u8 {
+    // A user-defined constant with the same name in a nested scope is not affected.
+    {
+        const __PRE_TOOL_ANNOTATION: u8 = 1;
+
+        [__PRE_TOOL_ANNOTATION; 4][index]
+    }
+}
+
+#[pre]
+fn main() {
+    #[assure(x > 0, reason = "1 > 0")]
+    #[assure("`x` is not too large", reason = "1 is small")]
+    let value = foo(1);
+    assert_eq!(value, 1);
+
+    #[assure(valid_ptr(ptr, r), reason = "`ptr` is a reference")]
+    let value = unsafe { read(&2) };
+    assert_eq!(value, 2);
+
+    #[assure(x < 10, reason = "3 < 10")]
+    let value = double(3);
+    assert_eq!(value, 6);
+
+    #[assure(kind = in_range(index, 0..4), reason = "2 is in range")]
+    let value = get(2);
+    assert_eq!(value, 1);
+}
@@ -1,40 +1,461 @@
+// The coverage of this module is recorded, when the `coverage` feature is enabled.
+#[cfg(feature = "coverage")]
+#[pre::pre]
+#[allow(dead_code)]
+mod covered {
+    #[pre(x > 0)]
+    #[pre("is called twice")]
+    fn foo(x: i32) {}
+
+    #[pre("is never called")]
+    fn bar() {}
+
+    fn call_foo() {
+        #[assure(x > 0, reason = "1 > 0")]
+        #[assure("is called twice", reason = "this is the first call")]
+        foo(1);
+
+        #[assure(x > 0, reason = "2 > 0")]
+        #[assure("is called twice", reason = "this is the second call")]
+        foo(2);
+    }
+
+    mod inner {
+        #[pre("is called once")]
+        fn baz() {}
+
+        fn call_baz() {
+            #[assure("is called once", reason = "this is the only call")]
+            baz();
+        }
+    }
+}
+
+// The signatures of this module are exported, when the `export-signatures` feature is enabled.
+#[cfg(feature = "export-signatures")]
+#[pre::extern_crate(core)]
+#[allow(dead_code)]
+mod exported {
+    mod ptr {
+        #[pre(valid_ptr(src, r))]
+        unsafe fn read<T>(src: *const T) -> T;
+    }
+
+    mod str {
+        #[pre("`v` must contain valid UTF-8")]
+        unsafe fn from_utf8_unchecked(v: &[u8]) -> &str;
+    }
+}
+
+// The `fuzzing` feature generates helpers calling these functions without assuring anything. The
+// build script enables `--cfg fuzzing` together with the feature, like `cargo fuzz` does.
+#[cfg(feature = "fuzzing")]
+#[pre::pre]
+#[allow(dead_code)]
+mod fuzzed {
+    #[pre(!data.is_empty())]
+    #[pre("`data` starts with a length prefix")]
+    pub(crate) fn parse(data: &[u8]) -> &[u8] {
+        let length = usize::from(data[0]).min(data.len() - 1);
+
+        &data[1..=length]
+    }
+
+    #[pre(valid_ptr(ptr, r))]
+    pub(crate) unsafe fn read<T: Copy>(ptr: *const T) -> T {
+        *ptr
+    }
+
+    pub(crate) fn parse_all(inputs: &[&[u8]]) -> usize {
+        inputs
+            .iter()
+            .filter(|data| !data.is_empty())
+            .map(|data| {
+                #[assure(!data.is_empty(), reason = "empty inputs are filtered out")]
+                #[assure(
+                    "`data` starts with a length prefix",
+                    reason = "all inputs are prefixed"
+                )]
+                parse(data)
+            })
+            .map(|parsed| parsed.len())
+            .sum()
+    }
+}
+
+// The contracts of these functions are registered, when the `registry` feature is enabled.
+#[cfg(feature = "registry")]
+#[pre::pre]
+#[allow(dead_code)]
+mod registered {
+    // The preconditions are registered in the order of the markers, not in declaration order.
+    #[pre("is registered")]
+    #[pre(x > 0)]
+    fn foo(x: i32) {}
+
+    fn no_preconditions() {}
+
+    mod inner {
+        #[pre(valid_ptr(ptr, r))]
+        unsafe fn read(ptr: *const u8) -> u8 {
+            *ptr
+        }
+    }
+}
+
+// Compile-fail fixtures are generated for these functions, when the `compile-fail-fixtures`
+// feature is enabled. They are public, so that the fixtures can call them.
+#[cfg(feature = "compile-fail-fixtures")]
+#[pre::pre]
+#[allow(dead_code, clippy::missing_safety_doc)]
+pub mod enforced {
+    #[pre(divisor != 0)]
+    pub fn divide(dividend: i32, divisor: i32) -> i32 {
+        dividend / divisor
+    }
+
+    #[pre("is not public")]
+    fn private() {}
+
+    #[pre(valid_ptr(ptr, r))]
+    pub unsafe fn generic<T: Copy>(ptr: *const T) -> T {
+        *ptr
+    }
+
+    pub mod inner {
+        #[pre(valid_ptr(ptr, r))]
+        #[pre("`ptr` points to the first byte of a header")]
+        pub unsafe fn read(ptr: *const u8) -> u8 {
+            *ptr
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::{fs, path::Path};
     use trybuild::TestCases;
 
     macro_rules! add_category {
         ($test_cases:expr, $scenario:literal, $category:literal) => {{
             $test_cases.pass(concat!($scenario, "/", $category, "/pass/*.rs"));
-            $test_cases.compile_fail(concat!($scenario, "/", $category, "/compile_fail/*.rs"));
+            add_compile_fail(&$test_cases, $scenario, $category);
         }};
     }
 
+    /// Adds the failing tests of a category. A feature that changes the output of a test has a
+    /// copy of it in `<feature>/overrides/<category>`, which is run instead if it is enabled.
+    fn add_compile_fail(test_cases: &TestCases, scenario: &str, category: &str) {
+        let mut overrides = Vec::new();
+
+        if cfg!(feature = "case-insensitive-custom") {
+            overrides.push("case_insensitive_custom");
+        }
+
+        if cfg!(feature = "diff-errors") {
+            overrides.push("diff_errors");
+        }
+
+        // Like an empty glob, a category without failing tests adds nothing.
+        let entries = match fs::read_dir(Path::new(scenario).join(category).join("compile_fail")) {
+            Ok(entries) => entries,
+            Err(_) => return,
+        };
+        let mut tests: Vec<_> = entries
+            .map(|entry| entry.expect("the test can be listed").path())
+            .filter(|path| path.extension() == Some("rs".as_ref()))
+            .collect();
+        tests.sort();
+
+        for test in tests {
+            let name = test.file_name().expect("the test has a name");
+            let overridden = overrides
+                .iter()
+                .map(|feature| {
+                    Path::new(scenario)
+                        .join(feature)
+                        .join("overrides")
+                        .join(category)
+                        .join(name)
+                })
+                .find(|path| path.exists());
+
+            test_cases.compile_fail(overridden.unwrap_or(test));
+        }
+    }
+
     macro_rules! add_testcases {
         ($test_cases:expr, $scenario:literal) => {{
             add_category!($test_cases, $scenario, "function");
             add_category!($test_cases, $scenario, "precondition_types");
             add_category!($test_cases, $scenario, "extern_crate");
             add_category!($test_cases, $scenario, "misc");
+
+            #[cfg(feature = "both")]
+            add_category!($test_cases, $scenario, "both");
+
+            #[cfg(feature = "verbose")]
+            add_category!($test_cases, $scenario, "verbose");
+
+            #[cfg(feature = "safety-comments")]
+            add_category!($test_cases, $scenario, "safety_comments");
+
+            #[cfg(feature = "suggest-preconditions")]
+            add_category!($test_cases, $scenario, "suggest_preconditions");
+
+            #[cfg(feature = "macro-arguments")]
+            add_category!($test_cases, $scenario, "macro_arguments");
+
+            #[cfg(feature = "runtime-checks")]
+            add_category!($test_cases, $scenario, "runtime_checks");
+
+            #[cfg(feature = "case-insensitive-custom")]
+            add_category!($test_cases, $scenario, "case_insensitive_custom");
+
+            #[cfg(feature = "diff-errors")]
+            add_category!($test_cases, $scenario, "diff_errors");
+
+            #[cfg(feature = "tool-annotations")]
+            add_category!($test_cases, $scenario, "tool_annotations");
+
+            #[cfg(feature = "registry")]
+            add_category!($test_cases, $scenario, "registry");
+
+            // The fixtures call functions of this crate that only exist with the
+            // `compile-fail-fixtures` feature.
+            #[cfg(feature = "compile-fail-fixtures")]
+            add_category!($test_cases, $scenario, "fixtures");
         }};
     }
 
-    #[cfg(not(nightly))]
+    macro_rules! add_pass_testcases {
+        ($test_cases:expr, $scenario:literal) => {{
+            $test_cases.pass(concat!($scenario, "/function/pass/*.rs"));
+            $test_cases.pass(concat!($scenario, "/precondition_types/pass/*.rs"));
+            $test_cases.pass(concat!($scenario, "/extern_crate/pass/*.rs"));
+            $test_cases.pass(concat!($scenario, "/misc/pass/*.rs"));
+        }};
+    }
+
+    #[cfg(all(not(nightly), not(feature = "assoc-const"), not(feature = "strip")))]
     #[test]
     fn stable_tests() {
         let test_cases = TestCases::new();
 
+        // The `strict-spans` feature makes every macro fail on the stable compiler, so only the
+        // tests confirming that are run. On the nightly compiler it has no effect.
+        if cfg!(feature = "strict-spans") {
+            test_cases.compile_fail("stable/strict_spans/compile_fail/*.rs");
+
+            return;
+        }
+
+        // With `PRE_CONTRACTS_FEATURE` set, nothing has a marker unless the `contracts` feature is
+        // enabled, so only the tests of the gated contracts are run, with and without the feature.
+        if option_env!("PRE_CONTRACTS_FEATURE").is_some() {
+            test_cases.pass("stable/contracts_feature/pass/*.rs");
+
+            return;
+        }
+
+        // The helpers of the `fuzzing` feature are gated behind `cfg(fuzzing)`, which causes
+        // warnings in every test, so only the tests of the helpers are run.
+        if cfg!(feature = "fuzzing") {
+            add_category!(test_cases, "stable", "fuzzing");
+
+            return;
+        }
+
+        // The `marker-first` feature moves the marker of every function, so markers passed by hand
+        // and the suggestions in some failing tests differ.
+        if cfg!(feature = "marker-first") {
+            add_pass_testcases!(test_cases, "stable");
+            test_cases.pass("stable/marker_first/pass/*.rs");
+
+            return;
+        }
+
         add_testcases!(test_cases, "stable");
 
         add_category!(test_cases, "stable", "stable-only");
     }
 
-    #[cfg(nightly)]
+    #[cfg(all(nightly, not(feature = "assoc-const"), not(feature = "strip")))]
     #[test]
     fn nightly_tests() {
         let test_cases = TestCases::new();
 
+        if option_env!("PRE_CONTRACTS_FEATURE").is_some() {
+            test_cases.pass("nightly/contracts_feature/pass/*.rs");
+
+            return;
+        }
+
+        if cfg!(feature = "fuzzing") {
+            add_category!(test_cases, "nightly", "fuzzing");
+
+            return;
+        }
+
+        if cfg!(feature = "marker-first") {
+            add_pass_testcases!(test_cases, "nightly");
+            test_cases.pass("nightly/marker_first/pass/*.rs");
+
+            return;
+        }
+
         add_testcases!(test_cases, "nightly");
 
         add_category!(test_cases, "nightly", "nightly-only");
     }
+
+    // The `assoc-const` feature does not change function signatures, so calls without `assure`
+    // attributes compile. The other categories don't apply to it.
+    #[cfg(feature = "assoc-const")]
+    #[test]
+    fn assoc_const_tests() {
+        let test_cases = TestCases::new();
+
+        if cfg!(nightly) {
+            add_category!(test_cases, "nightly", "assoc_const");
+        } else {
+            add_category!(test_cases, "stable", "assoc_const");
+        }
+    }
+
+    // The `strip` feature removes all checks, so only the tests confirming that are run.
+    #[cfg(feature = "strip")]
+    #[test]
+    fn strip_tests() {
+        let test_cases = TestCases::new();
+
+        if cfg!(nightly) {
+            add_category!(test_cases, "nightly", "strip");
+        } else {
+            add_category!(test_cases, "stable", "strip");
+        }
+    }
+
+    #[cfg(feature = "coverage")]
+    #[test]
+    fn coverage_report() {
+        let report = std::fs::read_to_string(concat!(env!("OUT_DIR"), "/pre-coverage.txt"))
+            .expect("the coverage report exists");
+        let lines: Vec<_> = report
+            .lines()
+            .filter(|line| line.starts_with("pre_tests::covered"))
+            .collect();
+
+        assert_eq!(
+            lines,
+            [
+                "pre_tests::covered\tbar\t\"is never called\"\t0",
+                "pre_tests::covered\tfoo\t\"is called twice\"\t2",
+                "pre_tests::covered\tfoo\tx > 0\t2",
+                "pre_tests::covered::inner\tbaz\t\"is called once\"\t1",
+            ]
+        );
+    }
+
+    #[cfg(feature = "fuzzing")]
+    #[test]
+    fn fuzz_style_caller() {
+        use super::fuzzed::{fuzz_parse, fuzz_read, parse_all};
+
+        // A fuzz target only needs to rule out inputs that the harness cannot handle.
+        for data in &[&[3, 1, 2, 3][..], &[9, 1], &[0]] {
+            assert!(fuzz_parse(data).len() < data.len());
+        }
+
+        let value = 42u64;
+        assert_eq!(unsafe { fuzz_read(&value) }, 42);
+
+        assert_eq!(parse_all(&[&[2, 1, 2], &[]]), 2);
+    }
+
+    #[cfg(feature = "registry")]
+    #[test]
+    fn registered_contracts() {
+        let mut contracts: Vec<_> = pre::CONTRACTS
+            .iter()
+            .filter(|contract| contract.module_path.starts_with("pre_tests::registered"))
+            .map(|contract| {
+                (
+                    contract.module_path,
+                    contract.function,
+                    contract.preconditions.to_vec(),
+                )
+            })
+            .collect();
+        contracts.sort();
+
+        assert_eq!(
+            contracts,
+            [
+                (
+                    "pre_tests::registered",
+                    "foo",
+                    vec!["x > 0", "\"is registered\""]
+                ),
+                (
+                    "pre_tests::registered::inner",
+                    "read",
+                    vec!["valid_ptr(ptr, r)"]
+                ),
+            ]
+        );
+    }
+
+    #[cfg(feature = "export-signatures")]
+    #[test]
+    fn exported_signatures() {
+        let report = std::fs::read_to_string(concat!(env!("OUT_DIR"), "/pre-signatures.txt"))
+            .expect("the exported signatures exist");
+        let lines: Vec<_> = report
+            .lines()
+            .filter(|line| line.starts_with("pre_tests::exported"))
+            .collect();
+
+        assert_eq!(
+            lines,
+            [
+                "pre_tests::exported::ptr\tcore::ptr::read\tunsafe fn read < T > ( src : * const T ) -> T",
+                "pre_tests::exported::str\tcore::str::from_utf8_unchecked\tunsafe fn from_utf8_unchecked ( v : & [ u8 ] ) -> & str",
+            ]
+        );
+    }
+
+    #[cfg(feature = "compile-fail-fixtures")]
+    #[test]
+    fn generated_fixtures() {
+        let dir = concat!(env!("OUT_DIR"), "/pre-fixtures");
+        // The tests run by `trybuild` write their fixtures to the same directory.
+        let mut fixtures: Vec<_> = std::fs::read_dir(dir)
+            .expect("the fixtures were generated")
+            .map(|entry| entry.expect("the fixture can be listed").path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .map_or(false, |name| name.starts_with("enforced__"))
+            })
+            .collect();
+        fixtures.sort();
+
+        let names: Vec<_> = fixtures
+            .iter()
+            .map(|path| path.file_name().expect("the fixture has a name").to_owned())
+            .collect();
+        assert_eq!(names, ["enforced__divide.rs", "enforced__inner__read.rs"]);
+
+        // The fixtures that are run by `trybuild` must match the generated ones.
+        for fixture in fixtures {
+            let expected = std::path::Path::new("templates/fixtures/compile_fail")
+                .join(fixture.file_name().expect("the fixture has a name"));
+
+            assert_eq!(
+                std::fs::read_to_string(&fixture).expect("the fixture can be read"),
+                std::fs::read_to_string(&expected).expect("the fixture is checked in"),
+            );
+        }
+    }
 }
@@ -0,0 +1,52 @@
+use pre::pre;
+
+mod nested {
+    use pre::pre;
+
+    #[pre(!values.is_empty())]
+    pub(crate) fn convert<T: Copy, U: From<T>>(values: &[T]) -> U {
+        U::from(values[0])
+    }
+}
+
+#[pre(!values.is_empty())]
+fn first<T: Copy>(values: &[T]) -> T {
+    values[0]
+}
+
+#[pre(capacity > 0)]
+fn collect_into<C: Default + Extend<u8>>(capacity: usize) -> C {
+    let mut collection = C::default();
+    collection.extend(std::iter::repeat(0).take(capacity));
+    collection
+}
+
+#[pre(value > 0)]
+fn fill<const N: usize>(value: u8) -> [u8; N] {
+    [value; N]
+}
+
+#[pre]
+fn main() {
+    // The signature is not changed, so the type parameter can be inferred from the type of a
+    // function pointer.
+    let pointer: fn(&[u8]) -> u8 = first;
+    assert_eq!(pointer(&[1]), 1);
+
+    // The called function is wrapped in a block, which does not prevent inferring the type
+    // parameter from the expected type.
+    #[assure(capacity > 0, reason = "2 > 0")]
+    let collected: Vec<u8> = collect_into(2);
+    assert_eq!(collected, [0, 0]);
+
+    // The const parameter is inferred in the same way.
+    #[assure(value > 0, reason = "1 > 0")]
+    let filled: [u8; 3] = fill(1);
+    assert_eq!(filled, [1, 1, 1]);
+
+    // The type arguments are removed from the path of the constant with the preconditions, so
+    // partially specified type arguments keep their meaning.
+    #[assure(!values.is_empty(), reason = "the slice contains an element")]
+    let converted = nested::convert::<_, u32>(&[2u8]);
+    assert_eq!(converted, 2);
+}
@@ -0,0 +1,31 @@
+use pre::pre;
+
+mod nested {
+    use pre::pre;
+
+    #[pre("is nested")]
+    pub(crate) fn bar<T>(val: T) -> T {
+        val
+    }
+}
+
+#[pre("is foo")]
+#[pre(val > 41)]
+fn foo(val: i32) -> i32 {
+    val
+}
+
+#[pre]
+fn main() {
+    // The signature is not changed, so the function can still be used as a function pointer.
+    let _: fn(i32) -> i32 = foo;
+
+    #[assure(val > 41, reason = "42 > 41")]
+    #[assure("is foo", reason = "it is foo")]
+    let val = foo(42);
+
+    #[assure("is nested", reason = "it is nested")]
+    let val = nested::bar::<i32>(val);
+
+    assert_eq!(val, 42);
+}
@@ -0,0 +1,17 @@
+use pre::pre;
+
+#[pre(no_debug_assert)]
+#[pre(valid_ptr(ptr, w))]
+unsafe fn write(ptr: *mut u8) {
+    if !ptr.is_null() {
+        *ptr = 0;
+    }
+}
+
+#[pre]
+fn main() {
+    #[assure(valid_ptr(ptr, w), reason = "null is handled by `write`")]
+    unsafe {
+        write(std::ptr::null_mut())
+    };
+}
@@ -0,0 +1,21 @@
+use pre::pre;
+
+#[pre::extern_crate(std)]
+mod pre_std {
+    mod ptr {
+        #[pre(valid_ptr(src, r))]
+        #[pre("`src` must point to a properly initialized value of type `T`")]
+        unsafe fn read_unaligned<T>(src: *const T) -> T;
+    }
+}
+
+#[pre]
+fn main() {
+    let val = 42;
+
+    #[forward(std -> pre_std)]
+    #[assure(valid_ptr(src, r), reason = "`src` is a reference")]
+    let result = unsafe { std::ptr::read_unaligned(&val) };
+
+    assert_eq!(result, 42);
+}
@@ -0,0 +1,12 @@
+use pre::pre;
+
+#[pre("Frees Memory")]
+#[pre("the `VALUE` is initialized")]
+fn foo() {}
+
+#[pre]
+fn main() {
+    #[assure("frees memory", reason = "case is ignored with `case-insensitive-custom`")]
+    #[assure("THE `value` IS INITIALIZED", reason = "case is ignored with `case-insensitive-custom`")]
+    foo();
+}
@@ -0,0 +1,17 @@
+#[pre::pre]
+mod whole_file {
+    #[pre(x > 17)]
+    #[pre("is a good value")]
+    #[pre("is a prime number")]
+    fn foo(x: i32) {}
+
+    pub(super) fn bar() {
+        #[assure("is a prime number", reason = "43 is a prime number")]
+        #[assure(x < 100, reason = "43 is less than 100")]
+        foo(43);
+    }
+}
+
+fn main() {
+    whole_file::bar();
+}
@@ -0,0 +1,19 @@
+#[pre::pre]
+mod whole_file {
+    #[pre(x > 17)]
+    #[pre("is a good value")]
+    #[pre("is a prime number")]
+    fn foo(x: i32) {}
+
+    pub(super) fn bar() {
+        #[assure("is a prime number", reason = "43 is a prime number")]
+        foo(43);
+
+        #[assure("is a good value", reason = "43 is a good value")]
+        self::foo(43);
+    }
+}
+
+fn main() {
+    whole_file::bar();
+}
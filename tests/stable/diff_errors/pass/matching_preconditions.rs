@@ -0,0 +1,16 @@
+#[pre::pre]
+mod whole_file {
+    #[pre(x > 17)]
+    #[pre("is a prime number")]
+    fn foo(x: i32) {}
+
+    pub(super) fn bar() {
+        #[assure("is a prime number", reason = "43 is a prime number")]
+        #[assure(x > 17, reason = "43 is greater than 17")]
+        foo(43);
+    }
+}
+
+fn main() {
+    whole_file::bar();
+}
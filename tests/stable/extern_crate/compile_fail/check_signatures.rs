@@ -0,0 +1,12 @@
+#[pre::extern_crate(core, check_signatures)]
+mod pre_core {
+    mod str {
+        #[pre("`v` must contain valid UTF-8")]
+        unsafe fn from_utf8_unchecked(v: &mut [u8]) -> &str;
+
+        #[pre("`v` must contain valid UTF-8")]
+        unsafe fn from_utf8_unchecekd(v: &[u8]) -> &str;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,36 @@
+use pre::pre;
+
+#[pre::extern_crate(core, check_signatures)]
+mod pre_core {
+    mod str {
+        #[pre("`v` must contain valid UTF-8")]
+        unsafe fn from_utf8_unchecked(v: &[u8]) -> &str;
+    }
+
+    mod ptr {
+        #[pre(valid_ptr(src, r))]
+        unsafe fn read<T>(src: *const T) -> T;
+
+        // The generic parameters may be named differently.
+        #[pre(valid_ptr(dst, w))]
+        unsafe fn write<U>(dst: *mut U, src: U);
+    }
+}
+
+#[pre]
+fn main() {
+    #[assure("`v` must contain valid UTF-8", reason = "it is an ASCII string")]
+    let s = unsafe { pre_core::str::from_utf8_unchecked(b"abc") };
+    assert_eq!(s, "abc");
+
+    #[assure(valid_ptr(src, r), reason = "`src` is a reference")]
+    let val = unsafe { pre_core::ptr::read(&42) };
+    assert_eq!(val, 42);
+
+    let mut x = 0;
+    #[assure(valid_ptr(dst, w), reason = "`dst` is a mutable reference")]
+    unsafe {
+        pre_core::ptr::write(&mut x, 42)
+    };
+    assert_eq!(x, 42);
+}
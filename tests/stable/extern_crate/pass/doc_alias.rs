@@ -0,0 +1,31 @@
+use pre::pre;
+
+#[pre::extern_crate(core, doc_alias)]
+mod pre_core {
+    mod ptr {
+        #[pre(valid_ptr(src, r))]
+        unsafe fn read<T>(src: *const T) -> T;
+    }
+}
+
+// The options can be combined in any order.
+#[pre::extern_crate(std, check_signatures, doc_alias)]
+mod pre_std {
+    mod str {
+        #[pre("`v` must contain valid UTF-8")]
+        unsafe fn from_utf8_unchecked(v: &[u8]) -> &str;
+    }
+}
+
+#[pre]
+fn main() {
+    let value = 42;
+
+    #[assure(valid_ptr(src, r), reason = "the pointer is created from a reference")]
+    let read = unsafe { pre_core::ptr::read(&value) };
+    assert_eq!(read, 42);
+
+    #[assure("`v` must contain valid UTF-8", reason = "the bytes are ASCII")]
+    let text = unsafe { pre_std::str::from_utf8_unchecked(b"pre") };
+    assert_eq!(text, "pre");
+}
@@ -0,0 +1,60 @@
+use pre::pre;
+
+#[pre::extern_crate(std)]
+mod pre_std {
+    mod vec {
+        impl<T> Vec<T> {
+            #[pre(capacity <= 1024)]
+            fn with_capacity(capacity: usize) -> Self;
+
+            #[pre(new_len <= self.capacity())]
+            #[pre("the elements at `old_len..new_len` are initialized")]
+            unsafe fn set_len(&mut self, new_len: usize);
+
+            #[pre(!self.is_empty())]
+            fn pop(&mut self) -> Option<T>;
+        }
+    }
+
+    mod ptr {
+        impl<T> NonNull<T> {
+            #[pre("the pointer is not used after the value is dropped")]
+            fn as_ptr(self) -> *mut T;
+        }
+    }
+}
+
+#[pre]
+fn main() {
+    #[assure(capacity <= 1024, reason = "4 is less than 1024")]
+    let mut v: Vec<u8> = pre_std::vec::Vec_with_capacity(4);
+    assert!(v.capacity() >= 4);
+
+    v.push(1);
+    v.push(2);
+    v.push(3);
+
+    #[assure(new_len <= this.capacity(), reason = "the vector only gets shorter")]
+    #[assure(
+        "the elements at `old_len..new_len` are initialized",
+        reason = "there are no elements in the range"
+    )]
+    unsafe {
+        pre_std::vec::Vec_set_len(&mut v, 2)
+    };
+    assert_eq!(v, [1, 2]);
+
+    #[assure(!this.is_empty(), reason = "`v` has two elements")]
+    let last = pre_std::vec::Vec_pop(&mut v);
+    assert_eq!(last, Some(2));
+
+    let mut value = 42;
+    let non_null = std::ptr::NonNull::from(&mut value);
+
+    #[assure(
+        "the pointer is not used after the value is dropped",
+        reason = "`value` lives until the end of `main`"
+    )]
+    let ptr = pre_std::ptr::NonNull_as_ptr(non_null);
+    assert_eq!(unsafe { *ptr }, 42);
+}
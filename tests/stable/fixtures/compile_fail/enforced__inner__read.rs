@@ -0,0 +1,9 @@
+// This fixture was generated by `pre` for `pre_tests::enforced::inner::read`.
+// It calls the function without assuring its preconditions, so it must fail to compile:
+// - valid_ptr(ptr, r)
+// - "`ptr` points to the first byte of a header"
+
+#[allow(unreachable_code)]
+fn main() {
+    unsafe { pre_tests::enforced::inner::read(::core::todo!()) };
+}
@@ -0,0 +1,15 @@
+use pre::pre;
+
+#[pre(x > 0)]
+fn double(x: i32) -> i32 {
+    x * 2
+}
+
+#[pre]
+fn only_call() -> i32 {
+    double(21)
+}
+
+fn main() {
+    only_call();
+}
@@ -0,0 +1,16 @@
+use pre::pre;
+
+#[pre(x > 0)]
+const fn double(x: i32) -> i32 {
+    x * 2
+}
+
+#[pre]
+const DOUBLED: i32 = {
+    #[assure(x > 0, reason = "this is wrong on purpose")]
+    double(-1)
+};
+
+fn main() {
+    assert_eq!(DOUBLED, -2);
+}
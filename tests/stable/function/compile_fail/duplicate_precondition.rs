@@ -0,0 +1,13 @@
+use pre::pre;
+
+#[pre(x > 0)]
+#[pre("x is a good value")]
+#[pre(x > 0)]
+fn foo(x: i32) {}
+
+#[pre]
+fn main() {
+    #[assure(x > 0, reason = "1 > 0")]
+    #[assure("x is a good value", reason = "1 is a good value")]
+    foo(1);
+}
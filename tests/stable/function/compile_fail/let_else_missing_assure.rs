@@ -0,0 +1,15 @@
+use pre::pre;
+
+#[pre(x > 17)]
+fn foo(x: i32) -> Result<i32, ()> {
+    Ok(x)
+}
+
+#[pre]
+fn main() {
+    #[assure(x > 18, reason = "42 > 18")]
+    let Ok(x) = foo(42) else {
+        return;
+    };
+    assert_eq!(x, 42);
+}
@@ -0,0 +1,47 @@
+use pre::pre;
+
+#[pre(x > 0)]
+fn is_even(x: i32) -> bool {
+    x % 2 == 0
+}
+
+#[pre(x > 0)]
+fn double(x: i32) -> i32 {
+    x * 2
+}
+
+#[pre]
+fn main() {
+    assert!(
+        #[assure(x > 0, reason = "42 > 0")]
+        is_even(42)
+    );
+
+    // The call can be part of the condition and the message can still be formatted.
+    let value = 3;
+    assert!(
+        !#[assure(x > 0, reason = "3 > 0")]
+        is_even(value),
+        "{} is odd",
+        value
+    );
+
+    assert_eq!(
+        #[assure(x > 0, reason = "2 > 0")]
+        double(2),
+        4
+    );
+    debug_assert_ne!(
+        #[assure(x > 0, reason = "2 > 0")]
+        double(2),
+        #[assure(x > 0, reason = "3 > 0")]
+        double(3),
+        "different values are doubled to different values"
+    );
+
+    // The macros can also be called through their full path.
+    std::assert!(
+        #[assure(x > 0, reason = "4 > 0")]
+        is_even(4)
+    );
+}
@@ -0,0 +1,48 @@
+use pre::pre;
+
+#[pre(x > 0)]
+fn foo(x: i32) -> i32 {
+    x * 2
+}
+
+#[pre]
+fn main() {
+    // The attribute can be placed on the call that is the value of the `break`.
+    let mut x = 0;
+    let value = loop {
+        x += 1;
+
+        if x == 3 {
+            break #[assure(x > 0, reason = "`x` is 3")]
+            foo(x);
+        }
+    };
+    assert_eq!(value, 6);
+
+    // It can also be placed on the whole `break` expression.
+    let value = loop {
+        #[assure(x > 0, reason = "`x` is still 3")]
+        break foo(x);
+    };
+    assert_eq!(value, 6);
+
+    // Labeled `break`s work the same way.
+    let value = 'outer: loop {
+        loop {
+            break 'outer #[assure(x > 0, reason = "`x` is still 3")]
+            foo(x);
+        }
+    };
+    assert_eq!(value, 6);
+
+    // Labeled blocks can also return a value with `break`.
+    let value = 'block: {
+        if x > 2 {
+            break 'block #[assure(x > 0, reason = "`x` is greater than 2")]
+            foo(x);
+        }
+
+        0
+    };
+    assert_eq!(value, 6);
+}
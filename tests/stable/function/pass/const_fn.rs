@@ -0,0 +1,60 @@
+use pre::{define_precondition_kind, pre};
+
+define_precondition_kind!(small(x));
+
+#[pre(x > 0)]
+#[pre("`x` is small")]
+const fn double(x: i32) -> i32 {
+    x * 2
+}
+
+#[pre(kind = small(x))]
+const fn halve(x: i32) -> i32 {
+    x / 2
+}
+
+#[pre(track_caller)]
+#[pre(kind = in_range(index, 0..4))]
+#[pre(!ptr.is_null())]
+const fn select(ptr: *const u8, index: usize) -> usize {
+    index
+}
+
+#[pre]
+const fn quadruple(x: i32) -> i32 {
+    #[assure(x > 0, reason = "`x` is positive")]
+    #[assure("`x` is small", reason = "`x` is small")]
+    let doubled = double(x);
+
+    doubled * 2
+}
+
+#[pre]
+const HALVED: i32 = {
+    #[assure(kind = small(x), reason = "42 is small")]
+    halve(42)
+};
+
+#[pre]
+const DOUBLED: i32 = {
+    #[assure(x > 0, reason = "21 > 0")]
+    #[assure("`x` is small", reason = "21 is small")]
+    double(21)
+};
+
+#[pre]
+const SELECTED: usize = {
+    #[assure(kind = in_range(index, 0..4), reason = "3 < 4")]
+    #[assure(!ptr.is_null(), reason = "the pointer is created from a reference")]
+    select(&0, 3)
+};
+
+#[pre]
+fn main() {
+    assert_eq!(DOUBLED, 42);
+    assert_eq!(SELECTED, 3);
+    assert_eq!(HALVED, 21);
+
+    const QUADRUPLED: i32 = quadruple(2);
+    assert_eq!(QUADRUPLED, 8);
+}
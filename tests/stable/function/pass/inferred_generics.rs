@@ -0,0 +1,63 @@
+use pre::pre;
+
+#[pre::extern_crate(std)]
+mod pre_std {
+    mod vec {
+        impl<T> Vec<T> {
+            #[pre("the capacity is large enough")]
+            fn with_capacity(capacity: usize) -> Vec<T>;
+        }
+    }
+}
+
+#[pre(capacity > 0)]
+fn with_capacity<T>(capacity: usize) -> Vec<T> {
+    Vec::with_capacity(capacity)
+}
+
+#[pre(!values.is_empty())]
+fn first<T: Copy>(values: &[T]) -> T {
+    values[0]
+}
+
+#[pre(capacity > 0)]
+fn collect_into<C: Default + Extend<u8>>(capacity: usize) -> C {
+    let mut collection = C::default();
+    collection.extend(std::iter::repeat(0).take(capacity));
+    collection
+}
+
+#[pre]
+fn main() {
+    // The element type is only known from a later use.
+    #[assure(capacity > 0, reason = "4 > 0")]
+    let mut values = with_capacity(4);
+    values.push(1u8);
+
+    // The element type is inferred from the argument.
+    #[assure(!values.is_empty(), reason = "`values` contains an element")]
+    let value = first(&values);
+    assert_eq!(value, 1u8);
+
+    // The type parameter is inferred from the type annotation.
+    #[assure(capacity > 0, reason = "2 > 0")]
+    let collected: Vec<u8> = collect_into(2);
+    assert_eq!(collected, [0, 0]);
+
+    // The type parameter of the impl block is inferred from a later use.
+    #[forward(impl pre_std::vec::Vec)]
+    #[assure("the capacity is large enough", reason = "only one element is pushed")]
+    let mut strings = Vec::with_capacity(1);
+    strings.push("a");
+    assert_eq!(strings, ["a"]);
+
+    // Explicit type arguments are still accepted.
+    #[assure(capacity > 0, reason = "1 > 0")]
+    let explicit = with_capacity::<i32>(1);
+    assert!(explicit.is_empty());
+
+    #[forward(impl pre_std::vec::Vec)]
+    #[assure("the capacity is large enough", reason = "no element is pushed")]
+    let explicit_impl = Vec::<i32>::with_capacity(1);
+    assert!(explicit_impl.is_empty());
+}
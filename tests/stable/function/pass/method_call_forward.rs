@@ -0,0 +1,37 @@
+use pre::pre;
+use std::mem::MaybeUninit;
+
+#[pre::extern_crate(core)]
+mod pre_core {
+    mod mem {
+        impl<T> MaybeUninit<T> {
+            #[pre("the `MaybeUninit` contains a fully initialized, valid value of `T`")]
+            unsafe fn assume_init(self) -> T;
+        }
+    }
+}
+
+#[pre]
+fn main() {
+    let value = MaybeUninit::new(42);
+
+    let value = unsafe {
+        #[forward(impl pre_core::mem::MaybeUninit)]
+        #[assure(
+            "the `MaybeUninit` contains a fully initialized, valid value of `T`",
+            reason = "it was created from a value"
+        )]
+        value.assume_init()
+    };
+    assert_eq!(value, 42);
+
+    let chained = unsafe {
+        #[forward(impl pre::core::mem::MaybeUninit)]
+        #[assure(
+            "the `MaybeUninit` contains a fully initialized, valid value of `T`",
+            reason = "it was created from a value"
+        )]
+        MaybeUninit::new([1, 2]).assume_init()
+    };
+    assert_eq!(chained, [1, 2]);
+}
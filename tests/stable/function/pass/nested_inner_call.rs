@@ -0,0 +1,49 @@
+use pre::pre;
+
+fn outer(x: i32) -> i32 {
+    x + 1
+}
+
+#[pre(x > 0)]
+fn inner(x: i32) -> i32 {
+    x * 2
+}
+
+#[pre(x > 0)]
+fn preconditioned_outer(x: i32) -> i32 {
+    x - 1
+}
+
+#[pre]
+fn main() {
+    // Only the inner call has preconditions, so the attribute is placed on it.
+    let value = outer(
+        #[assure(x > 0, reason = "1 > 0")]
+        inner(1),
+    );
+    assert_eq!(value, 3);
+
+    // The inner call is rendered first, so the outer call still sees a plain call.
+    let value = outer(outer(
+        #[assure(x > 0, reason = "2 > 0")]
+        inner(2),
+    ));
+    assert_eq!(value, 6);
+
+    // Both calls can have their own attributes.
+    #[assure(x > 0, reason = "the result of `inner` is at least 2")]
+    let value = preconditioned_outer(
+        #[assure(x > 0, reason = "3 > 0")]
+        inner(3),
+    );
+    assert_eq!(value, 5);
+
+    // Method calls with an inner call as the receiver or argument work as well.
+    let value = Some(
+        #[assure(x > 0, reason = "4 > 0")]
+        inner(4),
+    )
+    .map(outer)
+    .unwrap_or_default();
+    assert_eq!(value, 9);
+}
@@ -0,0 +1,32 @@
+use pre::pre;
+
+#[pre(x > T::default())]
+fn positive<T: Default + PartialOrd>(x: T) -> T {
+    x
+}
+
+#[pre("the array is not empty")]
+fn first<T: Copy, const N: usize>(array: [T; N]) -> T {
+    array[0]
+}
+
+#[pre(valid_ptr(ptr, r))]
+unsafe fn read<T, U: From<T>>(ptr: *const T) -> U {
+    U::from(ptr.read())
+}
+
+#[pre]
+fn main() {
+    #[assure(x > T::default(), reason = "42 > 0")]
+    let value = positive::<u32>(42);
+    assert_eq!(value, 42);
+
+    #[assure("the array is not empty", reason = "the array has three elements")]
+    let value = first::<u8, 3>([1, 2, 3]);
+    assert_eq!(value, 1);
+
+    let number = 7u8;
+    #[assure(valid_ptr(ptr, r), reason = "a reference is a valid pointer")]
+    let value = unsafe { read::<u8, u64>(&number) };
+    assert_eq!(value, 7);
+}
@@ -0,0 +1,11 @@
+use pre::pre;
+
+#[pre(x > 0)]
+fn foo(x: i32) -> i32 {
+    x * 2
+}
+
+// The helper is only present when compiling with `--cfg fuzzing`.
+fn main() {
+    fuzz_foo(0);
+}
@@ -0,0 +1,31 @@
+use pre::pre;
+
+#[pre]
+mod numbers {
+    use pre::{pre_call, pre_for_each};
+
+    #[pre(x > 0)]
+    pub(crate) fn positive(x: i32) -> i32 {
+        x
+    }
+
+    // The calls in `pre_call!` and `pre_for_each!` are assured by the macros, so they are not
+    // reported, even though the arguments of other macros are searched for calls.
+    #[forbid_pre]
+    pub(crate) fn sum(x: i32) -> i32 {
+        let value = pre::pre_call!(x > 0, reason = "1 > 0", positive(1));
+
+        let mut total = 0;
+        pre_for_each!(
+            x > 0,
+            reason = "all values are positive",
+            [1, 2, 3].iter() => |value| total += positive(*value)
+        );
+
+        value + total + pre_call!(x > 0, reason = "2 > 0", positive(2)) + x
+    }
+}
+
+fn main() {
+    assert_eq!(numbers::sum(4), 1 + 6 + 2 + 4);
+}
@@ -0,0 +1,40 @@
+use pre::pre;
+
+#[pre(x > 0)]
+fn double(x: i32) -> Option<i32> {
+    Some(x * 2)
+}
+
+macro_rules! repeat {
+    ($value:expr => $count:literal) => {
+        [$value; $count]
+    };
+}
+
+#[pre]
+fn main() {
+    assert!(matches!(
+        #[assure(x > 0, reason = "1 is positive")]
+        double(1),
+        Some(2)
+    ));
+
+    assert!(matches!(
+        #[assure(x > 0, reason = "2 is positive")]
+        double(2),
+        Some(x) if x > 2
+    ));
+
+    let formatted = format!(
+        "{:?}",
+        #[assure(x > 0, reason = "3 is positive")]
+        double(3)
+    );
+    assert_eq!(formatted, "Some(6)");
+
+    // The arguments of these macros are not expressions separated by commas, so they are left
+    // untouched.
+    let values = vec![Some(4); 2];
+    assert_eq!(values, [Some(4), Some(4)]);
+    assert_eq!(repeat!(5 => 2), [5, 5]);
+}
@@ -0,0 +1,12 @@
+use pre::pre;
+
+#[pre("is a good value")]
+#[pre(a < b)]
+fn foo(a: i32, b: i32) {}
+
+#[pre]
+fn main() {
+    #[assure("is a good value", reason = "42 is a good value", evidence = 42)]
+    #[assure(a < b, reason = "1 < 2", evidence = 1)]
+    foo(1, 2);
+}
@@ -0,0 +1,28 @@
+use pre::pre;
+
+#[pre(exclusive(x > 0, x < 0))]
+mod numbers {
+    #[pre(x > 0)]
+    fn positive(x: i32) {}
+
+    #[pre(x < 0)]
+    fn negative(x: i32) {}
+
+    #[pre(x > 0)]
+    #[pre(x < 0)]
+    fn contradictory(x: i32) {}
+
+    fn call() {
+        #[assure(x > 0, reason = "1 > 0")]
+        positive(1);
+
+        #[assure(x < 0, reason = "-1 < 0")]
+        negative(-1);
+
+        #[assure(x > 0, reason = "`x` is positive")]
+        #[assure(x < 0, reason = "`x` is also negative")]
+        contradictory(1);
+    }
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+use pre::pre;
+
+#[pre(exclusive(x > 0, "is not declared"))]
+mod undeclared {
+    #[pre(x > 0)]
+    fn positive(x: i32) {}
+}
+
+#[pre(exclusive(x > 0, x > 0))]
+mod same {
+    #[pre(x > 0)]
+    fn positive(x: i32) {}
+}
+
+#[pre(exclusive(x > 0, x < 0, x == 0))]
+mod too_many {
+    #[pre(x > 0)]
+    fn positive(x: i32) {}
+}
+
+#[pre(exclusive(x > 0, x < 0))]
+#[pre(x > 0)]
+#[pre(x < 0)]
+fn on_function(x: i32) {}
+
+fn main() {}
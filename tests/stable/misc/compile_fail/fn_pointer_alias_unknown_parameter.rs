@@ -0,0 +1,12 @@
+use pre::pre;
+
+#[pre(valid_ptr(arg1, r))]
+type Callback = fn(*const u8);
+
+#[pre(kind = non_null(ptr))]
+type Named = fn(ptr: *const u8, len: usize);
+
+#[pre(x > 0)]
+type NotAFunction = i32;
+
+fn main() {}
@@ -0,0 +1,33 @@
+use pre::pre;
+
+#[pre]
+mod numbers {
+    #[pre(x > 0)]
+    fn positive(x: i32) -> i32 {
+        x
+    }
+
+    #[forbid_pre]
+    mod nested {
+        #[pre("the value is small")]
+        pub(super) fn small(x: i32) -> i32 {
+            x
+        }
+
+        fn outer(x: i32) -> i32 {
+            super::positive(x)
+        }
+
+        fn same_module(x: i32) -> i32 {
+            self::small(x)
+        }
+    }
+
+    #[forbid_pre]
+    fn inner(x: i32) -> i32 {
+        #[assure("the value is small", reason = "the value was checked")]
+        nested::small(nested::small(x))
+    }
+}
+
+fn main() {}
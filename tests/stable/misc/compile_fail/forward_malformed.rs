@@ -0,0 +1,23 @@
+use pre::pre;
+
+mod a {
+    use pre::pre;
+
+    #[pre("some condition")]
+    pub(crate) fn foo() {}
+}
+
+#[pre]
+fn main() {
+    #[forward(a::)]
+    #[assure("some condition", reason = "the condition holds")]
+    a::foo();
+
+    #[forward(a b)]
+    #[assure("some condition", reason = "the condition holds")]
+    a::foo();
+
+    #[forward(a -> )]
+    #[assure("some condition", reason = "the condition holds")]
+    a::foo();
+}
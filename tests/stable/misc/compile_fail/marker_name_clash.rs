@@ -0,0 +1,15 @@
+use pre::pre;
+
+#[pre(marker = "x")]
+#[pre(x > 0)]
+fn foo(x: i32) -> i32 {
+    x
+}
+
+#[pre(marker = "not an identifier")]
+#[pre(x > 0)]
+fn bar(x: i32) -> i32 {
+    x
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+use pre::{pre, pre_for_each};
+
+#[pre(x > 0)]
+fn foo(x: i32) {}
+
+fn main() {
+    pre_for_each!(
+        x > 0,
+        reason = "all values are positive",
+        vec![(1, 2)] => |a, b| foo(a + b)
+    );
+
+    pre_for_each!(
+        x > 0,
+        reason = "all values are positive",
+        vec![1, 2] => |value: i32| foo(value)
+    );
+
+    pre_for_each!(
+        x > 0,
+        reason = "all values are positive",
+        vec![1, 2] => foo
+    );
+}
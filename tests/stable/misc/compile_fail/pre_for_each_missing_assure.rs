@@ -0,0 +1,13 @@
+use pre::{pre, pre_for_each};
+
+#[pre(x > 0)]
+#[pre(x < 10)]
+fn foo(x: i32) {}
+
+fn main() {
+    pre_for_each!(
+        x > 0,
+        reason = "all values are positive",
+        vec![1, 2, 3] => |value| foo(value)
+    );
+}
@@ -0,0 +1,35 @@
+use pre::pre;
+
+#[pre("some condition")]
+fn foo() {}
+
+#[pre(reason_pattern = "JIRA-\\d+")]
+fn main() {
+    #[assure("some condition", reason = "checked in the ticket")]
+    foo();
+}
+
+#[pre]
+mod outer {
+    #[pre("some condition")]
+    fn foo() {}
+
+    // Patterns on nested modules and `impl` blocks apply to the functions inside of them.
+    #[pre(reason_pattern = "JIRA-\\d+")]
+    mod inner {
+        fn bar() {
+            #[assure("some condition", reason = "checked in the ticket")]
+            super::foo();
+        }
+    }
+
+    struct X;
+
+    #[pre(reason_pattern = "JIRA-\\d+")]
+    impl X {
+        fn baz() {
+            #[assure("some condition", reason = "checked in the ticket")]
+            foo();
+        }
+    }
+}
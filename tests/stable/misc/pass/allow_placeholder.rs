@@ -0,0 +1,31 @@
+use pre::{pre, pre_call};
+
+#[pre("some condition")]
+#[pre(x > 0)]
+fn foo(x: i32) {}
+
+#[pre]
+fn main() {
+    #[assure("some condition", reason = "TODO", allow_placeholder)]
+    #[assure(x > 0, reason = "1 > 0")]
+    foo(1);
+
+    pre_call!(
+        "some condition",
+        reason = "?",
+        allow_placeholder,
+        x > 0,
+        reason = "2 > 0",
+        foo(2)
+    );
+
+    with_pattern();
+}
+
+#[pre(reason_pattern = "JIRA-\\d+")]
+fn with_pattern() {
+    // Placeholders are not required to match the pattern.
+    #[assure("some condition", reason = "todo", allow_placeholder)]
+    #[assure(x > 0, reason = "checked in JIRA-1234")]
+    foo(3);
+}
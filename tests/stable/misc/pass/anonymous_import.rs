@@ -0,0 +1,59 @@
+#![deny(warnings)]
+
+use pre::pre;
+
+mod checked {
+    use pre::pre;
+
+    #[pre("`x` is meaningful")]
+    pub(crate) fn foo(x: i32) -> i32 {
+        x
+    }
+}
+
+mod unchecked {
+    pub(crate) fn foo(x: i32) -> i32 {
+        x
+    }
+}
+
+// An anonymous import does not bring the name into scope, so the functions are called by path.
+#[allow(unused_imports)]
+use checked::foo as _;
+#[allow(unused_imports)]
+use unchecked::foo as _;
+
+#[pre]
+mod annotated {
+    #[allow(unused_imports)]
+    use super::checked::foo as _;
+
+    #[pre("`x` is meaningful")]
+    fn foo(x: i32) -> i32 {
+        x
+    }
+
+    pub(super) fn call_foo() -> i32 {
+        #[assure("`x` is meaningful", reason = "`1` is meaningful")]
+        let local = foo(1);
+
+        #[assure("`x` is meaningful", reason = "`2` is meaningful")]
+        let imported = super::checked::foo(2);
+
+        local + imported
+    }
+}
+
+#[pre]
+fn main() {
+    #[assure("`x` is meaningful", reason = "`1` is meaningful")]
+    let value = checked::foo(1);
+    assert_eq!(value, 1);
+
+    #[forward(unchecked -> checked)]
+    #[assure("`x` is meaningful", reason = "`2` is meaningful")]
+    let value = unchecked::foo(2);
+    assert_eq!(value, 2);
+
+    assert_eq!(annotated::call_foo(), 3);
+}
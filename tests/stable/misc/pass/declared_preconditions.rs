@@ -0,0 +1,35 @@
+use pre::{declared_preconditions, pre};
+
+#[pre("the value is small")]
+#[pre(x > 0)]
+fn double(x: i32) -> i32 {
+    x * 2
+}
+
+#[pre]
+mod inner {
+    #[pre(valid_ptr(ptr, r))]
+    #[pre(kind = in_range(len, 1..=16))]
+    pub(crate) unsafe fn read<T: Copy>(ptr: *const T, len: usize) -> T {
+        *ptr
+    }
+
+    #[cfg(unix)]
+    #[pre("running on unix")]
+    pub(crate) fn platform() {}
+}
+
+fn main() {
+    const DOUBLE: &[&str] = declared_preconditions!(double);
+    assert_eq!(DOUBLE, ["x > 0", "\"the value is small\""]);
+
+    assert_eq!(
+        declared_preconditions!(inner::read::<u8>),
+        ["valid_ptr(ptr, r)", "kind = in_range(len, 1 ..= 16)"]
+    );
+    #[cfg(unix)]
+    assert_eq!(
+        declared_preconditions!(crate::inner::platform),
+        ["\"running on unix\""]
+    );
+}
@@ -0,0 +1,93 @@
+use std::marker::PhantomData;
+
+use pre::pre;
+
+#[pre]
+mod numbers {
+    use pre::pre_call;
+    use std::marker::PhantomData;
+
+    #[pre(x > 0)]
+    pub(crate) fn positive(x: i32) -> i32 {
+        x
+    }
+
+    #[pre("the value is small")]
+    pub(crate) fn small(x: i32) -> i32 {
+        x
+    }
+
+    // This function takes a `PhantomData` argument of its own, but has no preconditions.
+    pub(crate) fn tagged<T>(x: i32, _tag: PhantomData<T>) -> i32 {
+        x
+    }
+
+    pub(crate) fn no_preconditions(x: i32) -> i32 {
+        x
+    }
+
+    #[forbid_pre]
+    pub(crate) fn sum(x: i32) -> i32 {
+        let mut sum = tagged::<u8>(no_preconditions(x), PhantomData);
+
+        if x > 0 {
+            #[assure(x > 0, reason = "checked by the `if`")]
+            let value = positive(x);
+            sum += value;
+
+            sum += pre_call!(x > 0, reason = "checked by the `if`", positive(x));
+        }
+
+        #[assure("the value is small", reason = "`x.min(10)` is at most 10")]
+        let value = small(
+            #[assure(x > 0, reason = "the value is clamped to positive values")]
+            positive(x.clamp(1, 10)),
+        );
+
+        // The attribute on the constructor applies to the call inside of it.
+        #[assure(x > 0, reason = "the value is clamped to positive values")]
+        let boxed = Box::new(positive(x.max(1)));
+
+        sum + value + *boxed
+    }
+
+    pub(crate) struct Counter(pub(crate) i32);
+
+    #[forbid_pre]
+    impl Counter {
+        pub(crate) fn add(&mut self, x: i32) {
+            if x > 0 {
+                #[assure(x > 0, reason = "checked by the `if`")]
+                let value = positive(x);
+                self.0 += value;
+            }
+        }
+    }
+
+    #[forbid_pre]
+    pub(crate) mod nested {
+        #[pre(x > 0)]
+        pub(crate) fn double(x: i32) -> i32 {
+            2 * x
+        }
+
+        pub(crate) fn quadruple(x: i32) -> i32 {
+            #[assure(x > 0, reason = "the result of `double` is positive for positive values")]
+            double(
+                #[assure(x > 0, reason = "only called with positive values")]
+                double(x),
+            )
+        }
+    }
+}
+
+fn main() {
+    assert_eq!(numbers::sum(3), 3 + 3 + 3 + 3 + 3);
+    assert_eq!(numbers::nested::quadruple(2), 8);
+
+    let mut counter = numbers::Counter(0);
+    counter.add(2);
+    counter.add(-2);
+    assert_eq!(counter.0, 2);
+    let _: PhantomData<()> = PhantomData;
+}
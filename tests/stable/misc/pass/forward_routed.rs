@@ -0,0 +1,63 @@
+#![deny(warnings)]
+
+use pre::pre;
+
+fn read(p: *const i32) -> i32 {
+    unsafe { *p }
+}
+
+mod checked {
+    use pre::pre;
+
+    #[pre(valid_ptr(p, r))]
+    pub(super) fn read(p: *const i32) -> i32 {
+        unsafe { *p }
+    }
+}
+
+mod documented {
+    use pre::pre;
+
+    #[pre("`p` points to the answer")]
+    pub(super) fn read(p: *const i32) -> i32 {
+        unsafe { *p }
+    }
+}
+
+mod both {
+    use pre::pre;
+
+    #[pre(valid_ptr(p, r))]
+    #[pre("`p` points to the answer")]
+    pub(super) fn read(p: *const i32) -> i32 {
+        unsafe { *p }
+    }
+}
+
+#[pre]
+fn main() {
+    let answer = 42;
+
+    #[forward(checked for (valid_ptr(p, r)))]
+    #[forward(documented for ("`p` points to the answer"))]
+    #[assure(valid_ptr(p, r), reason = "a reference is a valid pointer")]
+    #[assure("`p` points to the answer", reason = "`answer` is the answer")]
+    let value = read(&answer);
+    assert_eq!(value, 42);
+
+    // The attribute without a list receives the remaining preconditions.
+    #[forward(documented)]
+    #[forward(checked for (valid_ptr(p, r)))]
+    #[assure(valid_ptr(p, r), reason = "a reference is a valid pointer")]
+    #[assure("`p` points to the answer", reason = "`answer` is the answer")]
+    let value = read(&answer);
+    assert_eq!(value, 42);
+
+    // A precondition can be routed to multiple locations.
+    #[forward(checked for (valid_ptr(p, r)))]
+    #[forward(both for (valid_ptr(p, r), "`p` points to the answer"))]
+    #[assure(valid_ptr(p, r), reason = "a reference is a valid pointer")]
+    #[assure("`p` points to the answer", reason = "`answer` is the answer")]
+    let value = read(&answer);
+    assert_eq!(value, 42);
+}
@@ -0,0 +1,19 @@
+use pre::pre;
+
+#[pre(x > 0)]
+fn foo(x: i32) -> i32 {
+    x
+}
+
+#[pre]
+fn main() {
+    // Short reasons only cause a warning.
+    #[assure(x > 0, reason = "ok")]
+    foo(1);
+
+    #[assure(x > 0, reason = "ok", allow_placeholder)]
+    foo(2);
+
+    #[assure(x > 0, reason = "`3` is greater than zero")]
+    foo(3);
+}
@@ -0,0 +1,17 @@
+use pre::pre;
+
+#[pre(valid_ptr(ptr, w))]
+#[pre(kind = exclusive(ptr))]
+unsafe fn write(ptr: *mut u32, value: u32) {
+    ptr.write(value);
+}
+
+#[pre]
+fn main() {
+    let mut value = 0;
+
+    #[assure(valid_ptr(ptr, w), reason = "the pointer is created from a reference")]
+    unsafe {
+        write(&mut value, 42)
+    };
+}
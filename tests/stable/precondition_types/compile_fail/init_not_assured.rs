@@ -0,0 +1,18 @@
+use pre::pre;
+
+#[pre(valid_ptr(ptr, r))]
+#[pre(kind = aligned(ptr))]
+#[pre(kind = init(ptr))]
+unsafe fn read(ptr: *const u32) -> u32 {
+    *ptr
+}
+
+#[pre]
+fn main() {
+    let value = 42u32;
+
+    // Being valid and aligned does not imply being initialized.
+    #[assure(valid_ptr(ptr, r), reason = "the pointer is created from a reference")]
+    #[assure(kind = aligned(ptr), reason = "references are always aligned")]
+    let _ = unsafe { read(&value) };
+}
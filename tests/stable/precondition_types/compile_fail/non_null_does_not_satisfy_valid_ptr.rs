@@ -0,0 +1,28 @@
+use pre::pre;
+
+#[pre(valid_ptr(ptr, r))]
+unsafe fn read(ptr: *const u8) -> u8 {
+    *ptr
+}
+
+#[pre(kind = non_null(name))]
+fn name_len(name: *const u8) -> usize {
+    if name.is_null() {
+        0
+    } else {
+        1
+    }
+}
+
+#[pre]
+fn main() {
+    let value = 42u8;
+
+    #[assure(kind = non_null(ptr), reason = "the pointer is created from a reference")]
+    unsafe {
+        read(&value)
+    };
+
+    #[assure(valid_ptr(name, r), reason = "the pointer is created from a reference")]
+    name_len(&value);
+}
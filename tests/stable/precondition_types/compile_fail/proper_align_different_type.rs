@@ -0,0 +1,14 @@
+use pre::pre;
+
+#[pre(kind = proper_align(ptr, u64))]
+fn is_suitable(ptr: *const u8) -> bool {
+    !ptr.is_null()
+}
+
+#[pre]
+fn main() {
+    let value: u64 = 42;
+
+    #[assure(kind = proper_align(ptr, u32), reason = "the pointer is aligned for `u32`")]
+    is_suitable(&value as *const u64 as *const u8);
+}
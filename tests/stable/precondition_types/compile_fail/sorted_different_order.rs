@@ -0,0 +1,14 @@
+use pre::pre;
+
+#[pre(kind = sorted(haystack, by = "the length of the names"))]
+fn contains_length(haystack: &[&str], length: usize) -> bool {
+    haystack
+        .binary_search_by_key(&length, |name| name.len())
+        .is_ok()
+}
+
+#[pre]
+fn main() {
+    #[assure(kind = sorted(haystack), reason = "the names are sorted")]
+    contains_length(&["al", "bob", "carol"], 5);
+}
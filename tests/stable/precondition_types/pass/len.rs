@@ -0,0 +1,29 @@
+use pre::pre;
+
+#[pre(valid_ptr(buf, r))]
+#[pre(kind = min_len(buf, count))]
+unsafe fn sum(buf: *const u8, count: usize) -> u32 {
+    (0..count).map(|i| *buf.add(i) as u32).sum()
+}
+
+#[pre(kind = len(key, 16))]
+#[pre(kind = min_len(key, 8))]
+fn first_half(key: &[u8]) -> &[u8] {
+    &key[..8]
+}
+
+#[pre]
+fn main() {
+    let buf = [1, 2, 3, 4];
+
+    #[assure(valid_ptr(buf, r), reason = "the pointer is created from an array")]
+    #[assure(kind = min_len(buf, count), reason = "the array has four elements")]
+    let result = unsafe { sum(buf.as_ptr(), 3) };
+    assert_eq!(result, 6);
+
+    // `0x10` is the same length as `16`.
+    #[assure(kind = len(key, 0x10), reason = "the array has 16 elements")]
+    #[assure(kind = min_len(key, 8), reason = "16 >= 8")]
+    let result = first_half(&[0; 16]);
+    assert_eq!(result, [0; 8]);
+}
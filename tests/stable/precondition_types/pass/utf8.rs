@@ -0,0 +1,26 @@
+use pre::pre;
+
+#[pre(kind = utf8(bytes))]
+unsafe fn to_str(bytes: &[u8]) -> &str {
+    std::str::from_utf8_unchecked(bytes)
+}
+
+#[pre(valid_ptr(ptr, r))]
+#[pre(kind = utf8(ptr))]
+unsafe fn first_char(ptr: *const u8) -> char {
+    *ptr as char
+}
+
+#[pre]
+fn main() {
+    #[assure(kind = utf8(bytes), reason = "the bytes are taken from a string literal")]
+    let text = unsafe { to_str(b"pre") };
+    assert_eq!(text, "pre");
+
+    let bytes = "pre".as_bytes();
+
+    #[assure(valid_ptr(ptr, r), reason = "the pointer is created from a slice")]
+    #[assure(kind = utf8(ptr), reason = "the bytes are taken from a string literal")]
+    let first = unsafe { first_char(bytes.as_ptr()) };
+    assert_eq!(first, 'p');
+}
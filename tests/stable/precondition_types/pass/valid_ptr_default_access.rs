@@ -0,0 +1,38 @@
+use pre::pre;
+
+#[pre(valid_ptr(ptr))]
+unsafe fn increment(ptr: *mut i32) {
+    *ptr += 1;
+}
+
+#[pre(valid_ptr(src, r))]
+#[pre(valid_ptr(dst, w))]
+unsafe fn copy(src: *const i32, dst: *mut i32) {
+    dst.write(src.read());
+}
+
+#[pre]
+fn main() {
+    let mut value = 1;
+
+    #[assure(valid_ptr(ptr), reason = "`ptr` is created from a mutable reference")]
+    unsafe {
+        increment(&mut value)
+    };
+    assert_eq!(value, 2);
+
+    // Leaving out the access modes is the same as specifying `r+w`.
+    #[assure(valid_ptr(ptr, r+w), reason = "`ptr` is created from a mutable reference")]
+    unsafe {
+        increment(&mut value)
+    };
+    assert_eq!(value, 3);
+
+    let mut copy_of_value = 0;
+    #[assure(valid_ptr(src, r), reason = "`src` is created from a reference")]
+    #[assure(valid_ptr(dst, w), reason = "`dst` is created from a mutable reference")]
+    unsafe {
+        copy(&value, &mut copy_of_value)
+    };
+    assert_eq!(copy_of_value, 3);
+}
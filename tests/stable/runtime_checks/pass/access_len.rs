@@ -0,0 +1,36 @@
+use pre::pre;
+use std::{panic, ptr};
+
+#[pre(kind = readable_len(ptr, count))]
+unsafe fn sum(ptr: *const u8, count: usize) -> u32 {
+    if count == 0 {
+        return 0;
+    }
+
+    std::slice::from_raw_parts(ptr, count)
+        .iter()
+        .map(|&value| u32::from(value))
+        .sum()
+}
+
+#[pre]
+fn main() {
+    let values = [1, 2, 3];
+
+    #[assure(kind = readable_len(ptr, count), reason = "`values` has 3 elements")]
+    let result = unsafe { sum(values.as_ptr(), 3) };
+    assert_eq!(result, 6);
+
+    // A null pointer is fine, if no elements are read.
+    #[assure(kind = readable_len(ptr, count), reason = "no elements are read")]
+    let result = unsafe { sum(ptr::null(), 0) };
+    assert_eq!(result, 0);
+
+    let result = panic::catch_unwind(|| {
+        #[assure(kind = readable_len(ptr, count), reason = "this is wrong on purpose")]
+        unsafe {
+            sum(ptr::null(), 3)
+        }
+    });
+    assert!(result.is_err());
+}
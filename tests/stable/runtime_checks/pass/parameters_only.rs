@@ -0,0 +1,55 @@
+use pre::pre;
+use std::panic;
+
+const LIMIT: usize = 8;
+
+fn is_small(value: usize) -> bool {
+    value < LIMIT
+}
+
+#[pre(x > 0)]
+fn positive(x: i32) -> i32 {
+    x
+}
+
+// Constants, called functions and the parameters of closures are not values of the function.
+#[pre(condition = values.iter().all(|value| *value < LIMIT))]
+#[pre(is_small(values.len()))]
+fn below_limit(values: &[usize]) -> usize {
+    values.len()
+}
+
+// Expressions with macros or other bindings are not checked, because their names are not tracked.
+#[pre(pairs.iter().all(|(low, high)| low <= high))]
+#[pre(matches!(first, Some(value) if value < LIMIT))]
+fn ordered(pairs: &[(usize, usize)], first: Option<usize>) -> usize {
+    pairs.len()
+}
+
+// No `debug_assert` is generated here, so the precondition can refer to anything.
+#[pre(no_debug_assert)]
+#[pre(!std::thread::panicking())]
+fn not_panicking() {}
+
+#[pre]
+fn main() {
+    // `condition = ...` is the same precondition as the expression inside of it.
+    #[assure(values.iter().all(|value| *value < LIMIT), reason = "all values are small")]
+    #[assure(condition = is_small(values.len()), reason = "there are three values")]
+    let result = below_limit(&[1, 2, 3]);
+    assert_eq!(result, 3);
+
+    #[assure(pairs.iter().all(|(low, high)| low <= high), reason = "the pairs are ordered")]
+    #[assure(matches!(first, Some(value) if value < LIMIT), reason = "1 is small")]
+    let result = ordered(&[(1, 2)], Some(1));
+    assert_eq!(result, 1);
+
+    #[assure(!std::thread::panicking(), reason = "this is not called during a panic")]
+    not_panicking();
+
+    let result = panic::catch_unwind(|| {
+        #[assure(x > 0, reason = "this is wrong on purpose")]
+        positive(-1)
+    });
+    assert_eq!(result.is_err(), cfg!(debug_assertions));
+}
@@ -0,0 +1,34 @@
+use pre::pre;
+use std::panic;
+
+#[pre(kind = utf8(bytes))]
+unsafe fn to_str(bytes: &[u8]) -> &str {
+    std::str::from_utf8_unchecked(bytes)
+}
+
+#[pre(valid_ptr(ptr, r))]
+#[pre(kind = utf8(ptr))]
+unsafe fn first_byte(ptr: *const u8) -> u8 {
+    *ptr
+}
+
+#[pre]
+fn main() {
+    #[assure(kind = utf8(bytes), reason = "the bytes are taken from a string literal")]
+    let text = unsafe { to_str(b"pre") };
+    assert_eq!(text, "pre");
+
+    let bytes = [0xff];
+
+    #[assure(valid_ptr(ptr, r), reason = "the pointer is created from an array")]
+    #[assure(kind = utf8(ptr), reason = "pointers are not checked at runtime")]
+    let first = unsafe { first_byte(bytes.as_ptr()) };
+    assert_eq!(first, 0xff);
+
+    let result = panic::catch_unwind(|| {
+        #[assure(kind = utf8(bytes), reason = "this is wrong on purpose")]
+        let text = unsafe { to_str(&[0xff]) };
+        text.len()
+    });
+    assert!(result.is_err());
+}
@@ -0,0 +1,13 @@
+struct X;
+
+#[pre::pre("precondition on the impl block")]
+impl X {
+    fn foo(&self) {}
+
+    fn bar(&self) {}
+}
+
+fn main() {
+    X.foo();
+    X.bar();
+}
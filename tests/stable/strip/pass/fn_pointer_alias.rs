@@ -0,0 +1,9 @@
+use pre::pre;
+
+#[pre(valid_ptr(arg0, r))]
+type Callback = fn(*const u8) -> u8;
+
+fn main() {
+    let callback: Callback = |ptr| unsafe { *ptr };
+    assert_eq!(callback(&42), 42);
+}
@@ -0,0 +1,11 @@
+use pre::pre;
+
+#[pre(x > 0)]
+#[pre("is called in a test")]
+fn foo(x: i32) {}
+
+#[pre]
+fn main() {
+    #[assure(x > 0, reason = "1 > 0")]
+    foo(1);
+}
@@ -0,0 +1,22 @@
+use pre::pre;
+
+#[pre(valid_ptr(p, r))]
+#[pre(x > 0)]
+#[pre(kind = in_range(x, 1..10))]
+#[pre("is called in a test")]
+unsafe fn foo(p: *const u8, x: usize) -> u8 {
+    *p.add(x)
+}
+
+#[pre]
+fn main() {
+    let values = [1, 2, 3];
+
+    #[assure(valid_ptr(p, r), reason = "`p` is created from a reference")]
+    #[assure(x > 0, reason = "1 > 0")]
+    #[assure(kind = in_range(x, 1..10), reason = "1 lies in `1..10`")]
+    #[assure("is called in a test", reason = "this is a test")]
+    let value = unsafe { foo(values.as_ptr(), 1) };
+
+    assert_eq!(value, 2);
+}
@@ -0,0 +1,10 @@
+use pre::pre;
+
+#[pre("is foo")]
+fn foo() {}
+
+#[pre]
+fn main() {
+    #[assure("is bar", reason = "it is not foo")]
+    foo();
+}
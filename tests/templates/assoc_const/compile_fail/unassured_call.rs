@@ -0,0 +1,27 @@
+#[pre::pre]
+mod checked {
+    #[pre("is foo")]
+    fn foo() {}
+
+    fn no_preconditions() {}
+
+    pub(super) fn bar() {
+        no_preconditions();
+        foo();
+        self::foo();
+        inner::baz();
+    }
+
+    mod inner {
+        #[pre("is baz")]
+        pub(super) fn baz() {}
+
+        fn qux() {
+            super::foo();
+        }
+    }
+}
+
+fn main() {
+    checked::bar();
+}
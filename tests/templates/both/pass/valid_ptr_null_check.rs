@@ -0,0 +1,41 @@
+use pre::pre;
+use std::panic;
+
+#[pre(valid_ptr(ptr, r))]
+#[pre(len > 0)]
+unsafe fn first(ptr: *const u8, len: usize) -> u8 {
+    *ptr
+}
+
+#[pre]
+fn main() {
+    let value = 42;
+
+    #[assure(valid_ptr(ptr, r), reason = "`value` is a valid `u8`")]
+    #[assure(len > 0, reason = "1 > 0")]
+    let result = unsafe { first(&value, 1) };
+    assert_eq!(result, 42);
+
+    panic::set_hook(Box::new(|_| {}));
+
+    let result = panic::catch_unwind(|| {
+        #[assure(valid_ptr(ptr, r), reason = "this is wrong on purpose")]
+        #[assure(len > 0, reason = "1 > 0")]
+        unsafe {
+            first(std::ptr::null(), 1)
+        }
+    });
+    let _ = panic::take_hook();
+
+    let payload = result.expect_err("the pointer is null");
+    let message = payload
+        .downcast_ref::<String>()
+        .map(String::as_str)
+        .or_else(|| payload.downcast_ref::<&str>().copied())
+        .expect("the panic message is a string");
+
+    assert_eq!(
+        message,
+        "precondition was wrongly assured, the pointer is null: `valid_ptr(ptr, r)`"
+    );
+}
@@ -0,0 +1,22 @@
+use pre::pre;
+
+mod original {
+    pub async fn double(x: i32) -> i32 {
+        x * 2
+    }
+}
+
+#[pre::extern_crate(crate::original)]
+mod pre_original {
+    #[pre(x > 0)]
+    async fn double(x: i32) -> i32;
+}
+
+#[pre]
+async fn run() -> i32 {
+    pre_original::double(21).await
+}
+
+fn main() {
+    let _ = run();
+}
@@ -0,0 +1,17 @@
+#[pre::extern_crate(core, check_signatures)]
+mod pre_core {
+    mod str {
+        #[pre("`v` must contain valid UTF-8")]
+        unsafe fn from_utf8_unchecked(v: &[u8], len: usize) -> &str;
+    }
+
+    mod ptr {
+        #[pre("the source must be valid for reads")]
+        unsafe fn read<T>() -> T;
+
+        #[pre(valid_ptr(dst, w))]
+        unsafe fn write<T>(dst: *mut T, src: T, count: usize);
+    }
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[pre::extern_crate(core, from = "../../../signatures/malformed.pre")]
+mod pre_core {}
+
+#[pre::extern_crate(core, from = "../../../signatures/missing.pre")]
+mod pre_std {}
+
+fn main() {}
@@ -0,0 +1,67 @@
+use pre::pre;
+use std::{
+    future::Future,
+    pin::Pin,
+    ptr,
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+};
+
+mod original {
+    pub async fn double(x: i32) -> i32 {
+        x * 2
+    }
+
+    pub async fn first(values: &[i32]) -> i32 {
+        values[0]
+    }
+}
+
+#[pre::extern_crate(crate::original)]
+mod pre_original {
+    #[pre(x > 0)]
+    async fn double(x: i32) -> i32;
+
+    #[pre(!values.is_empty())]
+    async fn first(values: &[i32]) -> i32;
+}
+
+/// Polls the future to completion, which only works for futures that never wait.
+fn block_on<F: Future>(mut future: F) -> F::Output {
+    fn noop_raw_waker() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker {
+            noop_raw_waker()
+        }
+        fn noop(_: *const ()) {}
+
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+        RawWaker::new(ptr::null(), &VTABLE)
+    }
+
+    let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+    let mut context = Context::from_waker(&waker);
+    // SAFETY: the future is not moved after it is pinned here.
+    let mut future = unsafe { Pin::new_unchecked(&mut future) };
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+#[pre]
+async fn run() -> i32 {
+    #[assure(x > 0, reason = "21 > 0")]
+    let doubled = pre_original::double(21).await;
+
+    let values = [doubled, 1];
+    #[assure(!values.is_empty(), reason = "`values` has two elements")]
+    let value = pre_original::first(&values).await;
+
+    value
+}
+
+fn main() {
+    assert_eq!(block_on(run()), 42);
+}
@@ -0,0 +1,8 @@
+// This fixture was generated by `pre` for `pre_tests::enforced::divide`.
+// It calls the function without assuring its preconditions, so it must fail to compile:
+// - divisor != 0
+
+#[allow(unreachable_code)]
+fn main() {
+    pre_tests::enforced::divide(::core::todo!(), ::core::todo!());
+}
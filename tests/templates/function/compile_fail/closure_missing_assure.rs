@@ -0,0 +1,13 @@
+use pre::pre;
+
+#[pre(x > 0)]
+fn double(x: i32) -> i32 {
+    x * 2
+}
+
+#[pre]
+fn main() {
+    let f = || double(1);
+
+    f();
+}
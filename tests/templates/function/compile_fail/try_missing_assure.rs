@@ -0,0 +1,18 @@
+use pre::pre;
+
+#[pre(x > 0)]
+fn checked(x: i32) -> Result<i32, String> {
+    Ok(x)
+}
+
+#[pre]
+fn results() -> Result<i32, String> {
+    #[assure(x > 1, reason = "1 > 0")]
+    let value = checked(1)?;
+
+    Ok(value)
+}
+
+fn main() {
+    let _ = results();
+}
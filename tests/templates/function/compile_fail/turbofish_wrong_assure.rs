@@ -0,0 +1,12 @@
+use pre::pre;
+
+#[pre(x > T::default())]
+fn positive<T: Default + PartialOrd>(x: T) -> T {
+    x
+}
+
+#[pre]
+fn main() {
+    #[assure(x > 0, reason = "42 > 0")]
+    let _ = positive::<u32>(42);
+}
@@ -0,0 +1,76 @@
+use pre::pre;
+
+#[pre(x > 0)]
+fn foo(x: i32) -> i32 {
+    x * 2
+}
+
+#[pre(valid_ptr(ptr, r))]
+unsafe fn read(ptr: *const i32) -> i32 {
+    *ptr
+}
+
+mod registry {
+    pub(crate) fn register(value: i32) -> i32 {
+        value
+    }
+
+    pub(crate) fn register_pair(first: i32, second: i32) -> i32 {
+        first + second
+    }
+}
+
+fn register(value: i32) -> i32 {
+    value
+}
+
+#[pre]
+fn main() {
+    let value = register(
+        #[assure(x > 0, reason = "1 > 0")]
+        foo(1),
+    );
+    assert_eq!(value, 2);
+
+    // The outer function can be called through a path.
+    let value = registry::register(
+        #[assure(x > 0, reason = "2 > 0")]
+        foo(2),
+    );
+    assert_eq!(value, 4);
+
+    // Every argument can be a call with its own attribute.
+    let value = registry::register_pair(
+        #[assure(x > 0, reason = "3 > 0")]
+        foo(3),
+        #[assure(x > 0, reason = "4 > 0")]
+        foo(4),
+    );
+    assert_eq!(value, 14);
+
+    // Only some of the arguments need to have preconditions.
+    let value = registry::register_pair(
+        1,
+        #[assure(x > 0, reason = "5 > 0")]
+        foo(5),
+    );
+    assert_eq!(value, 11);
+
+    // The argument of a method call works the same way.
+    let mut values = Vec::new();
+    values.push(
+        #[assure(x > 0, reason = "6 > 0")]
+        foo(6),
+    );
+    assert_eq!(values, [12]);
+
+    // An `unsafe` block as the argument can carry the attribute for the call inside of it.
+    let value = 7;
+    let value = register(
+        #[assure(valid_ptr(ptr, r), reason = "`ptr` is created from a reference")]
+        unsafe {
+            read(&value)
+        },
+    );
+    assert_eq!(value, 7);
+}
@@ -0,0 +1,58 @@
+use pre::pre;
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+struct Meters(u32);
+
+#[pre(x > 0)]
+fn split(x: i32) -> (i32, i32) {
+    (x / 2, x - x / 2)
+}
+
+#[pre(x > 0)]
+fn point(x: i32) -> Point {
+    Point { x, y: -x }
+}
+
+#[pre("the distance is measured")]
+fn distance() -> Meters {
+    Meters(3)
+}
+
+#[pre(valid_ptr(ptr, r))]
+unsafe fn read_pair(ptr: *const [u8; 2]) -> [u8; 2] {
+    *ptr
+}
+
+#[pre]
+fn main() {
+    #[assure(x > 0, reason = "5 > 0")]
+    let (a, b) = split(5);
+    assert_eq!((a, b), (2, 3));
+
+    #[assure(x > 0, reason = "7 > 0")]
+    let (mut first, _): (i32, i32) = split(7);
+    first += 1;
+    assert_eq!(first, 4);
+
+    #[assure(x > 0, reason = "1 > 0")]
+    let Point { x, y: negated } = point(1);
+    assert_eq!((x, negated), (1, -1));
+
+    #[assure("the distance is measured", reason = "it is a constant")]
+    let Meters(meters) = distance();
+    assert_eq!(meters, 3);
+
+    let pair = [1, 2];
+
+    #[assure(valid_ptr(ptr, r), reason = "the pointer is created from a reference")]
+    let [low, high] = unsafe { read_pair(&pair) };
+    assert_eq!((low, high), (1, 2));
+
+    #[assure(x > 0, reason = "9 > 0")]
+    let ((a, b), ref c) = (split(9), 0);
+    assert_eq!((a, b, *c), (4, 5, 0));
+}
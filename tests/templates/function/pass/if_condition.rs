@@ -0,0 +1,46 @@
+use pre::pre;
+
+#[pre(x > 0)]
+fn is_even(x: i32) -> bool {
+    x % 2 == 0
+}
+
+#[pre(valid_ptr(ptr, r))]
+unsafe fn read(ptr: *const i32) -> i32 {
+    *ptr
+}
+
+#[pre]
+fn main() {
+    // The attribute can be placed on the call in the condition.
+    if #[assure(x > 0, reason = "42 > 0")]
+    is_even(42)
+    {
+        assert!(true);
+    } else {
+        unreachable!();
+    }
+
+    // The attribute can also be placed on the `if` expression, like for `if let`.
+    #[assure(x > 0, reason = "41 > 0")]
+    if is_even(41) {
+        unreachable!();
+    }
+
+    // The call can be part of a larger condition.
+    let value = 3;
+    if value > 2
+        && #[assure(x > 0, reason = "`value` is greater than 2")]
+        is_even(value)
+    {
+        unreachable!();
+    }
+
+    // The condition can be an `unsafe` block.
+    if #[assure(valid_ptr(ptr, r), reason = "`ptr` is created from a reference")]
+    unsafe {
+        read(&value) == 3
+    } {
+        assert_eq!(value, 3);
+    }
+}
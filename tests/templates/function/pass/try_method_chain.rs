@@ -0,0 +1,37 @@
+use pre::pre;
+use std::mem::MaybeUninit;
+
+fn init(value: i32) -> Result<MaybeUninit<i32>, ()> {
+    Ok(MaybeUninit::new(value))
+}
+
+fn init_nested(value: i32) -> Result<MaybeUninit<Result<i32, ()>>, ()> {
+    Ok(MaybeUninit::new(Ok(value)))
+}
+
+#[pre]
+fn read(value: i32) -> Result<i32, ()> {
+    let read = unsafe {
+        #[forward(impl pre::core::mem::MaybeUninit)]
+        #[assure(
+            "the `MaybeUninit` contains a fully initialized, valid value of `T`",
+            reason = "it was created from a value"
+        )]
+        init(value)?.assume_init()
+    };
+
+    let nested = unsafe {
+        #[forward(impl pre::core::mem::MaybeUninit)]
+        #[assure(
+            "the `MaybeUninit` contains a fully initialized, valid value of `T`",
+            reason = "it was created from a value"
+        )]
+        init_nested(read)?.assume_init()?
+    };
+
+    Ok(nested)
+}
+
+fn main() {
+    assert_eq!(read(42), Ok(42));
+}
@@ -0,0 +1,25 @@
+use pre::pre;
+
+#[pre(x > 0)]
+fn foo(x: i32) -> i32 {
+    x * 2
+}
+
+#[pre(valid_ptr(ptr, r))]
+#[pre("`ptr` points to an initialized value")]
+unsafe fn read<T: Copy>(ptr: *const T) -> T {
+    *ptr
+}
+
+// Regular calls are checked the same way as without the `fuzzing` feature.
+#[pre]
+fn main() {
+    #[assure(x > 0, reason = "1 > 0")]
+    let value = foo(1);
+    assert_eq!(value, 2);
+
+    #[assure(valid_ptr(ptr, r), reason = "`ptr` is created from a reference")]
+    #[assure("`ptr` points to an initialized value", reason = "the reference is initialized")]
+    let value = unsafe { read(&value) };
+    assert_eq!(value, 2);
+}
@@ -0,0 +1,16 @@
+use pre::pre;
+
+#[pre(x > 0)]
+fn double(x: i32) -> i32 {
+    x * 2
+}
+
+#[pre]
+fn main() {
+    let values = vec![
+        #[assure(x > 0, reason = "1 is positive")]
+        double(1);
+        2
+    ];
+    assert_eq!(values, [2, 2]);
+}
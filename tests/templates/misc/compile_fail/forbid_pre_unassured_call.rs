@@ -0,0 +1,33 @@
+use pre::pre;
+
+#[pre]
+mod numbers {
+    #[pre(x > 0)]
+    fn positive(x: i32) -> i32 {
+        x
+    }
+
+    #[forbid_pre]
+    fn unassured(x: i32) -> i32 {
+        #[assure(x > 0, reason = "only the outer call is assured")]
+        positive(positive(x))
+    }
+
+    #[forbid_pre]
+    mod nested {
+        #[pre("the value is small")]
+        fn small(x: i32) -> i32 {
+            x
+        }
+
+        fn in_assertion(x: i32) {
+            assert_eq!(small(x), x);
+        }
+    }
+}
+
+#[pre]
+#[forbid_pre]
+fn single_function() {}
+
+fn main() {}
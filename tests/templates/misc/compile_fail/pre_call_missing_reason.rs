@@ -0,0 +1,8 @@
+use pre::{pre, pre_call};
+
+#[pre("no arguments")]
+fn bar() {}
+
+fn main() {
+    pre_call!("no arguments", bar());
+}
@@ -0,0 +1,8 @@
+use pre::{pre, pre_call};
+
+#[pre("no arguments")]
+fn bar() {}
+
+fn main() {
+    pre_call!("some arguments", reason = "this is the wrong precondition", bar());
+}
@@ -0,0 +1,10 @@
+pre::precondition_alias! {
+    Unrelated = kind = non_null(p), kind = non_null(q);
+}
+
+pre::precondition_alias! {
+    Twice = kind = non_null(p);
+    Twice = kind = aligned(p);
+}
+
+fn main() {}
@@ -0,0 +1,31 @@
+use pre::pre;
+
+#[pre(vocabulary = "../../../vocabulary.txt")]
+mod buffer {
+    #[pre("the buffer is initialised")]
+    pub(crate) fn read(buf: &[u8]) -> u8 {
+        buf[0]
+    }
+
+    #[pre(x > 0)]
+    #[pre("the pointer is alligned")]
+    pub(crate) fn offset(ptr: *const u8, x: usize) -> *const u8 {
+        ptr
+    }
+}
+
+#[pre(vocabulary = "../../../missing_vocabulary.txt")]
+#[pre("the buffer is initialized")]
+fn missing() {}
+
+fn main() {}
+
+#[pre]
+mod outer {
+    // A vocabulary on a nested module applies to the functions inside of it.
+    #[pre(vocabulary = "../../../vocabulary.txt")]
+    mod inner {
+        #[pre("the buffer is initialised")]
+        fn read() {}
+    }
+}
@@ -0,0 +1,64 @@
+use pre::{pre, pre_call};
+use std::panic;
+
+#[pre(x > 0)]
+fn positive(x: i32) -> i32 {
+    x
+}
+
+#[pre(kind = in_range(index, 0..4))]
+fn lookup(index: usize) -> u8 {
+    [1, 2, 3, 4][index]
+}
+
+#[pre(x > 0)]
+#[pre(no_debug_assert)]
+fn unchecked(x: i32) -> i32 {
+    x
+}
+
+#[pre]
+fn main() {
+    panic::set_hook(Box::new(|_| {}));
+
+    let len = 3;
+    #[assure(x > 0, reason = "`len` is positive", evidence = len)]
+    let value = positive(len * 2);
+    assert_eq!(value, 6);
+
+    let index = 2;
+    #[assure(kind = in_range(index, 0..4), reason = "`index` is 2", evidence = index)]
+    let value = lookup(index);
+    assert_eq!(value, 3);
+
+    let value = pre_call!(x > 0, reason = "1 > 0", evidence = 1, positive(1));
+    assert_eq!(value, 1);
+
+    // The evidence does not shadow the values used in the arguments.
+    let x = 5;
+    #[assure(x > 0, reason = "7 > 0", evidence = 7)]
+    let value = positive(x);
+    assert_eq!(value, 5);
+
+    // The evidence is checked at the call site, even if the function does not check the
+    // precondition itself.
+    let result = panic::catch_unwind(|| {
+        #[assure(x > 0, reason = "this is wrong on purpose", evidence = -1)]
+        let value = unchecked(-1);
+
+        value
+    });
+
+    if cfg!(debug_assertions) {
+        let err = result.expect_err("the evidence is checked");
+        let message = err
+            .downcast_ref::<String>()
+            .cloned()
+            .or_else(|| err.downcast_ref::<&str>().map(|s| s.to_string()))
+            .expect("the panic contains a message");
+        assert_eq!(
+            message,
+            "the evidence does not satisfy the precondition `x > 0` (reason: this is wrong on purpose)"
+        );
+    }
+}
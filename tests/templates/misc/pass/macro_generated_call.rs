@@ -0,0 +1,58 @@
+use pre::pre;
+
+#[pre(x != 0)]
+fn divide(x: i32) -> i32 {
+    10 / x
+}
+
+// The function is passed as a `path` fragment, which is wrapped in an invisible group.
+macro_rules! checked_call {
+    ($name:ident, $f:path, $arg:expr) => {
+        #[pre]
+        fn $name() -> i32 {
+            #[assure(x != 0, reason = "the argument is a non-zero literal")]
+            let result = $f($arg);
+
+            result
+        }
+    };
+}
+
+checked_call!(divide_by_two, divide, 2);
+checked_call!(divide_by_five, self::divide, 5);
+
+// The whole call is passed as an `expr` fragment.
+macro_rules! assured {
+    ($call:expr) => {
+        #[pre]
+        fn divide_by_ten() -> i32 {
+            #[assure(x != 0, reason = "the argument is a non-zero literal")]
+            $call
+        }
+    };
+}
+
+assured!(divide(10));
+
+// The function with the preconditions is generated by the macro as well.
+macro_rules! define_divide {
+    ($name:ident) => {
+        #[pre(x != 0)]
+        fn $name(x: i32) -> i32 {
+            100 / x
+        }
+    };
+}
+
+define_divide!(divide_hundred);
+
+#[pre]
+fn main() {
+    assert_eq!(divide_by_two(), 5);
+    assert_eq!(divide_by_five(), 2);
+    assert_eq!(divide_by_ten(), 1);
+
+    #[assure(x != 0, reason = "4 is not 0")]
+    let quarter = divide_hundred(4);
+    assert_eq!(quarter, 25);
+}
@@ -0,0 +1,38 @@
+use pre::pre;
+
+macro_rules! sum_of {
+    ($($arg:ident),*) => {
+        0 $(+ $arg)*
+    };
+}
+
+#[pre(marker = "__pre")]
+#[pre(x > 0)]
+fn sum(x: i32, y: i32) -> i32 {
+    sum_of!(x, y)
+}
+
+#[pre(marker = "__pre")]
+#[pre(x > 0)]
+fn ignores_second(x: i32, _: i32) -> i32 {
+    x
+}
+
+#[pre(x > 0)]
+fn ignores_second_unnamed(x: i32, _: i32) -> i32 {
+    x
+}
+
+#[pre]
+fn main() {
+    #[assure(x > 0, reason = "1 > 0")]
+    let a = sum(1, 2);
+
+    #[assure(x > 0, reason = "1 > 0")]
+    let b = ignores_second(1, 2);
+
+    #[assure(x > 0, reason = "1 > 0")]
+    let c = ignores_second_unnamed(1, 2);
+
+    assert_eq!(a + b + c, 5);
+}
@@ -0,0 +1,27 @@
+use pre::{pre, pre_call};
+
+#[pre(x > 17)]
+#[pre("is a good value")]
+fn foo(x: i32) -> i32 {
+    x
+}
+
+#[pre("no arguments")]
+fn bar() -> i32 {
+    1
+}
+
+// No `pre` attribute needed here.
+fn main() {
+    let first = pre_call!(
+        x > 17,
+        reason = "42 > 17",
+        "is a good value",
+        reason = "42 is always a good value",
+        foo(42)
+    );
+
+    let second = 1 + pre_call!("no arguments", reason = "`bar` has no arguments", bar(),);
+
+    assert_eq!(first + second, 44);
+}
@@ -0,0 +1,79 @@
+use pre::{pre, pre_for_each};
+use std::panic;
+
+#[pre(x > 0)]
+fn positive(x: i32) -> i32 {
+    x
+}
+
+#[pre(valid_ptr(ptr, r))]
+#[pre("points to a single byte")]
+unsafe fn read(ptr: *const u8) -> u8 {
+    *ptr
+}
+
+#[pre(x > 0)]
+#[pre(no_debug_assert)]
+fn unchecked(x: i32) -> i32 {
+    x
+}
+
+fn main() {
+    panic::set_hook(Box::new(|_| {}));
+
+    let values = vec![1, 2, 3];
+    let mut sum = 0;
+    pre_for_each!(
+        x > 0,
+        reason = "all values are positive",
+        values.iter() => |value| sum += positive(*value)
+    );
+    assert_eq!(sum, 6);
+
+    let bytes = [4u8, 5, 6];
+    let mut total = 0;
+    pre_for_each!(
+        valid_ptr(ptr, r),
+        reason = "the pointers come from references",
+        "points to a single byte",
+        reason = "each pointer points to an element of `bytes`",
+        bytes.iter().map(|byte| byte as *const u8) => |ptr| total += unsafe { read(ptr) },
+    );
+    assert_eq!(total, 15);
+
+    // Patterns can destructure the items.
+    let mut products = 0;
+    pre_for_each!(
+        x > 0,
+        reason = "both values are positive",
+        evidence = a * b,
+        [(1, 2), (3, 4)].iter() => |&(a, b)| products += positive(a * b)
+    );
+    assert_eq!(products, 14);
+
+    // The evidence is checked in every iteration.
+    let result = panic::catch_unwind(|| {
+        let mut checked = 0;
+        pre_for_each!(
+            x > 0,
+            reason = "this is wrong on purpose",
+            evidence = value,
+            vec![1, -1, 2] => |value| checked += unchecked(value)
+        );
+
+        checked
+    });
+
+    if cfg!(debug_assertions) {
+        let err = result.expect_err("the evidence is checked");
+        let message = err
+            .downcast_ref::<String>()
+            .cloned()
+            .or_else(|| err.downcast_ref::<&str>().map(|s| s.to_string()))
+            .expect("the panic contains a message");
+        assert_eq!(
+            message,
+            "the evidence does not satisfy the precondition `x > 0` (reason: this is wrong on purpose)"
+        );
+    }
+}
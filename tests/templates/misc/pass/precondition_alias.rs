@@ -0,0 +1,55 @@
+use pre::pre;
+
+#[pre]
+mod bytes {
+    pre::precondition_alias! {
+        Readable = valid_ptr(p, r), kind = aligned(p), kind = init(p);
+        Bounded(n) = kind = nonzero(n), n <= 64;
+    }
+
+    #[pre(alias Readable for src)]
+    pub unsafe fn read(src: *const u8) -> u8 {
+        *src
+    }
+
+    #[pre(alias Bounded for count)]
+    pub fn bits(count: usize) -> u64 {
+        u64::MAX >> (64 - count)
+    }
+
+    pub mod nested {
+        #[pre(alias Readable for ptr)]
+        pub unsafe fn read_twice(ptr: *const u8) -> (u8, u8) {
+            #[assure(valid_ptr(src, r), reason = "`ptr` is valid for reads")]
+            #[assure(kind = aligned(src), reason = "`ptr` is aligned")]
+            #[assure(kind = init(src), reason = "`ptr` is initialized")]
+            let first = super::read(ptr);
+
+            (first, *ptr)
+        }
+    }
+
+    pub fn use_all() {
+        let value = 42;
+
+        #[assure(valid_ptr(src, r), reason = "`value` is a local variable")]
+        #[assure(kind = aligned(src), reason = "`u8` has an alignment of 1")]
+        #[assure(kind = init(src), reason = "`value` is initialized")]
+        let a = unsafe { read(&value) };
+
+        #[assure(kind = nonzero(count), reason = "8 is not zero")]
+        #[assure(count <= 64, reason = "8 <= 64")]
+        let b = bits(8);
+
+        #[assure(valid_ptr(ptr, r), reason = "`value` is a local variable")]
+        #[assure(kind = aligned(ptr), reason = "`u8` has an alignment of 1")]
+        #[assure(kind = init(ptr), reason = "`value` is initialized")]
+        let c = unsafe { nested::read_twice(&value) };
+
+        assert_eq!((a, b, c), (42, 0xff, (42, 42)));
+    }
+}
+
+fn main() {
+    bytes::use_all();
+}
@@ -0,0 +1,62 @@
+use pre::{pre, pre_call};
+use std::panic;
+
+#[pre(x > 0)]
+#[pre(no_debug_assert)]
+fn unchecked(x: i32) -> i32 {
+    x
+}
+
+fn panic_message(result: std::thread::Result<i32>) -> String {
+    let err = result.expect_err("the evidence is checked");
+
+    err.downcast_ref::<String>()
+        .cloned()
+        .or_else(|| err.downcast_ref::<&str>().map(|s| s.to_string()))
+        .expect("the panic contains a message")
+}
+
+#[pre(reason_pattern = "^x > 0 holds")]
+fn matches_pattern() {
+    // The pattern is matched against the reason with the placeholder replaced.
+    #[assure(x > 0, reason = "{cond} holds, because 1 is positive")]
+    unchecked(1);
+}
+
+#[pre]
+fn main() {
+    panic::set_hook(Box::new(|_| {}));
+
+    matches_pattern();
+
+    let result = panic::catch_unwind(|| {
+        #[assure(x > 0, reason = "{cond} is wrong on purpose", evidence = -1)]
+        let value = unchecked(-1);
+
+        value
+    });
+
+    if cfg!(debug_assertions) {
+        assert_eq!(
+            panic_message(result),
+            "the evidence does not satisfy the precondition `x > 0` (reason: x > 0 is wrong on purpose)"
+        );
+    }
+
+    // Literal braces are escaped by doubling them.
+    let result = panic::catch_unwind(|| {
+        pre_call!(
+            x > 0,
+            reason = "`Foo {{ x: -1 }}` does not satisfy {{cond}} and `{}` is kept",
+            evidence = -1,
+            unchecked(-1)
+        )
+    });
+
+    if cfg!(debug_assertions) {
+        assert_eq!(
+            panic_message(result),
+            "the evidence does not satisfy the precondition `x > 0` (reason: `Foo { x: -1 }` does not satisfy {cond} and `{}` is kept)"
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use pre::pre;
+
+#[pre(vocabulary = "../../../vocabulary.txt")]
+mod buffer {
+    #[pre("the buffer is initialized")]
+    #[pre("the buffer is not shared")]
+    pub(crate) fn read(buf: &[u8]) -> u8 {
+        buf[0]
+    }
+
+    #[pre(!buf.is_empty())]
+    #[pre("the buffer is initialized")]
+    pub(crate) fn first(buf: &[u8]) -> u8 {
+        #[assure("the buffer is initialized", reason = "declared by this function")]
+        #[assure("the buffer is not shared", reason = "`buf` is only used here")]
+        read(buf)
+    }
+}
+
+#[pre(vocabulary = "../../../vocabulary.txt")]
+#[pre("the pointer is aligned")]
+fn aligned(ptr: *const u8) -> *const u8 {
+    ptr
+}
+
+#[pre]
+fn main() {
+    #[assure(!buf.is_empty(), reason = "the buffer has one element")]
+    #[assure("the buffer is initialized", reason = "the buffer is created from an array")]
+    buffer::first(&[1]);
+
+    #[assure("the pointer is aligned", reason = "`u8` has an alignment of one")]
+    aligned(&0);
+}
@@ -0,0 +1,26 @@
+use pre::pre;
+
+struct X;
+
+impl X {
+    #[pre(x > 17)]
+    #[pre("precondition on async method")]
+    async fn foo(&self, x: i32) -> i32 {
+        x
+    }
+}
+
+#[pre]
+async fn caller() -> i32 {
+    #[assure(x > 17, reason = "42 > 17")]
+    #[assure("precondition on async method", reason = "it is on an async method")]
+    let val = X.foo(42).await;
+
+    #[assure(x > 17, reason = "18 > 17")]
+    #[assure("precondition on async method", reason = "it is on an async method")]
+    X.foo(18).await + val
+}
+
+fn main() {
+    let _ = caller();
+}
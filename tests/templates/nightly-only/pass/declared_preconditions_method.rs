@@ -0,0 +1,19 @@
+use pre::{declared_preconditions, pre};
+
+struct Buffer;
+
+#[pre("the buffer is initialized")]
+impl Buffer {
+    #[pre(index < 16)]
+    fn get(&self, index: usize) -> u8 {
+        let _ = index;
+        0
+    }
+}
+
+fn main() {
+    assert_eq!(
+        declared_preconditions!(Buffer::get),
+        ["index < 16", "\"the buffer is initialized\""]
+    );
+}
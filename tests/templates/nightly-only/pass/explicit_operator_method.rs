@@ -0,0 +1,35 @@
+use pre::pre;
+use std::ops::Add;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Meters(u32);
+
+impl Meters {
+    // Inherent methods take precedence over trait methods in method call syntax, so this is
+    // called by `a.add(b)`.
+    #[pre("the sum does not overflow")]
+    fn add(self, other: Meters) -> Meters {
+        Meters(self.0 + other.0)
+    }
+}
+
+impl Add for Meters {
+    type Output = Meters;
+
+    fn add(self, other: Meters) -> Meters {
+        Meters(self.0.checked_add(other.0).expect("no overflow"))
+    }
+}
+
+#[pre]
+fn main() {
+    let a = Meters(1);
+    let b = Meters(2);
+
+    #[assure("the sum does not overflow", reason = "both values are small")]
+    let sum = a.add(b);
+    assert_eq!(sum, Meters(3));
+
+    // The operator uses the trait implementation, which has no preconditions.
+    assert_eq!(a + b, Meters(3));
+}
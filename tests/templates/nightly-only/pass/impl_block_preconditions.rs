@@ -0,0 +1,43 @@
+use pre::pre;
+
+struct X;
+
+#[pre("precondition on the impl block")]
+impl X {
+    fn foo(&self) {}
+
+    #[pre("precondition on method")]
+    fn bar(&self) {}
+
+    #[pre("precondition on the impl block")]
+    fn baz(&self) {}
+}
+
+#[pre]
+mod inner {
+    pub(crate) struct Y;
+
+    #[pre("precondition on the nested impl block")]
+    impl Y {
+        pub(crate) fn qux(&self) {}
+    }
+}
+
+#[pre]
+fn main() {
+    #[assure("precondition on the impl block", reason = "it is on the impl block")]
+    X.foo();
+
+    #[assure("precondition on the impl block", reason = "it is on the impl block")]
+    #[assure("precondition on method", reason = "it is on a method")]
+    X.bar();
+
+    #[assure("precondition on the impl block", reason = "it is on the impl block")]
+    X.baz();
+
+    #[assure(
+        "precondition on the nested impl block",
+        reason = "it is on the nested impl block"
+    )]
+    inner::Y.qux();
+}
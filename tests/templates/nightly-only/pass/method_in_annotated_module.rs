@@ -0,0 +1,47 @@
+// `pre` is deliberately not imported in the module, the attributes on the methods are handled by
+// the attribute on the module.
+#[pre::pre]
+mod counter {
+    pub(crate) struct Counter {
+        count: u32,
+    }
+
+    impl Counter {
+        #[pre(start < 100)]
+        pub(crate) fn new(start: u32) -> Counter {
+            Counter { count: start }
+        }
+
+        #[pre("the counter is not at its maximum")]
+        pub(crate) fn increment(&mut self) {
+            self.count += 1;
+        }
+
+        pub(crate) fn get(&self) -> u32 {
+            self.count
+        }
+    }
+
+    pub(crate) fn count_to_two() -> u32 {
+        #[assure(start < 100, reason = "0 < 100")]
+        let mut counter = Counter::new(0);
+
+        #[assure(
+            "the counter is not at its maximum",
+            reason = "the counter was just created"
+        )]
+        counter.increment();
+
+        #[assure(
+            "the counter is not at its maximum",
+            reason = "the counter was only incremented once"
+        )]
+        counter.increment();
+
+        counter.get()
+    }
+}
+
+fn main() {
+    assert_eq!(counter::count_to_two(), 2);
+}
@@ -0,0 +1,15 @@
+use pre::pre;
+
+#[pre(kind = in_range(x, 10..0))]
+fn reversed(x: i32) {}
+
+#[pre(kind = in_range(x, 5..5))]
+fn empty(x: i32) {}
+
+#[pre(kind = in_range(x, ..))]
+fn unbounded(x: i32) {}
+
+#[pre(kind = in_range(x, 10))]
+fn not_a_range(x: i32) {}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use pre::{define_precondition_kind, pre};
+
+define_precondition_kind! {
+    /// The two buffers do not overlap.
+    disjoint(a, b)
+}
+
+#[pre(kind = disjoint(src))]
+fn foo(src: &[u8]) {}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use pre::{define_precondition_kind, pre};
+
+define_precondition_kind! {
+    /// The value is not zero.
+    non_zero(value)
+}
+
+#[pre(kind = non_zero(a))]
+fn foo(a: u32, _b: u32) {}
+
+#[pre]
+fn main() {
+    #[assure(kind = non_zero(_b), reason = "the wrong argument is assured")]
+    foo(1, 0);
+}
@@ -0,0 +1,23 @@
+use pre::{define_precondition_kind, pre};
+
+define_precondition_kind! {
+    /// The lock is held.
+    locked(value)
+}
+
+#[pre(kind = locked(value))]
+fn foo(value: &u32) -> u32 {
+    *value
+}
+
+#[pre(kind = self::locked(value))]
+fn bar(value: &u32) {}
+
+#[pre]
+fn main() {
+    #[assure(kind = self::locked(value), reason = "this is a different precondition")]
+    foo(&1);
+
+    #[assure(kind = locked(value), reason = "this is a different precondition")]
+    bar(&1);
+}
@@ -0,0 +1,20 @@
+use pre::pre;
+
+#[pre(kind = non_overlapping(dst, src, count))]
+unsafe fn copy_nonoverlapping(src: *const u8, dst: *mut u8, count: usize) {
+    std::ptr::copy_nonoverlapping(src, dst, count)
+}
+
+#[pre(kind = non_overlapping(dst, src, len))]
+unsafe fn not_a_parameter(src: *const u8, dst: *mut u8, count: usize) {}
+
+#[pre]
+fn main() {
+    let src = [0u8; 4];
+    let mut dst = [0u8; 4];
+
+    #[assure(kind = non_overlapping(dst, src, len), reason = "the arrays are different")]
+    unsafe {
+        copy_nonoverlapping(src.as_ptr(), dst.as_mut_ptr(), 4)
+    };
+}
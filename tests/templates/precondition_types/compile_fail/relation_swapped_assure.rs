@@ -0,0 +1,10 @@
+use pre::pre;
+
+#[pre(kind = relation("<", a, b))]
+fn foo(a: i32, b: i32) {}
+
+#[pre]
+fn main() {
+    #[assure(kind = relation("<", b, a), reason = "the names are swapped")]
+    foo(1, 2);
+}
@@ -0,0 +1,16 @@
+use pre::pre;
+
+#[pre(kind = same_provenance(start, end))]
+unsafe fn distance(start: *const u8, end: *const u8) -> isize {
+    end.offset_from(start)
+}
+
+#[pre]
+fn main() {
+    let array = [0u8; 4];
+    let start = array.as_ptr();
+    let end = unsafe { start.add(3) };
+
+    #[assure(kind = same_provenance(start, other), reason = "both point into `array`")]
+    let _ = unsafe { distance(start, end) };
+}
@@ -0,0 +1,10 @@
+use pre::pre;
+
+#[pre(see = "safety::Rules")]
+fn foo() {}
+
+#[pre]
+fn main() {
+    #[assure("safety::Rules", reason = "a custom precondition is not the same")]
+    foo();
+}
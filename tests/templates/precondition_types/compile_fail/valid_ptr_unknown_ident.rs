@@ -0,0 +1,12 @@
+use pre::pre;
+
+#[pre(valid_ptr(pointer, r))]
+fn foo(ptr: *const i32, len: usize) {
+    let _ = (ptr, len);
+}
+
+#[pre]
+fn main() {
+    #[assure(valid_ptr(pointer, r), reason = "it is from a reference")]
+    foo(&42, 1)
+}
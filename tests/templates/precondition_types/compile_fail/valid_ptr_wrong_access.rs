@@ -0,0 +1,26 @@
+use pre::pre;
+
+#[pre(valid_ptr(ptr))]
+unsafe fn increment(ptr: *mut i32) {
+    *ptr += 1;
+}
+
+#[pre(valid_ptr(dst, w))]
+unsafe fn write(dst: *mut i32) {
+    dst.write(42);
+}
+
+#[pre]
+fn main() {
+    let mut value = 1;
+
+    #[assure(valid_ptr(ptr, r), reason = "`ptr` is created from a mutable reference")]
+    unsafe {
+        increment(&mut value)
+    };
+
+    #[assure(valid_ptr(dst, r), reason = "`dst` is created from a mutable reference")]
+    unsafe {
+        write(&mut value)
+    };
+}
@@ -0,0 +1,13 @@
+use pre::pre;
+
+#[pre(valid_ptr(ptr: *const u8, r))]
+unsafe fn foo(ptr: *const u16) -> u16 {
+    *ptr
+}
+
+#[pre(valid_ptr(ptr: *const T, r))]
+unsafe fn bar<T: Copy>(ptr: *mut T) -> T {
+    *ptr
+}
+
+fn main() {}
@@ -0,0 +1,40 @@
+use pre::pre;
+
+#[pre(kind = readable_len(src, count))]
+#[pre(kind = writable_len(dst, count))]
+#[pre(kind = non_overlapping(src, dst, count))]
+unsafe fn copy(src: *const u8, dst: *mut u8, count: usize) {
+    std::ptr::copy_nonoverlapping(src, dst, count);
+}
+
+#[pre(kind = readable_len(ptr, 4))]
+#[pre(kind = writable_len(ptr, 4))]
+unsafe fn reverse_four(ptr: *mut u8) {
+    std::slice::from_raw_parts_mut(ptr, 4).reverse();
+}
+
+#[pre]
+fn main() {
+    let src = [1, 2, 3];
+    let mut dst = [0; 3];
+
+    #[assure(kind = readable_len(src, count), reason = "`src` has 3 elements")]
+    #[assure(kind = writable_len(dst, count), reason = "`dst` has 3 elements")]
+    #[assure(
+        kind = non_overlapping(src, dst, count),
+        reason = "`src` and `dst` are different arrays"
+    )]
+    unsafe {
+        copy(src.as_ptr(), dst.as_mut_ptr(), 3)
+    };
+    assert_eq!(dst, [1, 2, 3]);
+
+    // A pointer that is read and written needs both preconditions.
+    let mut values = [1, 2, 3, 4];
+    #[assure(kind = writable_len(ptr, 4), reason = "`values` has 4 elements")]
+    #[assure(kind = readable_len(ptr, 4), reason = "`values` has 4 elements")]
+    unsafe {
+        reverse_four(values.as_mut_ptr())
+    };
+    assert_eq!(values, [4, 3, 2, 1]);
+}
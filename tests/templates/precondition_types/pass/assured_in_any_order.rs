@@ -0,0 +1,23 @@
+use pre::pre;
+
+#[pre("a")]
+#[pre(valid_ptr(p, r))]
+#[pre(x > 0)]
+#[pre(kind = in_range(x, 0..10))]
+unsafe fn read(p: *const u8, x: usize) -> u8 {
+    *p.add(x)
+}
+
+#[pre]
+fn main() {
+    let buf = [1u8; 10];
+
+    // The preconditions are assured in a different order than they are declared in.
+    #[assure(kind = in_range(x, 0..10), reason = "`1` is in the range")]
+    #[assure(x > 0, reason = "`1` is greater than zero")]
+    #[assure(valid_ptr(p, r), reason = "`p` is created from a reference")]
+    #[assure("a", reason = "this is a test")]
+    let value = unsafe { read(buf.as_ptr(), 1) };
+
+    assert_eq!(value, 1);
+}
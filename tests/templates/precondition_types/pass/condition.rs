@@ -0,0 +1,41 @@
+use pre::pre;
+
+#[pre(condition = x > 0)]
+fn foo(x: i32) -> i32 {
+    x
+}
+
+#[pre(a < b)]
+fn bar(a: i32, b: i32) -> i32 {
+    b - a
+}
+
+fn condition(value: i32) -> bool {
+    value > 0
+}
+
+// This is a call of the function above, not a special form.
+#[pre(condition(x))]
+fn baz(x: i32) -> i32 {
+    x
+}
+
+#[pre]
+fn main() {
+    #[assure(condition = x > 0, reason = "42 > 0")]
+    let result = foo(42);
+    assert_eq!(result, 42);
+
+    // Both forms describe the same precondition.
+    #[assure(x > 0, reason = "42 > 0")]
+    let result = foo(42);
+    assert_eq!(result, 42);
+
+    #[assure(condition = a < b, reason = "1 < 2")]
+    let result = bar(1, 2);
+    assert_eq!(result, 1);
+
+    #[assure(condition(x), reason = "`condition` holds for 3")]
+    let result = baz(3);
+    assert_eq!(result, 3);
+}
@@ -0,0 +1,29 @@
+use pre::pre;
+
+#[pre(kind = nonzero(divisor))]
+fn divide(dividend: u32, divisor: u32) -> u32 {
+    dividend / divisor
+}
+
+#[pre(kind = nonzero(divisor))]
+#[pre(kind = len(values, 4))]
+#[pre(divisor < 100)]
+#[pre("the values are sorted")]
+fn scaled(values: &[u32], divisor: u32) -> u32 {
+    values.iter().map(|value| value / divisor).sum()
+}
+
+#[pre]
+fn main() {
+    #[assure(kind = nonzero(divisor), reason = "`4` is not zero")]
+    let result = divide(12, 4);
+    assert_eq!(result, 3);
+
+    // The order of the `assure` attributes does not matter.
+    #[assure("the values are sorted", reason = "the values are in ascending order")]
+    #[assure(divisor < 100, reason = "`2` is less than `100`")]
+    #[assure(kind = len(values, 4), reason = "the array has four elements")]
+    #[assure(kind = nonzero(divisor), reason = "`2` is not zero")]
+    let result = scaled(&[2, 4, 6, 8], 2);
+    assert_eq!(result, 10);
+}
@@ -0,0 +1,20 @@
+use pre::pre;
+
+#[pre(kind = on_thread("main"))]
+fn redraw(frames: &mut u32) {
+    *frames += 1;
+}
+
+#[pre(kind = on_thread("main"))]
+#[pre(kind = on_thread("ui"))]
+fn never_called() {}
+
+#[pre]
+fn main() {
+    let mut frames = 0;
+
+    #[assure(kind = on_thread("main"), reason = "this is called directly from `main`")]
+    redraw(&mut frames);
+
+    assert_eq!(frames, 1);
+}
@@ -0,0 +1,36 @@
+use pre::pre;
+
+#[pre(valid_ptr(ptr, r))]
+#[pre(kind = proper_align(ptr, u64))]
+unsafe fn read_u64(ptr: *const u8) -> u64 {
+    *(ptr as *const u64)
+}
+
+#[pre(kind = proper_align(ptr, [u16; 4]))]
+#[pre(kind = proper_align(ptr, u32))]
+fn is_suitable(ptr: *const u8) -> bool {
+    !ptr.is_null()
+}
+
+#[pre]
+fn main() {
+    let value: u64 = 42;
+    let ptr = &value as *const u64 as *const u8;
+
+    #[assure(valid_ptr(ptr, r), reason = "the pointer is created from a reference")]
+    #[assure(
+        kind = proper_align(ptr, u64),
+        reason = "the pointer is created from a reference to a `u64`"
+    )]
+    let result = unsafe { read_u64(ptr) };
+    assert_eq!(result, 42);
+
+    // The order of the `proper_align` preconditions does not matter.
+    #[assure(kind = proper_align(ptr, u32), reason = "`u64` is aligned more strictly than `u32`")]
+    #[assure(
+        kind = proper_align(ptr, [u16; 4]),
+        reason = "`u64` is aligned more strictly than `u16`"
+    )]
+    let suitable = is_suitable(ptr);
+    assert!(suitable);
+}
@@ -0,0 +1,45 @@
+use pre::pre;
+
+#[pre(kind = sorted(haystack))]
+fn contains(haystack: &[u32], needle: u32) -> bool {
+    haystack.binary_search(&needle).is_ok()
+}
+
+#[pre(kind = sorted(haystack, by = "the length of the names"))]
+#[pre("the names are unique")]
+fn contains_length(haystack: &[&str], length: usize) -> bool {
+    haystack
+        .binary_search_by_key(&length, |name| name.len())
+        .is_ok()
+}
+
+fn sorted(values: &[u32]) -> bool {
+    values.windows(2).all(|pair| pair[0] <= pair[1])
+}
+
+// This is a call of the function above, so it is checked at runtime like other boolean
+// preconditions.
+#[pre(sorted(haystack))]
+fn contains_checked(haystack: &[u32], needle: u32) -> bool {
+    haystack.binary_search(&needle).is_ok()
+}
+
+#[pre]
+fn main() {
+    #[assure(kind = sorted(haystack), reason = "the values are in ascending order")]
+    let found = contains(&[1, 4, 9, 16], 9);
+    assert!(found);
+
+    // A custom precondition can be assured next to the `sorted` precondition.
+    #[assure("the names are unique", reason = "every name is written once")]
+    #[assure(
+        kind = sorted(haystack, by = "the length of the names"),
+        reason = "the names get longer"
+    )]
+    let found = contains_length(&["al", "bob", "carol"], 5);
+    assert!(found);
+
+    #[assure(sorted(haystack), reason = "the values are in ascending order")]
+    let found = contains_checked(&[1, 4, 9], 4);
+    assert!(found);
+}
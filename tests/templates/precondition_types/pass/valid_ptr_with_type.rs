@@ -0,0 +1,41 @@
+use pre::pre;
+
+#[pre(valid_ptr(ptr: *const u8, r))]
+#[pre(valid_ptr(out: *mut [u16; 2], w))]
+unsafe fn foo(ptr: *const u8, out: *mut [u16; 2]) {
+    *out = [u16::from(*ptr); 2];
+}
+
+#[pre]
+fn main() {
+    let mut out = [0; 2];
+
+    #[assure(valid_ptr(ptr: *const u8, r), reason = "it is created from a reference")]
+    #[assure(valid_ptr(out: *mut [u16; 2], w), reason = "it is created from a reference")]
+    unsafe {
+        foo(&42, &mut out)
+    };
+
+    assert_eq!(out, [42, 42]);
+
+    generic();
+}
+
+#[pre(valid_ptr(ptr: *const T, r))]
+#[pre(valid_ptr(out: *mut T, w))]
+unsafe fn copy<T: Copy>(ptr: *const T, out: *mut T) {
+    *out = *ptr;
+}
+
+#[pre]
+fn generic() {
+    let mut out = 0;
+
+    #[assure(valid_ptr(ptr, r), reason = "it is created from a reference")]
+    #[assure(valid_ptr(out, w), reason = "it is created from a reference")]
+    unsafe {
+        copy(&42, &mut out)
+    };
+
+    assert_eq!(out, 42);
+}
@@ -0,0 +1,54 @@
+use pre::pre;
+
+#[pre(x > 0)]
+fn foo(x: i32) -> i32 {
+    x * 2
+}
+
+#[pre(valid_ptr(ptr, r))]
+#[pre("`ptr` points to an initialized value")]
+unsafe fn read(ptr: *const i32) -> i32 {
+    *ptr
+}
+
+mod inner {
+    use pre::pre;
+
+    #[pre(!v.is_empty())]
+    pub(crate) fn first(v: &[i32]) -> i32 {
+        v[0]
+    }
+}
+
+fn no_preconditions() {}
+
+fn contracts_of(module_path: &str) -> Vec<(&'static str, &'static [&'static str])> {
+    let mut contracts: Vec<_> = pre::CONTRACTS
+        .iter()
+        .filter(|contract| contract.module_path == module_path)
+        .map(|contract| (contract.function, contract.preconditions))
+        .collect();
+    contracts.sort();
+
+    contracts
+}
+
+fn main() {
+    // The contracts are registered even if the functions are never called.
+    assert_eq!(
+        contracts_of(module_path!()),
+        [
+            ("foo", &["x > 0"][..]),
+            (
+                "read",
+                &["valid_ptr(ptr, r)", "\"`ptr` points to an initialized value\""][..]
+            ),
+        ]
+    );
+    assert_eq!(
+        contracts_of(concat!(module_path!(), "::inner")),
+        [("first", &["! v.is_empty()"][..])]
+    );
+
+    no_preconditions();
+}
@@ -0,0 +1,10 @@
+use pre::pre;
+
+#[pre(count > 0)]
+fn foo(x: i32) {}
+
+#[pre(condition = x < limit)]
+fn bar(x: i32) {}
+
+#[pre]
+fn main() {}
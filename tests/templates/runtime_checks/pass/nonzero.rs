@@ -0,0 +1,20 @@
+use pre::pre;
+use std::panic;
+
+#[pre(kind = nonzero(divisor))]
+fn divide(dividend: u32, divisor: u32) -> u32 {
+    dividend.checked_div(divisor).unwrap_or(0)
+}
+
+#[pre]
+fn main() {
+    #[assure(kind = nonzero(divisor), reason = "`4` is not zero")]
+    let result = divide(12, 4);
+    assert_eq!(result, 3);
+
+    let result = panic::catch_unwind(|| {
+        #[assure(kind = nonzero(divisor), reason = "this is wrong on purpose")]
+        divide(12, 0)
+    });
+    assert!(result.is_err());
+}
@@ -0,0 +1,14 @@
+struct X;
+
+#[pre::pre]
+impl X {
+    #[pre("precondition on method")]
+    fn foo(&self) {}
+
+    fn bar(&self) {}
+}
+
+fn main() {
+    X.foo();
+    X.bar();
+}
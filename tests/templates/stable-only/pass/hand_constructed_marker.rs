@@ -0,0 +1,27 @@
+use pre::pre;
+
+#[pre(valid_ptr(p, r))]
+#[pre(x > 0)]
+#[pre("is a custom precondition")]
+unsafe fn foo(p: *const u8, x: usize) -> u8 {
+    *p.add(x - 1)
+}
+
+fn main() {
+    let values = [1, 2, 3];
+
+    // The order of the fields does not matter.
+    let value = unsafe {
+        foo(
+            values.as_ptr(),
+            2,
+            foo {
+                _boolean_x_20_3e_200: (),
+                _custom_is_20a_20custom_20precondition: (),
+                _valid_ptr_p_r: (),
+            },
+        )
+    };
+
+    assert_eq!(value, 2);
+}
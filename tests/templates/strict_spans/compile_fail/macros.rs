@@ -0,0 +1,21 @@
+use pre::{pre, pre_call, pre_for_each};
+
+#[pre::extern_crate(core)]
+mod c {
+    mod ptr {
+        #[pre(valid_ptr(src, r))]
+        unsafe fn read<T>(src: *const T) -> T;
+    }
+}
+
+#[pre(x > 0)]
+fn foo(x: i32) -> i32 {
+    x
+}
+
+fn main() {
+    pre_call!(x > 0, reason = "1 > 0", foo(1));
+
+    let mut total = 0;
+    pre_for_each!(x > 0, reason = "all values are positive", vec![1, 2] => |x| total += foo(x));
+}
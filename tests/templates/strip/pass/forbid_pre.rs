@@ -0,0 +1,33 @@
+use pre::pre;
+
+#[pre]
+mod numbers {
+    #[pre(x > 0)]
+    pub(crate) fn positive(x: i32) -> i32 {
+        x
+    }
+
+    pub(crate) struct Counter(pub(crate) i32);
+
+    #[forbid_pre]
+    impl Counter {
+        pub(crate) fn add(&mut self, x: i32) {
+            // The `forbid_pre` attribute is removed, so this is not checked.
+            self.0 += positive(x);
+        }
+    }
+
+    #[forbid_pre]
+    pub(crate) mod nested {
+        pub(crate) fn double(x: i32) -> i32 {
+            2 * super::positive(x)
+        }
+    }
+}
+
+fn main() {
+    let mut counter = numbers::Counter(0);
+    counter.add(2);
+    assert_eq!(counter.0, 2);
+    assert_eq!(numbers::nested::double(2), 4);
+}
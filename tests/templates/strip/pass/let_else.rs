@@ -0,0 +1,16 @@
+use pre::pre;
+
+#[pre(x > 10)]
+fn foo(x: i32) -> Option<i32> {
+    Some(x)
+}
+
+#[pre]
+fn main() {
+    // The attributes are removed from `let ... else` statements as well.
+    #[assure(x > 10, reason = "42 > 10")]
+    let Some(value) = foo(42) else {
+        return;
+    };
+    assert_eq!(value, 42);
+}
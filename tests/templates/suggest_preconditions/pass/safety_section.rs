@@ -0,0 +1,36 @@
+use pre::pre;
+
+#[pre]
+mod legacy {
+    /// Reads the value behind the pointer.
+    ///
+    /// # Safety
+    ///
+    /// - `valid_ptr(ptr, r)`: the pointer must be valid for reads
+    /// - `ptr` must point to an initialized value
+    pub(crate) unsafe fn read(ptr: *const u8) -> u8 {
+        *ptr
+    }
+
+    /// The suggestions don't change anything for functions with preconditions.
+    ///
+    /// # Safety
+    ///
+    /// - `x > 0`
+    /// - `x` must be small
+    #[pre(x > 0)]
+    pub(crate) fn double(x: i32) -> i32 {
+        x * 2
+    }
+}
+
+#[pre]
+fn main() {
+    let value = 42;
+    let result = unsafe { legacy::read(&value) };
+    assert_eq!(result, 42);
+
+    #[assure(x > 0, reason = "`21 > 0`")]
+    let doubled = legacy::double(21);
+    assert_eq!(doubled, 42);
+}
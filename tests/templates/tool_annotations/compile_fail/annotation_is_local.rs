@@ -0,0 +1,11 @@
+use pre::pre;
+
+#[pre(x > 0)]
+fn foo(x: i32) -> i32 {
+    x
+}
+
+fn main() {
+    // The annotation is only visible inside of the function body.
+    let _ = __PRE_TOOL_ANNOTATION;
+}